@@ -72,7 +72,19 @@ fn write_shader_execution(execution: &ShaderExecution) -> TokenStream {
             }
         }
         ShaderExecution::Fragment => quote! { ::vulkano::shader::ShaderExecution::Fragment },
-        ShaderExecution::Compute => quote! { ::vulkano::shader::ShaderExecution::Compute },
+        ShaderExecution::Compute(::vulkano::shader::ComputeShaderExecution { local_size }) => {
+            let local_size = match local_size {
+                Some([x, y, z]) => quote! { Some([#x, #y, #z]) },
+                None => quote! { None },
+            };
+            quote! {
+                ::vulkano::shader::ShaderExecution::Compute(
+                    ::vulkano::shader::ComputeShaderExecution {
+                        local_size: #local_size
+                    }
+                )
+            }
+        }
         ShaderExecution::RayGeneration => {
             quote! { ::vulkano::shader::ShaderExecution::RayGeneration}
         }
@@ -92,6 +104,7 @@ fn write_descriptor_requirements(
         let DescriptorRequirements {
             descriptor_types,
             descriptor_count,
+            buffer_min_size,
             image_format,
             image_multisampled,
             image_scalar_type,
@@ -110,6 +123,10 @@ fn write_descriptor_requirements(
             let ident = format_ident!("{}", format!("{:?}", ty));
             quote! { ::vulkano::descriptor_set::layout::DescriptorType::#ident }
         });
+        let buffer_min_size = match buffer_min_size {
+            Some(buffer_min_size) => quote! { Some(#buffer_min_size) },
+            None => quote! { None },
+        };
         let image_format = match image_format {
             Some(image_format) => {
                 let ident = format_ident!("{}", format!("{:?}", image_format));
@@ -202,6 +219,7 @@ fn write_descriptor_requirements(
                 ::vulkano::shader::DescriptorRequirements {
                     descriptor_types: vec![#(#descriptor_types),*],
                     descriptor_count: #descriptor_count,
+                    buffer_min_size: #buffer_min_size,
                     image_format: #image_format,
                     image_multisampled: #image_multisampled,
                     image_scalar_type: #image_scalar_type,
@@ -289,12 +307,14 @@ fn write_specialization_constant_requirements(
     let specialization_constant_requirements = specialization_constant_requirements
         .into_iter()
         .map(|(&constant_id, reqs)| {
-            let SpecializationConstantRequirements { size } = reqs;
+            let SpecializationConstantRequirements { size, ty } = reqs;
+            let ty = format_ident!("{}", format!("{:?}", ty));
             quote! {
                 (
                     #constant_id,
                     ::vulkano::shader::SpecializationConstantRequirements {
                         size: #size,
+                        ty: ::vulkano::shader::SpecializationConstantType::#ty,
                     },
                 ),
             }
@@ -1,4 +1,4 @@
-use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+use raw_window_handle::HasRawWindowHandle;
 use std::sync::Arc;
 use vulkano::instance::Instance;
 use vulkano::swapchain::Surface;
@@ -16,21 +16,23 @@ pub fn create_surface_from_handle<W>(
 where
     W: HasRawWindowHandle,
 {
-    unsafe {
-        match window.raw_window_handle() {
-            RawWindowHandle::AndroidNdk(h) => {
-                Surface::from_android(instance, h.a_native_window, window)
-            }
-            RawWindowHandle::UiKit(h) => Surface::from_ios(instance, h.ui_view, window),
-            RawWindowHandle::AppKit(h) => Surface::from_mac_os(instance, h.ns_view, window),
-            RawWindowHandle::Wayland(h) => {
-                Surface::from_wayland(instance, h.display, h.surface, window)
-            }
-            RawWindowHandle::Win32(h) => Surface::from_win32(instance, h.hinstance, h.hwnd, window),
-            RawWindowHandle::Xcb(h) => Surface::from_xcb(instance, h.connection, h.window, window),
-            RawWindowHandle::Xlib(h) => Surface::from_xlib(instance, h.display, h.window, window),
-            RawWindowHandle::Web(_) => unimplemented!(),
-            _ => unimplemented!(),
-        }
-    }
+    let handle = window.raw_window_handle();
+    unsafe { Surface::from_raw_window_handle(instance, handle, window) }
+}
+
+/// Same as `create_surface_from_handle`, but takes the window through an `Arc`, which is kept
+/// alive by the surface. This way, the window can't be destroyed while the surface still uses it,
+/// and it can still be accessed by the rest of the program.
+///
+/// The surface extension of the platform, for example `khr_win32_surface` or
+/// `khr_wayland_surface`, must be enabled on the instance, otherwise
+/// `SurfaceCreationError::MissingExtension` is returned.
+pub fn create_surface_from_window<W>(
+    window: Arc<W>,
+    instance: Arc<Instance>,
+) -> Result<Arc<Surface<Arc<W>>>, SurfaceCreationError>
+where
+    W: HasRawWindowHandle,
+{
+    Surface::from_window(instance, window)
 }
@@ -0,0 +1,960 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! An opaque data structure that is used to accelerate ray tracing.
+//!
+//! An acceleration structure is either *bottom-level*, in which case it contains geometry in the
+//! form of triangles or axis-aligned bounding boxes, or *top-level*, in which case it contains
+//! instances of bottom-level acceleration structures.
+//!
+//! Creating an acceleration structure only reserves storage for it in a buffer. Its contents are
+//! filled by recording a
+//! [`build_acceleration_structures`](crate::command_buffer::AutoCommandBufferBuilder::build_acceleration_structures)
+//! command. The size that the acceleration structure and the scratch buffer of the build need
+//! can be queried beforehand with [`AccelerationStructure::build_sizes`].
+
+use crate::{
+    buffer::{BufferAccess, BufferInner},
+    check_errors,
+    device::{Device, DeviceOwned},
+    format::Format,
+    pipeline::graphics::input_assembly::IndexType,
+    DeviceSize, Error, OomError, VulkanObject,
+};
+use smallvec::SmallVec;
+use std::{
+    error, fmt,
+    hash::{Hash, Hasher},
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    sync::Arc,
+};
+
+/// An opaque data structure that is used to accelerate ray tracing, stored in a buffer.
+#[derive(Debug)]
+pub struct AccelerationStructure {
+    handle: ash::vk::AccelerationStructureKHR,
+    device: Arc<Device>,
+    buffer: Arc<dyn BufferAccess>,
+
+    ty: AccelerationStructureType,
+}
+
+impl AccelerationStructure {
+    /// Creates a new `AccelerationStructure`, that is stored in `buffer`.
+    ///
+    /// The buffer must have been created with the `acceleration_structure_storage` usage, and
+    /// must start at an offset that is a multiple of 256. Its size can be determined with
+    /// [`build_sizes`](AccelerationStructure::build_sizes).
+    pub fn new(
+        buffer: Arc<dyn BufferAccess>,
+        create_info: AccelerationStructureCreateInfo,
+    ) -> Result<Arc<AccelerationStructure>, AccelerationStructureCreationError> {
+        let AccelerationStructureCreateInfo { ty, _ne: _ } = create_info;
+
+        let device = buffer.device().clone();
+        let size = buffer.size();
+        let BufferInner {
+            buffer: inner_buffer,
+            offset,
+        } = buffer.inner();
+
+        // VUID-vkCreateAccelerationStructureKHR-accelerationStructure-03611
+        if !device.enabled_features().acceleration_structure {
+            return Err(AccelerationStructureCreationError::FeatureNotEnabled {
+                feature: "acceleration_structure",
+                reason: "tried to create an acceleration structure",
+            });
+        }
+
+        // The acceleration structure, and the data it is built from, are referred to by their
+        // device address.
+        // VUID?
+        if !device.enabled_features().buffer_device_address {
+            return Err(AccelerationStructureCreationError::FeatureNotEnabled {
+                feature: "buffer_device_address",
+                reason: "tried to create an acceleration structure",
+            });
+        }
+
+        // VUID-VkAccelerationStructureCreateInfoKHR-buffer-03614
+        if !inner_buffer.usage().acceleration_structure_storage {
+            return Err(AccelerationStructureCreationError::BufferMissingUsage);
+        }
+
+        // VUID-VkAccelerationStructureCreateInfoKHR-offset-03734
+        if offset % 256 != 0 {
+            return Err(AccelerationStructureCreationError::OffsetNotAligned {
+                offset,
+                required_alignment: 256,
+            });
+        }
+
+        let create_info = ash::vk::AccelerationStructureCreateInfoKHR {
+            create_flags: ash::vk::AccelerationStructureCreateFlagsKHR::empty(),
+            buffer: inner_buffer.internal_object(),
+            offset,
+            size,
+            ty: ty.into(),
+            device_address: 0,
+            ..Default::default()
+        };
+
+        let handle = unsafe {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            check_errors((fns
+                .khr_acceleration_structure
+                .create_acceleration_structure_khr)(
+                device.internal_object(),
+                &create_info,
+                device.allocation_callbacks(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(Arc::new(AccelerationStructure {
+            handle,
+            device,
+            buffer,
+
+            ty,
+        }))
+    }
+
+    /// Returns the size that an acceleration structure and the scratch buffers need, in order to
+    /// be built on the device from `build_info`.
+    ///
+    /// `max_primitive_counts` contains, for each geometry in `build_info`, the maximum number of
+    /// primitives that will be built from it. The buffers and the acceleration structures in
+    /// `build_info` are ignored.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the length of `max_primitive_counts` is not equal to the number of geometries
+    ///   in `build_info`.
+    pub fn build_sizes(
+        device: &Device,
+        build_info: &AccelerationStructureBuildGeometryInfo,
+        max_primitive_counts: &[u32],
+    ) -> Result<AccelerationStructureBuildSizesInfo, AccelerationStructureCreationError> {
+        // VUID-vkGetAccelerationStructureBuildSizesKHR-accelerationStructure-08933
+        if !device.enabled_features().acceleration_structure {
+            return Err(AccelerationStructureCreationError::FeatureNotEnabled {
+                feature: "acceleration_structure",
+                reason: "tried to query the build sizes of an acceleration structure",
+            });
+        }
+
+        // VUID-vkGetAccelerationStructureBuildSizesKHR-pBuildInfo-03619
+        assert_eq!(
+            max_primitive_counts.len(),
+            build_info.geometries.len(),
+            "the length of max_primitive_counts must be equal to the number of geometries",
+        );
+
+        let geometries_vk = build_info.to_vulkan_geometries();
+        let build_info_vk = build_info.to_vulkan(&geometries_vk);
+        let mut build_sizes_vk = ash::vk::AccelerationStructureBuildSizesInfoKHR::default();
+
+        unsafe {
+            let fns = device.fns();
+            (fns.khr_acceleration_structure
+                .get_acceleration_structure_build_sizes_khr)(
+                device.internal_object(),
+                ash::vk::AccelerationStructureBuildTypeKHR::DEVICE,
+                &build_info_vk,
+                max_primitive_counts.as_ptr(),
+                &mut build_sizes_vk,
+            );
+        }
+
+        Ok(AccelerationStructureBuildSizesInfo {
+            acceleration_structure_size: build_sizes_vk.acceleration_structure_size,
+            update_scratch_size: build_sizes_vk.update_scratch_size,
+            build_scratch_size: build_sizes_vk.build_scratch_size,
+        })
+    }
+
+    /// Returns the buffer that the acceleration structure is stored in.
+    #[inline]
+    pub fn buffer(&self) -> &Arc<dyn BufferAccess> {
+        &self.buffer
+    }
+
+    /// Returns the type of the acceleration structure.
+    #[inline]
+    pub fn ty(&self) -> AccelerationStructureType {
+        self.ty
+    }
+
+    /// Returns the size of the acceleration structure.
+    #[inline]
+    pub fn size(&self) -> DeviceSize {
+        self.buffer.size()
+    }
+
+    /// Returns the device address of the acceleration structure.
+    ///
+    /// This is used to refer to a bottom-level acceleration structure from an instance of a
+    /// top-level acceleration structure.
+    pub fn device_address(&self) -> NonZeroU64 {
+        let info = ash::vk::AccelerationStructureDeviceAddressInfoKHR {
+            acceleration_structure: self.handle,
+            ..Default::default()
+        };
+
+        let ptr = unsafe {
+            let fns = self.device.fns();
+            (fns.khr_acceleration_structure
+                .get_acceleration_structure_device_address_khr)(
+                self.device.internal_object(),
+                &info,
+            )
+        };
+
+        NonZeroU64::new(ptr)
+            .expect("got null ptr from a valid GetAccelerationStructureDeviceAddressKHR call")
+    }
+}
+
+impl Drop for AccelerationStructure {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.khr_acceleration_structure
+                .destroy_acceleration_structure_khr)(
+                self.device.internal_object(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
+        }
+    }
+}
+
+unsafe impl VulkanObject for AccelerationStructure {
+    type Object = ash::vk::AccelerationStructureKHR;
+
+    #[inline]
+    fn internal_object(&self) -> ash::vk::AccelerationStructureKHR {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for AccelerationStructure {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl PartialEq for AccelerationStructure {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle && self.device() == other.device()
+    }
+}
+
+impl Eq for AccelerationStructure {}
+
+impl Hash for AccelerationStructure {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.handle.hash(state);
+        self.device().hash(state);
+    }
+}
+
+/// Parameters to create a new `AccelerationStructure`.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureCreateInfo {
+    /// The type of acceleration structure to create.
+    ///
+    /// The default value is [`AccelerationStructureType::Generic`].
+    pub ty: AccelerationStructureType,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for AccelerationStructureCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            ty: AccelerationStructureType::Generic,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl AccelerationStructureCreateInfo {
+    /// Returns an `AccelerationStructureCreateInfo` with the specified `ty`.
+    #[inline]
+    pub fn ty(ty: AccelerationStructureType) -> Self {
+        Self {
+            ty,
+            ..Default::default()
+        }
+    }
+}
+
+/// The type of an acceleration structure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum AccelerationStructureType {
+    /// Contains instances of bottom-level acceleration structures.
+    TopLevel = ash::vk::AccelerationStructureTypeKHR::TOP_LEVEL.as_raw(),
+
+    /// Contains triangles or axis-aligned bounding boxes.
+    BottomLevel = ash::vk::AccelerationStructureTypeKHR::BOTTOM_LEVEL.as_raw(),
+
+    /// The type is determined when the acceleration structure is built.
+    Generic = ash::vk::AccelerationStructureTypeKHR::GENERIC.as_raw(),
+}
+
+impl From<AccelerationStructureType> for ash::vk::AccelerationStructureTypeKHR {
+    #[inline]
+    fn from(val: AccelerationStructureType) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
+
+/// Parameters to build an acceleration structure, or to query the sizes needed to build it.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureBuildGeometryInfo {
+    /// Options for the build.
+    ///
+    /// The default value is empty.
+    pub flags: BuildAccelerationStructureFlags,
+
+    /// Whether to build the acceleration structure from scratch, or to update an existing one.
+    ///
+    /// The default value is [`BuildAccelerationStructureMode::Build`].
+    pub mode: BuildAccelerationStructureMode,
+
+    /// The geometries that the acceleration structure is built from.
+    ///
+    /// There is no default value.
+    pub geometries: AccelerationStructureGeometries,
+
+    /// The acceleration structure to build.
+    ///
+    /// This must be `Some` when building, and is ignored when querying the build sizes.
+    ///
+    /// The default value is `None`.
+    pub dst_acceleration_structure: Option<Arc<AccelerationStructure>>,
+
+    /// The buffer that is used as scratch memory during the build. It must have been created with
+    /// the `storage_buffer` and `device_address` usages, and must be at least as large as the
+    /// scratch size returned by [`AccelerationStructure::build_sizes`].
+    ///
+    /// This must be `Some` when building, and is ignored when querying the build sizes.
+    ///
+    /// The default value is `None`.
+    pub scratch_data: Option<Arc<dyn BufferAccess>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl AccelerationStructureBuildGeometryInfo {
+    /// Returns an `AccelerationStructureBuildGeometryInfo` with the specified `geometries`.
+    #[inline]
+    pub fn geometries(geometries: AccelerationStructureGeometries) -> Self {
+        Self {
+            flags: BuildAccelerationStructureFlags::none(),
+            mode: BuildAccelerationStructureMode::Build,
+            geometries,
+            dst_acceleration_structure: None,
+            scratch_data: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    pub(crate) fn to_vulkan_geometries(
+        &self,
+    ) -> SmallVec<[ash::vk::AccelerationStructureGeometryKHR; 1]> {
+        match &self.geometries {
+            AccelerationStructureGeometries::Triangles(geometries) => geometries
+                .iter()
+                .map(|triangles_data| {
+                    let &AccelerationStructureGeometryTrianglesData {
+                        flags,
+                        vertex_format,
+                        ref vertex_data,
+                        vertex_stride,
+                        max_vertex,
+                        ref index_data,
+                        index_type,
+                        ref transform_data,
+                        _ne: _,
+                    } = triangles_data;
+
+                    ash::vk::AccelerationStructureGeometryKHR {
+                        geometry_type: ash::vk::GeometryTypeKHR::TRIANGLES,
+                        geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                            triangles: ash::vk::AccelerationStructureGeometryTrianglesDataKHR {
+                                vertex_format: vertex_format.into(),
+                                vertex_data: device_address_const(vertex_data),
+                                vertex_stride: vertex_stride as DeviceSize,
+                                max_vertex,
+                                index_type: if index_data.is_some() {
+                                    index_type.into()
+                                } else {
+                                    ash::vk::IndexType::NONE_KHR
+                                },
+                                index_data: device_address_const(index_data),
+                                transform_data: device_address_const(transform_data),
+                                ..Default::default()
+                            },
+                        },
+                        flags: flags.into(),
+                        ..Default::default()
+                    }
+                })
+                .collect(),
+            AccelerationStructureGeometries::Aabbs(geometries) => geometries
+                .iter()
+                .map(|aabbs_data| {
+                    let &AccelerationStructureGeometryAabbsData {
+                        flags,
+                        ref data,
+                        stride,
+                        _ne: _,
+                    } = aabbs_data;
+
+                    ash::vk::AccelerationStructureGeometryKHR {
+                        geometry_type: ash::vk::GeometryTypeKHR::AABBS,
+                        geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                            aabbs: ash::vk::AccelerationStructureGeometryAabbsDataKHR {
+                                data: device_address_const(data),
+                                stride: stride as DeviceSize,
+                                ..Default::default()
+                            },
+                        },
+                        flags: flags.into(),
+                        ..Default::default()
+                    }
+                })
+                .collect(),
+            AccelerationStructureGeometries::Instances(instances_data) => {
+                let &AccelerationStructureGeometryInstancesData {
+                    flags,
+                    ref data,
+                    _ne: _,
+                } = instances_data;
+
+                [ash::vk::AccelerationStructureGeometryKHR {
+                    geometry_type: ash::vk::GeometryTypeKHR::INSTANCES,
+                    geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                        instances: ash::vk::AccelerationStructureGeometryInstancesDataKHR {
+                            array_of_pointers: ash::vk::FALSE,
+                            data: device_address_const(data),
+                            ..Default::default()
+                        },
+                    },
+                    flags: flags.into(),
+                    ..Default::default()
+                }]
+                .into_iter()
+                .collect()
+            }
+        }
+    }
+
+    pub(crate) fn to_vulkan(
+        &self,
+        geometries_vk: &[ash::vk::AccelerationStructureGeometryKHR],
+    ) -> ash::vk::AccelerationStructureBuildGeometryInfoKHR {
+        let &Self {
+            flags,
+            ref mode,
+            ref geometries,
+            ref dst_acceleration_structure,
+            ref scratch_data,
+            _ne: _,
+        } = self;
+
+        let (mode, src_acceleration_structure) = match mode {
+            BuildAccelerationStructureMode::Build => (
+                ash::vk::BuildAccelerationStructureModeKHR::BUILD,
+                ash::vk::AccelerationStructureKHR::null(),
+            ),
+            BuildAccelerationStructureMode::Update(src) => (
+                ash::vk::BuildAccelerationStructureModeKHR::UPDATE,
+                src.internal_object(),
+            ),
+        };
+
+        ash::vk::AccelerationStructureBuildGeometryInfoKHR {
+            ty: match geometries {
+                AccelerationStructureGeometries::Instances(_) => {
+                    ash::vk::AccelerationStructureTypeKHR::TOP_LEVEL
+                }
+                _ => ash::vk::AccelerationStructureTypeKHR::BOTTOM_LEVEL,
+            },
+            flags: flags.into(),
+            mode,
+            src_acceleration_structure,
+            dst_acceleration_structure: dst_acceleration_structure
+                .as_ref()
+                .map_or(ash::vk::AccelerationStructureKHR::null(), |dst| {
+                    dst.internal_object()
+                }),
+            geometry_count: geometries_vk.len() as u32,
+            p_geometries: geometries_vk.as_ptr(),
+            scratch_data: ash::vk::DeviceOrHostAddressKHR {
+                device_address: scratch_data
+                    .as_ref()
+                    .and_then(|buffer| buffer.raw_device_address().ok())
+                    .map_or(0, NonZeroU64::get),
+            },
+            ..Default::default()
+        }
+    }
+}
+
+// Returns the device address of `buffer`, or 0 if it is `None` or has no address.
+fn device_address_const(
+    buffer: &Option<Arc<dyn BufferAccess>>,
+) -> ash::vk::DeviceOrHostAddressConstKHR {
+    ash::vk::DeviceOrHostAddressConstKHR {
+        device_address: buffer
+            .as_ref()
+            .and_then(|buffer| buffer.raw_device_address().ok())
+            .map_or(0, NonZeroU64::get),
+    }
+}
+
+/// Options for building an acceleration structure.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BuildAccelerationStructureFlags {
+    /// The acceleration structure can be updated later, with
+    /// [`BuildAccelerationStructureMode::Update`].
+    pub allow_update: bool,
+
+    /// The acceleration structure can be compacted later.
+    pub allow_compaction: bool,
+
+    /// Prioritize the speed of tracing rays over the speed of building.
+    pub prefer_fast_trace: bool,
+
+    /// Prioritize the speed of building over the speed of tracing rays.
+    pub prefer_fast_build: bool,
+
+    /// Minimize the memory used by the acceleration structure and the scratch buffer, at the
+    /// expense of the speed of building and tracing rays.
+    pub low_memory: bool,
+}
+
+impl BuildAccelerationStructureFlags {
+    /// Returns a `BuildAccelerationStructureFlags` with none of the flags set.
+    #[inline]
+    pub const fn none() -> Self {
+        Self {
+            allow_update: false,
+            allow_compaction: false,
+            prefer_fast_trace: false,
+            prefer_fast_build: false,
+            low_memory: false,
+        }
+    }
+}
+
+impl From<BuildAccelerationStructureFlags> for ash::vk::BuildAccelerationStructureFlagsKHR {
+    #[inline]
+    fn from(val: BuildAccelerationStructureFlags) -> Self {
+        let mut result = ash::vk::BuildAccelerationStructureFlagsKHR::empty();
+        if val.allow_update {
+            result |= ash::vk::BuildAccelerationStructureFlagsKHR::ALLOW_UPDATE;
+        }
+        if val.allow_compaction {
+            result |= ash::vk::BuildAccelerationStructureFlagsKHR::ALLOW_COMPACTION;
+        }
+        if val.prefer_fast_trace {
+            result |= ash::vk::BuildAccelerationStructureFlagsKHR::PREFER_FAST_TRACE;
+        }
+        if val.prefer_fast_build {
+            result |= ash::vk::BuildAccelerationStructureFlagsKHR::PREFER_FAST_BUILD;
+        }
+        if val.low_memory {
+            result |= ash::vk::BuildAccelerationStructureFlagsKHR::LOW_MEMORY;
+        }
+        result
+    }
+}
+
+/// Whether an acceleration structure is built from scratch or updated.
+#[derive(Clone, Debug)]
+pub enum BuildAccelerationStructureMode {
+    /// The acceleration structure is built from scratch.
+    Build,
+
+    /// The acceleration structure is built by updating the provided acceleration structure, which
+    /// must have been built with the `allow_update` flag. The source can be the same as the
+    /// destination acceleration structure.
+    Update(Arc<AccelerationStructure>),
+}
+
+/// The geometries that an acceleration structure is built from.
+#[derive(Clone, Debug)]
+pub enum AccelerationStructureGeometries {
+    /// Triangles, to build a bottom-level acceleration structure.
+    Triangles(Vec<AccelerationStructureGeometryTrianglesData>),
+
+    /// Axis-aligned bounding boxes, to build a bottom-level acceleration structure.
+    Aabbs(Vec<AccelerationStructureGeometryAabbsData>),
+
+    /// Instances of bottom-level acceleration structures, to build a top-level acceleration
+    /// structure.
+    Instances(AccelerationStructureGeometryInstancesData),
+}
+
+impl AccelerationStructureGeometries {
+    /// Returns the number of geometries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Triangles(geometries) => geometries.len(),
+            Self::Aabbs(geometries) => geometries.len(),
+            Self::Instances(_) => 1,
+        }
+    }
+
+    /// Returns whether there are no geometries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A geometry consisting of triangles.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureGeometryTrianglesData {
+    /// Options for the geometry.
+    ///
+    /// The default value is empty.
+    pub flags: GeometryFlags,
+
+    /// The format of the vertex positions in `vertex_data`. The format must support the
+    /// `acceleration_structure_vertex_buffer` buffer format feature.
+    ///
+    /// There is no default value.
+    pub vertex_format: Format,
+
+    /// The buffer containing the vertex positions. It must have been created with the
+    /// `acceleration_structure_build_input_read_only` and `device_address` usages.
+    ///
+    /// This must be `Some` when building, and is ignored when querying the build sizes.
+    ///
+    /// The default value is `None`.
+    pub vertex_data: Option<Arc<dyn BufferAccess>>,
+
+    /// The number of bytes between the start of two consecutive vertices in `vertex_data`.
+    ///
+    /// The default value is `0`, which must be overridden.
+    pub vertex_stride: u32,
+
+    /// The highest index of a vertex that will be accessed.
+    ///
+    /// The default value is `0`.
+    pub max_vertex: u32,
+
+    /// The buffer containing the indices of the triangles, or `None` if the triangles are not
+    /// indexed. It must have been created with the `acceleration_structure_build_input_read_only`
+    /// and `device_address` usages.
+    ///
+    /// The default value is `None`.
+    pub index_data: Option<Arc<dyn BufferAccess>>,
+
+    /// The type of the indices in `index_data`. This is ignored if `index_data` is `None`.
+    ///
+    /// The default value is [`IndexType::U32`].
+    pub index_type: IndexType,
+
+    /// The buffer containing a `VkTransformMatrixKHR` that is applied to the vertices, or `None`
+    /// for the identity transform. It must have been created with the
+    /// `acceleration_structure_build_input_read_only` and `device_address` usages.
+    ///
+    /// The default value is `None`.
+    pub transform_data: Option<Arc<dyn BufferAccess>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl AccelerationStructureGeometryTrianglesData {
+    /// Returns an `AccelerationStructureGeometryTrianglesData` with the specified
+    /// `vertex_format`.
+    #[inline]
+    pub fn vertex_format(vertex_format: Format) -> Self {
+        Self {
+            flags: GeometryFlags::none(),
+            vertex_format,
+            vertex_data: None,
+            vertex_stride: 0,
+            max_vertex: 0,
+            index_data: None,
+            index_type: IndexType::U32,
+            transform_data: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// A geometry consisting of axis-aligned bounding boxes.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureGeometryAabbsData {
+    /// Options for the geometry.
+    ///
+    /// The default value is empty.
+    pub flags: GeometryFlags,
+
+    /// The buffer containing the bounding boxes, as `VkAabbPositionsKHR` values. It must have
+    /// been created with the `acceleration_structure_build_input_read_only` and `device_address`
+    /// usages.
+    ///
+    /// This must be `Some` when building, and is ignored when querying the build sizes.
+    ///
+    /// The default value is `None`.
+    pub data: Option<Arc<dyn BufferAccess>>,
+
+    /// The number of bytes between the start of two consecutive bounding boxes in `data`. It
+    /// must be a multiple of 8.
+    ///
+    /// The default value is `24`, the size of a `VkAabbPositionsKHR`.
+    pub stride: u32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for AccelerationStructureGeometryAabbsData {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            flags: GeometryFlags::none(),
+            data: None,
+            stride: 24,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// A geometry consisting of instances of bottom-level acceleration structures.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureGeometryInstancesData {
+    /// Options for the geometry.
+    ///
+    /// The default value is empty.
+    pub flags: GeometryFlags,
+
+    /// The buffer containing the instances, as tightly packed `VkAccelerationStructureInstanceKHR`
+    /// values. It must have been created with the `acceleration_structure_build_input_read_only`
+    /// and `device_address` usages.
+    ///
+    /// This must be `Some` when building, and is ignored when querying the build sizes.
+    ///
+    /// The default value is `None`.
+    pub data: Option<Arc<dyn BufferAccess>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for AccelerationStructureGeometryInstancesData {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            flags: GeometryFlags::none(),
+            data: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Options for a geometry of an acceleration structure.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GeometryFlags {
+    /// The geometry does not invoke any-hit shaders.
+    pub opaque: bool,
+
+    /// The any-hit shader is invoked only once for each primitive of the geometry.
+    pub no_duplicate_any_hit_invocation: bool,
+}
+
+impl GeometryFlags {
+    /// Returns a `GeometryFlags` with none of the flags set.
+    #[inline]
+    pub const fn none() -> Self {
+        Self {
+            opaque: false,
+            no_duplicate_any_hit_invocation: false,
+        }
+    }
+}
+
+impl From<GeometryFlags> for ash::vk::GeometryFlagsKHR {
+    #[inline]
+    fn from(val: GeometryFlags) -> Self {
+        let mut result = ash::vk::GeometryFlagsKHR::empty();
+        if val.opaque {
+            result |= ash::vk::GeometryFlagsKHR::OPAQUE;
+        }
+        if val.no_duplicate_any_hit_invocation {
+            result |= ash::vk::GeometryFlagsKHR::NO_DUPLICATE_ANY_HIT_INVOCATION;
+        }
+        result
+    }
+}
+
+/// The range of primitives of a geometry that is built into an acceleration structure.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccelerationStructureBuildRangeInfo {
+    /// The number of primitives to build.
+    pub primitive_count: u32,
+
+    /// The offset in bytes of the first primitive in the data buffer of the geometry, or in the
+    /// index buffer for indexed triangles.
+    pub primitive_offset: u32,
+
+    /// For triangles, the index of the first vertex to build from.
+    pub first_vertex: u32,
+
+    /// For triangles, the offset in bytes of the transform matrix in the transform buffer.
+    pub transform_offset: u32,
+}
+
+impl From<AccelerationStructureBuildRangeInfo> for ash::vk::AccelerationStructureBuildRangeInfoKHR {
+    #[inline]
+    fn from(val: AccelerationStructureBuildRangeInfo) -> Self {
+        Self {
+            primitive_count: val.primitive_count,
+            primitive_offset: val.primitive_offset,
+            first_vertex: val.first_vertex,
+            transform_offset: val.transform_offset,
+        }
+    }
+}
+
+/// The sizes that are needed to build an acceleration structure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccelerationStructureBuildSizesInfo {
+    /// The size of the buffer that the acceleration structure is stored in.
+    pub acceleration_structure_size: DeviceSize,
+
+    /// The size of the scratch buffer for an update.
+    pub update_scratch_size: DeviceSize,
+
+    /// The size of the scratch buffer for a build from scratch.
+    pub build_scratch_size: DeviceSize,
+}
+
+/// Error that can happen when creating an acceleration structure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccelerationStructureCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+
+    /// The buffer was not created with the `acceleration_structure_storage` usage.
+    BufferMissingUsage,
+
+    /// The offset of the buffer is not a multiple of the required alignment.
+    OffsetNotAligned {
+        offset: DeviceSize,
+        required_alignment: DeviceSize,
+    },
+}
+
+impl error::Error for AccelerationStructureCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AccelerationStructureCreationError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(f, "the feature {} must be enabled: {}", feature, reason)
+            }
+            Self::BufferMissingUsage => write!(
+                f,
+                "the buffer was not created with the `acceleration_structure_storage` usage",
+            ),
+            Self::OffsetNotAligned {
+                offset,
+                required_alignment,
+            } => write!(
+                f,
+                "the offset of the buffer ({}) is not a multiple of the required alignment ({})",
+                offset, required_alignment,
+            ),
+        }
+    }
+}
+
+impl From<OomError> for AccelerationStructureCreationError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<Error> for AccelerationStructureCreationError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            err @ Error::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AccelerationStructure, AccelerationStructureCreateInfo, AccelerationStructureCreationError,
+        AccelerationStructureType,
+    };
+    use crate::buffer::{BufferUsage, DeviceLocalBuffer};
+
+    #[test]
+    fn feature_not_enabled() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer = DeviceLocalBuffer::<[u8]>::array(
+            device,
+            256,
+            BufferUsage::storage_buffer(),
+            [queue.family()],
+        )
+        .unwrap();
+
+        match AccelerationStructure::new(
+            buffer,
+            AccelerationStructureCreateInfo::ty(AccelerationStructureType::BottomLevel),
+        ) {
+            Err(AccelerationStructureCreationError::FeatureNotEnabled {
+                feature: "acceleration_structure",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+}
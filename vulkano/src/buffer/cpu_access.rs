@@ -14,13 +14,15 @@
 //!
 //! Each access from the CPU or from the GPU locks the whole buffer for either reading or writing.
 //! You can read the buffer multiple times simultaneously. Trying to read and write simultaneously,
-//! or write and write simultaneously will block.
+//! or write and write simultaneously will block. The CPU can also lock only a range of the buffer
+//! for writing with `write_range`, in which case only accesses that overlap this range conflict.
 
 use super::{
     sys::UnsafeBuffer, BufferAccess, BufferAccessObject, BufferContents, BufferInner, BufferUsage,
 };
 use crate::{
     buffer::{sys::UnsafeBufferCreateInfo, BufferCreationError, TypedBufferAccess},
+    command_buffer::DrawIndexedIndirectCommand,
     device::{physical::QueueFamily, Device, DeviceOwned},
     memory::{
         pool::{
@@ -29,18 +31,22 @@ use crate::{
         },
         DedicatedAllocation, DeviceMemoryAllocationError, MemoryPool,
     },
-    sync::Sharing,
+    sync::{FenceSignalFuture, FlushError, GpuFuture, Sharing},
     DeviceSize,
 };
 use smallvec::SmallVec;
 use std::{
     error, fmt,
+    future::Future,
     hash::{Hash, Hasher},
     marker::PhantomData,
     mem::size_of,
     ops::{Deref, DerefMut, Range},
+    pin::Pin,
     ptr,
-    sync::{atomic::AtomicUsize, Arc},
+    sync::{atomic::AtomicUsize, Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
 };
 
 /// Buffer whose content is accessible by the CPU.
@@ -201,6 +207,31 @@ where
     }
 }
 
+impl CpuAccessibleBuffer<[DrawIndexedIndirectCommand]> {
+    /// Builds a new buffer of indexed indirect draw commands, with the
+    /// [`indirect_buffer`](BufferUsage::indirect_buffer) usage enabled.
+    ///
+    /// The buffer can be passed directly to
+    /// [`draw_indexed_indirect`](crate::command_buffer::AutoCommandBufferBuilder::draw_indexed_indirect),
+    /// and its commands can be modified afterwards through [`write`](Self::write).
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `data` is empty.
+    #[inline]
+    pub fn from_draw_commands<I>(
+        device: Arc<Device>,
+        host_cached: bool,
+        data: I,
+    ) -> Result<Arc<CpuAccessibleBuffer<[DrawIndexedIndirectCommand]>>, DeviceMemoryAllocationError>
+    where
+        I: IntoIterator<Item = DrawIndexedIndirectCommand>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        CpuAccessibleBuffer::from_iter(device, BufferUsage::indirect_buffer(), host_cached, data)
+    }
+}
+
 impl<T> CpuAccessibleBuffer<T>
 where
     T: BufferContents + ?Sized,
@@ -333,6 +364,9 @@ where
         let mapped_memory = self.memory.mapped_memory().unwrap();
         let offset = self.memory.offset();
         let memory_range = offset..offset + self.inner.size();
+        // The memory allocator aligns the allocation to the atom size if the memory is not
+        // host-coherent, so the aligned range doesn't overlap with other allocations.
+        let aligned_range = mapped_memory.atom_aligned_range(memory_range.clone());
 
         let bytes = unsafe {
             // If there are other read locks being held at this point, they also called
@@ -341,9 +375,11 @@ where
             // TODO: probably still more efficient to call it only if we're the first to acquire a
             // read lock, but the number of CPU locks isn't currently tracked anywhere.
             mapped_memory
-                .invalidate_range(memory_range.clone())
+                .invalidate_range(aligned_range.clone())
                 .unwrap();
-            mapped_memory.read(memory_range.clone()).unwrap()
+            let bytes = mapped_memory.read(aligned_range.clone()).unwrap();
+            &bytes[(memory_range.start - aligned_range.start) as usize
+                ..(memory_range.end - aligned_range.start) as usize]
         };
 
         Ok(ReadLock {
@@ -354,6 +390,36 @@ where
         })
     }
 
+    /// Returns a future that resolves to a read lock on the buffer once the GPU has finished
+    /// executing `future`.
+    ///
+    /// `future` is flushed immediately, and a fence is signaled after it. The returned
+    /// [`ReadAfterFuture`] can be `.await`ed in an async runtime, and will only try to lock the
+    /// buffer once the fence has been signaled, so that any writes to the buffer that were
+    /// submitted as part of `future` are visible to the host.
+    ///
+    /// The first time the returned future is polled, it spawns a thread that blocks on the fence
+    /// and wakes the task once the fence is signaled. This keeps the threads of the executor free
+    /// while the GPU is working, at the cost of one thread per future for the duration of the
+    /// GPU work. It is therefore better suited to a few large readbacks than to many small ones.
+    ///
+    /// Returns an error if `future` is not associated with a queue, for example if it is a
+    /// [`NowFuture`](crate::sync::NowFuture), or if flushing it fails.
+    pub fn read_after<F>(&self, future: F) -> Result<ReadAfterFuture<T, A>, ReadAfterError>
+    where
+        F: GpuFuture + Send + 'static,
+    {
+        if future.queue().is_none() {
+            return Err(ReadAfterError::NoQueue);
+        }
+
+        Ok(ReadAfterFuture {
+            buffer: self,
+            fence: Some(future.boxed_send().then_signal_fence_and_flush()?),
+            state: Arc::new(Mutex::new(ReadAfterState::default())),
+        })
+    }
+
     /// Locks the buffer in order to write its content from the CPU.
     ///
     /// If the buffer is currently in use by the GPU, this function will return an error. Similarly
@@ -375,21 +441,93 @@ where
         let mapped_memory = self.memory.mapped_memory().unwrap();
         let offset = self.memory.offset();
         let memory_range = offset..offset + self.size();
+        // The memory allocator aligns the allocation to the atom size if the memory is not
+        // host-coherent, so the aligned range doesn't overlap with other allocations.
+        let aligned_range = mapped_memory.atom_aligned_range(memory_range.clone());
 
         let bytes = unsafe {
             mapped_memory
-                .invalidate_range(memory_range.clone())
+                .invalidate_range(aligned_range.clone())
                 .unwrap();
-            mapped_memory.write(memory_range.clone()).unwrap()
+            let bytes = mapped_memory.write(aligned_range.clone()).unwrap();
+            &mut bytes[(memory_range.start - aligned_range.start) as usize
+                ..(memory_range.end - aligned_range.start) as usize]
         };
 
         Ok(WriteLock {
             inner: self,
             buffer_range,
-            memory_range,
+            memory_range: aligned_range,
             data: T::from_bytes_mut(bytes).unwrap(),
         })
     }
+
+    /// Locks a range of the buffer in order to write its content from the CPU. `range` is in
+    /// bytes, relative to the start of the buffer.
+    ///
+    /// This is the same as `write()`, except that only `range` is locked. Writes to ranges that
+    /// don't overlap can therefore happen at the same time, for example from different threads.
+    /// If the memory of the buffer is not host-coherent, the locked range is extended to a
+    /// multiple of the
+    /// [`non_coherent_atom_size`](crate::device::Properties::non_coherent_atom_size) device
+    /// property, so ranges that share an atom are considered to overlap.
+    ///
+    /// If the locked range overlaps a range that is currently in use by the GPU, this function
+    /// blocks until the GPU releases it. This happens when the future of the submission that uses
+    /// the buffer is cleaned up, for example when its fence is waited for, so that must be done
+    /// from another thread. If the locked range overlaps a range that is currently locked by the
+    /// CPU, this function returns an error instead.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `range` is empty or exceeds the size of the buffer.
+    #[inline]
+    pub fn write_range(
+        &self,
+        range: Range<DeviceSize>,
+    ) -> Result<WriteRangeLock<T, A>, WriteLockError> {
+        assert!(!range.is_empty() && range.end <= self.size());
+
+        let mapped_memory = self.memory.mapped_memory().unwrap();
+        let offset = self.memory.offset();
+        let memory_range = offset + range.start..offset + range.end;
+        // The memory allocator aligns the allocation to the atom size if the memory is not
+        // host-coherent, so the aligned range doesn't overlap with other allocations. It is
+        // locked as a whole, so that flushing it doesn't interfere with other locked ranges.
+        let aligned_range = mapped_memory.atom_aligned_range(memory_range.clone());
+        let buffer_range = self.inner().offset + aligned_range.start - offset
+            ..self.inner().offset + (aligned_range.end - offset).min(self.size());
+
+        let mut state = self.inner.state();
+
+        loop {
+            match state.check_cpu_write(buffer_range.clone()) {
+                Ok(()) => break,
+                Err(WriteLockError::GpuLocked) => self.inner.wait_gpu_unlock(&mut state),
+                Err(err) => return Err(err),
+            }
+        }
+
+        unsafe {
+            state.cpu_write_lock(buffer_range.clone());
+        }
+
+        let data = unsafe {
+            mapped_memory
+                .invalidate_range(aligned_range.clone())
+                .unwrap();
+            let bytes = mapped_memory.write(aligned_range.clone()).unwrap();
+            &mut bytes[(memory_range.start - aligned_range.start) as usize
+                ..(memory_range.end - aligned_range.start) as usize]
+        };
+
+        Ok(WriteRangeLock {
+            inner: self,
+            buffer_range,
+            memory_range: aligned_range,
+            data,
+        })
+    }
 }
 
 unsafe impl<T, A> BufferAccess for CpuAccessibleBuffer<T, A>
@@ -513,6 +651,87 @@ where
     }
 }
 
+/// Future returned by [`CpuAccessibleBuffer::read_after`].
+///
+/// Resolves to a [`ReadLock`] on the buffer once the GPU has finished executing the future that
+/// was passed to `read_after`.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadAfterFuture<'a, T, A>
+where
+    T: BufferContents + ?Sized + 'a,
+    A: MemoryPoolAlloc,
+{
+    buffer: &'a CpuAccessibleBuffer<T, A>,
+    // The fence that is signaled after the future passed to `read_after`. It is moved to the
+    // waiting thread the first time the future is polled.
+    fence: Option<FenceSignalFuture<Box<dyn GpuFuture + Send>>>,
+    state: Arc<Mutex<ReadAfterState>>,
+}
+
+// State that is shared between a `ReadAfterFuture` and the thread that waits for its fence.
+#[derive(Debug, Default)]
+struct ReadAfterState {
+    // The result of waiting for the fence, once the thread has finished waiting.
+    result: Option<Result<(), FlushError>>,
+    // The waker of the task that last polled the future.
+    waker: Option<Waker>,
+}
+
+impl<'a, T, A> fmt::Debug for ReadAfterFuture<'a, T, A>
+where
+    T: BufferContents + ?Sized + 'a,
+    A: MemoryPoolAlloc,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("ReadAfterFuture")
+            .field("buffer", &self.buffer.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T, A> Future for ReadAfterFuture<'a, T, A>
+where
+    T: BufferContents + ?Sized + 'a,
+    A: MemoryPoolAlloc,
+{
+    type Output = Result<ReadLock<'a, T, A>, ReadAfterError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        {
+            let mut state = self.state.lock().unwrap();
+
+            if let Some(result) = state.result.take() {
+                drop(state);
+                return Poll::Ready(match result {
+                    Ok(()) => self.buffer.read().map_err(Into::into),
+                    Err(err) => Err(err.into()),
+                });
+            }
+
+            state.waker = Some(cx.waker().clone());
+        }
+
+        if let Some(fence) = self.fence.take() {
+            let state = self.state.clone();
+
+            thread::spawn(move || {
+                // Waiting also releases the resources that were locked by the submission,
+                // including the GPU lock on the buffer.
+                let result = fence.wait(None);
+                let mut state = state.lock().unwrap();
+                state.result = Some(result);
+
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            });
+        }
+
+        Poll::Pending
+    }
+}
+
 /// Object that can be used to read or write the content of a `CpuAccessibleBuffer`.
 ///
 /// Note that this object holds a rwlock write guard on the chunk. If another thread tries to access
@@ -574,6 +793,69 @@ where
     }
 }
 
+/// Object that can be used to write a range of the content of a `CpuAccessibleBuffer`, returned
+/// by [`write_range`](CpuAccessibleBuffer::write_range).
+///
+/// Note that this object holds a write lock on the range. As long as it exists, locking an
+/// overlapping range of this buffer's content from the CPU or submitting a GPU command that uses
+/// the range will return an error, rather than wait for the lock to be released.
+#[derive(Debug)]
+pub struct WriteRangeLock<'a, T, A>
+where
+    T: BufferContents + ?Sized + 'a,
+    A: MemoryPoolAlloc,
+{
+    inner: &'a CpuAccessibleBuffer<T, A>,
+    buffer_range: Range<DeviceSize>,
+    memory_range: Range<DeviceSize>,
+    data: &'a mut [u8],
+}
+
+impl<'a, T, A> Drop for WriteRangeLock<'a, T, A>
+where
+    T: BufferContents + ?Sized + 'a,
+    A: MemoryPoolAlloc,
+{
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            self.inner
+                .memory
+                .mapped_memory()
+                .unwrap()
+                .flush_range(self.memory_range.clone())
+                .unwrap();
+
+            let mut state = self.inner.inner.state();
+            state.cpu_write_unlock(self.buffer_range.clone());
+        }
+    }
+}
+
+impl<'a, T, A> Deref for WriteRangeLock<'a, T, A>
+where
+    T: BufferContents + ?Sized + 'a,
+    A: MemoryPoolAlloc,
+{
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.data
+    }
+}
+
+impl<'a, T, A> DerefMut for WriteRangeLock<'a, T, A>
+where
+    T: BufferContents + ?Sized + 'a,
+    A: MemoryPoolAlloc,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.data
+    }
+}
+
 /// Error when attempting to CPU-read a buffer.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ReadLockError {
@@ -603,6 +885,53 @@ impl fmt::Display for ReadLockError {
     }
 }
 
+/// Error that can happen when creating or awaiting a [`ReadAfterFuture`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReadAfterError {
+    /// Flushing the GPU future or waiting for it to finish failed.
+    FlushError(FlushError),
+    /// The GPU future is not associated with a queue, so there is no submission to wait for.
+    NoQueue,
+    /// The GPU future finished, but locking the buffer for reading failed.
+    ReadLockError(ReadLockError),
+}
+
+impl error::Error for ReadAfterError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::FlushError(ref err) => Some(err),
+            Self::ReadLockError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ReadAfterError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::FlushError(_) => write!(fmt, "waiting for the GPU future failed"),
+            Self::NoQueue => write!(fmt, "the GPU future is not associated with a queue"),
+            Self::ReadLockError(_) => write!(fmt, "locking the buffer for reading failed"),
+        }
+    }
+}
+
+impl From<FlushError> for ReadAfterError {
+    #[inline]
+    fn from(err: FlushError) -> Self {
+        Self::FlushError(err)
+    }
+}
+
+impl From<ReadLockError> for ReadAfterError {
+    #[inline]
+    fn from(err: ReadLockError) -> Self {
+        Self::ReadLockError(err)
+    }
+}
+
 /// Error when attempting to CPU-write a buffer.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum WriteLockError {
@@ -630,7 +959,22 @@ impl fmt::Display for WriteLockError {
 
 #[cfg(test)]
 mod tests {
-    use crate::buffer::{BufferUsage, CpuAccessibleBuffer};
+    use super::{ReadAfterError, ReadLockError, WriteLockError};
+    use crate::{
+        buffer::{BufferUsage, CpuAccessibleBuffer},
+        command_buffer::{
+            AutoCommandBufferBuilder, CommandBufferUsage, CopyBufferInfo, FillBufferInfo,
+        },
+        sync::{self, GpuFuture},
+    };
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::{Arc, Barrier},
+        task::{Context, Poll, Wake, Waker},
+        thread,
+        time::Duration,
+    };
 
     #[test]
     fn create_empty_buffer() {
@@ -645,4 +989,170 @@ mod tests {
                 .unwrap();
         });
     }
+
+    #[test]
+    fn write_range_disjoint() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let atom_size = device.physical_device().properties().non_coherent_atom_size;
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device,
+            BufferUsage::all(),
+            false,
+            vec![0u8; atom_size as usize * 2],
+        )
+        .unwrap();
+
+        // Both threads hold their lock at the same time before writing.
+        let barrier = Arc::new(Barrier::new(2));
+        let threads: Vec<_> = (0..2)
+            .map(|i| {
+                let buffer = buffer.clone();
+                let barrier = barrier.clone();
+
+                thread::spawn(move || {
+                    let mut lock = buffer
+                        .write_range(atom_size * i..atom_size * (i + 1))
+                        .unwrap();
+                    barrier.wait();
+                    lock.fill(i as u8 + 1);
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let content = buffer.read().unwrap();
+        assert!(content[..atom_size as usize].iter().all(|&b| b == 1));
+        assert!(content[atom_size as usize..].iter().all(|&b| b == 2));
+        drop(content);
+
+        let _lock = buffer.write_range(0..atom_size).unwrap();
+        assert!(matches!(buffer.write(), Err(WriteLockError::CpuLocked)));
+        assert!(matches!(
+            buffer.write_range(0..atom_size * 2),
+            Err(WriteLockError::CpuLocked)
+        ));
+        assert!(buffer.write_range(atom_size..atom_size * 2).is_ok());
+    }
+
+    #[test]
+    fn write_range_overlapping() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let atom_size = device.physical_device().properties().non_coherent_atom_size;
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device,
+            BufferUsage::all(),
+            false,
+            vec![0u8; atom_size as usize * 3],
+        )
+        .unwrap();
+
+        let lock = buffer.write_range(atom_size..atom_size * 2).unwrap();
+
+        // Overlapping CPU locks fail immediately instead of waiting for the lock.
+        assert!(matches!(
+            buffer.write_range(0..atom_size * 2),
+            Err(WriteLockError::CpuLocked)
+        ));
+        assert!(matches!(
+            buffer.write_range(atom_size..atom_size * 3),
+            Err(WriteLockError::CpuLocked)
+        ));
+        assert!(matches!(buffer.read(), Err(ReadLockError::CpuWriteLocked)));
+
+        drop(lock);
+        assert!(buffer.write_range(0..atom_size * 3).is_ok());
+    }
+
+    #[test]
+    fn write_range_wait_gpu() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let src =
+            CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), false, [0u8; 64])
+                .unwrap();
+        let dst =
+            CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), false, [0u8; 64])
+                .unwrap();
+
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        cbb.copy_buffer(CopyBufferInfo::buffers(src.clone(), dst))
+            .unwrap();
+        let future = sync::now(device)
+            .then_execute(queue, cbb.build().unwrap())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+
+        // The range is read by the GPU until the future is waited for on the other thread.
+        let waiter = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            future.wait(None).unwrap();
+        });
+
+        let mut lock = src.write_range(1..2).unwrap();
+        lock[0] = 1;
+        drop(lock);
+        waiter.join().unwrap();
+
+        assert_eq!(src.read().unwrap()[1], 1);
+    }
+
+    #[test]
+    fn read_after() {
+        struct ThreadWaker(thread::Thread);
+
+        impl Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer =
+            CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), false, [0u32; 4])
+                .unwrap();
+
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        cbb.fill_buffer(FillBufferInfo {
+            data: 42,
+            ..FillBufferInfo::dst_buffer(buffer.clone())
+        })
+        .unwrap();
+        assert!(matches!(
+            buffer.read_after(sync::now(device.clone())),
+            Err(ReadAfterError::NoQueue)
+        ));
+
+        let future = sync::now(device)
+            .then_execute(queue, cbb.build().unwrap())
+            .unwrap();
+
+        let mut read_after = buffer.read_after(future).unwrap();
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+
+        let content = loop {
+            match Pin::new(&mut read_after).poll(&mut cx) {
+                Poll::Ready(result) => break result.unwrap(),
+                Poll::Pending => thread::park(),
+            }
+        };
+        assert_eq!(&*content, &[42; 4]);
+    }
 }
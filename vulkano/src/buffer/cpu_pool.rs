@@ -24,6 +24,7 @@ use crate::{
     DeviceSize, OomError,
 };
 use std::{
+    error, fmt,
     hash::{Hash, Hasher},
     marker::PhantomData,
     mem::size_of,
@@ -52,6 +53,11 @@ use std::{
 /// Contrary to a `Vec`, elements automatically free themselves when they are dropped (ie. usually
 /// when you call `cleanup_finished()` on a future, or when you drop that future).
 ///
+/// All the subbuffers are sub-allocated from a single buffer, backed by a single memory
+/// allocation. A new buffer, twice as large, is only allocated when there is no free range left
+/// in the current one. Once all the subbuffers of the previous buffer are dropped, its memory is
+/// freed.
+///
 /// # Arc-like
 ///
 /// The `CpuBufferPool` struct internally contains an `Arc`. You can clone the `CpuBufferPool` for
@@ -559,8 +565,15 @@ where
             let range = (index * size_of::<T>() as DeviceSize + align_offset + mem_off)
                 ..((index + requested_len) * size_of::<T>() as DeviceSize + align_offset + mem_off);
 
+            // If the memory is not host-coherent, the range that is mapped and flushed must be
+            // aligned to `non_coherent_atom_size`. The subbuffers of the pool are tightly packed,
+            // so the aligned range can include parts of neighbouring subbuffers. These are not
+            // written to, so flushing them is harmless.
             let mapped_memory = current_buffer.memory.mapped_memory().unwrap();
-            let bytes = mapped_memory.write(range.clone()).unwrap();
+            let flush_range = mapped_memory.atom_aligned_range(range.clone());
+            let bytes = mapped_memory.write(flush_range.clone()).unwrap();
+            let bytes = &mut bytes[(range.start - flush_range.start) as usize
+                ..(range.end - flush_range.start) as usize];
             let mapping = <[T]>::from_bytes_mut(bytes).unwrap();
 
             let mut written = 0;
@@ -569,7 +582,7 @@ where
                 written += 1;
             }
 
-            mapped_memory.flush_range(range).unwrap();
+            mapped_memory.flush_range(flush_range).unwrap();
 
             assert_eq!(
                 written, requested_len,
@@ -599,6 +612,123 @@ where
     }
 }
 
+/// Fixed-size ring buffer from which "sub-buffers" can be individually allocated.
+///
+/// This is similar to a `CpuBufferPool`, except that its capacity is chosen when it is created
+/// and never grows. If there is no room left for a subbuffer, an error is returned instead of a
+/// new buffer being allocated, which makes memory usage predictable for streaming workloads.
+///
+/// Like with a `CpuBufferPool`, a region of the ring is reclaimed when the subbuffer that occupies
+/// it is dropped. When a subbuffer is used in a command buffer, this happens once the future of
+/// its submission is cleaned up after its fence has signaled, for example through
+/// `cleanup_finished()` or by waiting on a `FenceSignalFuture`. The capacity should therefore be
+/// large enough for all the frames that can be in flight at the same time.
+pub struct CpuBufferRing<T, A = Arc<StdMemoryPool>>
+where
+    [T]: BufferContents,
+    A: MemoryPool,
+{
+    // The pool whose single buffer is used as the ring. It is never allowed to grow.
+    pool: CpuBufferPool<T, A>,
+}
+
+impl<T> CpuBufferRing<T>
+where
+    [T]: BufferContents,
+{
+    /// Builds a `CpuBufferRing` that can hold `capacity` elements, and allocates its memory.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `T` has zero size.
+    /// - Panics if `capacity` is 0.
+    pub fn new(
+        device: Arc<Device>,
+        usage: BufferUsage,
+        capacity: DeviceSize,
+    ) -> Result<CpuBufferRing<T>, DeviceMemoryAllocationError> {
+        assert!(capacity != 0);
+
+        let pool = CpuBufferPool::new(device, usage);
+        pool.reserve(capacity)?;
+
+        Ok(CpuBufferRing { pool })
+    }
+}
+
+impl<T, A> CpuBufferRing<T, A>
+where
+    [T]: BufferContents,
+    A: MemoryPool,
+{
+    /// Returns the capacity of the ring, in number of elements.
+    #[inline]
+    pub fn capacity(&self) -> DeviceSize {
+        self.pool.capacity()
+    }
+
+    /// Grants access to a new subbuffer and puts `data` in it.
+    ///
+    /// Returns an error if there is no room left in the ring, because the previous subbuffers are
+    /// still in use.
+    #[inline]
+    pub fn next(
+        &self,
+        data: T,
+    ) -> Result<Arc<CpuBufferPoolSubbuffer<T, A>>, CpuBufferRingFullError> {
+        let mut mutex = self.pool.current_buffer.lock().unwrap();
+        self.pool
+            .try_next_impl(&mut mutex, [data])
+            .map(|c| Arc::new(CpuBufferPoolSubbuffer { chunk: c }))
+            .map_err(|_| CpuBufferRingFullError)
+    }
+
+    /// Grants access to a new subbuffer and puts `data` in it.
+    ///
+    /// Returns an error if there is no room left in the ring, because the previous subbuffers are
+    /// still in use.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length of the iterator didn't match the actual number of element.
+    ///
+    pub fn chunk<I>(&self, data: I) -> Result<Arc<CpuBufferPoolChunk<T, A>>, CpuBufferRingFullError>
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut mutex = self.pool.current_buffer.lock().unwrap();
+        self.pool
+            .try_next_impl(&mut mutex, data)
+            .map(|c| Arc::new(c))
+            .map_err(|_| CpuBufferRingFullError)
+    }
+}
+
+unsafe impl<T, A> DeviceOwned for CpuBufferRing<T, A>
+where
+    [T]: BufferContents,
+    A: MemoryPool,
+{
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.pool.device()
+    }
+}
+
+/// Error returned when a `CpuBufferRing` has no room left for a subbuffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CpuBufferRingFullError;
+
+impl error::Error for CpuBufferRingFullError {}
+
+impl fmt::Display for CpuBufferRingFullError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "the ring buffer has no room left for the subbuffer")
+    }
+}
+
 // Can't automatically derive `Clone`, otherwise the compiler adds a `T: Clone` requirement.
 impl<T, A> Clone for CpuBufferPool<T, A>
 where
@@ -863,8 +993,10 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::buffer::CpuBufferPool;
+    use super::CpuBufferRingFullError;
+    use crate::buffer::{BufferAccess, BufferUsage, CpuBufferPool, CpuBufferRing};
     use std::mem;
+    use std::sync::Arc;
 
     #[test]
     fn basic_create() {
@@ -939,6 +1071,24 @@ mod tests {
         assert_eq!(pool.capacity(), 5);
     }
 
+    #[test]
+    fn chunks_share_buffer() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let pool = CpuBufferPool::<u32>::upload(device);
+        pool.reserve(16).unwrap();
+
+        let chunks = (0..4)
+            .map(|i| pool.chunk([i; 4]).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(pool.capacity(), 16);
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            assert!(Arc::ptr_eq(chunk.inner().buffer, chunks[0].inner().buffer));
+            assert_eq!(chunk.inner().offset, i as u64 * 16);
+        }
+    }
+
     #[test]
     fn chunk_0_elems_doesnt_pollute() {
         let (device, _) = gfx_dev_and_queue!();
@@ -948,4 +1098,23 @@ mod tests {
         let _ = pool.chunk(vec![]).unwrap();
         let _ = pool.chunk(vec![0, 0]).unwrap();
     }
+
+    #[test]
+    fn ring_doesnt_grow() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let ring = CpuBufferRing::<u8>::new(device, BufferUsage::transfer_src(), 4).unwrap();
+        assert_eq!(ring.capacity(), 4);
+
+        let a = ring.chunk(vec![0, 0]).unwrap();
+        let _b = ring.chunk(vec![0, 0]).unwrap();
+        assert!(matches!(ring.next(0), Err(CpuBufferRingFullError)));
+        assert_eq!(ring.capacity(), 4);
+
+        // Dropping the first chunk makes room at the start of the ring.
+        drop(a);
+        let c = ring.chunk(vec![0, 0]).unwrap();
+        assert_eq!(c.index, 0);
+        assert_eq!(ring.capacity(), 4);
+    }
 }
@@ -85,7 +85,7 @@
 
 pub use self::{
     cpu_access::CpuAccessibleBuffer,
-    cpu_pool::CpuBufferPool,
+    cpu_pool::{CpuBufferPool, CpuBufferRing},
     device_local::DeviceLocalBuffer,
     immutable::ImmutableBuffer,
     slice::BufferSlice,
@@ -37,14 +37,13 @@ use crate::{
     DeviceSize, Error, OomError, Version, VulkanObject,
 };
 use ash::vk::Handle;
-use parking_lot::{Mutex, MutexGuard};
+use parking_lot::{Condvar, Mutex, MutexGuard};
 use smallvec::SmallVec;
 use std::{
     error, fmt,
     hash::{Hash, Hasher},
     mem::MaybeUninit,
     ops::Range,
-    ptr,
     sync::Arc,
 };
 
@@ -55,9 +54,12 @@ pub struct UnsafeBuffer {
     device: Arc<Device>,
 
     size: DeviceSize,
+    sparse: Option<SparseLevel>,
     usage: BufferUsage,
 
     state: Mutex<BufferState>,
+    // Notified whenever the GPU releases its locks on ranges of the buffer.
+    gpu_unlocked: Condvar,
 }
 
 impl UnsafeBuffer {
@@ -163,7 +165,7 @@ impl UnsafeBuffer {
             check_errors((fns.v1_0.create_buffer)(
                 device.internal_object(),
                 &create_info.build(),
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -174,9 +176,11 @@ impl UnsafeBuffer {
             device,
 
             size,
+            sparse,
             usage,
 
             state: Mutex::new(BufferState::new(size)),
+            gpu_unlocked: Condvar::new(),
         };
 
         Ok(Arc::new(buffer))
@@ -283,6 +287,9 @@ impl UnsafeBuffer {
     ) -> Result<(), OomError> {
         let fns = self.device.fns();
 
+        // VUID-vkBindBufferMemory-buffer-01030
+        debug_assert!(self.sparse.is_none());
+
         // We check for correctness in debug mode.
         debug_assert!({
             let mut mem_reqs = MaybeUninit::uninit();
@@ -325,12 +332,29 @@ impl UnsafeBuffer {
         self.state.lock()
     }
 
+    /// Unlocks `state` and blocks the current thread until the GPU releases some of its locks on
+    /// the buffer, then locks `state` again.
+    pub(crate) fn wait_gpu_unlock(&self, state: &mut MutexGuard<BufferState>) {
+        self.gpu_unlocked.wait(state);
+    }
+
+    /// Wakes up the threads that are blocked in `wait_gpu_unlock`.
+    pub(crate) fn notify_gpu_unlock(&self) {
+        self.gpu_unlocked.notify_all();
+    }
+
     /// Returns the size of the buffer in bytes.
     #[inline]
     pub fn size(&self) -> DeviceSize {
         self.size
     }
 
+    /// Returns the level of sparse binding that the buffer was created with, if any.
+    #[inline]
+    pub fn sparse(&self) -> Option<SparseLevel> {
+        self.sparse
+    }
+
     /// Returns the usage the buffer was created with.
     #[inline]
     pub fn usage(&self) -> &BufferUsage {
@@ -349,7 +373,11 @@ impl Drop for UnsafeBuffer {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_buffer)(self.device.internal_object(), self.handle, ptr::null());
+            (fns.v1_0.destroy_buffer)(
+                self.device.internal_object(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
         }
     }
 }
@@ -402,6 +430,9 @@ pub struct UnsafeBufferCreateInfo {
 
     /// Create a buffer with sparsely bound memory.
     ///
+    /// Memory is not bound to a sparse buffer when it is created. Instead, it must be bound with
+    /// [`Queue::bind_sparse`](crate::device::Queue::bind_sparse).
+    ///
     /// The default value is `None`.
     pub sparse: Option<SparseLevel>,
 
@@ -505,7 +536,7 @@ impl From<Error> for BufferCreationError {
     }
 }
 
-/// The level of sparse binding that a buffer should be created with.
+/// The level of sparse binding that a buffer or image should be created with.
 #[derive(Clone, Copy, Debug)]
 pub struct SparseLevel {
     pub sparse_residency: bool,
@@ -8,7 +8,7 @@
 // according to those terms.
 
 use super::{sys::UnsafeBuffer, BufferContents, BufferSlice, BufferUsage};
-use crate::{device::DeviceOwned, DeviceSize, SafeDeref, VulkanObject};
+use crate::{device::DeviceOwned, DeviceSize, SafeDeref, Version, VulkanObject};
 use std::{
     error, fmt,
     hash::{Hash, Hasher},
@@ -59,7 +59,39 @@ pub unsafe trait BufferAccess: DeviceOwned + Send + Sync {
     /// Returns a `BufferSlice` for a single element in the buffer. Returns `None` if out of range.
     ///
     /// This method can be used when you want to perform an operation on a specific element of the
-    /// buffer and not on the whole buffer.
+    /// buffer and not on the whole buffer. The offset and size of the returned slice are derived
+    /// from the size of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::mem::size_of;
+    /// use vulkano::buffer::{BufferAccess, BufferUsage, CpuAccessibleBuffer};
+    /// # let device: std::sync::Arc<vulkano::device::Device> = return;
+    ///
+    /// #[repr(C)]
+    /// #[derive(Clone, Copy, Default, bytemuck::Zeroable, bytemuck::Pod)]
+    /// struct Particle {
+    ///     position: [f32; 2],
+    ///     velocity: [f32; 2],
+    /// }
+    ///
+    /// let buffer = CpuAccessibleBuffer::from_iter(
+    ///     device.clone(),
+    ///     BufferUsage::all(),
+    ///     false,
+    ///     [Particle::default(); 10],
+    /// )
+    /// .unwrap();
+    ///
+    /// // The fifth element of the buffer.
+    /// let element = buffer.index(4).unwrap();
+    /// assert_eq!(element.offset(), 4 * size_of::<Particle>() as u64);
+    /// assert_eq!(element.size(), size_of::<Particle>() as u64);
+    ///
+    /// // Out of range.
+    /// assert!(buffer.index(10).is_none());
+    /// ```
     #[inline]
     fn index<T>(self: &Arc<Self>, index: DeviceSize) -> Option<Arc<BufferSlice<T, Self>>>
     where
@@ -95,13 +127,22 @@ pub unsafe trait BufferAccess: DeviceOwned + Send + Sync {
                 ..Default::default()
             };
             let fns = device.fns();
-            let ptr = (fns.ext_buffer_device_address.get_buffer_device_address_ext)(
-                device.internal_object(),
-                &info,
-            );
+            let ptr = if device.api_version() >= Version::V1_2 {
+                (fns.v1_2.get_buffer_device_address)(device.internal_object(), &info)
+            } else if device.enabled_extensions().khr_buffer_device_address {
+                (fns.khr_buffer_device_address.get_buffer_device_address_khr)(
+                    device.internal_object(),
+                    &info,
+                )
+            } else {
+                (fns.ext_buffer_device_address.get_buffer_device_address_ext)(
+                    device.internal_object(),
+                    &info,
+                )
+            };
 
             if ptr == 0 {
-                panic!("got null ptr from a valid GetBufferDeviceAddressEXT call");
+                panic!("got null ptr from a valid GetBufferDeviceAddress call");
             }
 
             Ok(NonZeroU64::new_unchecked(ptr + inner.offset))
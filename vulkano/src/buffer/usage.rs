@@ -27,6 +27,8 @@ pub struct BufferUsage {
     pub vertex_buffer: bool,
     pub indirect_buffer: bool,
     pub device_address: bool,
+    pub acceleration_structure_build_input_read_only: bool,
+    pub acceleration_structure_storage: bool,
     pub _ne: crate::NonExhaustive,
 }
 
@@ -44,6 +46,8 @@ impl Default for BufferUsage {
             vertex_buffer: false,
             indirect_buffer: false,
             device_address: false,
+            acceleration_structure_build_input_read_only: false,
+            acceleration_structure_storage: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -64,6 +68,8 @@ impl BufferUsage {
             vertex_buffer: false,
             indirect_buffer: false,
             device_address: false,
+            acceleration_structure_build_input_read_only: false,
+            acceleration_structure_storage: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -82,6 +88,8 @@ impl BufferUsage {
             vertex_buffer: true,
             indirect_buffer: true,
             device_address: true,
+            acceleration_structure_build_input_read_only: true,
+            acceleration_structure_storage: true,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -235,6 +243,12 @@ impl From<BufferUsage> for ash::vk::BufferUsageFlags {
         if val.device_address {
             result |= ash::vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS;
         }
+        if val.acceleration_structure_build_input_read_only {
+            result |= ash::vk::BufferUsageFlags::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR;
+        }
+        if val.acceleration_structure_storage {
+            result |= ash::vk::BufferUsageFlags::ACCELERATION_STRUCTURE_STORAGE_KHR;
+        }
         result
     }
 }
@@ -255,6 +269,11 @@ impl BitOr for BufferUsage {
             vertex_buffer: self.vertex_buffer || rhs.vertex_buffer,
             indirect_buffer: self.indirect_buffer || rhs.indirect_buffer,
             device_address: self.device_address || rhs.device_address,
+            acceleration_structure_build_input_read_only: self
+                .acceleration_structure_build_input_read_only
+                || rhs.acceleration_structure_build_input_read_only,
+            acceleration_structure_storage: self.acceleration_structure_storage
+                || rhs.acceleration_structure_storage,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -54,7 +54,6 @@ use std::{
     hash::{Hash, Hasher},
     mem::MaybeUninit,
     ops::Range,
-    ptr,
     sync::Arc,
 };
 
@@ -210,7 +209,7 @@ where
             check_errors((fns.v1_0.create_buffer_view)(
                 device.internal_object(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -244,7 +243,7 @@ where
             (fns.v1_0.destroy_buffer_view)(
                 self.buffer.inner().buffer.device().internal_object(),
                 self.handle,
-                ptr::null(),
+                self.buffer.inner().buffer.device().allocation_callbacks(),
             );
         }
     }
@@ -10,12 +10,12 @@
 use super::{
     commands::pipeline::{
         CheckDescriptorSetsValidityError, CheckDispatchError, CheckDynamicStateValidityError,
-        CheckIndexBufferError, CheckIndirectBufferError, CheckPipelineError,
+        CheckIndexBufferError, CheckIndirectBufferError, CheckMultiDrawError, CheckPipelineError,
         CheckPushConstantsValidityError, CheckVertexBufferError,
     },
     pool::{
         standard::{StandardCommandPoolAlloc, StandardCommandPoolBuilder},
-        CommandPool, CommandPoolAlloc, CommandPoolBuilderAlloc,
+        CommandPool, CommandPoolAlloc, CommandPoolAllocError, CommandPoolBuilderAlloc,
     },
     synced::{
         CommandBufferState, SyncCommandBuffer, SyncCommandBufferBuilder,
@@ -454,6 +454,9 @@ pub enum CommandBufferBeginError {
     /// Not enough memory.
     OomError(OomError),
 
+    /// Allocating the command buffer from the pool failed.
+    CommandPoolAllocError(CommandPoolAllocError),
+
     FeatureNotEnabled {
         feature: &'static str,
         reason: &'static str,
@@ -483,6 +486,7 @@ impl error::Error for CommandBufferBeginError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
             Self::OomError(ref err) => Some(err),
+            Self::CommandPoolAllocError(ref err) => Some(err),
             _ => None,
         }
     }
@@ -493,6 +497,9 @@ impl fmt::Display for CommandBufferBeginError {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match *self {
             Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::CommandPoolAllocError(_) => {
+                write!(f, "allocating the command buffer from the pool failed")
+            }
 
             Self::FeatureNotEnabled { feature, reason } => {
                 write!(f, "the feature {} must be enabled: {}", feature, reason)
@@ -532,6 +539,16 @@ impl From<OomError> for CommandBufferBeginError {
     }
 }
 
+impl From<CommandPoolAllocError> for CommandBufferBeginError {
+    #[inline]
+    fn from(err: CommandPoolAllocError) -> Self {
+        match err {
+            CommandPoolAllocError::OomError(err) => Self::OomError(err),
+            _ => Self::CommandPoolAllocError(err),
+        }
+    }
+}
+
 impl<P> AutoCommandBufferBuilder<PrimaryAutoCommandBuffer<P::Alloc>, P>
 where
     P: CommandPoolBuilderAlloc,
@@ -626,7 +643,9 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             RenderPassStateType::BeginRenderPass(state) => {
                 let pipeline_subpass = match pipeline.render_pass() {
                     PipelineRenderPassType::BeginRenderPass(subpass) => subpass,
-                    PipelineRenderPassType::BeginRendering(_) => todo!(),
+                    PipelineRenderPassType::BeginRendering(_) => {
+                        return Err(AutoCommandBufferBuilderContextError::IncompatibleRenderPass)
+                    }
                 };
 
                 // Subpasses must be the same.
@@ -644,11 +663,56 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             }
             RenderPassStateType::BeginRendering(state) => {
                 let pipeline_rendering_info = match pipeline.render_pass() {
-                    PipelineRenderPassType::BeginRenderPass(_) => todo!(),
+                    PipelineRenderPassType::BeginRenderPass(_) => {
+                        return Err(AutoCommandBufferBuilderContextError::IncompatibleRenderPass)
+                    }
                     PipelineRenderPassType::BeginRendering(rendering_info) => rendering_info,
                 };
 
-                // TODO: checks
+                // VUID-vkCmdDraw-viewMask-06178
+                if pipeline_rendering_info.view_mask != state.view_mask {
+                    return Err(AutoCommandBufferBuilderContextError::IncompatibleRenderPass);
+                }
+
+                // VUID-vkCmdDraw-colorAttachmentCount-06179
+                if pipeline_rendering_info.color_attachment_formats.len()
+                    != state.color_attachments.len()
+                {
+                    return Err(AutoCommandBufferBuilderContextError::IncompatibleRenderPass);
+                }
+
+                // VUID-vkCmdDraw-colorAttachmentCount-06180
+                for (attachment_info, &pipeline_format) in state
+                    .color_attachments
+                    .iter()
+                    .zip(&pipeline_rendering_info.color_attachment_formats)
+                {
+                    if let Some(attachment_info) = attachment_info {
+                        if attachment_info.image_view.format() != pipeline_format {
+                            return Err(
+                                AutoCommandBufferBuilderContextError::IncompatibleRenderPass,
+                            );
+                        }
+                    }
+                }
+
+                // VUID-vkCmdDraw-pDepthAttachment-06181
+                if let Some(attachment_info) = &state.depth_attachment {
+                    if attachment_info.image_view.format()
+                        != pipeline_rendering_info.depth_attachment_format
+                    {
+                        return Err(AutoCommandBufferBuilderContextError::IncompatibleRenderPass);
+                    }
+                }
+
+                // VUID-vkCmdDraw-pStencilAttachment-06182
+                if let Some(attachment_info) = &state.stencil_attachment {
+                    if attachment_info.image_view.format()
+                        != pipeline_rendering_info.stencil_attachment_format
+                    {
+                        return Err(AutoCommandBufferBuilderContextError::IncompatibleRenderPass);
+                    }
+                }
             }
             RenderPassStateType::Inherited => {
                 match self
@@ -660,10 +724,13 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                     .unwrap()
                 {
                     CommandBufferInheritanceRenderPassType::BeginRenderPass(info) => {
-                        let pipeline_subpass = match pipeline.render_pass() {
-                            PipelineRenderPassType::BeginRenderPass(subpass) => subpass,
-                            PipelineRenderPassType::BeginRendering(_) => todo!(),
-                        };
+                        let pipeline_subpass =
+                            match pipeline.render_pass() {
+                                PipelineRenderPassType::BeginRenderPass(subpass) => subpass,
+                                PipelineRenderPassType::BeginRendering(_) => return Err(
+                                    AutoCommandBufferBuilderContextError::IncompatibleRenderPass,
+                                ),
+                            };
 
                         // Subpasses must be the same.
                         if pipeline_subpass.index() != info.subpass.index() {
@@ -680,15 +747,34 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                             );
                         }
                     }
-                    CommandBufferInheritanceRenderPassType::BeginRendering(_) => {
-                        let pipeline_rendering_info = match pipeline.render_pass() {
-                            PipelineRenderPassType::BeginRenderPass(_) => todo!(),
-                            PipelineRenderPassType::BeginRendering(rendering_info) => {
-                                rendering_info
-                            }
-                        };
-
-                        // TODO: checks
+                    CommandBufferInheritanceRenderPassType::BeginRendering(info) => {
+                        let pipeline_rendering_info =
+                            match pipeline.render_pass() {
+                                PipelineRenderPassType::BeginRenderPass(_) => return Err(
+                                    AutoCommandBufferBuilderContextError::IncompatibleRenderPass,
+                                ),
+                                PipelineRenderPassType::BeginRendering(rendering_info) => {
+                                    rendering_info
+                                }
+                            };
+
+                        // VUID-vkCmdDraw-viewMask-06178
+                        // VUID-vkCmdDraw-colorAttachmentCount-06179
+                        // VUID-vkCmdDraw-colorAttachmentCount-06180
+                        // VUID-vkCmdDraw-pDepthAttachment-06181
+                        // VUID-vkCmdDraw-pStencilAttachment-06182
+                        if pipeline_rendering_info.view_mask != info.view_mask
+                            || pipeline_rendering_info.color_attachment_formats
+                                != info.color_attachment_formats
+                            || pipeline_rendering_info.depth_attachment_format
+                                != info.depth_attachment_format
+                            || pipeline_rendering_info.stencil_attachment_format
+                                != info.stencil_attachment_format
+                        {
+                            return Err(
+                                AutoCommandBufferBuilderContextError::IncompatibleRenderPass,
+                            );
+                        }
                     }
                 }
             }
@@ -710,6 +796,17 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
     pub fn state(&self) -> CommandBufferState {
         self.inner.state()
     }
+
+    /// Returns the layout that `image` will be in at the current point of the command buffer.
+    ///
+    /// Layout transitions are inserted automatically before each command that needs the image to
+    /// be in a specific layout; this returns the layout left behind by the latest such command.
+    /// Returns `None` if `image` has not been used yet, or if its subresources are not all in the
+    /// same layout.
+    #[inline]
+    pub fn current_layout(&self, image: &dyn ImageAccess) -> Option<ImageLayout> {
+        self.inner.current_layout(image)
+    }
 }
 
 unsafe impl<L, P> DeviceOwned for AutoCommandBufferBuilder<L, P> {
@@ -1035,6 +1132,7 @@ err_gen!(DrawError {
     CheckPushConstantsValidityError,
     CheckDescriptorSetsValidityError,
     CheckVertexBufferError,
+    CheckMultiDrawError,
     SyncCommandBufferBuilderError,
 });
 
@@ -1046,6 +1144,7 @@ err_gen!(DrawIndexedError {
     CheckDescriptorSetsValidityError,
     CheckVertexBufferError,
     CheckIndexBufferError,
+    CheckMultiDrawError,
     SyncCommandBufferBuilderError,
 });
 
@@ -1166,8 +1265,15 @@ mod tests {
     use super::*;
     use crate::{
         buffer::{BufferUsage, CpuAccessibleBuffer},
-        command_buffer::{BufferCopy, CopyBufferInfoTyped, CopyError, ExecuteCommandsError},
+        command_buffer::{
+            BufferCopy, ClearColorImageInfo, CopyBufferInfoTyped, CopyError, ExecuteCommandsError,
+            RenderPassBeginInfo, RenderPassError,
+        },
         device::{physical::PhysicalDevice, DeviceCreateInfo, QueueCreateInfo},
+        format::Format,
+        image::{view::ImageView, AttachmentImage, ImageDimensions, StorageImage},
+        query::{QueryPool, QueryPoolCreateInfo},
+        render_pass::FramebufferCreateInfo,
     };
 
     #[test]
@@ -1316,6 +1422,137 @@ mod tests {
         }
     }
 
+    #[test]
+    fn secondary_render_pass_outside_render_pass() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let render_pass = crate::single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        )
+        .unwrap();
+
+        let builder = AutoCommandBufferBuilder::secondary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::MultipleSubmit,
+            CommandBufferInheritanceInfo {
+                render_pass: Some(
+                    CommandBufferInheritanceRenderPassInfo::subpass(
+                        Subpass::from(render_pass, 0).unwrap(),
+                    )
+                    .into(),
+                ),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let secondary = Arc::new(builder.build().unwrap());
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // A secondary command buffer that inherits a render pass can only be executed inside
+        // that render pass.
+        assert!(matches!(
+            builder.execute_commands(secondary),
+            Err(ExecuteCommandsError::RenderPassInheritanceForbidden {
+                command_buffer_index: 0,
+            })
+        ));
+    }
+
+    #[test]
+    fn render_pass_load_undefined_contents() {
+        let instance = instance!();
+
+        let phys = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let queue_family = match phys.queue_families().find(|q| q.supports_graphics()) {
+            Some(q) => q,
+            None => return,
+        };
+
+        let (device, mut queues) = Device::new(
+            phys,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo::family(queue_family)],
+                validate_attachment_contents: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let queue = queues.next().unwrap();
+
+        let render_pass = crate::single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Load,
+                    store: Store,
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        )
+        .unwrap();
+
+        let view = ImageView::new_default(
+            AttachmentImage::new(device.clone(), [4, 4], Format::R8G8B8A8_UNORM).unwrap(),
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                attachments: vec![view],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // The image was just created, so loading it would read garbage.
+        assert!(matches!(
+            builder.begin_render_pass(
+                RenderPassBeginInfo {
+                    clear_values: vec![None],
+                    ..RenderPassBeginInfo::framebuffer(framebuffer)
+                },
+                SubpassContents::Inline,
+            ),
+            Err(RenderPassError::AttachmentContentsUndefined {
+                attachment_index: 0,
+            })
+        ));
+    }
+
     #[test]
     fn buffer_self_copy_overlapping() {
         let (device, queue) = gfx_dev_and_queue!();
@@ -1398,4 +1635,129 @@ mod tests {
             })
         ));
     }
+
+    #[test]
+    fn current_layout() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let image = StorageImage::new(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 4,
+                height: 4,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8_UNORM,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert_eq!(builder.current_layout(&*image), None);
+
+        builder
+            .clear_color_image(ClearColorImageInfo::image(image.clone()))
+            .unwrap();
+
+        assert_eq!(
+            builder.current_layout(&*image),
+            Some(ImageLayout::TransferDstOptimal)
+        );
+    }
+
+    #[test]
+    fn secondary_occlusion_query_feature_not_enabled() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        assert!(matches!(
+            AutoCommandBufferBuilder::secondary(
+                device.clone(),
+                queue.family(),
+                CommandBufferUsage::OneTimeSubmit,
+                CommandBufferInheritanceInfo {
+                    occlusion_query: Some(QueryControlFlags { precise: false }),
+                    ..Default::default()
+                },
+            ),
+            Err(CommandBufferBeginError::FeatureNotEnabled {
+                feature: "inherited_queries",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn secondary_inherits_occlusion_query() {
+        let (device, queue) = gfx_dev_and_queue!(inherited_queries);
+
+        let query_pool = QueryPool::new(
+            device.clone(),
+            QueryPoolCreateInfo {
+                query_count: 1,
+                ..QueryPoolCreateInfo::query_type(QueryType::Occlusion)
+            },
+        )
+        .unwrap();
+
+        let inheriting = Arc::new(
+            AutoCommandBufferBuilder::secondary(
+                device.clone(),
+                queue.family(),
+                CommandBufferUsage::MultipleSubmit,
+                CommandBufferInheritanceInfo {
+                    occlusion_query: Some(QueryControlFlags { precise: false }),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .build()
+            .unwrap(),
+        );
+
+        let not_inheriting = Arc::new(
+            AutoCommandBufferBuilder::secondary(
+                device.clone(),
+                queue.family(),
+                CommandBufferUsage::MultipleSubmit,
+                Default::default(),
+            )
+            .unwrap()
+            .build()
+            .unwrap(),
+        );
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        unsafe {
+            builder
+                .reset_query_pool(query_pool.clone(), 0..1)
+                .unwrap()
+                .begin_query(query_pool.clone(), 0, QueryControlFlags { precise: false })
+                .unwrap();
+        }
+
+        builder.execute_commands(inheriting).unwrap();
+
+        // A secondary command buffer that doesn't inherit the occlusion query can't be executed
+        // while the query is active.
+        assert!(matches!(
+            builder.execute_commands(not_inheriting),
+            Err(ExecuteCommandsError::OcclusionQueryInheritanceRequired {
+                command_buffer_index: 0,
+            })
+        ));
+
+        builder.end_query(query_pool, 0).unwrap();
+    }
 }
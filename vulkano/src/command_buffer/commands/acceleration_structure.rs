@@ -0,0 +1,685 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    acceleration_structure::{
+        AccelerationStructureBuildGeometryInfo, AccelerationStructureBuildRangeInfo,
+        AccelerationStructureGeometries, AccelerationStructureGeometryAabbsData,
+        AccelerationStructureGeometryInstancesData, AccelerationStructureGeometryTrianglesData,
+        AccelerationStructureType, BuildAccelerationStructureMode,
+    },
+    buffer::BufferAccess,
+    command_buffer::{
+        synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder,
+    },
+    device::{Device, DeviceOwned},
+    sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
+    DeviceSize,
+};
+use smallvec::SmallVec;
+use std::{error, fmt, sync::Arc};
+
+/// # Commands to build acceleration structures.
+///
+/// These commands require a queue with a pipeline type that uses acceleration structures, such
+/// as ray tracing.
+impl<L, P> AutoCommandBufferBuilder<L, P> {
+    /// Builds or updates acceleration structures.
+    ///
+    /// Each element of `infos` contains the parameters of one build, together with one
+    /// [`AccelerationStructureBuildRangeInfo`] for each of its geometries.
+    ///
+    /// # Safety
+    ///
+    /// - The geometry data buffers must contain valid data for the ranges that are built.
+    /// - For top-level acceleration structures, the instances must refer to bottom-level
+    ///   acceleration structures that are valid and built when the command is executed.
+    /// - The scratch buffers must be at least as large as the scratch size returned by
+    ///   [`AccelerationStructure::build_sizes`](crate::acceleration_structure::AccelerationStructure::build_sizes).
+    /// - For updates, the source acceleration structure must have been built with the
+    ///   `allow_update` flag, from the same number and types of geometries.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the number of build range infos of an element of `infos` is not equal to the
+    ///   number of its geometries.
+    /// - Panics if any of the acceleration structures or buffers were not created from the same
+    ///   device as `self`.
+    pub unsafe fn build_acceleration_structures(
+        &mut self,
+        infos: impl IntoIterator<
+            Item = (
+                AccelerationStructureBuildGeometryInfo,
+                Vec<AccelerationStructureBuildRangeInfo>,
+            ),
+        >,
+    ) -> Result<&mut Self, AccelerationStructureBuildError> {
+        let infos: SmallVec<[_; 1]> = infos.into_iter().collect();
+        self.validate_build_acceleration_structures(&infos)?;
+
+        self.inner.build_acceleration_structures(infos)?;
+
+        Ok(self)
+    }
+
+    fn validate_build_acceleration_structures(
+        &self,
+        infos: &[(
+            AccelerationStructureBuildGeometryInfo,
+            Vec<AccelerationStructureBuildRangeInfo>,
+        )],
+    ) -> Result<(), AccelerationStructureBuildError> {
+        let device = self.device();
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-commandBuffer-parameter
+        if !device.enabled_extensions().khr_acceleration_structure {
+            return Err(AccelerationStructureBuildError::ExtensionNotEnabled {
+                extension: "khr_acceleration_structure",
+                reason: "called build_acceleration_structures",
+            });
+        }
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(AccelerationStructureBuildError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-commandBuffer-cmdpool
+        if !self.queue_family().supports_compute() {
+            return Err(AccelerationStructureBuildError::NotSupportedByQueueFamily);
+        }
+
+        let properties = device.physical_device().properties();
+
+        for (info_index, (info, build_range_infos)) in infos.iter().enumerate() {
+            let AccelerationStructureBuildGeometryInfo {
+                flags: _,
+                mode,
+                geometries,
+                dst_acceleration_structure,
+                scratch_data,
+                _ne: _,
+            } = info;
+
+            // VUID-vkCmdBuildAccelerationStructuresKHR-ppBuildRangeInfos-03676
+            assert_eq!(
+                build_range_infos.len(),
+                geometries.len(),
+                "the number of build range infos must be equal to the number of geometries",
+            );
+
+            // VUID-vkCmdBuildAccelerationStructuresKHR-dstAccelerationStructure-03800
+            let dst_acceleration_structure = dst_acceleration_structure.as_ref().ok_or(
+                AccelerationStructureBuildError::DstAccelerationStructureMissing { info_index },
+            )?;
+
+            // VUID-vkCmdBuildAccelerationStructuresKHR-commonparent
+            assert_eq!(device, dst_acceleration_structure.device());
+
+            // VUID-VkAccelerationStructureBuildGeometryInfoKHR-type-03789
+            // VUID-VkAccelerationStructureBuildGeometryInfoKHR-type-03791
+            match (dst_acceleration_structure.ty(), geometries) {
+                (AccelerationStructureType::Generic, _)
+                | (
+                    AccelerationStructureType::TopLevel,
+                    AccelerationStructureGeometries::Instances(_),
+                )
+                | (
+                    AccelerationStructureType::BottomLevel,
+                    AccelerationStructureGeometries::Triangles(_)
+                    | AccelerationStructureGeometries::Aabbs(_),
+                ) => (),
+                _ => {
+                    return Err(
+                        AccelerationStructureBuildError::AccelerationStructureTypeMismatch {
+                            info_index,
+                        },
+                    )
+                }
+            }
+
+            if let BuildAccelerationStructureMode::Update(src_acceleration_structure) = mode {
+                // VUID-vkCmdBuildAccelerationStructuresKHR-commonparent
+                assert_eq!(device, src_acceleration_structure.device());
+            }
+
+            // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03802
+            let scratch_data = scratch_data
+                .as_ref()
+                .ok_or(AccelerationStructureBuildError::ScratchDataMissing { info_index })?;
+
+            // VUID-vkCmdBuildAccelerationStructuresKHR-commonparent
+            assert_eq!(device, scratch_data.device());
+
+            // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03674
+            if !scratch_data.inner().buffer.usage().storage_buffer {
+                return Err(AccelerationStructureBuildError::BufferMissingUsage {
+                    info_index,
+                    usage: "storage_buffer",
+                });
+            }
+
+            let scratch_address = scratch_data.raw_device_address().map_err(|_| {
+                AccelerationStructureBuildError::BufferMissingUsage {
+                    info_index,
+                    usage: "device_address",
+                }
+            })?;
+
+            // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03710
+            let required_alignment = properties
+                .min_acceleration_structure_scratch_offset_alignment
+                .unwrap() as DeviceSize;
+
+            if scratch_address.get() % required_alignment != 0 {
+                return Err(AccelerationStructureBuildError::ScratchDataNotAligned {
+                    info_index,
+                    address: scratch_address.get(),
+                    required_alignment,
+                });
+            }
+
+            match geometries {
+                AccelerationStructureGeometries::Triangles(geometries) => {
+                    for (geometry_index, triangles_data) in geometries.iter().enumerate() {
+                        let AccelerationStructureGeometryTrianglesData {
+                            flags: _,
+                            vertex_format,
+                            vertex_data,
+                            vertex_stride: _,
+                            max_vertex: _,
+                            index_data,
+                            index_type: _,
+                            transform_data,
+                            _ne: _,
+                        } = triangles_data;
+
+                        // VUID-VkAccelerationStructureGeometryTrianglesDataKHR-vertexFormat-03797
+                        if !device
+                            .physical_device()
+                            .format_properties(*vertex_format)
+                            .buffer_features
+                            .acceleration_structure_vertex_buffer
+                        {
+                            return Err(
+                                AccelerationStructureBuildError::VertexFormatNotSupported {
+                                    info_index,
+                                    geometry_index,
+                                },
+                            );
+                        }
+
+                        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03804
+                        let vertex_data = vertex_data.as_ref().ok_or(
+                            AccelerationStructureBuildError::GeometryDataMissing {
+                                info_index,
+                                geometry_index,
+                            },
+                        )?;
+                        validate_geometry_buffer(device, vertex_data, info_index)?;
+
+                        if let Some(index_data) = index_data {
+                            validate_geometry_buffer(device, index_data, info_index)?;
+                        }
+
+                        if let Some(transform_data) = transform_data {
+                            validate_geometry_buffer(device, transform_data, info_index)?;
+                        }
+                    }
+                }
+                AccelerationStructureGeometries::Aabbs(geometries) => {
+                    for (geometry_index, aabbs_data) in geometries.iter().enumerate() {
+                        let AccelerationStructureGeometryAabbsData {
+                            flags: _,
+                            data,
+                            stride,
+                            _ne: _,
+                        } = aabbs_data;
+
+                        // VUID-VkAccelerationStructureGeometryAabbsDataKHR-stride-03545
+                        if stride % 8 != 0 {
+                            return Err(AccelerationStructureBuildError::AabbsStrideNotAligned {
+                                info_index,
+                                geometry_index,
+                                stride: *stride,
+                            });
+                        }
+
+                        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03811
+                        let data = data.as_ref().ok_or(
+                            AccelerationStructureBuildError::GeometryDataMissing {
+                                info_index,
+                                geometry_index,
+                            },
+                        )?;
+                        validate_geometry_buffer(device, data, info_index)?;
+                    }
+                }
+                AccelerationStructureGeometries::Instances(instances_data) => {
+                    let AccelerationStructureGeometryInstancesData {
+                        flags: _,
+                        data,
+                        _ne: _,
+                    } = instances_data;
+
+                    // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03813
+                    let data = data.as_ref().ok_or(
+                        AccelerationStructureBuildError::GeometryDataMissing {
+                            info_index,
+                            geometry_index: 0,
+                        },
+                    )?;
+                    validate_geometry_buffer(device, data, info_index)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Checks that `buffer` can be used as input data for an acceleration structure build.
+fn validate_geometry_buffer(
+    device: &Device,
+    buffer: &Arc<dyn BufferAccess>,
+    info_index: usize,
+) -> Result<(), AccelerationStructureBuildError> {
+    // VUID-vkCmdBuildAccelerationStructuresKHR-commonparent
+    assert_eq!(device, buffer.device().as_ref());
+
+    // VUID-vkCmdBuildAccelerationStructuresKHR-geometry-03673
+    if !buffer
+        .inner()
+        .buffer
+        .usage()
+        .acceleration_structure_build_input_read_only
+    {
+        return Err(AccelerationStructureBuildError::BufferMissingUsage {
+            info_index,
+            usage: "acceleration_structure_build_input_read_only",
+        });
+    }
+
+    buffer.raw_device_address().map_err(|_| {
+        AccelerationStructureBuildError::BufferMissingUsage {
+            info_index,
+            usage: "device_address",
+        }
+    })?;
+
+    Ok(())
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdBuildAccelerationStructuresKHR` on the builder.
+    #[inline]
+    pub unsafe fn build_acceleration_structures(
+        &mut self,
+        infos: SmallVec<
+            [(
+                AccelerationStructureBuildGeometryInfo,
+                Vec<AccelerationStructureBuildRangeInfo>,
+            ); 1],
+        >,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            infos: SmallVec<
+                [(
+                    AccelerationStructureBuildGeometryInfo,
+                    Vec<AccelerationStructureBuildRangeInfo>,
+                ); 1],
+            >,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "build_acceleration_structures"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.build_acceleration_structures(&self.infos);
+            }
+        }
+
+        let stages = PipelineStages {
+            acceleration_structure_build: true,
+            ..PipelineStages::none()
+        };
+        let mut resources = Vec::new();
+
+        for (info_index, (info, _)) in infos.iter().enumerate() {
+            let AccelerationStructureBuildGeometryInfo {
+                flags: _,
+                mode,
+                geometries,
+                dst_acceleration_structure,
+                scratch_data,
+                _ne: _,
+            } = info;
+
+            if let BuildAccelerationStructureMode::Update(src_acceleration_structure) = mode {
+                if Some(src_acceleration_structure) != dst_acceleration_structure.as_ref() {
+                    let buffer = src_acceleration_structure.buffer();
+                    resources.push((
+                        format!("src_acceleration_structure of info {}", info_index).into(),
+                        Resource::Buffer {
+                            buffer: buffer.clone(),
+                            range: 0..buffer.size(),
+                            memory: PipelineMemoryAccess {
+                                stages,
+                                access: AccessFlags {
+                                    acceleration_structure_read: true,
+                                    ..AccessFlags::none()
+                                },
+                                exclusive: false,
+                            },
+                        },
+                    ));
+                }
+            }
+
+            let buffer = dst_acceleration_structure.as_ref().unwrap().buffer();
+            resources.push((
+                format!("dst_acceleration_structure of info {}", info_index).into(),
+                Resource::Buffer {
+                    buffer: buffer.clone(),
+                    range: 0..buffer.size(),
+                    memory: PipelineMemoryAccess {
+                        stages,
+                        access: AccessFlags {
+                            acceleration_structure_read: true,
+                            acceleration_structure_write: true,
+                            ..AccessFlags::none()
+                        },
+                        exclusive: true,
+                    },
+                },
+            ));
+
+            let buffer = scratch_data.as_ref().unwrap();
+            resources.push((
+                format!("scratch_data of info {}", info_index).into(),
+                Resource::Buffer {
+                    buffer: buffer.clone(),
+                    range: 0..buffer.size(),
+                    memory: PipelineMemoryAccess {
+                        stages,
+                        access: AccessFlags {
+                            acceleration_structure_read: true,
+                            acceleration_structure_write: true,
+                            ..AccessFlags::none()
+                        },
+                        exclusive: true,
+                    },
+                },
+            ));
+
+            let geometry_buffers: SmallVec<[_; 4]> = match geometries {
+                AccelerationStructureGeometries::Triangles(geometries) => geometries
+                    .iter()
+                    .flat_map(|triangles_data| {
+                        [
+                            &triangles_data.vertex_data,
+                            &triangles_data.index_data,
+                            &triangles_data.transform_data,
+                        ]
+                    })
+                    .flatten()
+                    .collect(),
+                AccelerationStructureGeometries::Aabbs(geometries) => geometries
+                    .iter()
+                    .filter_map(|aabbs_data| aabbs_data.data.as_ref())
+                    .collect(),
+                AccelerationStructureGeometries::Instances(instances_data) => {
+                    instances_data.data.iter().collect()
+                }
+            };
+
+            for buffer in geometry_buffers {
+                resources.push((
+                    format!("geometry data of info {}", info_index).into(),
+                    Resource::Buffer {
+                        buffer: buffer.clone(),
+                        range: 0..buffer.size(),
+                        memory: PipelineMemoryAccess {
+                            stages,
+                            access: AccessFlags {
+                                shader_read: true,
+                                ..AccessFlags::none()
+                            },
+                            exclusive: false,
+                        },
+                    },
+                ));
+            }
+        }
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd { infos }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdBuildAccelerationStructuresKHR` on the builder.
+    #[inline]
+    pub unsafe fn build_acceleration_structures(
+        &mut self,
+        infos: &[(
+            AccelerationStructureBuildGeometryInfo,
+            Vec<AccelerationStructureBuildRangeInfo>,
+        )],
+    ) {
+        if infos.is_empty() {
+            return;
+        }
+
+        let geometries_vk: SmallVec<[_; 1]> = infos
+            .iter()
+            .map(|(info, _)| info.to_vulkan_geometries())
+            .collect();
+        let infos_vk: SmallVec<[_; 1]> = infos
+            .iter()
+            .zip(geometries_vk.iter())
+            .map(|((info, _), geometries_vk)| info.to_vulkan(geometries_vk))
+            .collect();
+        let build_range_infos_vk: SmallVec<[SmallVec<[_; 1]>; 1]> = infos
+            .iter()
+            .map(|(_, build_range_infos)| {
+                build_range_infos
+                    .iter()
+                    .copied()
+                    .map(ash::vk::AccelerationStructureBuildRangeInfoKHR::from)
+                    .collect()
+            })
+            .collect();
+        let build_range_info_ptrs_vk: SmallVec<[_; 1]> = build_range_infos_vk
+            .iter()
+            .map(|build_range_infos_vk| build_range_infos_vk.as_ptr())
+            .collect();
+
+        let fns = self.device.fns();
+        (fns.khr_acceleration_structure
+            .cmd_build_acceleration_structures_khr)(
+            self.handle,
+            infos_vk.len() as u32,
+            infos_vk.as_ptr(),
+            build_range_info_ptrs_vk.as_ptr(),
+        );
+    }
+}
+
+/// Error that can happen when recording an acceleration structure build command.
+#[derive(Clone, Debug)]
+pub enum AccelerationStructureBuildError {
+    SyncCommandBufferBuilderError(SyncCommandBufferBuilderError),
+
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+
+    /// The stride of an AABBs geometry is not a multiple of 8.
+    AabbsStrideNotAligned {
+        info_index: usize,
+        geometry_index: usize,
+        stride: u32,
+    },
+
+    /// The type of the destination acceleration structure does not match the type of the
+    /// geometries.
+    AccelerationStructureTypeMismatch {
+        info_index: usize,
+    },
+
+    /// A buffer was not created with a usage that is required.
+    BufferMissingUsage {
+        info_index: usize,
+        usage: &'static str,
+    },
+
+    /// No destination acceleration structure was provided.
+    DstAccelerationStructureMissing {
+        info_index: usize,
+    },
+
+    /// Operation forbidden inside of a render pass.
+    ForbiddenInsideRenderPass,
+
+    /// No data buffer was provided for a geometry.
+    GeometryDataMissing {
+        info_index: usize,
+        geometry_index: usize,
+    },
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// No scratch buffer was provided.
+    ScratchDataMissing {
+        info_index: usize,
+    },
+
+    /// The device address of the scratch buffer is not a multiple of the required alignment.
+    ScratchDataNotAligned {
+        info_index: usize,
+        address: u64,
+        required_alignment: DeviceSize,
+    },
+
+    /// The vertex format of a triangles geometry does not support the
+    /// `acceleration_structure_vertex_buffer` format feature.
+    VertexFormatNotSupported {
+        info_index: usize,
+        geometry_index: usize,
+    },
+}
+
+impl error::Error for AccelerationStructureBuildError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::SyncCommandBufferBuilderError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AccelerationStructureBuildError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::SyncCommandBufferBuilderError(_) => write!(f, "a SyncCommandBufferBuilderError"),
+
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                f,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+
+            Self::AabbsStrideNotAligned {
+                info_index,
+                geometry_index,
+                stride,
+            } => write!(
+                f,
+                "the stride ({}) of AABBs geometry {} of info {} is not a multiple of 8",
+                stride, geometry_index, info_index,
+            ),
+            Self::AccelerationStructureTypeMismatch { info_index } => write!(
+                f,
+                "the type of the destination acceleration structure of info {} does not match the type of the geometries",
+                info_index,
+            ),
+            Self::BufferMissingUsage { info_index, usage } => write!(
+                f,
+                "a buffer of info {} was not created with the `{}` usage",
+                info_index, usage,
+            ),
+            Self::DstAccelerationStructureMissing { info_index } => write!(
+                f,
+                "no destination acceleration structure was provided for info {}",
+                info_index,
+            ),
+            Self::ForbiddenInsideRenderPass => {
+                write!(f, "operation forbidden inside of a render pass")
+            }
+            Self::GeometryDataMissing {
+                info_index,
+                geometry_index,
+            } => write!(
+                f,
+                "no data buffer was provided for geometry {} of info {}",
+                geometry_index, info_index,
+            ),
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::ScratchDataMissing { info_index } => write!(
+                f,
+                "no scratch buffer was provided for info {}",
+                info_index,
+            ),
+            Self::ScratchDataNotAligned {
+                info_index,
+                address,
+                required_alignment,
+            } => write!(
+                f,
+                "the device address ({}) of the scratch buffer of info {} is not a multiple of the required alignment ({})",
+                address, info_index, required_alignment,
+            ),
+            Self::VertexFormatNotSupported {
+                info_index,
+                geometry_index,
+            } => write!(
+                f,
+                "the vertex format of geometry {} of info {} does not support the `acceleration_structure_vertex_buffer` format feature",
+                geometry_index, info_index,
+            ),
+        }
+    }
+}
+
+impl From<SyncCommandBufferBuilderError> for AccelerationStructureBuildError {
+    #[inline]
+    fn from(err: SyncCommandBufferBuilderError) -> Self {
+        Self::SyncCommandBufferBuilderError(err)
+    }
+}
@@ -406,6 +406,9 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
     ///   [`khr_push_descriptor`](crate::device::DeviceExtensions::khr_push_descriptor)
     ///   extension is not enabled on the device.
     /// - Panics if `set_num` is not less than the number of sets in `pipeline_layout`.
+    /// - Panics if the descriptor set layout at `set_num` in `pipeline_layout` was not created
+    ///   with the [`push_descriptor`](crate::descriptor_set::layout::DescriptorSetLayoutCreateInfo::push_descriptor)
+    ///   flag.
     /// - Panics if an element of `descriptor_writes` is not compatible with `pipeline_layout`.
     pub fn push_descriptor_set(
         &mut self,
@@ -429,14 +432,22 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             self.device().enabled_extensions().khr_push_descriptor,
             "the khr_push_descriptor extension must be enabled on the device"
         );
+        // VUID-vkCmdPushDescriptorSetKHR-set-00364
         assert!(
-            set_num as usize <= pipeline_layout.set_layouts().len(),
+            (set_num as usize) < pipeline_layout.set_layouts().len(),
             "the descriptor set slot being bound must be less than the number of sets in pipeline_layout"
         );
 
         let descriptor_writes: SmallVec<[_; 8]> = descriptor_writes.into_iter().collect();
         let descriptor_set_layout = &pipeline_layout.set_layouts()[set_num as usize];
 
+        // VUID-vkCmdPushDescriptorSetKHR-set-00365
+        assert!(
+            descriptor_set_layout.push_descriptor(),
+            "the descriptor set layout at slot {} of pipeline_layout must have been created with the push_descriptor flag",
+            set_num,
+        );
+
         for write in &descriptor_writes {
             check_descriptor_write(write, descriptor_set_layout, 0).unwrap();
         }
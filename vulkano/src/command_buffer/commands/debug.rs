@@ -16,13 +16,18 @@ use crate::{
     device::DeviceOwned,
     instance::debug::DebugUtilsLabel,
 };
-use std::{error, ffi::CString, fmt};
+use std::{
+    error,
+    ffi::CString,
+    fmt,
+    ops::{Deref, DerefMut},
+};
 
 /// # Commands for debugging.
 ///
-/// These commands all require the
-/// [`ext_debug_utils`](crate::instance::InstanceExtensions::ext_debug_utils) to be enabled on the
-/// instance.
+/// These commands do nothing if the
+/// [`ext_debug_utils`](crate::instance::InstanceExtensions::ext_debug_utils) extension is not
+/// enabled on the instance, so that labels can be left in place in release builds.
 impl<L, P> AutoCommandBufferBuilder<L, P> {
     /// Opens a command buffer debug label region.
     #[inline]
@@ -30,6 +35,15 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         &mut self,
         mut label_info: DebugUtilsLabel,
     ) -> Result<&mut Self, DebugUtilsError> {
+        if !self
+            .device()
+            .instance()
+            .enabled_extensions()
+            .ext_debug_utils
+        {
+            return Ok(self);
+        }
+
         self.validate_begin_debug_utils_label(&mut label_info)?;
 
         unsafe {
@@ -43,18 +57,6 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         &self,
         label_info: &mut DebugUtilsLabel,
     ) -> Result<(), DebugUtilsError> {
-        if !self
-            .device()
-            .instance()
-            .enabled_extensions()
-            .ext_debug_utils
-        {
-            return Err(DebugUtilsError::ExtensionNotEnabled {
-                extension: "ext_debug_utils",
-                reason: "tried to record a debug utils command",
-            });
-        }
-
         // VUID-vkCmdBeginDebugUtilsLabelEXT-commandBuffer-cmdpool
         if !(self.queue_family().supports_graphics() || self.queue_family().supports_compute()) {
             return Err(DebugUtilsError::NotSupportedByQueueFamily);
@@ -72,26 +74,23 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
     ///   buffer or a previously submitted one.
     #[inline]
     pub unsafe fn end_debug_utils_label(&mut self) -> Result<&mut Self, DebugUtilsError> {
-        self.validate_end_debug_utils_label()?;
-
-        self.inner.end_debug_utils_label();
-
-        Ok(self)
-    }
-
-    fn validate_end_debug_utils_label(&self) -> Result<(), DebugUtilsError> {
         if !self
             .device()
             .instance()
             .enabled_extensions()
             .ext_debug_utils
         {
-            return Err(DebugUtilsError::ExtensionNotEnabled {
-                extension: "ext_debug_utils",
-                reason: "tried to record a debug utils command",
-            });
+            return Ok(self);
         }
 
+        self.validate_end_debug_utils_label()?;
+
+        self.inner.end_debug_utils_label();
+
+        Ok(self)
+    }
+
+    fn validate_end_debug_utils_label(&self) -> Result<(), DebugUtilsError> {
         // VUID-vkCmdEndDebugUtilsLabelEXT-commandBuffer-cmdpool
         if !(self.queue_family().supports_graphics() || self.queue_family().supports_compute()) {
             return Err(DebugUtilsError::NotSupportedByQueueFamily);
@@ -106,12 +105,39 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         Ok(())
     }
 
+    /// Opens a command buffer debug label region, which is closed when the returned guard is
+    /// dropped.
+    ///
+    /// The guard borrows the builder mutably and dereferences to it, so that commands can be
+    /// recorded inside the region through the guard. Regions can be nested by calling this
+    /// function on the guard itself. Unlike with
+    /// [`end_debug_utils_label`](Self::end_debug_utils_label), the region is always closed within
+    /// this command buffer, even in the presence of early returns.
+    #[inline]
+    pub fn debug_label_scope(
+        &mut self,
+        label_info: DebugUtilsLabel,
+    ) -> Result<DebugLabelGuard<'_, L, P>, DebugUtilsError> {
+        self.begin_debug_utils_label(label_info)?;
+
+        Ok(DebugLabelGuard { builder: self })
+    }
+
     /// Inserts a command buffer debug label.
     #[inline]
     pub fn insert_debug_utils_label(
         &mut self,
         mut label_info: DebugUtilsLabel,
     ) -> Result<&mut Self, DebugUtilsError> {
+        if !self
+            .device()
+            .instance()
+            .enabled_extensions()
+            .ext_debug_utils
+        {
+            return Ok(self);
+        }
+
         self.validate_insert_debug_utils_label(&mut label_info)?;
 
         unsafe {
@@ -125,18 +151,6 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         &self,
         label_info: &mut DebugUtilsLabel,
     ) -> Result<(), DebugUtilsError> {
-        if !self
-            .device()
-            .instance()
-            .enabled_extensions()
-            .ext_debug_utils
-        {
-            return Err(DebugUtilsError::ExtensionNotEnabled {
-                extension: "ext_debug_utils",
-                reason: "tried to record a debug utils command",
-            });
-        }
-
         // VUID-vkCmdInsertDebugUtilsLabelEXT-commandBuffer-cmdpool
         if !(self.queue_family().supports_graphics() || self.queue_family().supports_compute()) {
             return Err(DebugUtilsError::NotSupportedByQueueFamily);
@@ -146,6 +160,40 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
     }
 }
 
+/// A command buffer debug label region that is closed when this guard is dropped.
+///
+/// Returned by [`AutoCommandBufferBuilder::debug_label_scope`].
+pub struct DebugLabelGuard<'a, L, P> {
+    builder: &'a mut AutoCommandBufferBuilder<L, P>,
+}
+
+impl<'a, L, P> Deref for DebugLabelGuard<'a, L, P> {
+    type Target = AutoCommandBufferBuilder<L, P>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.builder
+    }
+}
+
+impl<'a, L, P> DerefMut for DebugLabelGuard<'a, L, P> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.builder
+    }
+}
+
+impl<'a, L, P> Drop for DebugLabelGuard<'a, L, P> {
+    #[inline]
+    fn drop(&mut self) {
+        // The region was opened by `debug_label_scope` in this same command buffer, and the
+        // validation is the same as when opening it, so this can't fail.
+        unsafe {
+            self.builder.end_debug_utils_label().unwrap();
+        }
+    }
+}
+
 impl SyncCommandBufferBuilder {
     /// Calls `vkCmdBeginDebugUtilsLabelEXT` on the builder.
     ///
@@ -18,6 +18,7 @@ use crate::{
         graphics::{
             color_blend::LogicOp,
             depth_stencil::{CompareOp, StencilFaces, StencilOp, StencilOps},
+            fragment_shading_rate::{FragmentShadingRate, FragmentShadingRateCombinerOp},
             input_assembly::PrimitiveTopology,
             rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
             viewport::{Scissor, Viewport},
@@ -580,6 +581,148 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         Ok(())
     }
 
+    /// Sets the dynamic fragment shading rate for future draw calls.
+    ///
+    /// `combiner_ops[0]` combines `fragment_size` with the rate of the primitive, and
+    /// `combiner_ops[1]` combines that result with the rate of the fragment shading rate
+    /// attachment.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`khr_fragment_shading_rate`](crate::device::DeviceExtensions::khr_fragment_shading_rate)
+    ///   extension is not enabled on the device.
+    /// - Panics if none of the
+    ///   [`pipeline_fragment_shading_rate`](crate::device::Features::pipeline_fragment_shading_rate),
+    ///   [`primitive_fragment_shading_rate`](crate::device::Features::primitive_fragment_shading_rate)
+    ///   or
+    ///   [`attachment_fragment_shading_rate`](crate::device::Features::attachment_fragment_shading_rate)
+    ///   features are enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if `fragment_size` is not `[1, 1]` and the `pipeline_fragment_shading_rate`
+    ///   feature is not enabled.
+    /// - Panics if an element of `fragment_size` is not 1, 2 or 4, or exceeds the
+    ///   [`max_fragment_size`](crate::device::Properties::max_fragment_size) device property.
+    /// - Panics if `combiner_ops[0]` is not `Keep` and the `primitive_fragment_shading_rate`
+    ///   feature is not enabled.
+    /// - Panics if `combiner_ops[1]` is not `Keep` and the `attachment_fragment_shading_rate`
+    ///   feature is not enabled.
+    /// - Panics if an element of `combiner_ops` is not `Keep` or `Replace`, and the
+    ///   [`fragment_shading_rate_non_trivial_combiner_ops`](crate::device::Properties::fragment_shading_rate_non_trivial_combiner_ops)
+    ///   device property is `false`.
+    #[inline]
+    pub fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> &mut Self {
+        self.validate_set_fragment_shading_rate(fragment_size, combiner_ops)
+            .unwrap();
+
+        unsafe {
+            self.inner
+                .set_fragment_shading_rate(fragment_size, combiner_ops);
+        }
+
+        self
+    }
+
+    fn validate_set_fragment_shading_rate(
+        &self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> Result<(), SetDynamicStateError> {
+        if self.has_fixed_state(DynamicState::FragmentShadingRate) {
+            return Err(SetDynamicStateError::PipelineHasFixedState);
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-commandBuffer-cmdpool
+        if !self.queue_family().supports_graphics() {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        if !self.device().enabled_extensions().khr_fragment_shading_rate {
+            return Err(SetDynamicStateError::ExtensionNotEnabled {
+                extension: "khr_fragment_shading_rate",
+                reason: "called set_fragment_shading_rate",
+            });
+        }
+
+        let features = self.device().enabled_features();
+        let properties = self.device().physical_device().properties();
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04509
+        if !(features.pipeline_fragment_shading_rate
+            || features.primitive_fragment_shading_rate
+            || features.attachment_fragment_shading_rate)
+        {
+            return Err(SetDynamicStateError::FeatureNotEnabled {
+                feature: "pipeline_fragment_shading_rate",
+                reason: "called set_fragment_shading_rate",
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04513
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04514
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04515
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04516
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04517
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04518
+        if fragment_size.iter().any(|&size| !matches!(size, 1 | 2 | 4)) {
+            return Err(SetDynamicStateError::FragmentSizeNotSupported { fragment_size });
+        }
+
+        if fragment_size != [1, 1] {
+            // VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04507
+            if !features.pipeline_fragment_shading_rate {
+                return Err(SetDynamicStateError::FeatureNotEnabled {
+                    feature: "pipeline_fragment_shading_rate",
+                    reason: "fragment_size was not [1, 1]",
+                });
+            }
+
+            // VUID?
+            let max_fragment_size = properties.max_fragment_size.unwrap();
+
+            if fragment_size[0] > max_fragment_size[0] || fragment_size[1] > max_fragment_size[1] {
+                return Err(SetDynamicStateError::FragmentSizeNotSupported { fragment_size });
+            }
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-primitiveFragmentShadingRate-04510
+        if combiner_ops[0] != FragmentShadingRateCombinerOp::Keep
+            && !features.primitive_fragment_shading_rate
+        {
+            return Err(SetDynamicStateError::FeatureNotEnabled {
+                feature: "primitive_fragment_shading_rate",
+                reason: "combiner_ops[0] was not Keep",
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-attachmentFragmentShadingRate-04511
+        if combiner_ops[1] != FragmentShadingRateCombinerOp::Keep
+            && !features.attachment_fragment_shading_rate
+        {
+            return Err(SetDynamicStateError::FeatureNotEnabled {
+                feature: "attachment_fragment_shading_rate",
+                reason: "combiner_ops[1] was not Keep",
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-fragmentSizeNonTrivialCombinerOps-04512
+        if !properties
+            .fragment_shading_rate_non_trivial_combiner_ops
+            .unwrap_or(false)
+        {
+            if let Some(&op) = combiner_ops.iter().find(|op| !op.is_trivial()) {
+                return Err(SetDynamicStateError::FragmentShadingRateCombinerOpNotSupported { op });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Sets the dynamic front face for future draw calls.
     ///
     /// # Panics
@@ -1754,6 +1897,38 @@ impl SyncCommandBufferBuilder {
         }));
     }
 
+    /// Calls `vkCmdSetFragmentShadingRateKHR` on the builder.
+    #[inline]
+    pub unsafe fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) {
+        struct Cmd {
+            fragment_size: [u32; 2],
+            combiner_ops: [FragmentShadingRateCombinerOp; 2],
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_fragment_shading_rate"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_fragment_shading_rate(self.fragment_size, self.combiner_ops);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd {
+            fragment_size,
+            combiner_ops,
+        }));
+        self.current_state.fragment_shading_rate = Some(FragmentShadingRate {
+            fragment_size,
+            combiner_ops,
+        });
+    }
+
     /// Calls `vkCmdSetFrontFaceEXT` on the builder.
     #[inline]
     pub unsafe fn set_front_face(&mut self, face: FrontFace) {
@@ -2404,6 +2579,26 @@ impl UnsafeCommandBufferBuilder {
         );
     }
 
+    /// Calls `vkCmdSetFragmentShadingRateKHR` on the builder.
+    #[inline]
+    pub unsafe fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) {
+        debug_assert!(self.device.enabled_extensions().khr_fragment_shading_rate);
+
+        let fragment_size = ash::vk::Extent2D {
+            width: fragment_size[0],
+            height: fragment_size[1],
+        };
+        let combiner_ops = [combiner_ops[0].into(), combiner_ops[1].into()];
+
+        let fns = self.device.fns();
+        (fns.khr_fragment_shading_rate
+            .cmd_set_fragment_shading_rate_khr)(self.handle, &fragment_size, &combiner_ops);
+    }
+
     /// Calls `vkCmdSetFrontFaceEXT` on the builder.
     #[inline]
     pub unsafe fn set_front_face(&mut self, face: FrontFace) {
@@ -2700,6 +2895,12 @@ enum SetDynamicStateError {
     /// The provided `factor` is not between 1 and 256 inclusive.
     FactorOutOfRange,
 
+    /// The provided fragment shading rate combiner operation is not supported by the device.
+    FragmentShadingRateCombinerOpNotSupported { op: FragmentShadingRateCombinerOp },
+
+    /// The provided fragment size is not supported by the device.
+    FragmentSizeNotSupported { fragment_size: [u32; 2] },
+
     /// The [`max_discard_rectangles`](crate::device::Properties::max_discard_rectangles)
     /// limit has been exceeded.
     MaxDiscardRectanglesExceeded { provided: u32, max: u32 },
@@ -2744,6 +2945,16 @@ impl fmt::Display for SetDynamicStateError {
                 f,
                 "the provided `factor` is not between 1 and 256 inclusive",
             ),
+            Self::FragmentShadingRateCombinerOpNotSupported { op } => write!(
+                f,
+                "the provided fragment shading rate combiner operation {:?} is not supported by the device",
+                op,
+            ),
+            Self::FragmentSizeNotSupported { fragment_size } => write!(
+                f,
+                "the provided fragment size {:?} is not supported by the device",
+                fragment_size,
+            ),
             Self::MaxDiscardRectanglesExceeded { .. } => write!(
                 f,
                 "the `max_discard_rectangles` limit has been exceeded",
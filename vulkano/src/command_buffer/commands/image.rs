@@ -205,8 +205,8 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         // VUID-VkBlitImageInfo2-srcImage-00233
         if src_image.samples() != SampleCount::Sample1 {
             return Err(CopyError::SampleCountInvalid {
-                resource: CopyErrorResource::Destination,
-                sample_count: dst_image.samples(),
+                resource: CopyErrorResource::Source,
+                sample_count: src_image.samples(),
                 allowed_sample_counts: SampleCounts {
                     sample1: true,
                     sample2: false,
@@ -268,7 +268,10 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             Filter::Linear => {
                 // VUID-VkBlitImageInfo2-filter-02001
                 if !src_image.format_features().sampled_image_filter_linear {
-                    return Err(CopyError::FilterNotSupportedByFormat);
+                    return Err(CopyError::MissingFormatFeature {
+                        resource: CopyErrorResource::Source,
+                        format_feature: "sampled_image_filter_linear",
+                    });
                 }
             }
             Filter::Cubic => {
@@ -281,7 +284,10 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
 
                 // VUID-VkBlitImageInfo2-filter-02002
                 if !src_image.format_features().sampled_image_filter_cubic {
-                    return Err(CopyError::FilterNotSupportedByFormat);
+                    return Err(CopyError::MissingFormatFeature {
+                        resource: CopyErrorResource::Source,
+                        format_feature: "sampled_image_filter_cubic",
+                    });
                 }
 
                 // VUID-VkBlitImageInfo2-filter-00237
@@ -501,8 +507,8 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                     let dst_subresource_axes = [
                         dst_image_inner.first_mipmap_level + dst_subresource.mip_level
                             ..dst_image_inner.first_mipmap_level + dst_subresource.mip_level + 1,
-                        dst_image_inner.first_layer + src_subresource.array_layers.start
-                            ..dst_image_inner.first_layer + src_subresource.array_layers.end,
+                        dst_image_inner.first_layer + dst_subresource.array_layers.start
+                            ..dst_image_inner.first_layer + dst_subresource.array_layers.end,
                     ];
 
                     if src_subresource_axes.iter().zip(dst_subresource_axes).any(
@@ -566,6 +572,66 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         Ok(())
     }
 
+    /// Generates the mip levels of an image from its first mip level, by blitting each mip level
+    /// into the next one with `filter`. All array layers are processed.
+    ///
+    /// The layout transitions between the blits are inserted automatically, like for any other
+    /// command. At the end of the command buffer, the image is transitioned back to its final
+    /// layout, which is `ShaderReadOnlyOptimal` for sampled images such as `ImmutableImage`.
+    ///
+    /// The image must have more than one mip level, and must have been created with the
+    /// `transfer_src` and `transfer_dst` usages. If `filter` is `Linear`, the format of the image
+    /// must support the `sampled_image_filter_linear` format feature.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `image` was not created with the same device as the command buffer.
+    pub fn generate_mipmaps(
+        &mut self,
+        image: Arc<dyn ImageAccess>,
+        filter: Filter,
+    ) -> Result<&mut Self, CopyError> {
+        let mip_levels = image.mip_levels();
+
+        if mip_levels < 2 {
+            return Err(CopyError::SingleMipLevel);
+        }
+
+        let dimensions = image.dimensions();
+
+        for level in 1..mip_levels {
+            let src_size = dimensions
+                .mip_level_dimensions(level - 1)
+                .unwrap()
+                .width_height_depth();
+            let dst_size = dimensions
+                .mip_level_dimensions(level)
+                .unwrap()
+                .width_height_depth();
+
+            self.blit_image(BlitImageInfo {
+                regions: [ImageBlit {
+                    src_subresource: ImageSubresourceLayers {
+                        mip_level: level - 1,
+                        ..image.subresource_layers()
+                    },
+                    src_offsets: [[0; 3], src_size],
+                    dst_subresource: ImageSubresourceLayers {
+                        mip_level: level,
+                        ..image.subresource_layers()
+                    },
+                    dst_offsets: [[0; 3], dst_size],
+                    ..Default::default()
+                }]
+                .into(),
+                filter,
+                ..BlitImageInfo::images(image.clone(), image.clone())
+            })?;
+        }
+
+        Ok(self)
+    }
+
     /// Clears a color image with a specific value.
     pub fn clear_color_image(
         &mut self,
@@ -662,6 +728,28 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
             });
         }
 
+        // VUID?
+        if let Some(numeric_type) = image.format().type_color() {
+            let compatible = match numeric_type {
+                NumericType::SFLOAT
+                | NumericType::UFLOAT
+                | NumericType::SNORM
+                | NumericType::UNORM
+                | NumericType::SSCALED
+                | NumericType::USCALED
+                | NumericType::SRGB => matches!(clear_value, ClearColorValue::Float(_)),
+                NumericType::SINT => matches!(clear_value, ClearColorValue::Int(_)),
+                NumericType::UINT => matches!(clear_value, ClearColorValue::Uint(_)),
+            };
+
+            if !compatible {
+                return Err(CopyError::ClearValueNotCompatible {
+                    clear_value,
+                    format: image.format(),
+                });
+            }
+        }
+
         for (region_index, subresource_range) in regions.iter().enumerate() {
             // VUID-VkImageSubresourceRange-aspectMask-requiredbitmask
             assert!(subresource_range.aspects != ImageAspects::none());
@@ -686,15 +774,15 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                     resource: CopyErrorResource::Destination,
                     region_index,
                     mip_levels_range_end: subresource_range.mip_levels.end,
-                    image_mip_levels: image.dimensions().array_layers(),
+                    image_mip_levels: image.mip_levels(),
                 });
             }
 
             // VUID-VkImageSubresourceRange-layerCount-01721
             assert!(!subresource_range.array_layers.is_empty());
 
-            // VUID-vkCmdClearDepthStencilImage-baseArrayLayer-01476
-            // VUID-vkCmdClearDepthStencilImage-pRanges-01695
+            // VUID-vkCmdClearColorImage-baseArrayLayer-01472
+            // VUID-vkCmdClearColorImage-pRanges-01693
             if subresource_range.array_layers.end > image.dimensions().array_layers() {
                 return Err(CopyError::ArrayLayersOutOfRange {
                     resource: CopyErrorResource::Destination,
@@ -827,7 +915,7 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
                     resource: CopyErrorResource::Destination,
                     region_index,
                     mip_levels_range_end: subresource_range.mip_levels.end,
-                    image_mip_levels: image.dimensions().array_layers(),
+                    image_mip_levels: image.mip_levels(),
                 });
             }
 
@@ -2001,6 +2089,9 @@ pub struct ClearColorImageInfo {
 
     /// The color value to clear the image to.
     ///
+    /// The variant must match the numeric type of the format of `image`: `Int` for `SINT`
+    /// formats, `Uint` for `UINT` formats and `Float` for all others.
+    ///
     /// The default value is `ClearColorValue::Float([0.0; 4])`.
     pub clear_value: ClearColorValue,
 
@@ -2208,3 +2299,168 @@ impl Default for ImageResolve {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        buffer::{BufferUsage, CpuAccessibleBuffer},
+        command_buffer::{CommandBufferUsage, CopyImageToBufferInfo, PrimaryCommandBuffer},
+        format::Format,
+        image::{AttachmentImage, ImageUsage, StorageImage},
+        sync::GpuFuture,
+    };
+
+    #[test]
+    fn blit_multisampled_source() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            transfer_src: true,
+            transfer_dst: true,
+            color_attachment: true,
+            ..ImageUsage::none()
+        };
+        let src_image = match AttachmentImage::multisampled_with_usage(
+            device.clone(),
+            [32, 32],
+            SampleCount::Sample4,
+            Format::R8G8B8A8_UNORM,
+            usage,
+        ) {
+            Ok(image) => image,
+            Err(_) => return,
+        };
+        let dst_image =
+            AttachmentImage::with_usage(device.clone(), [32, 32], Format::R8G8B8A8_UNORM, usage)
+                .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        match builder.blit_image(BlitImageInfo::images(src_image, dst_image)) {
+            Err(CopyError::SampleCountInvalid {
+                resource: CopyErrorResource::Source,
+                sample_count: SampleCount::Sample4,
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn generate_mipmaps_single_mip_level() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            transfer_src: true,
+            transfer_dst: true,
+            color_attachment: true,
+            ..ImageUsage::none()
+        };
+        let image =
+            AttachmentImage::with_usage(device.clone(), [32, 32], Format::R8G8B8A8_UNORM, usage)
+                .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        match builder.generate_mipmaps(image, Filter::Linear) {
+            Err(CopyError::SingleMipLevel) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn clear_color_image_read_back() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let image = StorageImage::new(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 4,
+                height: 4,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8_UINT,
+            Some(queue.family()),
+        )
+        .unwrap();
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_dst(),
+            true,
+            (0..4 * 4 * 4).map(|_| 0u8),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .clear_color_image(ClearColorImageInfo {
+                clear_value: ClearColorValue::Uint([1, 2, 3, 4]),
+                ..ClearColorImageInfo::image(image.clone())
+            })
+            .unwrap()
+            .copy_image_to_buffer(CopyImageToBufferInfo::image_buffer(image, buffer.clone()))
+            .unwrap();
+
+        builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        let result = buffer.read().unwrap();
+        assert!(result.chunks(4).all(|texel| texel == [1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn clear_color_image_value_not_compatible() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let image = StorageImage::new(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 4,
+                height: 4,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8_UINT,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        match builder.clear_color_image(ClearColorImageInfo {
+            clear_value: ClearColorValue::Float([1.0; 4]),
+            ..ClearColorImageInfo::image(image)
+        }) {
+            Err(CopyError::ClearValueNotCompatible { .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -7,6 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+pub(super) mod acceleration_structure;
 pub(super) mod bind_push;
 pub(super) mod debug;
 pub(super) mod dynamic_state;
@@ -20,7 +21,7 @@ pub(super) mod transfer;
 
 use super::synced::SyncCommandBufferBuilderError;
 use crate::{
-    format::Format,
+    format::{ClearColorValue, Format},
     image::{ImageAspects, ImageLayout, SampleCount, SampleCounts},
     DeviceSize,
 };
@@ -115,6 +116,12 @@ pub enum CopyError {
         min: u32,
     },
 
+    /// The clear value is not compatible with the numeric type of the format of the image.
+    ClearValueNotCompatible {
+        clear_value: ClearColorValue,
+        format: Format,
+    },
+
     /// The provided data has a size larger than the maximum allowed.
     DataTooLarge {
         size: DeviceSize,
@@ -262,6 +269,9 @@ pub enum CopyError {
         dst_sample_count: SampleCount,
     },
 
+    /// Mip levels were to be generated for an image that has only one mip level.
+    SingleMipLevel,
+
     /// The buffer size of a region is not a multiple of the required buffer alignment.
     SizeNotAlignedForBuffer {
         resource: CopyErrorResource,
@@ -385,6 +395,14 @@ impl fmt::Display for CopyError {
                 "the {} buffer row length length ({}) of region {} is smaller than the {} image extent width ({})",
                 resource, row_length, region_index, resource, min,
             ),
+            Self::ClearValueNotCompatible {
+                clear_value,
+                format,
+            } => write!(
+                f,
+                "the clear value ({:?}) is not compatible with the numeric type of the format of the image ({:?})",
+                clear_value, format,
+            ),
             Self::DataTooLarge {
                 size,
                 max,
@@ -563,6 +581,10 @@ impl fmt::Display for CopyError {
                 "the source image has a different sample count ({:?}) than the destination image ({:?})",
                 src_sample_count, dst_sample_count,
             ),
+            Self::SingleMipLevel => write!(
+                f,
+                "the image has only one mip level, so no mip levels can be generated",
+            ),
             Self::SizeNotAlignedForBuffer {
                 resource,
                 region_index,
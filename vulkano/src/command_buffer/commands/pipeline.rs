@@ -17,10 +17,13 @@ use crate::{
         sys::UnsafeCommandBufferBuilder,
         AutoCommandBufferBuilder, AutoCommandBufferBuilderContextError, DispatchError,
         DispatchIndirectCommand, DispatchIndirectError, DrawError, DrawIndexedError,
-        DrawIndexedIndirectCommand, DrawIndexedIndirectError, DrawIndirectCommand,
-        DrawIndirectError,
+        DrawIndexedIndirectCommand, DrawIndexedIndirectError, DrawIndexedInfo, DrawIndirectCommand,
+        DrawIndirectError, DrawInfo,
+    },
+    descriptor_set::{
+        layout::{DescriptorSetLayoutBinding, DescriptorType},
+        DescriptorBindingResources,
     },
-    descriptor_set::{layout::DescriptorType, DescriptorBindingResources},
     device::{Device, DeviceOwned},
     format::Format,
     image::{
@@ -38,9 +41,10 @@ use crate::{
     sampler::{Sampler, SamplerImageViewIncompatibleError},
     shader::{DescriptorRequirements, ShaderScalarType, ShaderStage},
     sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
-    DeviceSize, VulkanObject,
+    DeviceSize, Version, VulkanObject,
 };
-use std::{borrow::Cow, error, fmt, mem::size_of, ops::Range, sync::Arc};
+use smallvec::SmallVec;
+use std::{borrow::Cow, error, fmt, mem::size_of, ops::Range, ptr, sync::Arc};
 
 /// # Commands to execute a bound pipeline.
 ///
@@ -70,6 +74,33 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         Ok(self)
     }
 
+    /// Perform a single compute operation that covers at least `total` invocations, using a
+    /// compute pipeline whose shader has a local workgroup size of `local_size`.
+    ///
+    /// The number of workgroups in each dimension is `total` divided by `local_size`, rounded up.
+    /// The local size of the shader of a compute pipeline can be retrieved with
+    /// [`ComputePipeline::local_size`]. As the invocation count may be rounded up, the shader
+    /// must ignore the invocations that are outside of `total`.
+    ///
+    /// The same requirements as for [`dispatch`](Self::dispatch) apply.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if any element of `local_size` is zero.
+    #[inline]
+    pub fn dispatch_for(
+        &mut self,
+        total: [u32; 3],
+        local_size: [u32; 3],
+    ) -> Result<&mut Self, DispatchError> {
+        assert!(
+            !local_size.contains(&0),
+            "the local size must not contain zero dimensions",
+        );
+
+        self.dispatch(group_counts_for(total, local_size))
+    }
+
     /// Perform multiple compute operations using a compute pipeline. One dispatch is performed for
     /// each [`DispatchIndirectCommand`] struct in `indirect_buffer`.
     ///
@@ -317,6 +348,207 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
 
         Ok(self)
     }
+
+    /// Perform multiple draw operations using a graphics pipeline, using an index buffer, with
+    /// the number of draws read from a buffer.
+    ///
+    /// The number of draws is read from `count_buffer` when the command is executed, and is
+    /// clamped to `max_draw_count`. One draw is performed for each of the first `draw_count`
+    /// [`DrawIndexedIndirectCommand`] structs in `indirect_buffer`. `max_draw_count` must not
+    /// be greater than the number of commands in `indirect_buffer`.
+    ///
+    /// The [`draw_indirect_count`](crate::device::Features::draw_indirect_count) feature or the
+    /// [`khr_draw_indirect_count`](crate::device::DeviceExtensions::khr_draw_indirect_count)
+    /// extension must be enabled on the device.
+    ///
+    /// The same requirements as for [`draw_indexed_indirect`](Self::draw_indexed_indirect)
+    /// apply to the bound pipeline, index buffer and other state.
+    #[inline]
+    pub fn draw_indexed_indirect_count<Inb, Cb>(
+        &mut self,
+        indirect_buffer: Arc<Inb>,
+        count_buffer: Arc<Cb>,
+        max_draw_count: u32,
+    ) -> Result<&mut Self, DrawIndexedIndirectError>
+    where
+        Inb: TypedBufferAccess<Content = [DrawIndexedIndirectCommand]> + 'static,
+        Cb: TypedBufferAccess<Content = u32> + 'static,
+    {
+        // VUID-vkCmdDrawIndexedIndirectCount-None-04445
+        if !(self.device().enabled_features().draw_indirect_count
+            || self.device().enabled_extensions().khr_draw_indirect_count)
+        {
+            return Err(CheckIndirectBufferError::FeatureNotEnabled {
+                feature: "draw_indirect_count",
+                reason: "called draw_indexed_indirect_count",
+            }
+            .into());
+        }
+
+        let pipeline = check_pipeline_graphics(self.state())?;
+        self.ensure_inside_render_pass_inline(pipeline)?;
+        check_dynamic_state_validity(self.state(), pipeline)?;
+        check_descriptor_sets_validity(self.state(), pipeline, pipeline.descriptor_requirements())?;
+        check_push_constants_validity(self.state(), pipeline.layout())?;
+        check_vertex_buffers(self.state(), pipeline, None, None)?;
+        check_index_buffer(self.state(), None)?;
+        check_indirect_buffer(self.device(), indirect_buffer.as_ref())?;
+
+        // VUID-vkCmdDrawIndexedIndirectCount-countBuffer-02714
+        // VUID-vkCmdDrawIndexedIndirectCount-countBuffer-02715
+        check_indirect_buffer(self.device(), count_buffer.as_ref()).map_err(|err| match err {
+            CheckIndirectBufferError::BufferMissingUsage => {
+                CheckIndirectBufferError::CountBufferMissingUsage
+            }
+            err => err,
+        })?;
+
+        // VUID-vkCmdDrawIndexedIndirectCount-maxDrawCount-03143
+        if max_draw_count as DeviceSize > indirect_buffer.len() {
+            return Err(CheckIndirectBufferError::MaxDrawCountOutOfRange {
+                max_draw_count,
+                indirect_buffer_len: indirect_buffer.len(),
+            }
+            .into());
+        }
+
+        unsafe {
+            self.inner.draw_indexed_indirect_count(
+                indirect_buffer,
+                count_buffer,
+                max_draw_count,
+                size_of::<DrawIndexedIndirectCommand>() as u32,
+            )?;
+        }
+
+        Ok(self)
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline, in a single command.
+    ///
+    /// One draw is performed for each [`DrawInfo`] in `vertex_info`, each drawing
+    /// `instance_count` instances starting at `first_instance`. This is equivalent to calling
+    /// [`draw`](Self::draw) once for each element, but avoids the per-command overhead.
+    ///
+    /// The [`multi_draw`](crate::device::Features::multi_draw) feature must be enabled on the
+    /// device, and the number of draws must be less than the
+    /// [`max_multi_draw_count`](crate::device::Properties::max_multi_draw_count) limit.
+    ///
+    /// The same requirements as for [`draw`](Self::draw) apply to the bound pipeline, vertex
+    /// buffers and other state, for each element of `vertex_info`.
+    #[inline]
+    pub fn draw_multi(
+        &mut self,
+        vertex_info: &[DrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, DrawError> {
+        check_multi_draw(self.device(), vertex_info.len())?;
+
+        let pipeline = check_pipeline_graphics(self.state())?;
+        self.ensure_inside_render_pass_inline(pipeline)?;
+        check_dynamic_state_validity(self.state(), pipeline)?;
+        check_descriptor_sets_validity(self.state(), pipeline, pipeline.descriptor_requirements())?;
+        check_push_constants_validity(self.state(), pipeline.layout())?;
+
+        for draw_info in vertex_info {
+            check_vertex_buffers(
+                self.state(),
+                pipeline,
+                Some((draw_info.first_vertex, draw_info.vertex_count)),
+                Some((first_instance, instance_count)),
+            )?;
+        }
+
+        unsafe {
+            self.inner
+                .draw_multi(vertex_info, instance_count, first_instance)?;
+        }
+
+        Ok(self)
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline and an index buffer, in a
+    /// single command.
+    ///
+    /// One draw is performed for each [`DrawIndexedInfo`] in `index_info`, each drawing
+    /// `instance_count` instances starting at `first_instance`. If `vertex_offset` is `Some`, it
+    /// is used for every draw and the `vertex_offset` of each `DrawIndexedInfo` is ignored.
+    ///
+    /// The [`multi_draw`](crate::device::Features::multi_draw) feature must be enabled on the
+    /// device, and the number of draws must be less than the
+    /// [`max_multi_draw_count`](crate::device::Properties::max_multi_draw_count) limit.
+    ///
+    /// The same requirements as for [`draw_indexed`](Self::draw_indexed) apply to the bound
+    /// pipeline, index buffer and other state, for each element of `index_info`.
+    #[inline]
+    pub fn draw_multi_indexed(
+        &mut self,
+        index_info: &[DrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> Result<&mut Self, DrawIndexedError> {
+        check_multi_draw(self.device(), index_info.len())?;
+
+        let pipeline = check_pipeline_graphics(self.state())?;
+        self.ensure_inside_render_pass_inline(pipeline)?;
+        check_dynamic_state_validity(self.state(), pipeline)?;
+        check_descriptor_sets_validity(self.state(), pipeline, pipeline.descriptor_requirements())?;
+        check_push_constants_validity(self.state(), pipeline.layout())?;
+        check_vertex_buffers(
+            self.state(),
+            pipeline,
+            None,
+            Some((first_instance, instance_count)),
+        )?;
+
+        for draw_info in index_info {
+            check_index_buffer(
+                self.state(),
+                Some((draw_info.first_index, draw_info.index_count)),
+            )?;
+        }
+
+        unsafe {
+            self.inner.draw_multi_indexed(
+                index_info,
+                instance_count,
+                first_instance,
+                vertex_offset,
+            )?;
+        }
+
+        Ok(self)
+    }
+}
+
+fn check_multi_draw(device: &Device, draw_count: usize) -> Result<(), CheckMultiDrawError> {
+    // VUID-vkCmdDrawMultiEXT-None-04933
+    // VUID-vkCmdDrawMultiIndexedEXT-None-04937
+    if !device.enabled_features().multi_draw {
+        return Err(CheckMultiDrawError::FeatureNotEnabled {
+            feature: "multi_draw",
+            reason: "called draw_multi or draw_multi_indexed",
+        });
+    }
+
+    // VUID-vkCmdDrawMultiEXT-drawCount-04934
+    // VUID-vkCmdDrawMultiIndexedEXT-drawCount-04939
+    let limit = device
+        .physical_device()
+        .properties()
+        .max_multi_draw_count
+        .unwrap_or(0);
+
+    if draw_count as u64 >= limit as u64 {
+        return Err(CheckMultiDrawError::MaxMultiDrawCountExceeded {
+            limit,
+            requested: draw_count.try_into().unwrap_or(u32::MAX),
+        });
+    }
+
+    Ok(())
 }
 
 fn check_pipeline_compute(
@@ -391,7 +623,20 @@ fn check_descriptor_sets_validity<'a, P: Pipeline>(
         let layout_binding =
             &pipeline.layout().set_layouts()[set_num as usize].bindings()[&binding_num];
 
-        let check_buffer = |index: u32, buffer: &Arc<dyn BufferAccess>| Ok(());
+        let check_buffer = |index: u32, buffer: &Arc<dyn BufferAccess>| {
+            // The shader reads the whole uniform block, so a smaller buffer would be read out of
+            // bounds.
+            if let Some(required_size) = reqs.buffer_min_size {
+                if buffer.size() < required_size {
+                    return Err(InvalidDescriptorResource::BufferTooSmall {
+                        required_size,
+                        provided_size: buffer.size(),
+                    });
+                }
+            }
+
+            Ok(())
+        };
 
         let check_buffer_view = |index: u32, buffer_view: &Arc<dyn BufferViewAbstract>| {
             if layout_binding.descriptor_type == DescriptorType::StorageTexelBuffer {
@@ -617,28 +862,64 @@ fn check_descriptor_sets_validity<'a, P: Pipeline>(
 
         match binding_resources {
             DescriptorBindingResources::None(elements) => {
-                check_resources(set_num, binding_num, reqs, elements, check_none)?;
+                check_resources(
+                    set_num,
+                    binding_num,
+                    layout_binding,
+                    reqs,
+                    elements,
+                    check_none,
+                )?;
             }
             DescriptorBindingResources::Buffer(elements) => {
-                check_resources(set_num, binding_num, reqs, elements, check_buffer)?;
+                check_resources(
+                    set_num,
+                    binding_num,
+                    layout_binding,
+                    reqs,
+                    elements,
+                    check_buffer,
+                )?;
             }
             DescriptorBindingResources::BufferView(elements) => {
-                check_resources(set_num, binding_num, reqs, elements, check_buffer_view)?;
+                check_resources(
+                    set_num,
+                    binding_num,
+                    layout_binding,
+                    reqs,
+                    elements,
+                    check_buffer_view,
+                )?;
             }
             DescriptorBindingResources::ImageView(elements) => {
-                check_resources(set_num, binding_num, reqs, elements, check_image_view)?;
+                check_resources(
+                    set_num,
+                    binding_num,
+                    layout_binding,
+                    reqs,
+                    elements,
+                    check_image_view,
+                )?;
             }
             DescriptorBindingResources::ImageViewSampler(elements) => {
                 check_resources(
                     set_num,
                     binding_num,
+                    layout_binding,
                     reqs,
                     elements,
                     check_image_view_sampler,
                 )?;
             }
             DescriptorBindingResources::Sampler(elements) => {
-                check_resources(set_num, binding_num, reqs, elements, check_sampler)?;
+                check_resources(
+                    set_num,
+                    binding_num,
+                    layout_binding,
+                    reqs,
+                    elements,
+                    check_sampler,
+                )?;
             }
         }
     }
@@ -700,6 +981,7 @@ impl fmt::Display for CheckDescriptorSetsValidityError {
 fn check_resources<T>(
     set_num: u32,
     binding_num: u32,
+    layout_binding: &DescriptorSetLayoutBinding,
     reqs: &DescriptorRequirements,
     elements: &[Option<T>],
     mut extra_check: impl FnMut(u32, &T) -> Result<(), InvalidDescriptorResource>,
@@ -713,6 +995,9 @@ fn check_resources<T>(
         // VUID-vkCmdDispatch-None-02699
         let element = match element {
             Some(x) => x,
+            // Unwritten descriptors are allowed in partially bound bindings, as long as they are
+            // not dynamically used. This can't be checked here.
+            None if layout_binding.partially_bound => continue,
             None => {
                 return Err(
                     CheckDescriptorSetsValidityError::InvalidDescriptorResource {
@@ -742,6 +1027,10 @@ fn check_resources<T>(
 
 #[derive(Clone, Copy, Debug)]
 pub enum InvalidDescriptorResource {
+    BufferTooSmall {
+        required_size: DeviceSize,
+        provided_size: DeviceSize,
+    },
     ImageViewFormatMismatch {
         required: Format,
         obtained: Option<Format>,
@@ -789,6 +1078,12 @@ impl fmt::Display for InvalidDescriptorResource {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match self {
+            Self::BufferTooSmall {
+                required_size,
+                provided_size,
+            } => {
+                write!(fmt, "the bound buffer was smaller than the block declared in the shader; required {} bytes, obtained {} bytes", required_size, provided_size)
+            }
             Self::ImageViewFormatMismatch { required, obtained } => {
                 write!(fmt, "the bound image view did not have the required format; required {:?}, obtained {:?}", required, obtained)
             }
@@ -1000,7 +1295,11 @@ fn check_dynamic_state_validity(
                 }
             }
             DynamicState::ExclusiveScissor => todo!(),
-            DynamicState::FragmentShadingRate => todo!(),
+            DynamicState::FragmentShadingRate => {
+                if current_state.fragment_shading_rate().is_none() {
+                    return Err(CheckDynamicStateValidityError::NotSet { dynamic_state });
+                }
+            }
             DynamicState::FrontFace => {
                 if current_state.front_face().is_none() {
                     return Err(CheckDynamicStateValidityError::NotSet { dynamic_state });
@@ -1403,6 +1702,14 @@ fn check_indirect_buffer(
         return Err(CheckIndirectBufferError::BufferMissingUsage);
     }
 
+    // VUID-vkCmdDrawIndirect-offset-02710
+    // VUID-vkCmdDrawIndexedIndirect-offset-02710
+    if buffer.inner().offset % 4 != 0 {
+        return Err(CheckIndirectBufferError::BufferOffsetNotAligned {
+            offset: buffer.inner().offset,
+        });
+    }
+
     Ok(())
 }
 
@@ -1411,6 +1718,18 @@ fn check_indirect_buffer(
 pub enum CheckIndirectBufferError {
     /// The "indirect buffer" usage must be enabled on the indirect buffer.
     BufferMissingUsage,
+    /// The offset of the indirect buffer within its underlying buffer is not a multiple of 4.
+    BufferOffsetNotAligned {
+        /// The offset that was provided.
+        offset: DeviceSize,
+    },
+    /// The "indirect buffer" usage must be enabled on the count buffer.
+    CountBufferMissingUsage,
+    /// A feature required for the operation is not enabled.
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
     /// The maximum number of indirect draws has been exceeded.
     MaxDrawIndirectCountLimitExceeded {
         /// The limit that must be fulfilled.
@@ -1418,6 +1737,13 @@ pub enum CheckIndirectBufferError {
         /// What was requested.
         requested: u32,
     },
+    /// The maximum draw count is greater than the number of commands in the indirect buffer.
+    MaxDrawCountOutOfRange {
+        /// The maximum draw count that was provided.
+        max_draw_count: u32,
+        /// The number of commands in the indirect buffer.
+        indirect_buffer_len: DeviceSize,
+    },
 }
 
 impl error::Error for CheckIndirectBufferError {}
@@ -1425,21 +1751,66 @@ impl error::Error for CheckIndirectBufferError {}
 impl fmt::Display for CheckIndirectBufferError {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(
-            fmt,
-            "{}",
-            match *self {
-                CheckIndirectBufferError::BufferMissingUsage => {
-                    "the indirect buffer usage must be enabled on the indirect buffer"
-                }
-                CheckIndirectBufferError::MaxDrawIndirectCountLimitExceeded {
-                    limit,
-                    requested,
-                } => {
-                    "the maximum number of indirect draws has been exceeded"
-                }
+        match *self {
+            CheckIndirectBufferError::BufferMissingUsage => write!(
+                fmt,
+                "the indirect buffer usage must be enabled on the indirect buffer",
+            ),
+            CheckIndirectBufferError::BufferOffsetNotAligned { .. } => write!(
+                fmt,
+                "the offset of the indirect buffer is not a multiple of 4",
+            ),
+            CheckIndirectBufferError::CountBufferMissingUsage => write!(
+                fmt,
+                "the indirect buffer usage must be enabled on the count buffer",
+            ),
+            CheckIndirectBufferError::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason,)
             }
-        )
+            CheckIndirectBufferError::MaxDrawIndirectCountLimitExceeded { .. } => write!(
+                fmt,
+                "the maximum number of indirect draws has been exceeded",
+            ),
+            CheckIndirectBufferError::MaxDrawCountOutOfRange { .. } => write!(
+                fmt,
+                "the maximum draw count is greater than the number of commands in the indirect \
+                buffer",
+            ),
+        }
+    }
+}
+
+/// Error that can happen when checking whether a multi-draw command is valid.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckMultiDrawError {
+    /// A feature required for the operation is not enabled.
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+    /// The maximum number of draws in a single multi-draw command has been exceeded.
+    MaxMultiDrawCountExceeded {
+        /// The limit that must be fulfilled.
+        limit: u32,
+        /// What was requested.
+        requested: u32,
+    },
+}
+
+impl error::Error for CheckMultiDrawError {}
+
+impl fmt::Display for CheckMultiDrawError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            CheckMultiDrawError::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
+            CheckMultiDrawError::MaxMultiDrawCountExceeded { .. } => write!(
+                fmt,
+                "the maximum number of draws in a multi-draw command has been exceeded",
+            ),
+        }
     }
 }
 
@@ -1604,6 +1975,17 @@ impl fmt::Display for CheckVertexBufferError {
 }
 
 /// Checks whether the dispatch dimensions are supported by the device.
+// Returns the number of workgroups that are needed to cover `total` invocations.
+fn group_counts_for(total: [u32; 3], local_size: [u32; 3]) -> [u32; 3] {
+    let mut group_counts = [0; 3];
+
+    for ((count, total), local_size) in group_counts.iter_mut().zip(total).zip(local_size) {
+        *count = total / local_size + (total % local_size != 0) as u32;
+    }
+
+    group_counts
+}
+
 fn check_dispatch(device: &Device, dimensions: [u32; 3]) -> Result<(), CheckDispatchError> {
     let max = device
         .physical_device()
@@ -1977,6 +2359,179 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
+    /// Calls `vkCmdDrawIndexedIndirectCount` on the builder.
+    #[inline]
+    pub unsafe fn draw_indexed_indirect_count(
+        &mut self,
+        indirect_buffer: Arc<dyn BufferAccess>,
+        count_buffer: Arc<dyn BufferAccess>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            indirect_buffer: Arc<dyn BufferAccess>,
+            count_buffer: Arc<dyn BufferAccess>,
+            max_draw_count: u32,
+            stride: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw_indexed_indirect_count"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_indexed_indirect_count(
+                    self.indirect_buffer.as_ref(),
+                    self.count_buffer.as_ref(),
+                    self.max_draw_count,
+                    self.stride,
+                );
+            }
+        }
+
+        let pipeline = self.current_state.pipeline_graphics.as_ref().unwrap();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_set_resources(
+            &mut resources,
+            PipelineBindPoint::Graphics,
+            pipeline.descriptor_requirements(),
+        );
+        self.add_vertex_buffer_resources(&mut resources, pipeline.vertex_input_state());
+        self.add_index_buffer_resources(&mut resources);
+        self.add_indirect_buffer_resources(&mut resources, &indirect_buffer);
+        self.add_indirect_buffer_resources(&mut resources, &count_buffer);
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            indirect_buffer,
+            count_buffer,
+            max_draw_count,
+            stride,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdDrawMultiEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_multi(
+        &mut self,
+        vertex_info: &[DrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            vertex_info: Vec<DrawInfo>,
+            instance_count: u32,
+            first_instance: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw_multi"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_multi(&self.vertex_info, self.instance_count, self.first_instance);
+            }
+        }
+
+        let pipeline = self.current_state.pipeline_graphics.as_ref().unwrap();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_set_resources(
+            &mut resources,
+            PipelineBindPoint::Graphics,
+            pipeline.descriptor_requirements(),
+        );
+        self.add_vertex_buffer_resources(&mut resources, pipeline.vertex_input_state());
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            vertex_info: vertex_info.to_vec(),
+            instance_count,
+            first_instance,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdDrawMultiIndexedEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_multi_indexed(
+        &mut self,
+        index_info: &[DrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            index_info: Vec<DrawIndexedInfo>,
+            instance_count: u32,
+            first_instance: u32,
+            vertex_offset: Option<i32>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw_multi_indexed"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_multi_indexed(
+                    &self.index_info,
+                    self.instance_count,
+                    self.first_instance,
+                    self.vertex_offset,
+                );
+            }
+        }
+
+        let pipeline = self.current_state.pipeline_graphics.as_ref().unwrap();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_set_resources(
+            &mut resources,
+            PipelineBindPoint::Graphics,
+            pipeline.descriptor_requirements(),
+        );
+        self.add_vertex_buffer_resources(&mut resources, pipeline.vertex_input_state());
+        self.add_index_buffer_resources(&mut resources);
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            index_info: index_info.to_vec(),
+            instance_count,
+            first_instance,
+            vertex_offset,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
     fn add_descriptor_set_resources<'a>(
         &self,
         resources: &mut Vec<(Cow<'static, str>, Resource)>,
@@ -2339,9 +2894,16 @@ impl UnsafeCommandBufferBuilder {
     ) {
         let fns = self.device.fns();
 
+        debug_assert!(
+            draw_count == 0
+                || ((stride % 4) == 0)
+                    && stride as usize >= size_of::<ash::vk::DrawIndexedIndirectCommand>()
+        );
+
         let inner = buffer.inner();
         debug_assert!(inner.offset < inner.buffer.size());
         debug_assert!(inner.buffer.usage().indirect_buffer);
+        debug_assert_eq!(inner.offset % 4, 0);
 
         (fns.v1_0.cmd_draw_indexed_indirect)(
             self.handle,
@@ -2351,11 +2913,182 @@ impl UnsafeCommandBufferBuilder {
             stride,
         );
     }
+
+    /// Calls `vkCmdDrawIndexedIndirectCount` on the builder.
+    #[inline]
+    pub unsafe fn draw_indexed_indirect_count(
+        &mut self,
+        buffer: &dyn BufferAccess,
+        count_buffer: &dyn BufferAccess,
+        max_draw_count: u32,
+        stride: u32,
+    ) {
+        let fns = self.device.fns();
+
+        debug_assert!(
+            max_draw_count <= 1
+                || ((stride % 4) == 0)
+                    && stride as usize >= size_of::<ash::vk::DrawIndexedIndirectCommand>()
+        );
+
+        let inner = buffer.inner();
+        debug_assert!(inner.offset < inner.buffer.size());
+        debug_assert!(inner.buffer.usage().indirect_buffer);
+        debug_assert_eq!(inner.offset % 4, 0);
+
+        let count_inner = count_buffer.inner();
+        debug_assert!(count_inner.offset < count_inner.buffer.size());
+        debug_assert!(count_inner.buffer.usage().indirect_buffer);
+        debug_assert_eq!(count_inner.offset % 4, 0);
+
+        if self.device.api_version() >= Version::V1_2 {
+            (fns.v1_2.cmd_draw_indexed_indirect_count)(
+                self.handle,
+                inner.buffer.internal_object(),
+                inner.offset,
+                count_inner.buffer.internal_object(),
+                count_inner.offset,
+                max_draw_count,
+                stride,
+            );
+        } else {
+            debug_assert!(self.device.enabled_extensions().khr_draw_indirect_count);
+            (fns.khr_draw_indirect_count
+                .cmd_draw_indexed_indirect_count_khr)(
+                self.handle,
+                inner.buffer.internal_object(),
+                inner.offset,
+                count_inner.buffer.internal_object(),
+                count_inner.offset,
+                max_draw_count,
+                stride,
+            );
+        }
+    }
+
+    /// Calls `vkCmdDrawMultiEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_multi(
+        &mut self,
+        vertex_info: &[DrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) {
+        if vertex_info.is_empty() {
+            return;
+        }
+
+        let vertex_info_vk: SmallVec<[_; 8]> = vertex_info
+            .iter()
+            .map(|draw_info| ash::vk::MultiDrawInfoEXT {
+                first_vertex: draw_info.first_vertex,
+                vertex_count: draw_info.vertex_count,
+            })
+            .collect();
+
+        let fns = self.device.fns();
+        (fns.ext_multi_draw.cmd_draw_multi_ext)(
+            self.handle,
+            vertex_info_vk.len() as u32,
+            vertex_info_vk.as_ptr(),
+            instance_count,
+            first_instance,
+            size_of::<ash::vk::MultiDrawInfoEXT>() as u32,
+        );
+    }
+
+    /// Calls `vkCmdDrawMultiIndexedEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_multi_indexed(
+        &mut self,
+        index_info: &[DrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) {
+        if index_info.is_empty() {
+            return;
+        }
+
+        let index_info_vk: SmallVec<[_; 8]> = index_info
+            .iter()
+            .map(|draw_info| ash::vk::MultiDrawIndexedInfoEXT {
+                first_index: draw_info.first_index,
+                index_count: draw_info.index_count,
+                vertex_offset: draw_info.vertex_offset,
+            })
+            .collect();
+
+        let fns = self.device.fns();
+        (fns.ext_multi_draw.cmd_draw_multi_indexed_ext)(
+            self.handle,
+            index_info_vk.len() as u32,
+            index_info_vk.as_ptr(),
+            instance_count,
+            first_instance,
+            size_of::<ash::vk::MultiDrawIndexedInfoEXT>() as u32,
+            vertex_offset
+                .as_ref()
+                .map_or(ptr::null(), |vertex_offset| vertex_offset as *const _),
+        );
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        buffer::{BufferUsage, CpuAccessibleBuffer},
+        command_buffer::CommandBufferUsage,
+    };
+
+    #[test]
+    fn draw_indexed_indirect_command_layout() {
+        assert_eq!(
+            size_of::<DrawIndexedIndirectCommand>(),
+            size_of::<ash::vk::DrawIndexedIndirectCommand>(),
+        );
+        assert_eq!(
+            size_of::<DrawIndirectCommand>(),
+            size_of::<ash::vk::DrawIndirectCommand>(),
+        );
+    }
+
+    #[test]
+    fn draw_indexed_indirect_count_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let indirect_buffer = CpuAccessibleBuffer::from_draw_commands(
+            device.clone(),
+            false,
+            [DrawIndexedIndirectCommand::default()],
+        )
+        .unwrap();
+        let count_buffer = CpuAccessibleBuffer::from_data(
+            device.clone(),
+            BufferUsage::indirect_buffer(),
+            false,
+            1u32,
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        match builder.draw_indexed_indirect_count(indirect_buffer, count_buffer, 1) {
+            Err(DrawIndexedIndirectError::CheckIndirectBufferError(
+                CheckIndirectBufferError::FeatureNotEnabled {
+                    feature: "draw_indirect_count",
+                    ..
+                },
+            )) => (),
+            _ => panic!(),
+        }
+    }
 
     #[test]
     fn max_checked() {
@@ -2392,4 +3125,28 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn group_counts_rounded_up() {
+        assert_eq!(group_counts_for([64, 1, 1], [64, 1, 1]), [1, 1, 1]);
+        assert_eq!(group_counts_for([65, 1, 1], [64, 1, 1]), [2, 1, 1]);
+        assert_eq!(group_counts_for([100, 30, 7], [8, 8, 4]), [13, 4, 2]);
+        assert_eq!(
+            group_counts_for([u32::MAX, 0, 1], [2, 1, 1]),
+            [u32::MAX / 2 + 1, 0, 1]
+        );
+    }
+
+    #[test]
+    fn multi_draw_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match check_multi_draw(&device, 1) {
+            Err(CheckMultiDrawError::FeatureNotEnabled {
+                feature: "multi_draw",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
 }
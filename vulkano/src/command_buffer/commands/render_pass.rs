@@ -18,7 +18,7 @@ use crate::{
     },
     device::DeviceOwned,
     format::{ClearColorValue, ClearValue, Format, NumericType},
-    image::{ImageLayout, ImageViewAbstract, SampleCount},
+    image::{ImageAccess, ImageLayout, ImageViewAbstract, SampleCount},
     render_pass::{
         AttachmentDescription, Framebuffer, LoadOp, RenderPass, ResolveMode, StoreOp,
         SubpassDescription,
@@ -132,11 +132,29 @@ where
         {
             let attachment_index = attachment_index as u32;
             let &AttachmentDescription {
+                load_op,
+                stencil_load_op,
                 initial_layout,
                 final_layout,
                 ..
             } = attachment_desc;
 
+            // Not required by Vulkan, only checked if requested when creating the device.
+            if device.validate_attachment_contents() {
+                let image = image_view.image();
+                let aspects = image.format().aspects();
+
+                // An image whose layout was never initialized has not been used by any command
+                // since it was created, so its contents are still undefined.
+                if ((load_op == LoadOp::Load && (aspects.color || aspects.depth))
+                    || (stencil_load_op == LoadOp::Load && aspects.stencil))
+                    && !image.is_layout_initialized()
+                    && image.initial_layout() == ImageLayout::Undefined
+                {
+                    return Err(RenderPassError::AttachmentContentsUndefined { attachment_index });
+                }
+            }
+
             for layout in [initial_layout, final_layout] {
                 match layout {
                     ImageLayout::ColorAttachmentOptimal => {
@@ -2569,6 +2587,12 @@ pub enum RenderPassError {
         reason: &'static str,
     },
 
+    /// An attachment is loaded at the start of the render pass, but its image has not been used
+    /// since it was created, so its contents are undefined.
+    AttachmentContentsUndefined {
+        attachment_index: u32,
+    },
+
     /// A framebuffer image did not have the required usage enabled.
     AttachmentImageMissingUsage {
         attachment_index: u32,
@@ -2831,6 +2855,11 @@ impl fmt::Display for RenderPassError {
                 write!(f, "the feature {} must be enabled: {}", feature, reason)
             }
 
+            Self::AttachmentContentsUndefined { attachment_index } => write!(
+                f,
+                "attachment {} is loaded at the start of the render pass, but the contents of its image are undefined",
+                attachment_index,
+            ),
             Self::AttachmentImageMissingUsage { attachment_index, usage } => write!(
                 f,
                 "the framebuffer image attached to attachment index {} did not have the required usage {} enabled",
@@ -14,7 +14,8 @@ use crate::{
         synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
         sys::UnsafeCommandBufferBuilder,
         AutoCommandBufferBuilder, CommandBufferExecError, CommandBufferInheritanceRenderPassType,
-        CommandBufferUsage, PrimaryAutoCommandBuffer, SecondaryCommandBuffer, SubpassContents,
+        CommandBufferLevel, CommandBufferUsage, PrimaryAutoCommandBuffer, SecondaryCommandBuffer,
+        SubpassContents,
     },
     device::DeviceOwned,
     format::Format,
@@ -119,8 +120,20 @@ where
             return Err(ExecuteCommandsError::NotSupportedByQueueFamily);
         }
 
-        // TODO:
+        // VUID-vkCmdExecuteCommands-pCommandBuffers-00088
+        // Secondary command buffers are unsafe to implement, so make sure here.
+        assert_eq!(
+            command_buffer.inner().level(),
+            CommandBufferLevel::Secondary,
+            "the provided command buffer was not allocated as a secondary command buffer",
+        );
+
         // VUID-vkCmdExecuteCommands-pCommandBuffers-00094
+        if command_buffer.inner().queue_family() != self.queue_family() {
+            return Err(ExecuteCommandsError::QueueFamilyMismatch {
+                command_buffer_index,
+            });
+        }
 
         if let Some(render_pass_state) = &self.render_pass_state {
             // VUID-vkCmdExecuteCommands-contents-06018
@@ -373,7 +386,6 @@ where
         // VUID-vkCmdExecuteCommands-bufferlevel
         // Ensured by the type of the impl block.
 
-        // VUID-vkCmdExecuteCommands-pCommandBuffers-00088
         // VUID-vkCmdExecuteCommands-pCommandBuffers-00089
         // Ensured by the SecondaryCommandBuffer trait.
 
@@ -539,7 +551,7 @@ impl UnsafeCommandBufferBuilderExecuteCommands {
     where
         C: ?Sized + SecondaryCommandBuffer,
     {
-        // TODO: debug assert that it is a secondary command buffer?
+        debug_assert_eq!(cb.inner().level(), CommandBufferLevel::Secondary);
         self.raw_cbs.push(cb.inner().internal_object());
     }
 
@@ -590,6 +602,12 @@ pub enum ExecuteCommandsError {
         inherited_flags: QueryPipelineStatisticFlags,
     },
 
+    /// A command buffer was allocated for a different queue family than the primary command
+    /// buffer.
+    QueueFamilyMismatch {
+        command_buffer_index: u32,
+    },
+
     /// The inherited color attachment count of a command buffer does not match the current
     /// attachment count.
     RenderPassColorAttachmentCountMismatch {
@@ -742,6 +760,13 @@ impl fmt::Display for ExecuteCommandsError {
                 "the inherited pipeline statistics query flags ({:?}) of command buffer {} are not a superset of the currently active flags ({:?})",
                 inherited_flags, command_buffer_index, required_flags,
             ),
+            Self::QueueFamilyMismatch {
+                command_buffer_index,
+            } => write!(
+                f,
+                "command buffer {} was allocated for a different queue family than the primary command buffer",
+                command_buffer_index,
+            ),
             Self::RenderPassColorAttachmentCountMismatch {
                 command_buffer_index,
                 required_count,
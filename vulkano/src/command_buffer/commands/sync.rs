@@ -8,19 +8,913 @@
 // according to those terms.
 
 use crate::{
+    buffer::BufferAccess,
     command_buffer::{
         synced::{Command, SyncCommandBufferBuilder},
         sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder,
     },
-    image::ImageLayout,
+    device::{Device, DeviceOwned},
+    image::{ImageAccess, ImageLayout, ImageSubresourceRange},
     sync::{
-        BufferMemoryBarrier, DependencyInfo, Event, ImageMemoryBarrier, MemoryBarrier,
-        PipelineStages,
+        AccessFlags, BufferMemoryBarrier, DependencyInfo, Event, ImageMemoryBarrier, MemoryBarrier,
+        PipelineStages, QueueFamilyTransfer,
     },
     Version, VulkanObject,
 };
 use smallvec::SmallVec;
-use std::sync::Arc;
+use std::{error, fmt, sync::Arc};
+
+/// # Commands for synchronization.
+///
+/// These commands allow finer-grained dependencies than the pipeline barriers that are
+/// automatically inserted by the builder. An event is set once the specified stages of all
+/// previously recorded commands have completed, and a later wait on the event blocks the
+/// specified stages of subsequent commands until then.
+impl<L, P> AutoCommandBufferBuilder<L, P> {
+    /// Sets `event` once the given `stages` of all previously recorded commands have completed.
+    #[inline]
+    pub fn set_event(
+        &mut self,
+        event: Arc<Event>,
+        stages: PipelineStages,
+    ) -> Result<&mut Self, SynchronizationError> {
+        self.validate_set_event(&event, stages)?;
+
+        unsafe {
+            self.inner.set_event(event, stages);
+        }
+
+        Ok(self)
+    }
+
+    fn validate_set_event(
+        &self,
+        event: &Event,
+        stages: PipelineStages,
+    ) -> Result<(), SynchronizationError> {
+        // VUID-vkCmdSetEvent-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(SynchronizationError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdSetEvent-commandBuffer-cmdpool
+        if !(self.queue_family().supports_graphics() || self.queue_family().supports_compute()) {
+            return Err(SynchronizationError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetEvent-commonparent
+        assert_eq!(self.device(), event.device());
+
+        // VUID-vkCmdSetEvent-stageMask-01149
+        if stages.host {
+            return Err(SynchronizationError::HostStageNotAllowed);
+        }
+
+        // VUID-vkCmdSetEvent-stageMask-04090
+        // VUID-vkCmdSetEvent-stageMask-04091
+        // VUID-vkCmdSetEvent-stageMask-04098
+        // VUID-vkCmdSetEvent-stageMask-requiredbitmask
+        self.validate_event_stages(stages)?;
+
+        Ok(())
+    }
+
+    /// Waits for all of `events` to be set before executing the destination stages of
+    /// subsequent commands, then applies the barriers of `dependency_info`.
+    ///
+    /// The source stages of the dependency are the union of the `source_stages` of all barriers,
+    /// and likewise for the destination stages. To express a pure execution dependency, provide a
+    /// memory barrier with the desired stages and no access types.
+    ///
+    /// # Safety
+    ///
+    /// - The union of the source stages of all barriers must be equal to the union of the stages
+    ///   that were given when setting `events`, plus the `host` stage if any of the events are set
+    ///   from the host.
+    /// - Each event must be set, either by a previously recorded command or by the host, before
+    ///   the wait is executed. Otherwise the device will hang or be lost.
+    /// - The buffers and images in the barriers are not tracked by the automatic synchronization
+    ///   of the builder, so the barriers can't be used to change the layout of an image or to
+    ///   transfer ownership of a resource to another queue family.
+    pub unsafe fn wait_events(
+        &mut self,
+        events: impl IntoIterator<Item = Arc<Event>>,
+        dependency_info: DependencyInfo,
+    ) -> Result<&mut Self, SynchronizationError> {
+        let events: SmallVec<[Arc<Event>; 4]> = events.into_iter().collect();
+        self.validate_wait_events(&events, &dependency_info)?;
+
+        self.inner.wait_events(events, dependency_info);
+
+        Ok(self)
+    }
+
+    fn validate_wait_events(
+        &self,
+        events: &[Arc<Event>],
+        dependency_info: &DependencyInfo,
+    ) -> Result<(), SynchronizationError> {
+        // Waiting on events inside a render pass is only allowed with restrictions on the
+        // barriers that the builder can't verify, so it is forbidden altogether for now.
+        if self.render_pass_state.is_some() {
+            return Err(SynchronizationError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdWaitEvents-commandBuffer-cmdpool
+        if !(self.queue_family().supports_graphics() || self.queue_family().supports_compute()) {
+            return Err(SynchronizationError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdWaitEvents-eventCount-arraylength
+        if events.is_empty() {
+            return Err(SynchronizationError::NoEvents);
+        }
+
+        let device = self.device();
+
+        for event in events {
+            // VUID-vkCmdWaitEvents-commonparent
+            assert_eq!(device, event.device());
+        }
+
+        let DependencyInfo {
+            memory_barriers,
+            buffer_memory_barriers,
+            image_memory_barriers,
+            _ne: _,
+        } = dependency_info;
+
+        let mut source_stages = PipelineStages::none();
+        let mut destination_stages = PipelineStages::none();
+
+        let mut check_barrier = |barrier_source_stages: PipelineStages,
+                                 barrier_source_access: AccessFlags,
+                                 barrier_destination_stages: PipelineStages,
+                                 barrier_destination_access: AccessFlags|
+         -> Result<(), SynchronizationError> {
+            // VUID-vkCmdWaitEvents-srcAccessMask-02815
+            // VUID-vkCmdWaitEvents-dstAccessMask-02816
+            if !barrier_source_stages
+                .supported_access()
+                .contains(&barrier_source_access)
+                || !barrier_destination_stages
+                    .supported_access()
+                    .contains(&barrier_destination_access)
+            {
+                return Err(SynchronizationError::AccessNotSupportedByStages);
+            }
+
+            source_stages |= barrier_source_stages;
+            destination_stages |= barrier_destination_stages;
+
+            Ok(())
+        };
+
+        for barrier in memory_barriers {
+            check_barrier(
+                barrier.source_stages,
+                barrier.source_access,
+                barrier.destination_stages,
+                barrier.destination_access,
+            )?;
+        }
+
+        for barrier in buffer_memory_barriers {
+            // VUID-VkBufferMemoryBarrier2-srcStageMask-03929
+            // VUID-VkBufferMemoryBarrier2-srcAccessMask-03900
+            // and similar for the other stages and access types
+            check_barrier(
+                barrier.source_stages,
+                barrier.source_access,
+                barrier.destination_stages,
+                barrier.destination_access,
+            )?;
+
+            // VUID-vkCmdWaitEvents-commonparent
+            assert_eq!(device, barrier.buffer.device());
+
+            // VUID-VkBufferMemoryBarrier-offset-01187
+            // VUID-VkBufferMemoryBarrier-size-01188
+            // VUID-VkBufferMemoryBarrier-size-01189
+            if barrier.range.is_empty() || barrier.range.end > barrier.buffer.size() {
+                return Err(SynchronizationError::BarrierRangeOutOfBounds);
+            }
+
+            if barrier.queue_family_transfer.is_some() {
+                return Err(SynchronizationError::QueueFamilyTransferNotAllowed);
+            }
+        }
+
+        for barrier in image_memory_barriers {
+            // VUID-VkImageMemoryBarrier2-srcStageMask-03929
+            // VUID-VkImageMemoryBarrier2-srcAccessMask-03900
+            // and similar for the other stages and access types
+            check_barrier(
+                barrier.source_stages,
+                barrier.source_access,
+                barrier.destination_stages,
+                barrier.destination_access,
+            )?;
+
+            // VUID-vkCmdWaitEvents-commonparent
+            assert_eq!(device, barrier.image.device());
+
+            let subresource_range = &barrier.subresource_range;
+
+            // VUID-VkImageMemoryBarrier-subresourceRange-01486
+            // VUID-VkImageMemoryBarrier-subresourceRange-01724
+            // VUID-VkImageMemoryBarrier-subresourceRange-01488
+            // VUID-VkImageMemoryBarrier-subresourceRange-01725
+            if subresource_range.mip_levels.is_empty()
+                || subresource_range.mip_levels.end > barrier.image.mip_levels()
+                || subresource_range.array_layers.is_empty()
+                || subresource_range.array_layers.end > barrier.image.dimensions().array_layers()
+                || !barrier
+                    .image
+                    .format()
+                    .unwrap()
+                    .aspects()
+                    .contains(&subresource_range.aspects)
+            {
+                return Err(SynchronizationError::BarrierRangeOutOfBounds);
+            }
+
+            if barrier.old_layout != barrier.new_layout {
+                return Err(SynchronizationError::ImageLayoutTransitionNotAllowed);
+            }
+
+            if barrier.queue_family_transfer.is_some() {
+                return Err(SynchronizationError::QueueFamilyTransferNotAllowed);
+            }
+        }
+
+        // VUID-vkCmdWaitEvents-srcStageMask-04090
+        // VUID-vkCmdWaitEvents-srcStageMask-04091
+        // VUID-vkCmdWaitEvents-srcStageMask-04098
+        // VUID-vkCmdWaitEvents-srcStageMask-requiredbitmask
+        self.validate_event_stages(source_stages)?;
+
+        // VUID-vkCmdWaitEvents-dstStageMask-04090
+        // VUID-vkCmdWaitEvents-dstStageMask-04091
+        // VUID-vkCmdWaitEvents-dstStageMask-04098
+        // VUID-vkCmdWaitEvents-dstStageMask-requiredbitmask
+        self.validate_event_stages(destination_stages)?;
+
+        // VUID-vkCmdWaitEvents-dstStageMask-06462
+        if destination_stages.host {
+            return Err(SynchronizationError::HostStageNotAllowed);
+        }
+
+        // VUID-vkCmdWaitEvents-srcStageMask-01158
+        // VUID-vkCmdWaitEvents-srcStageMask-07308
+        // TODO: not checked, so unsafe for now
+
+        Ok(())
+    }
+
+    /// Records a pipeline barrier, using the barriers of `dependency_info` as they are.
+    ///
+    /// Contrary to [`wait_events`](Self::wait_events), each barrier is recorded with its own
+    /// source and destination stages, rather than the union of the stages of all barriers. A
+    /// memory barrier with the desired stages and no access types can be used to express a pure
+    /// execution dependency.
+    ///
+    /// The builder already inserts the barriers that are needed between the commands it records,
+    /// so this is only useful for dependencies that it can't see, such as memory that is written
+    /// through a buffer device address.
+    ///
+    /// The stages and access types that only exist with `synchronization2`, such as
+    /// [`copy`](PipelineStages::copy) or
+    /// [`shader_storage_read`](AccessFlags::shader_storage_read), can be used in the barriers.
+    ///
+    /// The [`synchronization2`](crate::device::Features::synchronization2) feature must be
+    /// enabled on the device.
+    ///
+    /// # Safety
+    ///
+    /// - The buffers and images in the barriers are not tracked by the automatic synchronization
+    ///   of the builder, so the barriers can't be used to change the layout of an image or to
+    ///   transfer ownership of a resource to another queue family.
+    pub unsafe fn pipeline_barrier2(
+        &mut self,
+        dependency_info: DependencyInfo,
+    ) -> Result<&mut Self, SynchronizationError> {
+        self.validate_pipeline_barrier2(&dependency_info)?;
+
+        self.inner.pipeline_barrier2(dependency_info);
+
+        Ok(self)
+    }
+
+    fn validate_pipeline_barrier2(
+        &self,
+        dependency_info: &DependencyInfo,
+    ) -> Result<(), SynchronizationError> {
+        // VUID-vkCmdPipelineBarrier2-synchronization2-03848
+        if !self.device().enabled_features().synchronization2 {
+            return Err(SynchronizationError::FeatureNotEnabled {
+                feature: "synchronization2",
+                reason: "called pipeline_barrier2",
+            });
+        }
+
+        // Barriers inside a render pass are only allowed with restrictions on the barriers that
+        // the builder can't verify, so they are forbidden altogether for now.
+        if self.render_pass_state.is_some() {
+            return Err(SynchronizationError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdPipelineBarrier2-commandBuffer-cmdpool
+        if !(self.queue_family().explicitly_supports_transfers()
+            || self.queue_family().supports_graphics()
+            || self.queue_family().supports_compute())
+        {
+            return Err(SynchronizationError::NotSupportedByQueueFamily);
+        }
+
+        let device = self.device();
+        let DependencyInfo {
+            memory_barriers,
+            buffer_memory_barriers,
+            image_memory_barriers,
+            _ne: _,
+        } = dependency_info;
+
+        let check_barrier = |barrier_source_stages: PipelineStages,
+                             barrier_source_access: AccessFlags,
+                             barrier_destination_stages: PipelineStages,
+                             barrier_destination_access: AccessFlags|
+         -> Result<(), SynchronizationError> {
+            // VUID-VkMemoryBarrier2-srcStageMask-03929
+            // VUID-VkMemoryBarrier2-srcStageMask-03930
+            // VUID-VkMemoryBarrier2-srcStageMask-07946
+            // VUID-vkCmdPipelineBarrier2-srcStageMask-03849
+            self.validate_barrier2_stages(barrier_source_stages)?;
+
+            // VUID-VkMemoryBarrier2-dstStageMask-03929
+            // VUID-VkMemoryBarrier2-dstStageMask-03930
+            // VUID-VkMemoryBarrier2-dstStageMask-07946
+            // VUID-vkCmdPipelineBarrier2-dstStageMask-03850
+            self.validate_barrier2_stages(barrier_destination_stages)?;
+
+            // VUID-VkMemoryBarrier2-srcAccessMask-03900
+            // VUID-VkMemoryBarrier2-srcAccessMask-03901
+            // ...
+            // VUID-VkMemoryBarrier2-srcAccessMask-03925
+            if !barrier_source_stages
+                .supported_access()
+                .contains(&barrier_source_access)
+            {
+                return Err(SynchronizationError::AccessNotSupportedByStages);
+            }
+
+            // VUID-VkMemoryBarrier2-dstAccessMask-03900
+            // VUID-VkMemoryBarrier2-dstAccessMask-03901
+            // ...
+            // VUID-VkMemoryBarrier2-dstAccessMask-03925
+            if !barrier_destination_stages
+                .supported_access()
+                .contains(&barrier_destination_access)
+            {
+                return Err(SynchronizationError::AccessNotSupportedByStages);
+            }
+
+            Ok(())
+        };
+
+        for barrier in memory_barriers {
+            check_barrier(
+                barrier.source_stages,
+                barrier.source_access,
+                barrier.destination_stages,
+                barrier.destination_access,
+            )?;
+        }
+
+        for barrier in buffer_memory_barriers {
+            check_barrier(
+                barrier.source_stages,
+                barrier.source_access,
+                barrier.destination_stages,
+                barrier.destination_access,
+            )?;
+
+            // VUID-vkCmdPipelineBarrier2-commonparent
+            assert_eq!(device, barrier.buffer.device());
+
+            // VUID-VkBufferMemoryBarrier2-offset-01187
+            // VUID-VkBufferMemoryBarrier2-size-01188
+            // VUID-VkBufferMemoryBarrier2-size-01189
+            if barrier.range.is_empty() || barrier.range.end > barrier.buffer.size() {
+                return Err(SynchronizationError::BarrierRangeOutOfBounds);
+            }
+
+            if barrier.queue_family_transfer.is_some() {
+                return Err(SynchronizationError::QueueFamilyTransferNotAllowed);
+            }
+        }
+
+        for barrier in image_memory_barriers {
+            check_barrier(
+                barrier.source_stages,
+                barrier.source_access,
+                barrier.destination_stages,
+                barrier.destination_access,
+            )?;
+
+            // VUID-vkCmdPipelineBarrier2-commonparent
+            assert_eq!(device, barrier.image.device());
+
+            let subresource_range = &barrier.subresource_range;
+
+            // VUID-VkImageMemoryBarrier2-subresourceRange-01486
+            // VUID-VkImageMemoryBarrier2-subresourceRange-01724
+            // VUID-VkImageMemoryBarrier2-subresourceRange-01488
+            // VUID-VkImageMemoryBarrier2-subresourceRange-01725
+            if subresource_range.mip_levels.is_empty()
+                || subresource_range.mip_levels.end > barrier.image.mip_levels()
+                || subresource_range.array_layers.is_empty()
+                || subresource_range.array_layers.end > barrier.image.dimensions().array_layers()
+                || !barrier
+                    .image
+                    .format()
+                    .unwrap()
+                    .aspects()
+                    .contains(&subresource_range.aspects)
+            {
+                return Err(SynchronizationError::BarrierRangeOutOfBounds);
+            }
+
+            if barrier.old_layout != barrier.new_layout {
+                return Err(SynchronizationError::ImageLayoutTransitionNotAllowed);
+            }
+
+            if barrier.queue_family_transfer.is_some() {
+                return Err(SynchronizationError::QueueFamilyTransferNotAllowed);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Checks a stage mask of a `synchronization2` barrier. Contrary to the stages of events and
+    // `synchronization1` barriers, an empty stage mask is allowed.
+    fn validate_barrier2_stages(&self, stages: PipelineStages) -> Result<(), SynchronizationError> {
+        let stage_flags: ash::vk::PipelineStageFlags2 = stages.into();
+        let graphics_stages = ash::vk::PipelineStageFlags2::DRAW_INDIRECT
+            | ash::vk::PipelineStageFlags2::VERTEX_INPUT
+            | ash::vk::PipelineStageFlags2::INDEX_INPUT
+            | ash::vk::PipelineStageFlags2::VERTEX_ATTRIBUTE_INPUT
+            | ash::vk::PipelineStageFlags2::PRE_RASTERIZATION_SHADERS
+            | ash::vk::PipelineStageFlags2::VERTEX_SHADER
+            | ash::vk::PipelineStageFlags2::TESSELLATION_CONTROL_SHADER
+            | ash::vk::PipelineStageFlags2::TESSELLATION_EVALUATION_SHADER
+            | ash::vk::PipelineStageFlags2::GEOMETRY_SHADER
+            | ash::vk::PipelineStageFlags2::FRAGMENT_SHADER
+            | ash::vk::PipelineStageFlags2::EARLY_FRAGMENT_TESTS
+            | ash::vk::PipelineStageFlags2::LATE_FRAGMENT_TESTS
+            | ash::vk::PipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT
+            | ash::vk::PipelineStageFlags2::ALL_GRAPHICS
+            | ash::vk::PipelineStageFlags2::RESOLVE
+            | ash::vk::PipelineStageFlags2::BLIT;
+        let compute_stages = ash::vk::PipelineStageFlags2::COMPUTE_SHADER
+            | ash::vk::PipelineStageFlags2::RAY_TRACING_SHADER_KHR
+            | ash::vk::PipelineStageFlags2::ACCELERATION_STRUCTURE_BUILD_KHR;
+
+        if !self.queue_family().supports_graphics() && stage_flags.intersects(graphics_stages)
+            || !self.queue_family().supports_compute() && stage_flags.intersects(compute_stages)
+        {
+            return Err(SynchronizationError::StageNotSupported);
+        }
+
+        if stages.geometry_shader && !self.device().enabled_features().geometry_shader {
+            return Err(SynchronizationError::FeatureNotEnabled {
+                feature: "geometry_shader",
+                reason: "stages.geometry_shader was enabled",
+            });
+        }
+
+        if (stages.tessellation_control_shader || stages.tessellation_evaluation_shader)
+            && !self.device().enabled_features().tessellation_shader
+        {
+            return Err(SynchronizationError::FeatureNotEnabled {
+                feature: "tessellation_shader",
+                reason: "stages.tessellation_control_shader or stages.tessellation_evaluation_shader was enabled",
+            });
+        }
+
+        if stages.ray_tracing_shader
+            && !(self.device().enabled_extensions().khr_ray_tracing_pipeline
+                || self.device().enabled_extensions().nv_ray_tracing)
+        {
+            return Err(SynchronizationError::ExtensionNotEnabled {
+                extension: "khr_ray_tracing_pipeline",
+                reason: "stages.ray_tracing_shader was enabled",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Releases ownership of `buffer` from the queue family `source_queue_family_index`, so that
+    /// it can be acquired by the queue family `destination_queue_family_index`.
+    ///
+    /// The command buffer must belong to the source queue family. `source_stages` and
+    /// `source_access` are the stages and accesses of the previous uses of the buffer that must
+    /// complete before the ownership is released. A matching
+    /// [`acquire_buffer_ownership`](Self::acquire_buffer_ownership) must then be recorded in a
+    /// command buffer of the destination queue family.
+    ///
+    /// # Safety
+    ///
+    /// - `buffer` must have been created with [`Sharing::Exclusive`](crate::sync::Sharing), and
+    ///   must currently be owned by the source queue family.
+    /// - The matching acquire operation must use the same queue family indices, and must be
+    ///   submitted after this command buffer, with a semaphore in between.
+    /// - The ownership transfer is not tracked by the automatic synchronization of the builder.
+    pub unsafe fn release_buffer_ownership(
+        &mut self,
+        buffer: Arc<dyn BufferAccess>,
+        source_queue_family_index: u32,
+        destination_queue_family_index: u32,
+        source_stages: PipelineStages,
+        source_access: AccessFlags,
+    ) -> Result<&mut Self, SynchronizationError> {
+        let queue_family_transfer = QueueFamilyTransfer {
+            source_index: source_queue_family_index,
+            destination_index: destination_queue_family_index,
+        };
+        self.validate_queue_family_transfer(
+            buffer.device(),
+            queue_family_transfer,
+            true,
+            source_stages,
+            source_access,
+        )?;
+
+        let buffer_inner = buffer.inner();
+
+        self.inner.pipeline_barrier2(DependencyInfo {
+            buffer_memory_barriers: [BufferMemoryBarrier {
+                source_stages,
+                source_access,
+                queue_family_transfer: Some(queue_family_transfer),
+                range: buffer_inner.offset..buffer_inner.offset + buffer.size(),
+                ..BufferMemoryBarrier::buffer(buffer_inner.buffer.clone())
+            }]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        });
+
+        Ok(self)
+    }
+
+    /// Acquires ownership of `buffer` for the queue family `destination_queue_family_index`,
+    /// after it was released by the queue family `source_queue_family_index`.
+    ///
+    /// The command buffer must belong to the destination queue family. `destination_stages` and
+    /// `destination_access` are the stages and accesses of the subsequent uses of the buffer that
+    /// must wait for the ownership to be acquired. This must match a previous
+    /// [`release_buffer_ownership`](Self::release_buffer_ownership) recorded in a command buffer
+    /// of the source queue family.
+    ///
+    /// # Safety
+    ///
+    /// - `buffer` must have been created with [`Sharing::Exclusive`](crate::sync::Sharing).
+    /// - The matching release operation must use the same queue family indices, and must be
+    ///   submitted before this command buffer, with a semaphore in between.
+    /// - The ownership transfer is not tracked by the automatic synchronization of the builder.
+    pub unsafe fn acquire_buffer_ownership(
+        &mut self,
+        buffer: Arc<dyn BufferAccess>,
+        source_queue_family_index: u32,
+        destination_queue_family_index: u32,
+        destination_stages: PipelineStages,
+        destination_access: AccessFlags,
+    ) -> Result<&mut Self, SynchronizationError> {
+        let queue_family_transfer = QueueFamilyTransfer {
+            source_index: source_queue_family_index,
+            destination_index: destination_queue_family_index,
+        };
+        self.validate_queue_family_transfer(
+            buffer.device(),
+            queue_family_transfer,
+            false,
+            destination_stages,
+            destination_access,
+        )?;
+
+        let buffer_inner = buffer.inner();
+
+        self.inner.pipeline_barrier2(DependencyInfo {
+            buffer_memory_barriers: [BufferMemoryBarrier {
+                destination_stages,
+                destination_access,
+                queue_family_transfer: Some(queue_family_transfer),
+                range: buffer_inner.offset..buffer_inner.offset + buffer.size(),
+                ..BufferMemoryBarrier::buffer(buffer_inner.buffer.clone())
+            }]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        });
+
+        Ok(self)
+    }
+
+    /// Releases ownership of `image` from the queue family `source_queue_family_index`, so that
+    /// it can be acquired by the queue family `destination_queue_family_index`.
+    ///
+    /// The image must be in `layout`, which is kept during the transfer. Otherwise, this behaves
+    /// the same as [`release_buffer_ownership`](Self::release_buffer_ownership).
+    ///
+    /// # Safety
+    ///
+    /// - `image` must have been created with [`Sharing::Exclusive`](crate::sync::Sharing), and
+    ///   must currently be owned by the source queue family.
+    /// - All subresources of `image` must be in `layout`.
+    /// - The matching acquire operation must use the same queue family indices and layout, and
+    ///   must be submitted after this command buffer, with a semaphore in between.
+    /// - The ownership transfer is not tracked by the automatic synchronization of the builder.
+    pub unsafe fn release_image_ownership(
+        &mut self,
+        image: Arc<dyn ImageAccess>,
+        layout: ImageLayout,
+        source_queue_family_index: u32,
+        destination_queue_family_index: u32,
+        source_stages: PipelineStages,
+        source_access: AccessFlags,
+    ) -> Result<&mut Self, SynchronizationError> {
+        let queue_family_transfer = QueueFamilyTransfer {
+            source_index: source_queue_family_index,
+            destination_index: destination_queue_family_index,
+        };
+        self.validate_queue_family_transfer(
+            image.device(),
+            queue_family_transfer,
+            true,
+            source_stages,
+            source_access,
+        )?;
+        self.validate_queue_family_transfer_layout(layout)?;
+
+        self.inner.pipeline_barrier2(DependencyInfo {
+            image_memory_barriers: [ImageMemoryBarrier {
+                source_stages,
+                source_access,
+                old_layout: layout,
+                new_layout: layout,
+                queue_family_transfer: Some(queue_family_transfer),
+                subresource_range: image_subresource_range(image.as_ref()),
+                ..ImageMemoryBarrier::image(image.inner().image.clone())
+            }]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        });
+
+        Ok(self)
+    }
+
+    /// Acquires ownership of `image` for the queue family `destination_queue_family_index`,
+    /// after it was released by the queue family `source_queue_family_index`.
+    ///
+    /// The image must be in `layout`, which is kept during the transfer. Otherwise, this behaves
+    /// the same as [`acquire_buffer_ownership`](Self::acquire_buffer_ownership).
+    ///
+    /// # Safety
+    ///
+    /// - `image` must have been created with [`Sharing::Exclusive`](crate::sync::Sharing).
+    /// - The matching release operation must use the same queue family indices and layout, and
+    ///   must be submitted before this command buffer, with a semaphore in between.
+    /// - The ownership transfer is not tracked by the automatic synchronization of the builder.
+    pub unsafe fn acquire_image_ownership(
+        &mut self,
+        image: Arc<dyn ImageAccess>,
+        layout: ImageLayout,
+        source_queue_family_index: u32,
+        destination_queue_family_index: u32,
+        destination_stages: PipelineStages,
+        destination_access: AccessFlags,
+    ) -> Result<&mut Self, SynchronizationError> {
+        let queue_family_transfer = QueueFamilyTransfer {
+            source_index: source_queue_family_index,
+            destination_index: destination_queue_family_index,
+        };
+        self.validate_queue_family_transfer(
+            image.device(),
+            queue_family_transfer,
+            false,
+            destination_stages,
+            destination_access,
+        )?;
+        self.validate_queue_family_transfer_layout(layout)?;
+
+        self.inner.pipeline_barrier2(DependencyInfo {
+            image_memory_barriers: [ImageMemoryBarrier {
+                destination_stages,
+                destination_access,
+                old_layout: layout,
+                new_layout: layout,
+                queue_family_transfer: Some(queue_family_transfer),
+                subresource_range: image_subresource_range(image.as_ref()),
+                ..ImageMemoryBarrier::image(image.inner().image.clone())
+            }]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        });
+
+        Ok(self)
+    }
+
+    fn validate_queue_family_transfer(
+        &self,
+        resource_device: &Arc<Device>,
+        queue_family_transfer: QueueFamilyTransfer,
+        release: bool,
+        stages: PipelineStages,
+        access: AccessFlags,
+    ) -> Result<(), SynchronizationError> {
+        // Barriers inside a render pass are only allowed with restrictions on the barriers that
+        // the builder can't verify, so they are forbidden altogether for now.
+        if self.render_pass_state.is_some() {
+            return Err(SynchronizationError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdPipelineBarrier-commandBuffer-cmdpool
+        if !(self.queue_family().explicitly_supports_transfers()
+            || self.queue_family().supports_graphics()
+            || self.queue_family().supports_compute())
+        {
+            return Err(SynchronizationError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdPipelineBarrier-commonparent
+        assert_eq!(self.device(), resource_device);
+
+        let QueueFamilyTransfer {
+            source_index,
+            destination_index,
+        } = queue_family_transfer;
+
+        // VUID-VkBufferMemoryBarrier-buffer-04088
+        // VUID-VkImageMemoryBarrier-image-04071
+        for queue_family_index in [source_index, destination_index] {
+            if !self
+                .device()
+                .active_queue_families()
+                .any(|queue_family| queue_family.id() == queue_family_index)
+            {
+                return Err(SynchronizationError::QueueFamilyIndexInvalid { queue_family_index });
+            }
+        }
+
+        if source_index == destination_index {
+            return Err(SynchronizationError::QueueFamilyTransferSameQueueFamily);
+        }
+
+        // The release must be executed on the source queue family, and the acquire on the
+        // destination queue family.
+        let required_queue_family_index = if release {
+            source_index
+        } else {
+            destination_index
+        };
+
+        if self.queue_family().id() != required_queue_family_index {
+            return Err(SynchronizationError::QueueFamilyTransferWrongQueueFamily {
+                required_queue_family_index,
+            });
+        }
+
+        // VUID-VkBufferMemoryBarrier2-srcAccessMask-03900
+        // VUID-VkBufferMemoryBarrier2-dstAccessMask-03900
+        // and similar for the other access types
+        if !stages.supported_access().contains(&access) {
+            return Err(SynchronizationError::AccessNotSupportedByStages);
+        }
+
+        // Empty stages are allowed, and are replaced by the top or bottom of the pipe.
+        if stages != PipelineStages::none() {
+            self.validate_event_stages(stages)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_queue_family_transfer_layout(
+        &self,
+        layout: ImageLayout,
+    ) -> Result<(), SynchronizationError> {
+        // VUID-VkImageMemoryBarrier-newLayout-01198
+        if matches!(layout, ImageLayout::Undefined | ImageLayout::Preinitialized) {
+            return Err(SynchronizationError::ImageLayoutInvalid { layout });
+        }
+
+        Ok(())
+    }
+
+    /// Resets `event` once the given `stages` of all previously recorded commands have
+    /// completed.
+    ///
+    /// # Safety
+    ///
+    /// - When the command is executed, there must be no `wait_events` command waiting on `event`
+    ///   that is still pending execution.
+    #[inline]
+    pub unsafe fn reset_event(
+        &mut self,
+        event: Arc<Event>,
+        stages: PipelineStages,
+    ) -> Result<&mut Self, SynchronizationError> {
+        self.validate_reset_event(&event, stages)?;
+
+        self.inner.reset_event(event, stages);
+
+        Ok(self)
+    }
+
+    fn validate_reset_event(
+        &self,
+        event: &Event,
+        stages: PipelineStages,
+    ) -> Result<(), SynchronizationError> {
+        // VUID-vkCmdResetEvent-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(SynchronizationError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdResetEvent-commandBuffer-cmdpool
+        if !(self.queue_family().supports_graphics() || self.queue_family().supports_compute()) {
+            return Err(SynchronizationError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdResetEvent-commonparent
+        assert_eq!(self.device(), event.device());
+
+        // VUID-vkCmdResetEvent-stageMask-01153
+        if stages.host {
+            return Err(SynchronizationError::HostStageNotAllowed);
+        }
+
+        // VUID-vkCmdResetEvent-stageMask-04090
+        // VUID-vkCmdResetEvent-stageMask-04091
+        // VUID-vkCmdResetEvent-stageMask-04098
+        // VUID-vkCmdResetEvent-stageMask-requiredbitmask
+        self.validate_event_stages(stages)?;
+
+        // VUID-vkCmdResetEvent-event-03834
+        // TODO: not checked, so unsafe for now
+
+        Ok(())
+    }
+
+    fn validate_event_stages(&self, stages: PipelineStages) -> Result<(), SynchronizationError> {
+        if stages == PipelineStages::none() {
+            return Err(SynchronizationError::StagesEmpty);
+        }
+
+        let stage_flags: ash::vk::PipelineStageFlags = stages.into();
+        let graphics_stages = ash::vk::PipelineStageFlags::DRAW_INDIRECT
+            | ash::vk::PipelineStageFlags::VERTEX_INPUT
+            | ash::vk::PipelineStageFlags::VERTEX_SHADER
+            | ash::vk::PipelineStageFlags::TESSELLATION_CONTROL_SHADER
+            | ash::vk::PipelineStageFlags::TESSELLATION_EVALUATION_SHADER
+            | ash::vk::PipelineStageFlags::GEOMETRY_SHADER
+            | ash::vk::PipelineStageFlags::FRAGMENT_SHADER
+            | ash::vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS
+            | ash::vk::PipelineStageFlags::LATE_FRAGMENT_TESTS
+            | ash::vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT
+            | ash::vk::PipelineStageFlags::ALL_GRAPHICS;
+
+        if !self.queue_family().supports_graphics() && stage_flags.intersects(graphics_stages)
+            || !self.queue_family().supports_compute() && stages.compute_shader
+        {
+            return Err(SynchronizationError::StageNotSupported);
+        }
+
+        if stages.geometry_shader && !self.device().enabled_features().geometry_shader {
+            return Err(SynchronizationError::FeatureNotEnabled {
+                feature: "geometry_shader",
+                reason: "stages.geometry_shader was enabled",
+            });
+        }
+
+        if (stages.tessellation_control_shader || stages.tessellation_evaluation_shader)
+            && !self.device().enabled_features().tessellation_shader
+        {
+            return Err(SynchronizationError::FeatureNotEnabled {
+                feature: "tessellation_shader",
+                reason: "stages.tessellation_control_shader or stages.tessellation_evaluation_shader was enabled",
+            });
+        }
+
+        Ok(())
+    }
+}
 
 impl SyncCommandBufferBuilder {
     /// Calls `vkCmdSetEvent` on the builder.
@@ -64,6 +958,57 @@ impl SyncCommandBufferBuilder {
 
         self.commands.push(Box::new(Cmd { event, stages }));
     }
+
+    /// Calls `vkCmdPipelineBarrier2` on the builder.
+    #[inline]
+    pub unsafe fn pipeline_barrier2(&mut self, dependency_info: DependencyInfo) {
+        struct Cmd {
+            dependency_info: DependencyInfo,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "pipeline_barrier2"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.pipeline_barrier(&self.dependency_info);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { dependency_info }));
+    }
+
+    /// Calls `vkCmdWaitEvents` on the builder.
+    #[inline]
+    pub unsafe fn wait_events(
+        &mut self,
+        events: SmallVec<[Arc<Event>; 4]>,
+        dependency_info: DependencyInfo,
+    ) {
+        struct Cmd {
+            events: SmallVec<[Arc<Event>; 4]>,
+            dependency_info: DependencyInfo,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "wait_events"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.wait_events(
+                    self.events.iter().map(|event| event.as_ref()),
+                    &self.dependency_info,
+                );
+            }
+        }
+
+        self.commands.push(Box::new(Cmd {
+            events,
+            dependency_info,
+        }));
+    }
 }
 
 impl UnsafeCommandBufferBuilder {
@@ -228,149 +1173,13 @@ impl UnsafeCommandBufferBuilder {
                 (fns.khr_synchronization2.cmd_pipeline_barrier2_khr)(self.handle, &dependency_info);
             }
         } else {
-            let mut src_stage_mask = ash::vk::PipelineStageFlags::empty();
-            let mut dst_stage_mask = ash::vk::PipelineStageFlags::empty();
-
-            let memory_barriers: SmallVec<[_; 2]> = memory_barriers
-                .into_iter()
-                .map(|barrier| {
-                    let &MemoryBarrier {
-                        source_stages,
-                        source_access,
-                        destination_stages,
-                        destination_access,
-                        _ne: _,
-                    } = barrier;
-
-                    debug_assert!(source_stages.supported_access().contains(&source_access));
-                    debug_assert!(destination_stages
-                        .supported_access()
-                        .contains(&destination_access));
-
-                    src_stage_mask |= source_stages.into();
-                    dst_stage_mask |= destination_stages.into();
-
-                    ash::vk::MemoryBarrier {
-                        src_access_mask: source_access.into(),
-                        dst_access_mask: destination_access.into(),
-                        ..Default::default()
-                    }
-                })
-                .collect();
-
-            let buffer_memory_barriers: SmallVec<[_; 8]> = buffer_memory_barriers
-                .into_iter()
-                .map(|barrier| {
-                    let &BufferMemoryBarrier {
-                        source_stages,
-                        source_access,
-                        destination_stages,
-                        destination_access,
-                        queue_family_transfer,
-                        ref buffer,
-                        ref range,
-                        _ne: _,
-                    } = barrier;
-
-                    debug_assert!(source_stages.supported_access().contains(&source_access));
-                    debug_assert!(destination_stages
-                        .supported_access()
-                        .contains(&destination_access));
-                    debug_assert!(!range.is_empty());
-                    debug_assert!(range.end <= buffer.size());
-
-                    src_stage_mask |= source_stages.into();
-                    dst_stage_mask |= destination_stages.into();
-
-                    ash::vk::BufferMemoryBarrier {
-                        src_access_mask: source_access.into(),
-                        dst_access_mask: destination_access.into(),
-                        src_queue_family_index: queue_family_transfer
-                            .map_or(ash::vk::QUEUE_FAMILY_IGNORED, |transfer| {
-                                transfer.source_index
-                            }),
-                        dst_queue_family_index: queue_family_transfer
-                            .map_or(ash::vk::QUEUE_FAMILY_IGNORED, |transfer| {
-                                transfer.destination_index
-                            }),
-                        buffer: buffer.internal_object(),
-                        offset: range.start,
-                        size: range.end - range.start,
-                        ..Default::default()
-                    }
-                })
-                .collect();
-
-            let image_memory_barriers: SmallVec<[_; 8]> = image_memory_barriers
-                .into_iter()
-                .map(|barrier| {
-                    let &ImageMemoryBarrier {
-                        source_stages,
-                        source_access,
-                        destination_stages,
-                        destination_access,
-                        old_layout,
-                        new_layout,
-                        queue_family_transfer,
-                        ref image,
-                        ref subresource_range,
-                        _ne: _,
-                    } = barrier;
-
-                    debug_assert!(source_stages.supported_access().contains(&source_access));
-                    debug_assert!(destination_stages
-                        .supported_access()
-                        .contains(&destination_access));
-                    debug_assert!(!matches!(
-                        new_layout,
-                        ImageLayout::Undefined | ImageLayout::Preinitialized
-                    ));
-                    debug_assert!(image
-                        .format()
-                        .unwrap()
-                        .aspects()
-                        .contains(&subresource_range.aspects));
-                    debug_assert!(!subresource_range.mip_levels.is_empty());
-                    debug_assert!(subresource_range.mip_levels.end <= image.mip_levels());
-                    debug_assert!(!subresource_range.array_layers.is_empty());
-                    debug_assert!(
-                        subresource_range.array_layers.end <= image.dimensions().array_layers()
-                    );
-
-                    src_stage_mask |= source_stages.into();
-                    dst_stage_mask |= destination_stages.into();
-
-                    ash::vk::ImageMemoryBarrier {
-                        src_access_mask: source_access.into(),
-                        dst_access_mask: destination_access.into(),
-                        old_layout: old_layout.into(),
-                        new_layout: new_layout.into(),
-                        src_queue_family_index: queue_family_transfer
-                            .map_or(ash::vk::QUEUE_FAMILY_IGNORED, |transfer| {
-                                transfer.source_index
-                            }),
-                        dst_queue_family_index: queue_family_transfer
-                            .map_or(ash::vk::QUEUE_FAMILY_IGNORED, |transfer| {
-                                transfer.destination_index
-                            }),
-                        image: image.internal_object(),
-                        subresource_range: subresource_range.clone().into(),
-                        ..Default::default()
-                    }
-                })
-                .collect();
-
-            if src_stage_mask.is_empty() {
-                // "VK_PIPELINE_STAGE_2_TOP_OF_PIPE_BIT is [...] equivalent to
-                // VK_PIPELINE_STAGE_2_NONE in the first scope."
-                src_stage_mask |= ash::vk::PipelineStageFlags::TOP_OF_PIPE;
-            }
-
-            if dst_stage_mask.is_empty() {
-                // "VK_PIPELINE_STAGE_2_BOTTOM_OF_PIPE_BIT is [...] equivalent to
-                // VK_PIPELINE_STAGE_2_NONE in the second scope."
-                dst_stage_mask |= ash::vk::PipelineStageFlags::BOTTOM_OF_PIPE;
-            }
+            let (
+                src_stage_mask,
+                dst_stage_mask,
+                memory_barriers,
+                buffer_memory_barriers,
+                image_memory_barriers,
+            ) = dependency_info_v1_0(dependency_info);
 
             let fns = self.device.fns();
             (fns.v1_0.cmd_pipeline_barrier)(
@@ -397,6 +1206,44 @@ impl UnsafeCommandBufferBuilder {
         (fns.v1_0.cmd_set_event)(self.handle, event.internal_object(), stages.into());
     }
 
+    /// Calls `vkCmdWaitEvents` on the builder.
+    ///
+    /// The source and destination stage masks are the union of the stages of all barriers.
+    pub unsafe fn wait_events<'a>(
+        &mut self,
+        events: impl IntoIterator<Item = &'a Event>,
+        dependency_info: &DependencyInfo,
+    ) {
+        let events: SmallVec<[_; 4]> = events
+            .into_iter()
+            .map(|event| event.internal_object())
+            .collect();
+        debug_assert!(!events.is_empty());
+
+        let (
+            src_stage_mask,
+            dst_stage_mask,
+            memory_barriers,
+            buffer_memory_barriers,
+            image_memory_barriers,
+        ) = dependency_info_v1_0(dependency_info);
+
+        let fns = self.device.fns();
+        (fns.v1_0.cmd_wait_events)(
+            self.handle,
+            events.len() as u32,
+            events.as_ptr(),
+            src_stage_mask,
+            dst_stage_mask,
+            memory_barriers.len() as u32,
+            memory_barriers.as_ptr(),
+            buffer_memory_barriers.len() as u32,
+            buffer_memory_barriers.as_ptr(),
+            image_memory_barriers.len() as u32,
+            image_memory_barriers.as_ptr(),
+        );
+    }
+
     /// Calls `vkCmdResetEvent` on the builder.
     #[inline]
     pub unsafe fn reset_event(&mut self, event: &Event, stages: PipelineStages) {
@@ -408,3 +1255,635 @@ impl UnsafeCommandBufferBuilder {
         (fns.v1_0.cmd_reset_event)(self.handle, event.internal_object(), stages.into());
     }
 }
+
+// Returns the subresource range of the `UnsafeImage` that `image` covers.
+fn image_subresource_range(image: &dyn ImageAccess) -> ImageSubresourceRange {
+    let image_inner = image.inner();
+
+    ImageSubresourceRange {
+        aspects: image.subresource_range().aspects,
+        mip_levels: image_inner.first_mipmap_level
+            ..image_inner.first_mipmap_level + image_inner.num_mipmap_levels,
+        array_layers: image_inner.first_layer..image_inner.first_layer + image_inner.num_layers,
+    }
+}
+
+/// Converts `dependency_info` to the barriers and stage masks of the original Vulkan 1.0
+/// synchronization commands, which specify the stages once for all barriers.
+fn dependency_info_v1_0(
+    dependency_info: &DependencyInfo,
+) -> (
+    ash::vk::PipelineStageFlags,
+    ash::vk::PipelineStageFlags,
+    SmallVec<[ash::vk::MemoryBarrier; 2]>,
+    SmallVec<[ash::vk::BufferMemoryBarrier; 8]>,
+    SmallVec<[ash::vk::ImageMemoryBarrier; 8]>,
+) {
+    let DependencyInfo {
+        memory_barriers,
+        buffer_memory_barriers,
+        image_memory_barriers,
+        _ne: _,
+    } = dependency_info;
+
+    let mut src_stage_mask = ash::vk::PipelineStageFlags::empty();
+    let mut dst_stage_mask = ash::vk::PipelineStageFlags::empty();
+
+    let memory_barriers: SmallVec<[_; 2]> = memory_barriers
+        .into_iter()
+        .map(|barrier| {
+            let &MemoryBarrier {
+                source_stages,
+                source_access,
+                destination_stages,
+                destination_access,
+                _ne: _,
+            } = barrier;
+
+            debug_assert!(source_stages.supported_access().contains(&source_access));
+            debug_assert!(destination_stages
+                .supported_access()
+                .contains(&destination_access));
+
+            src_stage_mask |= source_stages.into();
+            dst_stage_mask |= destination_stages.into();
+
+            ash::vk::MemoryBarrier {
+                src_access_mask: source_access.into(),
+                dst_access_mask: destination_access.into(),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    let buffer_memory_barriers: SmallVec<[_; 8]> = buffer_memory_barriers
+        .into_iter()
+        .map(|barrier| {
+            let &BufferMemoryBarrier {
+                source_stages,
+                source_access,
+                destination_stages,
+                destination_access,
+                queue_family_transfer,
+                ref buffer,
+                ref range,
+                _ne: _,
+            } = barrier;
+
+            debug_assert!(source_stages.supported_access().contains(&source_access));
+            debug_assert!(destination_stages
+                .supported_access()
+                .contains(&destination_access));
+            debug_assert!(!range.is_empty());
+            debug_assert!(range.end <= buffer.size());
+
+            src_stage_mask |= source_stages.into();
+            dst_stage_mask |= destination_stages.into();
+
+            ash::vk::BufferMemoryBarrier {
+                src_access_mask: source_access.into(),
+                dst_access_mask: destination_access.into(),
+                src_queue_family_index: queue_family_transfer
+                    .map_or(ash::vk::QUEUE_FAMILY_IGNORED, |transfer| {
+                        transfer.source_index
+                    }),
+                dst_queue_family_index: queue_family_transfer
+                    .map_or(ash::vk::QUEUE_FAMILY_IGNORED, |transfer| {
+                        transfer.destination_index
+                    }),
+                buffer: buffer.internal_object(),
+                offset: range.start,
+                size: range.end - range.start,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    let image_memory_barriers: SmallVec<[_; 8]> = image_memory_barriers
+        .into_iter()
+        .map(|barrier| {
+            let &ImageMemoryBarrier {
+                source_stages,
+                source_access,
+                destination_stages,
+                destination_access,
+                old_layout,
+                new_layout,
+                queue_family_transfer,
+                ref image,
+                ref subresource_range,
+                _ne: _,
+            } = barrier;
+
+            debug_assert!(source_stages.supported_access().contains(&source_access));
+            debug_assert!(destination_stages
+                .supported_access()
+                .contains(&destination_access));
+            debug_assert!(!matches!(
+                new_layout,
+                ImageLayout::Undefined | ImageLayout::Preinitialized
+            ));
+            debug_assert!(image
+                .format()
+                .unwrap()
+                .aspects()
+                .contains(&subresource_range.aspects));
+            debug_assert!(!subresource_range.mip_levels.is_empty());
+            debug_assert!(subresource_range.mip_levels.end <= image.mip_levels());
+            debug_assert!(!subresource_range.array_layers.is_empty());
+            debug_assert!(subresource_range.array_layers.end <= image.dimensions().array_layers());
+
+            src_stage_mask |= source_stages.into();
+            dst_stage_mask |= destination_stages.into();
+
+            ash::vk::ImageMemoryBarrier {
+                src_access_mask: source_access.into(),
+                dst_access_mask: destination_access.into(),
+                old_layout: old_layout.into(),
+                new_layout: new_layout.into(),
+                src_queue_family_index: queue_family_transfer
+                    .map_or(ash::vk::QUEUE_FAMILY_IGNORED, |transfer| {
+                        transfer.source_index
+                    }),
+                dst_queue_family_index: queue_family_transfer
+                    .map_or(ash::vk::QUEUE_FAMILY_IGNORED, |transfer| {
+                        transfer.destination_index
+                    }),
+                image: image.internal_object(),
+                subresource_range: subresource_range.clone().into(),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    if src_stage_mask.is_empty() {
+        // "VK_PIPELINE_STAGE_2_TOP_OF_PIPE_BIT is [...] equivalent to
+        // VK_PIPELINE_STAGE_2_NONE in the first scope."
+        src_stage_mask |= ash::vk::PipelineStageFlags::TOP_OF_PIPE;
+    }
+
+    if dst_stage_mask.is_empty() {
+        // "VK_PIPELINE_STAGE_2_BOTTOM_OF_PIPE_BIT is [...] equivalent to
+        // VK_PIPELINE_STAGE_2_NONE in the second scope."
+        dst_stage_mask |= ash::vk::PipelineStageFlags::BOTTOM_OF_PIPE;
+    }
+
+    (
+        src_stage_mask,
+        dst_stage_mask,
+        memory_barriers,
+        buffer_memory_barriers,
+        image_memory_barriers,
+    )
+}
+
+/// Error that can happen when recording a synchronization command.
+#[derive(Clone, Debug)]
+pub enum SynchronizationError {
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+
+    /// The access types of a barrier are not supported by its pipeline stages.
+    AccessNotSupportedByStages,
+
+    /// The range of a buffer or the subresource range of an image in a barrier is empty or out of
+    /// bounds of the resource.
+    BarrierRangeOutOfBounds,
+
+    /// Operation forbidden inside of a render pass.
+    ForbiddenInsideRenderPass,
+
+    /// The `host` stage was included in a stage mask that doesn't allow it.
+    HostStageNotAllowed,
+
+    /// The provided image layout is not allowed for this operation.
+    ImageLayoutInvalid { layout: ImageLayout },
+
+    /// An image barrier has a different old and new layout, which is not allowed for this
+    /// operation.
+    ImageLayoutTransitionNotAllowed,
+
+    /// No events were provided.
+    NoEvents,
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// The provided queue family index is not one of the queue families that the device was
+    /// created with.
+    QueueFamilyIndexInvalid { queue_family_index: u32 },
+
+    /// A barrier specifies a queue family ownership transfer, which is not allowed for this
+    /// operation.
+    QueueFamilyTransferNotAllowed,
+
+    /// The source and destination queue families of an ownership transfer are the same.
+    QueueFamilyTransferSameQueueFamily,
+
+    /// The command buffer does not belong to the queue family that must execute this half of the
+    /// ownership transfer.
+    QueueFamilyTransferWrongQueueFamily { required_queue_family_index: u32 },
+
+    /// One of the provided stages is not supported by the queue family.
+    StageNotSupported,
+
+    /// The provided stages are empty.
+    StagesEmpty,
+}
+
+impl error::Error for SynchronizationError {}
+
+impl fmt::Display for SynchronizationError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                f,
+                "the extension {} must be enabled: {}",
+                extension, reason,
+            ),
+            Self::FeatureNotEnabled { feature, reason } => write!(
+                f,
+                "the feature {} must be enabled: {}",
+                feature, reason,
+            ),
+            Self::AccessNotSupportedByStages => write!(
+                f,
+                "the access types of a barrier are not supported by its pipeline stages",
+            ),
+            Self::BarrierRangeOutOfBounds => write!(
+                f,
+                "the range of a resource in a barrier is empty or out of bounds of the resource",
+            ),
+            Self::ForbiddenInsideRenderPass => {
+                write!(f, "operation forbidden inside of a render pass")
+            }
+            Self::HostStageNotAllowed => write!(
+                f,
+                "the host stage was included in a stage mask that doesn't allow it",
+            ),
+            Self::ImageLayoutInvalid { layout } => write!(
+                f,
+                "the provided image layout {:?} is not allowed for this operation",
+                layout,
+            ),
+            Self::ImageLayoutTransitionNotAllowed => write!(
+                f,
+                "an image barrier has a different old and new layout, which is not allowed for this operation",
+            ),
+            Self::NoEvents => write!(f, "no events were provided"),
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::QueueFamilyIndexInvalid { queue_family_index } => write!(
+                f,
+                "the queue family index {} is not one of the queue families that the device was created with",
+                queue_family_index,
+            ),
+            Self::QueueFamilyTransferNotAllowed => write!(
+                f,
+                "a barrier specifies a queue family ownership transfer, which is not allowed for this operation",
+            ),
+            Self::QueueFamilyTransferSameQueueFamily => write!(
+                f,
+                "the source and destination queue families of an ownership transfer are the same",
+            ),
+            Self::QueueFamilyTransferWrongQueueFamily {
+                required_queue_family_index,
+            } => write!(
+                f,
+                "the command buffer does not belong to the queue family {}, which must execute this half of the ownership transfer",
+                required_queue_family_index,
+            ),
+            Self::StageNotSupported => write!(
+                f,
+                "one of the provided stages is not supported by the queue family",
+            ),
+            Self::StagesEmpty => write!(f, "the provided stages are empty"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        buffer::{BufferUsage, CpuAccessibleBuffer},
+        command_buffer::{CommandBufferUsage, PrimaryCommandBuffer},
+        sync::{EventCreateInfo, GpuFuture},
+    };
+
+    #[test]
+    fn set_and_wait_event() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let event = Arc::new(Event::new(device.clone(), EventCreateInfo::default()).unwrap());
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let stages = PipelineStages {
+            transfer: true,
+            ..PipelineStages::none()
+        };
+
+        builder.set_event(event.clone(), stages).unwrap();
+
+        unsafe {
+            builder
+                .wait_events(
+                    [event.clone()],
+                    DependencyInfo {
+                        memory_barriers: [MemoryBarrier {
+                            source_stages: stages,
+                            destination_stages: stages,
+                            ..Default::default()
+                        }]
+                        .into_iter()
+                        .collect(),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+        }
+
+        let cb = builder.build().unwrap();
+
+        cb.execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert!(event.signaled().unwrap());
+    }
+
+    #[test]
+    fn invalid_stages() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let event = Arc::new(Event::new(device.clone(), EventCreateInfo::default()).unwrap());
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            builder.set_event(event.clone(), PipelineStages::none()),
+            Err(SynchronizationError::StagesEmpty)
+        ));
+
+        assert!(matches!(
+            builder.set_event(
+                event.clone(),
+                PipelineStages {
+                    host: true,
+                    ..PipelineStages::none()
+                },
+            ),
+            Err(SynchronizationError::HostStageNotAllowed)
+        ));
+
+        unsafe {
+            assert!(matches!(
+                builder.wait_events([], DependencyInfo::default()),
+                Err(SynchronizationError::NoEvents)
+            ));
+        }
+    }
+
+    #[test]
+    fn pipeline_barrier2_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let dependency_info = DependencyInfo {
+            memory_barriers: [MemoryBarrier {
+                source_stages: PipelineStages {
+                    transfer: true,
+                    ..PipelineStages::none()
+                },
+                source_access: AccessFlags {
+                    transfer_write: true,
+                    ..AccessFlags::none()
+                },
+                destination_stages: PipelineStages {
+                    compute_shader: true,
+                    ..PipelineStages::none()
+                },
+                destination_access: AccessFlags {
+                    shader_read: true,
+                    ..AccessFlags::none()
+                },
+                ..Default::default()
+            }]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+
+        unsafe {
+            assert!(matches!(
+                builder.pipeline_barrier2(dependency_info),
+                Err(SynchronizationError::FeatureNotEnabled {
+                    feature: "synchronization2",
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn pipeline_barrier2() {
+        let (device, queue) = gfx_dev_and_queue!(synchronization2);
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        unsafe {
+            assert!(matches!(
+                builder.pipeline_barrier2(DependencyInfo {
+                    memory_barriers: [MemoryBarrier {
+                        source_stages: PipelineStages {
+                            transfer: true,
+                            ..PipelineStages::none()
+                        },
+                        source_access: AccessFlags {
+                            shader_write: true,
+                            ..AccessFlags::none()
+                        },
+                        ..Default::default()
+                    }]
+                    .into_iter()
+                    .collect(),
+                    ..Default::default()
+                }),
+                Err(SynchronizationError::AccessNotSupportedByStages)
+            ));
+
+            assert!(matches!(
+                builder.pipeline_barrier2(DependencyInfo {
+                    memory_barriers: [MemoryBarrier {
+                        source_stages: PipelineStages {
+                            clear: true,
+                            ..PipelineStages::none()
+                        },
+                        source_access: AccessFlags {
+                            transfer_read: true,
+                            ..AccessFlags::none()
+                        },
+                        ..Default::default()
+                    }]
+                    .into_iter()
+                    .collect(),
+                    ..Default::default()
+                }),
+                Err(SynchronizationError::AccessNotSupportedByStages)
+            ));
+
+            builder
+                .pipeline_barrier2(DependencyInfo {
+                    memory_barriers: [MemoryBarrier {
+                        source_stages: PipelineStages {
+                            copy: true,
+                            ..PipelineStages::none()
+                        },
+                        source_access: AccessFlags {
+                            transfer_write: true,
+                            ..AccessFlags::none()
+                        },
+                        destination_stages: PipelineStages {
+                            compute_shader: true,
+                            ..PipelineStages::none()
+                        },
+                        destination_access: AccessFlags {
+                            shader_storage_read: true,
+                            ..AccessFlags::none()
+                        },
+                        ..Default::default()
+                    }]
+                    .into_iter()
+                    .collect(),
+                    ..Default::default()
+                })
+                .unwrap();
+
+            builder
+                .pipeline_barrier2(DependencyInfo {
+                    memory_barriers: [MemoryBarrier {
+                        source_stages: PipelineStages {
+                            transfer: true,
+                            ..PipelineStages::none()
+                        },
+                        source_access: AccessFlags {
+                            transfer_write: true,
+                            ..AccessFlags::none()
+                        },
+                        destination_stages: PipelineStages {
+                            compute_shader: true,
+                            ..PipelineStages::none()
+                        },
+                        destination_access: AccessFlags {
+                            shader_read: true,
+                            ..AccessFlags::none()
+                        },
+                        ..Default::default()
+                    }]
+                    .into_iter()
+                    .collect(),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+
+        let cb = builder.build().unwrap();
+
+        cb.execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+    }
+
+    #[test]
+    fn queue_family_ownership_transfer_invalid() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::from_data(
+            device.clone(),
+            BufferUsage::transfer_src(),
+            false,
+            0u32,
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let queue_family_index = queue.family().id();
+        let stages = PipelineStages {
+            transfer: true,
+            ..PipelineStages::none()
+        };
+        let access = AccessFlags {
+            transfer_read: true,
+            ..AccessFlags::none()
+        };
+
+        unsafe {
+            assert!(matches!(
+                builder.release_buffer_ownership(
+                    buffer.clone(),
+                    queue_family_index,
+                    queue_family_index,
+                    stages,
+                    access,
+                ),
+                Err(SynchronizationError::QueueFamilyTransferSameQueueFamily)
+            ));
+
+            assert!(matches!(
+                builder.acquire_buffer_ownership(
+                    buffer.clone(),
+                    u32::MAX,
+                    queue_family_index,
+                    stages,
+                    access,
+                ),
+                Err(SynchronizationError::QueueFamilyIndexInvalid {
+                    queue_family_index: u32::MAX,
+                })
+            ));
+        }
+    }
+}
@@ -203,6 +203,41 @@ impl<L, P> AutoCommandBufferBuilder<L, P> {
         Ok(self)
     }
 
+    /// Copies a region of a single subresource of an image to a subresource of another image.
+    ///
+    /// This is a shortcut for [`copy_image`](Self::copy_image) with a single region, which can be
+    /// used for example to copy a single mip level from one array layer to another. The same
+    /// restrictions apply. `src_image` and `dst_image` can be the same image, as long as the
+    /// copied regions don't overlap.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `src_image` or `dst_image` were not created from the same device
+    ///   as `self`.
+    #[inline]
+    pub fn copy_image_subresource(
+        &mut self,
+        src_image: Arc<dyn ImageAccess>,
+        src_subresource: ImageSubresourceLayers,
+        src_offset: [u32; 3],
+        dst_image: Arc<dyn ImageAccess>,
+        dst_subresource: ImageSubresourceLayers,
+        dst_offset: [u32; 3],
+        extent: [u32; 3],
+    ) -> Result<&mut Self, CopyError> {
+        self.copy_image(CopyImageInfo {
+            regions: smallvec![ImageCopy {
+                src_subresource,
+                src_offset,
+                dst_subresource,
+                dst_offset,
+                extent,
+                ..Default::default()
+            }],
+            ..CopyImageInfo::images(src_image, dst_image)
+        })
+    }
+
     fn validate_copy_image(&self, copy_image_info: &mut CopyImageInfo) -> Result<(), CopyError> {
         let device = self.device();
 
@@ -3320,9 +3355,17 @@ impl CopyBufferToImageInfo {
         src_buffer: Arc<dyn BufferAccess>,
         dst_image: Arc<dyn ImageAccess>,
     ) -> Self {
+        let image_extent = dst_image.dimensions().width_height_depth();
+        let block_extent = dst_image.format().block_extent();
+
+        // The data is tightly packed, with each row and slice made of whole texel blocks.
         let region = BufferImageCopy {
+            buffer_row_length: (image_extent[0] + block_extent[0] - 1) / block_extent[0]
+                * block_extent[0],
+            buffer_image_height: (image_extent[1] + block_extent[1] - 1) / block_extent[1]
+                * block_extent[1],
             image_subresource: dst_image.subresource_layers(),
-            image_extent: dst_image.dimensions().width_height_depth(),
+            image_extent,
             ..Default::default()
         };
 
@@ -3334,6 +3377,70 @@ impl CopyBufferToImageInfo {
             _ne: crate::NonExhaustive(()),
         }
     }
+
+    /// Returns a `CopyBufferToImageInfo` with the specified `src_buffer` and `dst_image`, and
+    /// one region for each mip level of the image.
+    ///
+    /// The data of the mip levels is expected to be tightly packed in the buffer, one after the
+    /// other, starting with mip level 0. The start of each mip level is rounded up to a multiple
+    /// of both the texel block size of the format and 4 bytes.
+    pub fn buffer_image_mip_levels(
+        src_buffer: Arc<dyn BufferAccess>,
+        dst_image: Arc<dyn ImageAccess>,
+    ) -> Self {
+        let image_subresource = dst_image.subresource_layers();
+        let format = match dst_image.format().planes() {
+            [] => dst_image.format(),
+            planes => planes[0],
+        };
+        let block_extent = format.block_extent();
+        let block_size = format.block_size().unwrap();
+
+        // The least common multiple of the block size and 4.
+        let offset_alignment = match block_size % 4 {
+            0 => block_size,
+            2 => block_size * 2,
+            _ => block_size * 4,
+        };
+
+        let mut buffer_offset = 0;
+        let regions = (0..dst_image.mip_levels())
+            .map(|mip_level| {
+                let image_extent = dst_image
+                    .dimensions()
+                    .mip_level_dimensions(mip_level)
+                    .unwrap()
+                    .width_height_depth();
+
+                // The data is tightly packed, with each row and slice made of whole texel blocks.
+                let region = BufferImageCopy {
+                    buffer_offset: (buffer_offset + offset_alignment - 1) / offset_alignment
+                        * offset_alignment,
+                    buffer_row_length: (image_extent[0] + block_extent[0] - 1) / block_extent[0]
+                        * block_extent[0],
+                    buffer_image_height: (image_extent[1] + block_extent[1] - 1) / block_extent[1]
+                        * block_extent[1],
+                    image_subresource: ImageSubresourceLayers {
+                        mip_level,
+                        ..image_subresource.clone()
+                    },
+                    image_extent,
+                    ..Default::default()
+                };
+                buffer_offset = region.buffer_offset + region.buffer_copy_size(format);
+
+                region
+            })
+            .collect();
+
+        Self {
+            src_buffer,
+            dst_image,
+            dst_image_layout: ImageLayout::TransferDstOptimal,
+            regions,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
 }
 
 /// Parameters to copy data from an image to a buffer.
@@ -3401,14 +3508,16 @@ pub struct BufferImageCopy {
 
     /// The number of texels between successive rows of image data in the buffer.
     ///
-    /// If set to `0`, the width of the image is used.
+    /// If set to `0`, the width of the image is used. For compressed formats, this is still
+    /// given in texels, and must be a multiple of the block width.
     ///
     /// The default value is `0`.
     pub buffer_row_length: u32,
 
     /// The number of rows between successive depth slices of image data in the buffer.
     ///
-    /// If set to `0`, the height of the image is used.
+    /// If set to `0`, the height of the image is used. For compressed formats, this is still
+    /// given in texels, and must be a multiple of the block height.
     ///
     /// The default value is `0`.
     pub buffer_image_height: u32,
@@ -3564,7 +3673,16 @@ impl FillBufferInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::format::Format;
+    use crate::{
+        buffer::{BufferUsage, CpuAccessibleBuffer},
+        command_buffer::{CommandBufferUsage, PrimaryCommandBuffer},
+        format::Format,
+        image::{
+            ImageCreateFlags, ImageDimensions, ImageUsage, ImmutableImage, MipmapsCount,
+            StorageImage,
+        },
+        sync::GpuFuture,
+    };
 
     #[test]
     fn test_required_len_for_format() {
@@ -3603,4 +3721,217 @@ mod tests {
             29584
         );
     }
+
+    #[test]
+    fn copy_image_subresource() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let dimensions = ImageDimensions::Dim2d {
+            width: 4,
+            height: 4,
+            array_layers: 2,
+        };
+        let src_image = StorageImage::new(
+            device.clone(),
+            dimensions,
+            Format::R8G8B8A8_UNORM,
+            Some(queue.family()),
+        )
+        .unwrap();
+        let dst_image = StorageImage::new(
+            device.clone(),
+            dimensions,
+            Format::R8G8B8A8_UNORM,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let layer = |mip_level, array_layers| ImageSubresourceLayers {
+            aspects: ImageAspects {
+                color: true,
+                ..ImageAspects::none()
+            },
+            mip_level,
+            array_layers,
+        };
+
+        assert!(matches!(
+            builder.copy_image_subresource(
+                src_image.clone(),
+                layer(1, 0..1),
+                [0; 3],
+                dst_image.clone(),
+                layer(0, 1..2),
+                [0; 3],
+                [4, 4, 1],
+            ),
+            Err(CopyError::MipLevelsOutOfRange {
+                resource: CopyErrorResource::Source,
+                mip_levels_range_end: 2,
+                image_mip_levels: 1,
+                ..
+            })
+        ));
+
+        assert!(matches!(
+            builder.copy_image_subresource(
+                src_image.clone(),
+                layer(0, 0..1),
+                [0; 3],
+                dst_image.clone(),
+                layer(0, 2..3),
+                [0; 3],
+                [4, 4, 1],
+            ),
+            Err(CopyError::ArrayLayersOutOfRange {
+                resource: CopyErrorResource::Destination,
+                array_layers_range_end: 3,
+                image_array_layers: 2,
+                ..
+            })
+        ));
+
+        builder
+            .copy_image_subresource(
+                src_image,
+                layer(0, 0..1),
+                [0; 3],
+                dst_image,
+                layer(0, 1..2),
+                [0; 3],
+                [4, 4, 1],
+            )
+            .unwrap();
+        builder.build().unwrap();
+    }
+
+    #[test]
+    fn copy_buffer_to_image_mip_levels() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let (image, initializer) = ImmutableImage::uninitialized(
+            device.clone(),
+            ImageDimensions::Dim2d {
+                width: 16,
+                height: 8,
+                array_layers: 1,
+            },
+            Format::R8G8B8A8_UNORM,
+            MipmapsCount::Log2,
+            ImageUsage {
+                transfer_dst: true,
+                sampled: true,
+                ..ImageUsage::none()
+            },
+            ImageCreateFlags::none(),
+            ImageLayout::ShaderReadOnlyOptimal,
+            Some(queue.family()),
+        )
+        .unwrap();
+        assert_eq!(image.mip_levels(), 5);
+
+        // 16x8 + 8x4 + 4x2 + 2x1 + 1x1 texels, 4 bytes each.
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_src(),
+            false,
+            (0..(128 + 32 + 8 + 2 + 1) * 4).map(|_| 0u8),
+        )
+        .unwrap();
+
+        let copy_info = CopyBufferToImageInfo::buffer_image_mip_levels(buffer, initializer);
+        assert_eq!(copy_info.regions.len(), 5);
+        assert_eq!(
+            copy_info
+                .regions
+                .iter()
+                .map(|region| region.buffer_offset)
+                .collect::<Vec<_>>(),
+            [0, 512, 640, 672, 680],
+        );
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder.copy_buffer_to_image(copy_info).unwrap();
+        builder.build().unwrap();
+    }
+
+    #[test]
+    fn fill_and_update_buffer() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_dst(),
+            true,
+            [7u32; 8].iter().copied(),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .fill_buffer(FillBufferInfo {
+                data: 0,
+                dst_offset: 4,
+                size: 16,
+                ..FillBufferInfo::dst_buffer(buffer.clone())
+            })
+            .unwrap()
+            .update_buffer(Box::new([1u32, 2]) as Box<[_]>, buffer.clone(), 6 * 4)
+            .unwrap();
+
+        builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert_eq!(*buffer.read().unwrap(), [7, 0, 0, 0, 0, 7, 1, 2]);
+    }
+
+    #[test]
+    fn update_buffer_data_too_large() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::transfer_dst(),
+            false,
+            (0..65540).map(|_| 0u8),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            device,
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        match builder.update_buffer(vec![0u8; 65540].into_boxed_slice(), buffer, 0) {
+            Err(CopyError::DataTooLarge { .. }) => (),
+            _ => panic!(),
+        }
+    }
 }
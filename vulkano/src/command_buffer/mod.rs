@@ -90,14 +90,15 @@
 //! information.
 
 pub use self::commands::{
-    debug::DebugUtilsError,
+    acceleration_structure::AccelerationStructureBuildError,
+    debug::{DebugLabelGuard, DebugUtilsError},
     image::{
         BlitImageInfo, ClearColorImageInfo, ClearDepthStencilImageInfo, ImageBlit, ImageResolve,
         ResolveImageInfo,
     },
     pipeline::{
         CheckDescriptorSetsValidityError, CheckDispatchError, CheckDynamicStateValidityError,
-        CheckIndexBufferError, CheckIndirectBufferError, CheckPipelineError,
+        CheckIndexBufferError, CheckIndirectBufferError, CheckMultiDrawError, CheckPipelineError,
         CheckPushConstantsValidityError, CheckVertexBufferError,
     },
     query::QueryError,
@@ -106,6 +107,7 @@ pub use self::commands::{
         RenderingAttachmentResolveInfo, RenderingInfo,
     },
     secondary::{ExecuteCommandsError, UnsafeCommandBufferBuilderExecuteCommands},
+    sync::SynchronizationError,
     transfer::{
         BufferCopy, BufferImageCopy, CopyBufferInfo, CopyBufferInfoTyped, CopyBufferToImageInfo,
         CopyImageInfo, CopyImageToBufferInfo, FillBufferInfo, ImageCopy,
@@ -150,16 +152,38 @@ pub struct DrawIndirectCommand {
     pub first_instance: u32,
 }
 
+/// A single draw of a [`draw_indexed_indirect`](AutoCommandBufferBuilder::draw_indexed_indirect)
+/// command.
+///
+/// This has the same memory layout as `VkDrawIndexedIndirectCommand`, so a
+/// `CpuAccessibleBuffer<[DrawIndexedIndirectCommand]>` can be filled from the CPU through its
+/// [`write`](crate::buffer::CpuAccessibleBuffer::write) lock.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
 pub struct DrawIndexedIndirectCommand {
     pub index_count: u32,
     pub instance_count: u32,
     pub first_index: u32,
-    pub vertex_offset: u32,
+    pub vertex_offset: i32,
     pub first_instance: u32,
 }
 
+/// A single draw of a [`draw_multi`](AutoCommandBufferBuilder::draw_multi) command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DrawInfo {
+    pub first_vertex: u32,
+    pub vertex_count: u32,
+}
+
+/// A single draw of a [`draw_multi_indexed`](AutoCommandBufferBuilder::draw_multi_indexed)
+/// command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DrawIndexedInfo {
+    pub first_index: u32,
+    pub index_count: u32,
+    pub vertex_offset: i32,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
 pub struct DispatchIndirectCommand {
@@ -17,7 +17,10 @@
 //! this trait yourself by wrapping around the `UnsafeCommandPool` type.
 
 pub use self::{
-    standard::StandardCommandPool,
+    standard::{
+        StandardCommandPool, StandardCommandPoolCreateInfo, StandardCommandPoolSet,
+        StandardCommandPoolStats, StandardCommandPoolThreadHandle,
+    },
     sys::{
         CommandPoolTrimError, UnsafeCommandPool, UnsafeCommandPoolAlloc,
         UnsafeCommandPoolCreateInfo, UnsafeCommandPoolCreationError,
@@ -28,6 +31,7 @@ use crate::{
     device::{physical::QueueFamily, DeviceOwned},
     OomError,
 };
+use std::{error, fmt};
 
 pub mod standard;
 mod sys;
@@ -68,7 +72,7 @@ pub unsafe trait CommandPool: DeviceOwned {
         &self,
         level: CommandBufferLevel,
         command_buffer_count: u32,
-    ) -> Result<Self::Iter, OomError>;
+    ) -> Result<Self::Iter, CommandPoolAllocError>;
 
     /// Returns the queue family that this pool targets.
     fn queue_family(&self) -> QueueFamily;
@@ -92,6 +96,12 @@ pub unsafe trait CommandPoolBuilderAlloc: DeviceOwned {
 
     /// Returns the queue family that the pool targets.
     fn queue_family(&self) -> QueueFamily;
+
+    /// Returns the level of the command buffer.
+    #[inline]
+    fn level(&self) -> CommandBufferLevel {
+        self.inner().level()
+    }
 }
 
 /// A command buffer allocated from a pool that has finished being recorded.
@@ -106,4 +116,81 @@ pub unsafe trait CommandPoolAlloc: DeviceOwned + Send + Sync {
 
     /// Returns the queue family that the pool targets.
     fn queue_family(&self) -> QueueFamily;
+
+    /// Returns the level of the command buffer.
+    #[inline]
+    fn level(&self) -> CommandBufferLevel {
+        self.inner().level()
+    }
+}
+
+/// Error that can happen when allocating command buffers from a `CommandPool`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommandPoolAllocError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// Inheritance info was provided for primary command buffers, which don't inherit any state.
+    InheritanceInfoForPrimary,
+
+    /// The queue family index that the pool was created for was not less than the number of
+    /// queue families in the physical device.
+    QueueFamilyIndexOutOfRange {
+        queue_family_index: u32,
+        queue_family_count: u32,
+    },
+}
+
+impl error::Error for CommandPoolAllocError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CommandPoolAllocError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::OomError(_) => write!(fmt, "not enough memory"),
+            Self::InheritanceInfoForPrimary => write!(
+                fmt,
+                "inheritance info was provided for primary command buffers",
+            ),
+            Self::QueueFamilyIndexOutOfRange {
+                queue_family_index,
+                queue_family_count,
+            } => write!(
+                fmt,
+                "the queue family index of the pool ({}) was not less than the number of queue families in the physical device ({})",
+                queue_family_index, queue_family_count,
+            ),
+        }
+    }
+}
+
+impl From<OomError> for CommandPoolAllocError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<UnsafeCommandPoolCreationError> for CommandPoolAllocError {
+    #[inline]
+    fn from(err: UnsafeCommandPoolCreationError) -> Self {
+        match err {
+            UnsafeCommandPoolCreationError::OomError(err) => Self::OomError(err),
+            UnsafeCommandPoolCreationError::QueueFamilyIndexOutOfRange {
+                queue_family_index,
+                queue_family_count,
+            } => Self::QueueFamilyIndexOutOfRange {
+                queue_family_index,
+                queue_family_count,
+            },
+        }
+    }
 }
@@ -1,20 +1,24 @@
 use super::{
-    sys::{CommandBufferAllocateInfo, UnsafeCommandPoolCreateInfo, UnsafeCommandPoolCreationError},
-    CommandPool, CommandPoolAlloc, CommandPoolBuilderAlloc, UnsafeCommandPool,
-    UnsafeCommandPoolAlloc,
+    sys::{CommandBufferAllocateInfo, CommandPoolTrimError, UnsafeCommandPoolCreateInfo},
+    CommandPool, CommandPoolAlloc, CommandPoolAllocError, CommandPoolBuilderAlloc,
+    UnsafeCommandPool, UnsafeCommandPoolAlloc,
 };
 use crate::{
-    command_buffer::CommandBufferLevel,
+    command_buffer::{CommandBufferInheritanceInfo, CommandBufferLevel},
     device::{physical::QueueFamily, Device, DeviceOwned},
-    OomError, VulkanObject,
+    OomError, Version, VulkanObject,
 };
 use crossbeam_queue::SegQueue;
 use std::{
     collections::HashMap,
+    iter,
     marker::PhantomData,
     mem::ManuallyDrop,
     ptr,
-    sync::{Arc, Mutex, Weak},
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex, Weak,
+    },
     thread,
     vec::IntoIter as VecIntoIter,
 };
@@ -44,10 +48,31 @@ pub struct StandardCommandPool {
     // Identifier of the queue family.
     queue_family: u32,
 
+    // Parameters used to create the Vulkan pool of each thread.
+    transient: bool,
+    reset_command_buffer: bool,
+
+    // Maximum length of the reuse lists of each thread.
+    max_available_primary_command_buffers: Option<usize>,
+    max_available_secondary_command_buffers: Option<usize>,
+
+    // Name given to the Vulkan pool of each thread.
+    debug_name: Option<String>,
+
     // For each thread, we store thread-specific info.
     per_thread: Mutex<HashMap<thread::ThreadId, Weak<StandardCommandPoolPerThread>>>,
+
+    // Size that `per_thread` must reach before expired entries are swept from it.
+    per_thread_sweep_threshold: AtomicUsize,
+
+    // Counters used to build a `StandardCommandPoolStats`, shared with the per-thread structs.
+    counters: Arc<StandardCommandPoolCounters>,
 }
 
+// Minimum size that the `per_thread` map of a `StandardCommandPool` must reach before it is
+// swept.
+const PER_THREAD_SWEEP_MIN_THRESHOLD: usize = 16;
+
 unsafe impl Send for StandardCommandPool {}
 unsafe impl Sync for StandardCommandPool {}
 
@@ -59,85 +84,409 @@ struct StandardCommandPoolPerThread {
     available_primary_command_buffers: SegQueue<UnsafeCommandPoolAlloc>,
     // List of existing secondary command buffers that are available for reuse.
     available_secondary_command_buffers: SegQueue<UnsafeCommandPoolAlloc>,
+    // Counters of the parent `StandardCommandPool`.
+    counters: Arc<StandardCommandPoolCounters>,
+}
+
+impl StandardCommandPoolPerThread {
+    // Takes a command buffer of the given level out of the reuse list.
+    fn pop_available(&self, level: CommandBufferLevel) -> Option<UnsafeCommandPoolAlloc> {
+        let (existing, available_count) = match level {
+            CommandBufferLevel::Primary => (
+                &self.available_primary_command_buffers,
+                &self.counters.available_primary_command_buffers,
+            ),
+            CommandBufferLevel::Secondary => (
+                &self.available_secondary_command_buffers,
+                &self.counters.available_secondary_command_buffers,
+            ),
+        };
+
+        let cmd = existing.pop()?;
+        available_count.fetch_sub(1, Ordering::Relaxed);
+        Some(cmd)
+    }
+
+    // Puts a command buffer back into the reuse list of its level, unless the list already holds
+    // `max_available` command buffers, in which case the command buffer is given back.
+    fn push_available(
+        &self,
+        level: CommandBufferLevel,
+        cmd: UnsafeCommandPoolAlloc,
+        max_available: Option<usize>,
+    ) -> Result<(), UnsafeCommandPoolAlloc> {
+        let (existing, available_count) = match level {
+            CommandBufferLevel::Primary => (
+                &self.available_primary_command_buffers,
+                &self.counters.available_primary_command_buffers,
+            ),
+            CommandBufferLevel::Secondary => (
+                &self.available_secondary_command_buffers,
+                &self.counters.available_secondary_command_buffers,
+            ),
+        };
+
+        if max_available.map_or(false, |max| existing.len() >= max) {
+            return Err(cmd);
+        }
+
+        existing.push(cmd);
+        available_count.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    // Frees all the command buffers of the reuse lists. `pool` must be the locked `self.pool`.
+    fn free_available(&self, pool: &UnsafeCommandPool) {
+        let available = iter::from_fn(|| {
+            self.pop_available(CommandBufferLevel::Primary)
+                .or_else(|| self.pop_available(CommandBufferLevel::Secondary))
+        });
+
+        // Safe because the command buffers in the reuse lists are neither in use nor pending
+        // execution, and the caller holds the pool lock.
+        unsafe {
+            pool.free_command_buffers(available);
+        }
+    }
+}
+
+impl Drop for StandardCommandPoolPerThread {
+    fn drop(&mut self) {
+        // This runs once the thread's last command buffer has been dropped. Neither that command
+        // buffer nor those of the reuse lists are freed individually. `UnsafeCommandPoolAlloc` has
+        // no destructor, and they are all freed at once when `pool` is dropped right after this,
+        // which destroys the Vulkan pool.
+        self.counters.available_primary_command_buffers.fetch_sub(
+            self.available_primary_command_buffers.len(),
+            Ordering::Relaxed,
+        );
+        self.counters.available_secondary_command_buffers.fetch_sub(
+            self.available_secondary_command_buffers.len(),
+            Ordering::Relaxed,
+        );
+        self.counters
+            .per_thread_pools
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Default)]
+struct StandardCommandPoolCounters {
+    per_thread_pools: AtomicUsize,
+    available_primary_command_buffers: AtomicUsize,
+    available_secondary_command_buffers: AtomicUsize,
+    allocated_command_buffers: AtomicU64,
+    recycled_command_buffers: AtomicU64,
 }
 
 impl StandardCommandPool {
-    /// Builds a new pool.
+    /// Builds a new pool with the default `StandardCommandPoolCreateInfo`.
     ///
     /// # Panic
     ///
     /// - Panics if the device and the queue family don't belong to the same physical device.
     ///
+    #[inline]
     pub fn new(device: Arc<Device>, queue_family: QueueFamily) -> StandardCommandPool {
+        Self::with_create_info(device, queue_family, Default::default())
+    }
+
+    /// Builds a new pool, using `create_info` to configure the Vulkan pool of each thread.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the device and the queue family don't belong to the same physical device.
+    ///
+    pub fn with_create_info(
+        device: Arc<Device>,
+        queue_family: QueueFamily,
+        create_info: StandardCommandPoolCreateInfo,
+    ) -> StandardCommandPool {
         assert_eq!(
             device.physical_device().internal_object(),
             queue_family.physical_device().internal_object()
         );
 
+        let StandardCommandPoolCreateInfo {
+            transient,
+            reset_command_buffer,
+            max_available_primary_command_buffers,
+            max_available_secondary_command_buffers,
+            debug_name,
+            _ne: _,
+        } = create_info;
+
         StandardCommandPool {
             device: device,
             queue_family: queue_family.id(),
+            transient,
+            reset_command_buffer,
+            max_available_primary_command_buffers,
+            max_available_secondary_command_buffers,
+            debug_name,
             per_thread: Mutex::new(Default::default()),
+            per_thread_sweep_threshold: AtomicUsize::new(PER_THREAD_SWEEP_MIN_THRESHOLD),
+            counters: Default::default(),
         }
     }
-}
 
-unsafe impl CommandPool for Arc<StandardCommandPool> {
-    type Iter = VecIntoIter<StandardCommandPoolBuilder>;
-    type Builder = StandardCommandPoolBuilder;
-    type Alloc = StandardCommandPoolAlloc;
+    /// Returns whether the Vulkan pools are created with the transient hint.
+    #[inline]
+    pub fn transient(&self) -> bool {
+        self.transient
+    }
 
-    fn allocate(
-        &self,
+    /// Returns whether command buffers can be reset individually, and are therefore reused
+    /// instead of being freed when they are dropped.
+    #[inline]
+    pub fn reset_command_buffer(&self) -> bool {
+        self.reset_command_buffer
+    }
+
+    /// Trims the Vulkan pool of every thread that is still using this pool, which recycles unused
+    /// internal memory back to the system.
+    ///
+    /// If `free_available` is true, the command buffers that are kept around for reuse are freed
+    /// first, so that their memory can be reclaimed as well.
+    ///
+    /// Threads that no longer hold any command buffer from this pool are skipped. This function
+    /// can be called from any thread.
+    ///
+    /// This function is supported only if the device API version is at least 1.1, or if the
+    /// [`khr_maintenance1`](crate::device::DeviceExtensions::khr_maintenance1) extension is
+    /// enabled on the device. Otherwise an error is returned.
+    pub fn trim(&self, free_available: bool) -> Result<(), CommandPoolTrimError> {
+        if !(self.device.api_version() >= Version::V1_1
+            || self.device.enabled_extensions().khr_maintenance1)
+        {
+            return Err(CommandPoolTrimError::Maintenance1ExtensionNotEnabled);
+        }
+
+        for per_thread in self.live_per_thread() {
+            let pool_lock = per_thread.pool.lock().unwrap();
+
+            if free_available {
+                per_thread.free_available(&pool_lock);
+            }
+
+            pool_lock.trim()?;
+        }
+
+        Ok(())
+    }
+
+    /// Resets the Vulkan pool of every thread that is still using this pool, which puts all the
+    /// command buffers allocated from this pool back into the initial state.
+    ///
+    /// If `release_resources` is true, the command buffers that are kept around for reuse are
+    /// freed, and the implementation is hinted to free all the memory internally allocated for
+    /// the pools. Otherwise they stay available for reuse.
+    ///
+    /// # Safety
+    ///
+    /// - No command buffer allocated from this pool must currently be recording or pending
+    ///   execution.
+    /// - The command buffers allocated from this pool that are still alive must not be submitted
+    ///   again, as their contents are discarded.
+    pub unsafe fn reset(&self, release_resources: bool) -> Result<(), OomError> {
+        for per_thread in self.live_per_thread() {
+            let pool_lock = per_thread.pool.lock().unwrap();
+
+            if release_resources {
+                per_thread.free_available(&pool_lock);
+            }
+
+            pool_lock.reset(release_resources)?;
+        }
+
+        Ok(())
+    }
+
+    // Returns the per-thread structs of the threads that are still using this pool.
+    fn live_per_thread(&self) -> Vec<Arc<StandardCommandPoolPerThread>> {
+        self.per_thread
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(Weak::upgrade)
+            .collect()
+    }
+
+    /// Returns statistics about the command buffers allocated from this pool.
+    ///
+    /// The values are read without synchronizing with other threads, so they may be slightly out
+    /// of date if the pool is being used concurrently.
+    pub fn statistics(&self) -> StandardCommandPoolStats {
+        let counters = &self.counters;
+
+        StandardCommandPoolStats {
+            per_thread_pools: counters.per_thread_pools.load(Ordering::Relaxed),
+            available_primary_command_buffers: counters
+                .available_primary_command_buffers
+                .load(Ordering::Relaxed),
+            available_secondary_command_buffers: counters
+                .available_secondary_command_buffers
+                .load(Ordering::Relaxed),
+            allocated_command_buffers: counters.allocated_command_buffers.load(Ordering::Relaxed),
+            recycled_command_buffers: counters.recycled_command_buffers.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Allocates command buffers from this pool, and pushes them at the end of `out`.
+    ///
+    /// This is the same as [`CommandPool::allocate`], except that the caller's `Vec` is reused
+    /// instead of a new one being allocated on each call.
+    #[inline]
+    pub fn allocate_into(
+        self: &Arc<Self>,
         level: CommandBufferLevel,
-        mut command_buffer_count: u32,
-    ) -> Result<Self::Iter, OomError> {
+        command_buffer_count: u32,
+        out: &mut Vec<StandardCommandPoolBuilder>,
+    ) -> Result<(), CommandPoolAllocError> {
+        self.allocate_inner(level, command_buffer_count, None, out)
+    }
+
+    /// Allocates command buffers from this pool, and stores `inheritance_info` in each of them
+    /// so that it can later be retrieved with
+    /// [`StandardCommandPoolBuilder::inheritance_info`].
+    ///
+    /// Returns an error if `inheritance_info` is `Some` and `level` is
+    /// [`CommandBufferLevel::Primary`], as only secondary command buffers inherit state.
+    pub fn allocate_with_inheritance_info(
+        self: &Arc<Self>,
+        level: CommandBufferLevel,
+        command_buffer_count: u32,
+        inheritance_info: Option<CommandBufferInheritanceInfo>,
+    ) -> Result<VecIntoIter<StandardCommandPoolBuilder>, CommandPoolAllocError> {
+        if level == CommandBufferLevel::Primary && inheritance_info.is_some() {
+            return Err(CommandPoolAllocError::InheritanceInfoForPrimary);
+        }
+
+        let mut output = Vec::with_capacity(command_buffer_count as usize);
+        self.allocate_inner(
+            level,
+            command_buffer_count,
+            inheritance_info.as_ref(),
+            &mut output,
+        )?;
+
+        Ok(output.into_iter())
+    }
+
+    /// Returns a handle to the Vulkan pool of the current thread, creating it if necessary.
+    ///
+    /// Allocating through the handle skips the lookup of the current thread that every call to
+    /// [`CommandPool::allocate`] performs, which is useful when allocating many command buffers
+    /// in a row. The handle can't be sent to another thread.
+    ///
+    /// The handle keeps the Vulkan pool of the current thread alive. It stays valid if the pool
+    /// is [reset](StandardCommandPool::reset), as resetting doesn't replace the Vulkan pools.
+    pub fn per_thread_handle(
+        self: &Arc<Self>,
+    ) -> Result<StandardCommandPoolThreadHandle, CommandPoolAllocError> {
+        Ok(StandardCommandPoolThreadHandle {
+            per_thread: self.current_per_thread()?,
+            pool: self.clone(),
+            dummy_avoid_send_sync: PhantomData,
+        })
+    }
+
+    fn allocate_inner(
+        self: &Arc<Self>,
+        level: CommandBufferLevel,
+        command_buffer_count: u32,
+        inheritance_info: Option<&CommandBufferInheritanceInfo>,
+        out: &mut Vec<StandardCommandPoolBuilder>,
+    ) -> Result<(), CommandPoolAllocError> {
+        let per_thread = self.current_per_thread()?;
+        self.allocate_from(
+            &per_thread,
+            level,
+            command_buffer_count,
+            inheritance_info,
+            out,
+        )
+    }
+
+    // Returns the `StandardCommandPoolPerThread` of the current thread, creating it if necessary.
+    fn current_per_thread(
+        &self,
+    ) -> Result<Arc<StandardCommandPoolPerThread>, CommandPoolAllocError> {
         // Find the correct `StandardCommandPoolPerThread` structure.
         let mut hashmap = self.per_thread.lock().unwrap();
-        // TODO: meh for iterating everything every time
-        hashmap.retain(|_, w| w.upgrade().is_some());
-
         let this_thread = thread::current().id();
 
         // Get an appropriate `Arc<StandardCommandPoolPerThread>`.
         let per_thread = if let Some(entry) = hashmap.get(&this_thread).and_then(Weak::upgrade) {
             entry
         } else {
+            // Entries of threads that stopped using the pool are only removed once the map has
+            // grown enough since the last sweep, so that the cost of iterating is amortized.
+            if hashmap.len() >= self.per_thread_sweep_threshold.load(Ordering::Relaxed) {
+                hashmap.retain(|_, w| w.upgrade().is_some());
+                self.per_thread_sweep_threshold.store(
+                    (hashmap.len() * 2).max(PER_THREAD_SWEEP_MIN_THRESHOLD),
+                    Ordering::Relaxed,
+                );
+            }
+
             let new_pool = UnsafeCommandPool::new(
                 self.device.clone(),
                 UnsafeCommandPoolCreateInfo {
-                    queue_family_index: self.queue_family().id(),
-                    reset_command_buffer: true,
+                    queue_family_index: self.queue_family,
+                    transient: self.transient,
+                    reset_command_buffer: self.reset_command_buffer,
                     ..Default::default()
                 },
-            )
-            .map_err(|err| match err {
-                UnsafeCommandPoolCreationError::OomError(err) => err,
-                _ => panic!("Unexpected error: {}", err),
-            })?;
+            )?;
+
+            if let Some(debug_name) = &self.debug_name {
+                if self.device.instance().enabled_extensions().ext_debug_utils {
+                    self.device.set_debug_utils_object_name(
+                        &new_pool,
+                        Some(&format!(
+                            "StandardCommandPool[{}]#{:?}",
+                            debug_name, this_thread
+                        )),
+                    )?;
+                }
+            }
+
             let pt = Arc::new(StandardCommandPoolPerThread {
                 pool: Mutex::new(new_pool),
                 available_primary_command_buffers: SegQueue::new(),
                 available_secondary_command_buffers: SegQueue::new(),
+                counters: self.counters.clone(),
             });
+            self.counters
+                .per_thread_pools
+                .fetch_add(1, Ordering::Relaxed);
 
             hashmap.insert(this_thread, Arc::downgrade(&pt));
             pt
         };
 
-        // The final output.
-        let mut output = Vec::with_capacity(command_buffer_count as usize);
+        Ok(per_thread)
+    }
+
+    // Allocates command buffers from the Vulkan pool of `per_thread`, which must belong to this
+    // pool and to the current thread.
+    fn allocate_from(
+        self: &Arc<Self>,
+        per_thread: &Arc<StandardCommandPoolPerThread>,
+        level: CommandBufferLevel,
+        mut command_buffer_count: u32,
+        inheritance_info: Option<&CommandBufferInheritanceInfo>,
+        out: &mut Vec<StandardCommandPoolBuilder>,
+    ) -> Result<(), CommandPoolAllocError> {
+        out.reserve(command_buffer_count as usize);
+        let start_len = out.len();
 
         // First, pick from already-existing command buffers.
         {
-            let existing = match level {
-                CommandBufferLevel::Primary => &per_thread.available_primary_command_buffers,
-                CommandBufferLevel::Secondary => &per_thread.available_secondary_command_buffers,
-            };
-
             for _ in 0..command_buffer_count as usize {
-                if let Some(cmd) = existing.pop() {
-                    output.push(StandardCommandPoolBuilder {
+                if let Some(cmd) = per_thread.pop_available(level) {
+                    out.push(StandardCommandPoolBuilder {
                         inner: StandardCommandPoolAlloc {
                             cmd: ManuallyDrop::new(cmd),
                             pool: per_thread.clone(),
@@ -145,25 +494,33 @@ unsafe impl CommandPool for Arc<StandardCommandPool> {
                             level,
                             device: self.device.clone(),
                         },
+                        inheritance_info: inheritance_info.cloned(),
                         dummy_avoid_send_sync: PhantomData,
                     });
                 } else {
                     break;
                 }
             }
+
+            self.counters
+                .recycled_command_buffers
+                .fetch_add((out.len() - start_len) as u64, Ordering::Relaxed);
         };
 
         // Then allocate the rest.
-        if output.len() < command_buffer_count as usize {
+        if out.len() - start_len < command_buffer_count as usize {
             let pool_lock = per_thread.pool.lock().unwrap();
-            command_buffer_count -= output.len() as u32;
+            command_buffer_count -= (out.len() - start_len) as u32;
 
             for cmd in pool_lock.allocate_command_buffers(CommandBufferAllocateInfo {
                 level,
                 command_buffer_count,
                 ..Default::default()
             })? {
-                output.push(StandardCommandPoolBuilder {
+                self.counters
+                    .allocated_command_buffers
+                    .fetch_add(1, Ordering::Relaxed);
+                out.push(StandardCommandPoolBuilder {
                     inner: StandardCommandPoolAlloc {
                         cmd: ManuallyDrop::new(cmd),
                         pool: per_thread.clone(),
@@ -171,11 +528,107 @@ unsafe impl CommandPool for Arc<StandardCommandPool> {
                         level,
                         device: self.device.clone(),
                     },
+                    inheritance_info: inheritance_info.cloned(),
                     dummy_avoid_send_sync: PhantomData,
                 });
             }
         }
 
+        Ok(())
+    }
+}
+
+/// Statistics about a `StandardCommandPool`, returned by
+/// [`statistics`](StandardCommandPool::statistics).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StandardCommandPoolStats {
+    /// The number of threads that currently have a Vulkan pool.
+    pub per_thread_pools: usize,
+
+    /// The number of primary command buffers that are currently available for reuse.
+    pub available_primary_command_buffers: usize,
+
+    /// The number of secondary command buffers that are currently available for reuse.
+    pub available_secondary_command_buffers: usize,
+
+    /// The total number of command buffers that were newly allocated from a Vulkan pool.
+    pub allocated_command_buffers: u64,
+
+    /// The total number of command buffers that were handed out again instead of being newly
+    /// allocated.
+    pub recycled_command_buffers: u64,
+}
+
+/// Parameters to create a `StandardCommandPool`.
+#[derive(Clone, Debug)]
+pub struct StandardCommandPoolCreateInfo {
+    /// A hint to the implementation that the command buffers allocated from the pool will be
+    /// short-lived.
+    ///
+    /// The default value is `false`.
+    pub transient: bool,
+
+    /// Whether the command buffers allocated from the pool can be reset individually.
+    ///
+    /// If this is `true`, command buffers are kept around and reused once they are dropped. If
+    /// it is `false`, they are freed instead, and the pool only reclaims their memory when the
+    /// whole Vulkan pool is reset or destroyed.
+    ///
+    /// The default value is `true`.
+    pub reset_command_buffer: bool,
+
+    /// The maximum number of primary command buffers that each thread keeps around for reuse.
+    /// Once this number is reached, dropped command buffers are freed instead.
+    ///
+    /// The default value is `None`, which means that there is no limit.
+    pub max_available_primary_command_buffers: Option<usize>,
+
+    /// The maximum number of secondary command buffers that each thread keeps around for reuse.
+    /// Once this number is reached, dropped command buffers are freed instead.
+    ///
+    /// The default value is `None`, which means that there is no limit.
+    pub max_available_secondary_command_buffers: Option<usize>,
+
+    /// A name to give to the Vulkan pool of each thread, to make them easier to tell apart in
+    /// debugging tools.
+    ///
+    /// Each pool is named `StandardCommandPool[<debug_name>]#<thread id>`. Naming requires the
+    /// [`ext_debug_utils`](crate::instance::InstanceExtensions::ext_debug_utils) extension to be
+    /// enabled on the instance, otherwise the pools are left unnamed.
+    ///
+    /// The default value is `None`.
+    pub debug_name: Option<String>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for StandardCommandPoolCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            transient: false,
+            reset_command_buffer: true,
+            max_available_primary_command_buffers: None,
+            max_available_secondary_command_buffers: None,
+            debug_name: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+unsafe impl CommandPool for Arc<StandardCommandPool> {
+    type Iter = VecIntoIter<StandardCommandPoolBuilder>;
+    type Builder = StandardCommandPoolBuilder;
+    type Alloc = StandardCommandPoolAlloc;
+
+    fn allocate(
+        &self,
+        level: CommandBufferLevel,
+        command_buffer_count: u32,
+    ) -> Result<Self::Iter, CommandPoolAllocError> {
+        let mut output = Vec::with_capacity(command_buffer_count as usize);
+        self.allocate_into(level, command_buffer_count, &mut output)?;
+
         // Final output.
         Ok(output.into_iter())
     }
@@ -196,15 +649,144 @@ unsafe impl DeviceOwned for StandardCommandPool {
     }
 }
 
+/// Set of `StandardCommandPool`s of a device, one per queue family.
+///
+/// The pool of a queue family is created the first time it is requested, and is then kept alive
+/// for as long as the set is.
+#[derive(Debug)]
+pub struct StandardCommandPoolSet {
+    device: Arc<Device>,
+    create_info: StandardCommandPoolCreateInfo,
+    pools: Mutex<HashMap<u32, Arc<StandardCommandPool>>>,
+}
+
+impl StandardCommandPoolSet {
+    /// Builds a new set, whose pools are created with the default
+    /// `StandardCommandPoolCreateInfo`.
+    #[inline]
+    pub fn new(device: Arc<Device>) -> StandardCommandPoolSet {
+        Self::with_create_info(device, Default::default())
+    }
+
+    /// Builds a new set, whose pools are created with `create_info`.
+    #[inline]
+    pub fn with_create_info(
+        device: Arc<Device>,
+        create_info: StandardCommandPoolCreateInfo,
+    ) -> StandardCommandPoolSet {
+        StandardCommandPoolSet {
+            device,
+            create_info,
+            pools: Mutex::new(Default::default()),
+        }
+    }
+
+    /// Returns the pool of the set that allocates command buffers for `queue_family`, creating
+    /// it if necessary.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the device and the queue family don't belong to the same physical device.
+    ///
+    pub fn pool_for(&self, queue_family: QueueFamily) -> Arc<StandardCommandPool> {
+        assert_eq!(
+            self.device.physical_device().internal_object(),
+            queue_family.physical_device().internal_object()
+        );
+
+        self.pools
+            .lock()
+            .unwrap()
+            .entry(queue_family.id())
+            .or_insert_with(|| {
+                Arc::new(StandardCommandPool::with_create_info(
+                    self.device.clone(),
+                    queue_family,
+                    self.create_info.clone(),
+                ))
+            })
+            .clone()
+    }
+}
+
+unsafe impl DeviceOwned for StandardCommandPoolSet {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+/// Handle to the Vulkan pool that a `StandardCommandPool` uses for the current thread, returned
+/// by [`per_thread_handle`](StandardCommandPool::per_thread_handle).
+#[derive(Debug)]
+pub struct StandardCommandPoolThreadHandle {
+    per_thread: Arc<StandardCommandPoolPerThread>,
+    pool: Arc<StandardCommandPool>,
+    // The Vulkan pool must only be used from the thread it was created for.
+    dummy_avoid_send_sync: PhantomData<*const u8>,
+}
+
+impl StandardCommandPoolThreadHandle {
+    /// Returns the pool that this handle belongs to.
+    #[inline]
+    pub fn pool(&self) -> &Arc<StandardCommandPool> {
+        &self.pool
+    }
+
+    /// Allocates command buffers from the Vulkan pool of the current thread.
+    #[inline]
+    pub fn allocate(
+        &self,
+        level: CommandBufferLevel,
+        command_buffer_count: u32,
+    ) -> Result<VecIntoIter<StandardCommandPoolBuilder>, CommandPoolAllocError> {
+        let mut output = Vec::with_capacity(command_buffer_count as usize);
+        self.allocate_into(level, command_buffer_count, &mut output)?;
+
+        Ok(output.into_iter())
+    }
+
+    /// Allocates command buffers from the Vulkan pool of the current thread, and pushes them at
+    /// the end of `out`.
+    #[inline]
+    pub fn allocate_into(
+        &self,
+        level: CommandBufferLevel,
+        command_buffer_count: u32,
+        out: &mut Vec<StandardCommandPoolBuilder>,
+    ) -> Result<(), CommandPoolAllocError> {
+        self.pool
+            .allocate_from(&self.per_thread, level, command_buffer_count, None, out)
+    }
+}
+
+unsafe impl DeviceOwned for StandardCommandPoolThreadHandle {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.pool.device
+    }
+}
+
 /// Command buffer allocated from a `StandardCommandPool` and that is currently being built.
 pub struct StandardCommandPoolBuilder {
     // The only difference between a `StandardCommandPoolBuilder` and a `StandardCommandPoolAlloc`
     // is that the former must not implement `Send` and `Sync`. Therefore we just share the structs.
     inner: StandardCommandPoolAlloc,
+    // The inheritance info that was provided when allocating a secondary command buffer.
+    inheritance_info: Option<CommandBufferInheritanceInfo>,
     // Unimplemented `Send` and `Sync` from the builder.
     dummy_avoid_send_sync: PhantomData<*const u8>,
 }
 
+impl StandardCommandPoolBuilder {
+    /// Returns the inheritance info that was provided when allocating the command buffer with
+    /// [`StandardCommandPool::allocate_with_inheritance_info`].
+    #[inline]
+    pub fn inheritance_info(&self) -> Option<&CommandBufferInheritanceInfo> {
+        self.inheritance_info.as_ref()
+    }
+}
+
 unsafe impl CommandPoolBuilderAlloc for StandardCommandPoolBuilder {
     type Alloc = StandardCommandPoolAlloc;
 
@@ -222,6 +804,11 @@ unsafe impl CommandPoolBuilderAlloc for StandardCommandPoolBuilder {
     fn queue_family(&self) -> QueueFamily {
         self.inner.queue_family()
     }
+
+    #[inline]
+    fn level(&self) -> CommandBufferLevel {
+        self.inner.level
+    }
 }
 
 unsafe impl DeviceOwned for StandardCommandPoolBuilder {
@@ -235,7 +822,8 @@ unsafe impl DeviceOwned for StandardCommandPoolBuilder {
 pub struct StandardCommandPoolAlloc {
     // The actual command buffer. Extracted in the `Drop` implementation.
     cmd: ManuallyDrop<UnsafeCommandPoolAlloc>,
-    // We hold a reference to the command pool for our destructor.
+    // We hold a reference to the command pool for our destructor. Since `cmd` is never dropped
+    // by itself, the Vulkan pool is guaranteed to outlive every use of the command buffer.
     pool: Arc<StandardCommandPoolPerThread>,
     // Keep alive the `StandardCommandPool`, otherwise it would be destroyed.
     pool_parent: Arc<StandardCommandPool>,
@@ -263,6 +851,11 @@ unsafe impl CommandPoolAlloc for StandardCommandPoolAlloc {
             .queue_family_by_id(queue_family_id)
             .unwrap()
     }
+
+    #[inline]
+    fn level(&self) -> CommandBufferLevel {
+        self.level
+    }
 }
 
 unsafe impl DeviceOwned for StandardCommandPoolAlloc {
@@ -279,10 +872,32 @@ impl Drop for StandardCommandPoolAlloc {
         // Safe because `self.cmd` is wrapped in a `ManuallyDrop`.
         let cmd: UnsafeCommandPoolAlloc = unsafe { ptr::read(&*self.cmd) };
 
-        match self.level {
-            CommandBufferLevel::Primary => self.pool.available_primary_command_buffers.push(cmd),
-            CommandBufferLevel::Secondary => {
-                self.pool.available_secondary_command_buffers.push(cmd)
+        // If this is the last command buffer keeping the thread's pool alive, the Vulkan pool is
+        // destroyed when `self.pool` is dropped after this function returns, which frees all of
+        // its command buffers at once.
+        if Arc::strong_count(&self.pool) == 1 {
+            return;
+        }
+
+        // Without per-buffer reset, a command buffer can't be recorded again, so it is never
+        // kept for reuse.
+        let max_available = if !self.pool_parent.reset_command_buffer {
+            Some(0)
+        } else {
+            match self.level {
+                CommandBufferLevel::Primary => {
+                    self.pool_parent.max_available_primary_command_buffers
+                }
+                CommandBufferLevel::Secondary => {
+                    self.pool_parent.max_available_secondary_command_buffers
+                }
+            }
+        };
+
+        // If the command buffer isn't kept for reuse, give it back to the Vulkan pool.
+        if let Err(cmd) = self.pool.push_available(self.level, cmd, max_available) {
+            unsafe {
+                self.pool.pool.lock().unwrap().free_command_buffers([cmd]);
             }
         }
     }
@@ -290,13 +905,26 @@ impl Drop for StandardCommandPoolAlloc {
 
 #[cfg(test)]
 mod tests {
+    use super::PER_THREAD_SWEEP_MIN_THRESHOLD;
     use crate::command_buffer::pool::CommandPool;
+    use crate::command_buffer::pool::CommandPoolAlloc;
+    use crate::command_buffer::pool::CommandPoolAllocError;
     use crate::command_buffer::pool::CommandPoolBuilderAlloc;
+    use crate::command_buffer::pool::CommandPoolTrimError;
     use crate::command_buffer::pool::StandardCommandPool;
+    use crate::command_buffer::pool::StandardCommandPoolCreateInfo;
+    use crate::command_buffer::pool::StandardCommandPoolSet;
+    use crate::command_buffer::pool::StandardCommandPoolStats;
+    use crate::command_buffer::CommandBufferInheritanceInfo;
+    use crate::command_buffer::CommandBufferInheritanceRenderPassInfo;
+    use crate::command_buffer::CommandBufferInheritanceRenderPassType;
     use crate::command_buffer::CommandBufferLevel;
     use crate::device::Device;
+    use crate::render_pass::{RenderPass, Subpass};
+    use crate::Version;
     use crate::VulkanObject;
     use std::sync::Arc;
+    use std::thread;
 
     #[test]
     fn reuse_command_buffers() {
@@ -345,4 +973,368 @@ mod tests {
         drop(cb);
         assert!(pool_weak.upgrade().is_none());
     }
+
+    #[test]
+    fn no_reuse_without_reset_command_buffer() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(StandardCommandPool::with_create_info(
+            device,
+            queue.family(),
+            StandardCommandPoolCreateInfo {
+                transient: true,
+                reset_command_buffer: false,
+                ..Default::default()
+            },
+        ));
+        assert!(pool.transient());
+        assert!(!pool.reset_command_buffer());
+
+        // Avoid the weak reference to StandardCommandPoolPerThread expiring.
+        let _cb_hold_weakref = pool
+            .allocate(CommandBufferLevel::Primary, 1)
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let cb = pool
+            .allocate(CommandBufferLevel::Primary, 1)
+            .unwrap()
+            .next()
+            .unwrap();
+        drop(cb);
+
+        assert!(pool
+            .per_thread
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(|w| w.upgrade())
+            .all(|pt| pt.available_primary_command_buffers.is_empty()));
+    }
+
+    #[test]
+    fn trim_frees_available() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(StandardCommandPool::new(device.clone(), queue.family()));
+        // Avoid the weak reference to StandardCommandPoolPerThread expiring.
+        let _cb_hold_weakref = pool
+            .allocate(CommandBufferLevel::Primary, 1)
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let cb = pool
+            .allocate(CommandBufferLevel::Primary, 1)
+            .unwrap()
+            .next()
+            .unwrap();
+        drop(cb);
+
+        if device.api_version() >= Version::V1_1 {
+            pool.trim(true).unwrap();
+
+            assert!(pool
+                .per_thread
+                .lock()
+                .unwrap()
+                .values()
+                .filter_map(|w| w.upgrade())
+                .all(|pt| pt.available_primary_command_buffers.is_empty()));
+        } else {
+            assert_eq!(
+                pool.trim(true),
+                Err(CommandPoolTrimError::Maintenance1ExtensionNotEnabled)
+            );
+        }
+    }
+
+    #[test]
+    fn statistics() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(StandardCommandPool::new(device, queue.family()));
+        assert_eq!(pool.statistics(), StandardCommandPoolStats::default());
+
+        let cbs: Vec<_> = pool
+            .allocate(CommandBufferLevel::Primary, 2)
+            .unwrap()
+            .collect();
+        let stats = pool.statistics();
+        assert_eq!(stats.per_thread_pools, 1);
+        assert_eq!(stats.allocated_command_buffers, 2);
+        assert_eq!(stats.recycled_command_buffers, 0);
+
+        let mut cbs = cbs.into_iter();
+        let _cb_hold_weakref = cbs.next().unwrap();
+        drop(cbs);
+        assert_eq!(pool.statistics().available_primary_command_buffers, 1);
+
+        let _cb = pool
+            .allocate(CommandBufferLevel::Primary, 1)
+            .unwrap()
+            .next()
+            .unwrap();
+        let stats = pool.statistics();
+        assert_eq!(stats.available_primary_command_buffers, 0);
+        assert_eq!(stats.allocated_command_buffers, 2);
+        assert_eq!(stats.recycled_command_buffers, 1);
+    }
+
+    #[test]
+    fn per_thread_map_bounded() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(StandardCommandPool::new(device, queue.family()));
+
+        for _ in 0..2000 {
+            let pool = pool.clone();
+            thread::spawn(move || {
+                let _cb = pool
+                    .allocate(CommandBufferLevel::Primary, 1)
+                    .unwrap()
+                    .next()
+                    .unwrap();
+            })
+            .join()
+            .unwrap();
+        }
+
+        assert!(pool.per_thread.lock().unwrap().len() <= PER_THREAD_SWEEP_MIN_THRESHOLD);
+        assert_eq!(pool.statistics().per_thread_pools, 0);
+    }
+
+    #[test]
+    fn allocate_into_appends() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(StandardCommandPool::new(device, queue.family()));
+        let mut cbs = Vec::new();
+
+        pool.allocate_into(CommandBufferLevel::Primary, 2, &mut cbs)
+            .unwrap();
+        pool.allocate_into(CommandBufferLevel::Secondary, 3, &mut cbs)
+            .unwrap();
+        assert_eq!(cbs.len(), 5);
+
+        // Keep one command buffer alive, so that the others go back to the reuse lists.
+        cbs.truncate(1);
+        pool.allocate_into(CommandBufferLevel::Primary, 1, &mut cbs)
+            .unwrap();
+        assert_eq!(cbs.len(), 2);
+        assert_eq!(pool.statistics().recycled_command_buffers, 1);
+    }
+
+    #[test]
+    fn pool_set_reuses_pools() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let set = StandardCommandPoolSet::new(device);
+        let pool = set.pool_for(queue.family());
+        assert!(Arc::ptr_eq(&pool, &set.pool_for(queue.family())));
+        assert_eq!(pool.queue_family().id(), queue.family().id());
+    }
+
+    #[test]
+    fn level_of_reused_command_buffers() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(StandardCommandPool::new(device, queue.family()));
+        // Avoid the weak reference to StandardCommandPoolPerThread expiring.
+        let _cb_hold_weakref = pool
+            .allocate(CommandBufferLevel::Primary, 1)
+            .unwrap()
+            .next()
+            .unwrap();
+
+        drop(pool.allocate(CommandBufferLevel::Secondary, 1).unwrap());
+
+        let cb = pool
+            .allocate(CommandBufferLevel::Secondary, 1)
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(pool.statistics().recycled_command_buffers, 1);
+        assert_eq!(cb.level(), CommandBufferLevel::Secondary);
+        assert_eq!(cb.into_alloc().level(), CommandBufferLevel::Secondary);
+    }
+
+    #[test]
+    fn max_available_command_buffers() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(StandardCommandPool::with_create_info(
+            device,
+            queue.family(),
+            StandardCommandPoolCreateInfo {
+                max_available_primary_command_buffers: Some(2),
+                ..Default::default()
+            },
+        ));
+        // Avoid the weak reference to StandardCommandPoolPerThread expiring.
+        let _cb_hold_weakref = pool
+            .allocate(CommandBufferLevel::Primary, 1)
+            .unwrap()
+            .next()
+            .unwrap();
+
+        drop(pool.allocate(CommandBufferLevel::Primary, 5).unwrap());
+        drop(pool.allocate(CommandBufferLevel::Secondary, 5).unwrap());
+
+        let stats = pool.statistics();
+        assert_eq!(stats.available_primary_command_buffers, 2);
+        assert_eq!(stats.available_secondary_command_buffers, 5);
+    }
+
+    #[test]
+    fn reset_releases_available() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(StandardCommandPool::new(device, queue.family()));
+        // Avoid the weak reference to StandardCommandPoolPerThread expiring.
+        let _cb_hold_weakref = pool
+            .allocate(CommandBufferLevel::Primary, 1)
+            .unwrap()
+            .next()
+            .unwrap();
+
+        drop(pool.allocate(CommandBufferLevel::Primary, 3).unwrap());
+
+        unsafe {
+            pool.reset(false).unwrap();
+        }
+        assert_eq!(pool.statistics().available_primary_command_buffers, 3);
+
+        unsafe {
+            pool.reset(true).unwrap();
+        }
+        assert_eq!(pool.statistics().available_primary_command_buffers, 0);
+    }
+
+    #[test]
+    fn debug_name() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(StandardCommandPool::with_create_info(
+            device,
+            queue.family(),
+            StandardCommandPoolCreateInfo {
+                debug_name: Some("test".into()),
+                ..Default::default()
+            },
+        ));
+
+        // Naming is skipped because the test instance doesn't enable `ext_debug_utils`.
+        let _cb = pool
+            .allocate(CommandBufferLevel::Primary, 1)
+            .unwrap()
+            .next()
+            .unwrap();
+    }
+
+    #[test]
+    fn thread_teardown_destroys_pool() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(StandardCommandPool::new(device, queue.family()));
+
+        {
+            let pool = pool.clone();
+            thread::spawn(move || {
+                let cbs: Vec<_> = pool
+                    .allocate(CommandBufferLevel::Primary, 3)
+                    .unwrap()
+                    .collect();
+                assert_eq!(pool.statistics().per_thread_pools, 1);
+                drop(cbs);
+            })
+            .join()
+            .unwrap();
+        }
+
+        let stats = pool.statistics();
+        assert_eq!(stats.per_thread_pools, 0);
+        assert_eq!(stats.available_primary_command_buffers, 0);
+    }
+
+    #[test]
+    fn inheritance_info_roundtrip() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(StandardCommandPool::new(device.clone(), queue.family()));
+        let render_pass = RenderPass::empty_single_pass(device).unwrap();
+
+        let cb = pool
+            .allocate_with_inheritance_info(
+                CommandBufferLevel::Secondary,
+                1,
+                Some(CommandBufferInheritanceInfo {
+                    render_pass: Some(
+                        CommandBufferInheritanceRenderPassInfo::subpass(
+                            Subpass::from(render_pass, 0).unwrap(),
+                        )
+                        .into(),
+                    ),
+                    ..Default::default()
+                }),
+            )
+            .unwrap()
+            .next()
+            .unwrap();
+
+        match cb.inheritance_info().unwrap().render_pass {
+            Some(CommandBufferInheritanceRenderPassType::BeginRenderPass(ref info)) => {
+                assert_eq!(info.subpass.index(), 0)
+            }
+            _ => panic!(),
+        }
+
+        assert!(matches!(
+            pool.allocate_with_inheritance_info(
+                CommandBufferLevel::Primary,
+                1,
+                Some(Default::default()),
+            ),
+            Err(CommandPoolAllocError::InheritanceInfoForPrimary)
+        ));
+    }
+
+    #[test]
+    fn per_thread_handle() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Arc::new(StandardCommandPool::new(device.clone(), queue.family()));
+        let handle = pool.per_thread_handle().unwrap();
+        assert!(Arc::ptr_eq(handle.pool(), &pool));
+
+        // The handle allocates from the same Vulkan pool as the pool itself on this thread.
+        let cb = handle
+            .allocate(CommandBufferLevel::Primary, 1)
+            .unwrap()
+            .next()
+            .unwrap();
+        assert!(Arc::ptr_eq(&cb.inner.pool, &handle.per_thread));
+        drop(cb);
+
+        let cb = pool
+            .allocate(CommandBufferLevel::Primary, 1)
+            .unwrap()
+            .next()
+            .unwrap();
+        assert!(Arc::ptr_eq(&cb.inner.pool, &handle.per_thread));
+        drop(cb);
+        assert_eq!(pool.statistics().recycled_command_buffers, 1);
+
+        // The handle stays usable after the pool is reset.
+        unsafe { pool.reset(true).unwrap() };
+        let mut out = Vec::new();
+        handle
+            .allocate_into(CommandBufferLevel::Secondary, 2, &mut out)
+            .unwrap();
+        assert_eq!(out.len(), 2);
+
+        let handle = Device::command_pool_for_current_thread(&device, queue.family()).unwrap();
+        assert!(handle.pool().reset_command_buffer());
+    }
 }
@@ -19,7 +19,6 @@ use std::{
     hash::{Hash, Hasher},
     marker::PhantomData,
     mem::MaybeUninit,
-    ptr,
     sync::Arc,
 };
 
@@ -131,7 +130,7 @@ impl UnsafeCommandPool {
             check_errors((fns.v1_0.create_command_pool)(
                 device.internal_object(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -200,6 +199,7 @@ impl UnsafeCommandPool {
         };
 
         let device = self.device.clone();
+        let queue_family_index = self.queue_family_index;
 
         Ok(out
             .into_iter()
@@ -208,6 +208,7 @@ impl UnsafeCommandPool {
                 device: device.clone(),
 
                 level,
+                queue_family_index,
             }))
     }
 
@@ -288,7 +289,7 @@ impl Drop for UnsafeCommandPool {
             (fns.v1_0.destroy_command_pool)(
                 self.device.internal_object(),
                 self.handle,
-                ptr::null(),
+                self.device.allocation_callbacks(),
             );
         }
     }
@@ -373,6 +374,7 @@ impl From<Error> for UnsafeCommandPoolCreationError {
     fn from(err: Error) -> Self {
         match err {
             err @ Error::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
             _ => panic!("unexpected error: {:?}", err),
         }
     }
@@ -446,6 +448,7 @@ pub struct UnsafeCommandPoolAlloc {
     handle: ash::vk::CommandBuffer,
     device: Arc<Device>,
     level: CommandBufferLevel,
+    queue_family_index: u32,
 }
 
 impl UnsafeCommandPoolAlloc {
@@ -454,6 +457,15 @@ impl UnsafeCommandPoolAlloc {
     pub fn level(&self) -> CommandBufferLevel {
         self.level
     }
+
+    /// Returns the queue family on which the command buffer can be executed.
+    #[inline]
+    pub fn queue_family(&self) -> QueueFamily {
+        self.device
+            .physical_device()
+            .queue_family_by_id(self.queue_family_index)
+            .unwrap()
+    }
 }
 
 unsafe impl VulkanObject for UnsafeCommandPoolAlloc {
@@ -11,6 +11,7 @@ use crate::buffer::sys::UnsafeBuffer;
 use crate::check_errors;
 use crate::device::Queue;
 use crate::image::sys::UnsafeImage;
+use crate::image::ImageAspects;
 use crate::memory::DeviceMemory;
 use crate::sync::Fence;
 use crate::sync::Semaphore;
@@ -23,6 +24,7 @@ use smallvec::SmallVec;
 use std::error;
 use std::fmt;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 // TODO: correctly implement Debug on all the structs of this module
 
@@ -509,3 +511,321 @@ impl From<Error> for SubmitBindSparseError {
         }
     }
 }
+
+/// Parameters to execute sparse bind operations on a queue.
+#[derive(Clone, Debug)]
+pub struct BindSparseInfo {
+    /// The semaphores to wait for before beginning the execution of this batch of
+    /// sparse bind operations.
+    ///
+    /// The default value is empty.
+    pub wait_semaphores: Vec<Arc<Semaphore>>,
+
+    /// The bind operations to perform for buffers.
+    ///
+    /// The default value is empty.
+    pub buffer_binds: Vec<(Arc<UnsafeBuffer>, Vec<SparseBufferMemoryBind>)>,
+
+    /// The bind operations to perform for images with an opaque memory layout.
+    ///
+    /// This should be used for mip tail regions, the metadata aspect, and for the normal regions
+    /// of images that do not have the `sparse_residency` flag set.
+    ///
+    /// The default value is empty.
+    pub image_opaque_binds: Vec<(Arc<UnsafeImage>, Vec<SparseImageOpaqueMemoryBind>)>,
+
+    /// The bind operations to perform for images with a known memory layout.
+    ///
+    /// This type of sparse bind can only be used for images that have the `sparse_residency`
+    /// flag set.
+    ///
+    /// The default value is empty.
+    pub image_binds: Vec<(Arc<UnsafeImage>, Vec<SparseImageMemoryBind>)>,
+
+    /// The semaphores to signal after the execution of this batch of sparse bind operations
+    /// has completed.
+    ///
+    /// The default value is empty.
+    pub signal_semaphores: Vec<Arc<Semaphore>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for BindSparseInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            wait_semaphores: Vec::new(),
+            buffer_binds: Vec::new(),
+            image_opaque_binds: Vec::new(),
+            image_binds: Vec::new(),
+            signal_semaphores: Vec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Parameters for a single sparse bind operation on a buffer.
+#[derive(Clone, Debug)]
+pub struct SparseBufferMemoryBind {
+    /// The offset in bytes from the start of the buffer's memory, where memory is to be (un)bound.
+    ///
+    /// The default value is `0`.
+    pub offset: DeviceSize,
+
+    /// The size in bytes of the memory to be (un)bound.
+    ///
+    /// The default value is `0`, which must be overridden.
+    pub size: DeviceSize,
+
+    /// If `Some`, specifies the memory and an offset into that memory that is to be bound.
+    /// The provided memory must match the buffer's memory requirements.
+    ///
+    /// If `None`, specifies that existing memory at the specified location is to be unbound.
+    ///
+    /// The default value is `None`.
+    pub memory: Option<(Arc<DeviceMemory>, DeviceSize)>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for SparseBufferMemoryBind {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            size: 0,
+            memory: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Parameters for a single sparse bind operation on parts of an image with an opaque memory
+/// layout.
+#[derive(Clone, Debug)]
+pub struct SparseImageOpaqueMemoryBind {
+    /// The offset in bytes from the start of the image's memory, where memory is to be (un)bound.
+    ///
+    /// The default value is `0`.
+    pub offset: DeviceSize,
+
+    /// The size in bytes of the memory to be (un)bound.
+    ///
+    /// The default value is `0`, which must be overridden.
+    pub size: DeviceSize,
+
+    /// If `Some`, specifies the memory and an offset into that memory that is to be bound.
+    /// The provided memory must match the image's memory requirements.
+    ///
+    /// If `None`, specifies that existing memory at the specified location is to be unbound.
+    ///
+    /// The default value is `None`.
+    pub memory: Option<(Arc<DeviceMemory>, DeviceSize)>,
+
+    /// Sets whether the binding should apply to the metadata aspect of the image, or to the
+    /// normal texel data.
+    ///
+    /// The default value is `false`.
+    pub metadata: bool,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for SparseImageOpaqueMemoryBind {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            size: 0,
+            memory: None,
+            metadata: false,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Parameters for a single sparse bind operation on parts of an image with a known memory
+/// layout.
+#[derive(Clone, Debug)]
+pub struct SparseImageMemoryBind {
+    /// The aspect of the image where memory is to be (un)bound.
+    ///
+    /// The default value is [`ImageAspects::none()`], which must be overridden.
+    pub aspects: ImageAspects,
+
+    /// The mip level of the image where memory is to be (un)bound.
+    ///
+    /// The default value is `0`.
+    pub mip_level: u32,
+
+    /// The array layer of the image where memory is to be (un)bound.
+    ///
+    /// The default value is `0`.
+    pub array_layer: u32,
+
+    /// The offset in texels (or for compressed images, texel blocks) from the origin of the image,
+    /// where memory is to be (un)bound.
+    ///
+    /// This must be a multiple of the
+    /// [`SparseImageFormatProperties::image_granularity`](crate::image::SparseImageFormatProperties::image_granularity)
+    /// value of the image.
+    ///
+    /// The default value is `[0; 3]`.
+    pub offset: [u32; 3],
+
+    /// The extent in texels (or for compressed images, texel blocks) of the image where
+    /// memory is to be (un)bound.
+    ///
+    /// This must be a multiple of the
+    /// [`SparseImageFormatProperties::image_granularity`](crate::image::SparseImageFormatProperties::image_granularity)
+    /// value of the image, or `offset + extent` for that dimension must equal the image's total
+    /// extent.
+    ///
+    /// The default value is `[0; 3]`, which must be overridden.
+    pub extent: [u32; 3],
+
+    /// If `Some`, specifies the memory and an offset into that memory that is to be bound.
+    /// The provided memory must match the image's memory requirements.
+    ///
+    /// If `None`, specifies that existing memory at the specified location is to be unbound.
+    ///
+    /// The default value is `None`.
+    pub memory: Option<(Arc<DeviceMemory>, DeviceSize)>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for SparseImageMemoryBind {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            aspects: ImageAspects::none(),
+            mip_level: 0,
+            array_layer: 0,
+            offset: [0; 3],
+            extent: [0; 3],
+            memory: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Error that can happen when executing sparse bind operations on a queue.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BindSparseError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The connection to the device has been lost.
+    DeviceLost,
+
+    /// The queue family of the queue does not support sparse binding operations.
+    NotSupportedByQueueFamily,
+
+    /// A buffer or image was not created with sparse binding enabled.
+    ResourceNotSparse,
+
+    /// An image in `image_binds` was not created with the `sparse_residency` flag set.
+    ImageNotSparseResidency,
+
+    /// The `metadata` flag was set on a bind operation, but the image does not have a
+    /// metadata aspect.
+    MetadataNotSupported,
+
+    /// The range of a bind operation is not within the resource it applies to.
+    RegionOutOfRange {
+        offset: DeviceSize,
+        size: DeviceSize,
+        resource_size: DeviceSize,
+    },
+
+    /// The range of a bind operation is not within the memory that it binds.
+    MemoryOutOfRange {
+        memory_offset: DeviceSize,
+        size: DeviceSize,
+        allocation_size: DeviceSize,
+    },
+
+    /// The aspects of an image bind operation were not exactly one aspect that is present in the
+    /// image's format.
+    AspectsNotAllowed,
+
+    /// The mip level or array layer of an image bind operation is not present in the image.
+    SubresourceOutOfRange { mip_level: u32, array_layer: u32 },
+
+    /// The region of an image bind operation is not within the dimensions of the image.
+    ImageRegionOutOfRange {
+        offset: [u32; 3],
+        extent: [u32; 3],
+        image_extent: [u32; 3],
+    },
+}
+
+impl error::Error for BindSparseError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BindSparseError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::OomError(_) => write!(fmt, "not enough memory"),
+            Self::DeviceLost => write!(fmt, "the connection to the device has been lost"),
+            Self::NotSupportedByQueueFamily => write!(
+                fmt,
+                "the queue family of the queue does not support sparse binding operations",
+            ),
+            Self::ResourceNotSparse => write!(
+                fmt,
+                "a buffer or image was not created with sparse binding enabled",
+            ),
+            Self::ImageNotSparseResidency => write!(
+                fmt,
+                "an image in `image_binds` was not created with the `sparse_residency` flag set",
+            ),
+            Self::MetadataNotSupported => write!(
+                fmt,
+                "the `metadata` flag was set on a bind operation, but the image does not have a metadata aspect",
+            ),
+            Self::RegionOutOfRange { .. } => write!(
+                fmt,
+                "the range of a bind operation is not within the resource it applies to",
+            ),
+            Self::MemoryOutOfRange { .. } => write!(
+                fmt,
+                "the range of a bind operation is not within the memory that it binds",
+            ),
+            Self::AspectsNotAllowed => write!(
+                fmt,
+                "the aspects of an image bind operation were not exactly one aspect that is present in the image's format",
+            ),
+            Self::SubresourceOutOfRange { .. } => write!(
+                fmt,
+                "the mip level or array layer of an image bind operation is not present in the image",
+            ),
+            Self::ImageRegionOutOfRange { .. } => write!(
+                fmt,
+                "the region of an image bind operation is not within the dimensions of the image",
+            ),
+        }
+    }
+}
+
+impl From<Error> for BindSparseError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            err @ Error::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            Error::DeviceLost => Self::DeviceLost,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
@@ -13,6 +13,11 @@
 //! module. These structs are low-level and unsafe, and are mostly used to implement other parts
 //! of vulkano, so you are encouraged to not use them directly.
 
+pub use self::bind_sparse::BindSparseError;
+pub use self::bind_sparse::BindSparseInfo;
+pub use self::bind_sparse::SparseBufferMemoryBind;
+pub use self::bind_sparse::SparseImageMemoryBind;
+pub use self::bind_sparse::SparseImageOpaqueMemoryBind;
 pub use self::bind_sparse::SubmitBindSparseBatchBuilder;
 pub use self::bind_sparse::SubmitBindSparseBufferBindBuilder;
 pub use self::bind_sparse::SubmitBindSparseBuilder;
@@ -23,6 +28,8 @@ pub use self::queue_present::SubmitPresentBuilder;
 pub use self::queue_present::SubmitPresentError;
 pub use self::queue_submit::SubmitCommandBufferBuilder;
 pub use self::queue_submit::SubmitCommandBufferError;
+pub use self::queue_submit::SubmitError;
+pub use self::queue_submit::SubmitInfo;
 pub use self::semaphores_wait::SubmitSemaphoresWaitBuilder;
 
 mod bind_sparse;
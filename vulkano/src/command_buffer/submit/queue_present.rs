@@ -11,6 +11,7 @@ use smallvec::SmallVec;
 use std::error;
 use std::fmt;
 use std::marker::PhantomData;
+use std::num::NonZeroU64;
 use std::ptr;
 
 use crate::device::DeviceOwned;
@@ -33,6 +34,7 @@ pub struct SubmitPresentBuilder<'a> {
     image_indices: SmallVec<[u32; 4]>,
     present_regions: SmallVec<[ash::vk::PresentRegionKHR; 4]>,
     rect_layers: SmallVec<[ash::vk::RectLayerKHR; 4]>,
+    present_ids: SmallVec<[u64; 4]>,
     marker: PhantomData<&'a ()>,
 }
 
@@ -46,6 +48,7 @@ impl<'a> SubmitPresentBuilder<'a> {
             image_indices: SmallVec::new(),
             present_regions: SmallVec::new(),
             rect_layers: SmallVec::new(),
+            present_ids: SmallVec::new(),
             marker: PhantomData,
         }
     }
@@ -80,6 +83,9 @@ impl<'a> SubmitPresentBuilder<'a> {
     ///
     /// If `VK_KHR_incremental_present` is not enabled, the `present_region` parameter is ignored.
     ///
+    /// A `present_id` can be provided, which can later be waited on with
+    /// `Swapchain::wait_for_present`.
+    ///
     /// # Safety
     ///
     /// - If you submit this builder, the swapchain must be kept alive until you are
@@ -87,12 +93,16 @@ impl<'a> SubmitPresentBuilder<'a> {
     ///
     /// - The swapchains and semaphores must all belong to the same device.
     ///
+    /// - If `present_id` is `Some`, the `present_id` feature must be enabled on the device, and
+    ///   the id must be greater than any present id previously used with the swapchain.
+    ///
     #[inline]
     pub unsafe fn add_swapchain<W>(
         &mut self,
         swapchain: &'a Swapchain<W>,
         image_num: u32,
         present_region: Option<&'a PresentRegion>,
+        present_id: Option<NonZeroU64>,
     ) {
         debug_assert!(image_num < swapchain.image_count());
 
@@ -121,6 +131,13 @@ impl<'a> SubmitPresentBuilder<'a> {
             self.present_regions.push(vk_present_region);
         }
 
+        if swapchain.device().enabled_features().present_id {
+            // A present id of 0 means that no id is associated with this swapchain.
+            self.present_ids.push(present_id.map_or(0, NonZeroU64::get));
+        } else {
+            debug_assert!(present_id.is_none());
+        }
+
         self.swapchains.push(swapchain.internal_object());
         self.image_indices.push(image_num);
     }
@@ -158,16 +175,32 @@ impl<'a> SubmitPresentBuilder<'a> {
                 }
             };
 
+            let present_ids = if self.present_ids.iter().any(|&id| id != 0) {
+                debug_assert_eq!(self.swapchains.len(), self.present_ids.len());
+                Some(ash::vk::PresentIdKHR {
+                    p_next: present_regions
+                        .as_ref()
+                        .map(|pr| pr as *const ash::vk::PresentRegionsKHR as *const _)
+                        .unwrap_or(ptr::null()),
+                    swapchain_count: self.present_ids.len() as u32,
+                    p_present_ids: self.present_ids.as_ptr(),
+                    ..Default::default()
+                })
+            } else {
+                None
+            };
+
             let mut results = vec![ash::vk::Result::SUCCESS; self.swapchains.len()];
 
             let fns = queue.device().fns();
             let queue = queue.internal_object_guard();
 
             let infos = ash::vk::PresentInfoKHR {
-                p_next: present_regions
-                    .as_ref()
-                    .map(|pr| pr as *const ash::vk::PresentRegionsKHR as *const _)
-                    .unwrap_or(ptr::null()),
+                p_next: match (&present_ids, &present_regions) {
+                    (Some(pi), _) => pi as *const ash::vk::PresentIdKHR as *const _,
+                    (None, Some(pr)) => pr as *const ash::vk::PresentRegionsKHR as *const _,
+                    (None, None) => ptr::null(),
+                },
                 wait_semaphore_count: self.wait_semaphores.len() as u32,
                 p_wait_semaphores: self.wait_semaphores.as_ptr(),
                 swapchain_count: self.swapchains.len() as u32,
@@ -9,10 +9,12 @@
 
 use crate::check_errors;
 use crate::command_buffer::sys::UnsafeCommandBuffer;
+use crate::command_buffer::PrimaryCommandBuffer;
 use crate::device::Queue;
 use crate::sync::Fence;
 use crate::sync::PipelineStages;
 use crate::sync::Semaphore;
+use crate::sync::TimelineSemaphore;
 use crate::Error;
 use crate::OomError;
 use crate::SynchronizedVulkanObject;
@@ -21,14 +23,19 @@ use smallvec::SmallVec;
 use std::error;
 use std::fmt;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 /// Prototype for a submission that executes command buffers.
 // TODO: example here
 #[derive(Debug)]
 pub struct SubmitCommandBufferBuilder<'a> {
     wait_semaphores: SmallVec<[ash::vk::Semaphore; 16]>,
+    wait_semaphore_values: SmallVec<[u64; 16]>,
     destination_stages: SmallVec<[ash::vk::PipelineStageFlags; 8]>,
     signal_semaphores: SmallVec<[ash::vk::Semaphore; 16]>,
+    signal_semaphore_values: SmallVec<[u64; 16]>,
+    // True if a timeline semaphore was added, in which case the values above must be provided.
+    has_timeline_semaphores: bool,
     command_buffers: SmallVec<[ash::vk::CommandBuffer; 4]>,
     fence: ash::vk::Fence,
     marker: PhantomData<&'a ()>,
@@ -40,8 +47,11 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     pub fn new() -> SubmitCommandBufferBuilder<'a> {
         SubmitCommandBufferBuilder {
             wait_semaphores: SmallVec::new(),
+            wait_semaphore_values: SmallVec::new(),
             destination_stages: SmallVec::new(),
             signal_semaphores: SmallVec::new(),
+            signal_semaphore_values: SmallVec::new(),
+            has_timeline_semaphores: false,
             command_buffers: SmallVec::new(),
             fence: ash::vk::Fence::null(),
             marker: PhantomData,
@@ -140,9 +150,44 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
         debug_assert!(!ash::vk::PipelineStageFlags::from(stages).is_empty());
         // TODO: debug assert that the device supports the stages
         self.wait_semaphores.push(semaphore.internal_object());
+        // Ignored for binary semaphores.
+        self.wait_semaphore_values.push(0);
         self.destination_stages.push(stages.into());
     }
 
+    /// Adds a timeline semaphore to be waited upon before the command buffers are executed. The
+    /// wait completes once the counter of the semaphore has reached `value`.
+    ///
+    /// Only the given `stages` of the command buffers added afterwards will wait upon
+    /// the semaphore. Other stages not included in `stages` can execute before waiting.
+    ///
+    /// # Safety
+    ///
+    /// - The stages must be supported by the device.
+    ///
+    /// - If you submit this builder, the semaphore must be kept alive until you are guaranteed
+    ///   that the GPU has at least started executing the command buffers.
+    ///
+    /// - If you submit this builder, the counter of the semaphore must reach `value`, either
+    ///   because it was already reached or because of a signal operation from the CPU or from
+    ///   another submission. In other words, you must not block the queue forever.
+    ///
+    /// - The fence, command buffers, and semaphores must all belong to the same device.
+    ///
+    #[inline]
+    pub unsafe fn add_wait_timeline_semaphore(
+        &mut self,
+        semaphore: &'a TimelineSemaphore,
+        value: u64,
+        stages: PipelineStages,
+    ) {
+        debug_assert!(!ash::vk::PipelineStageFlags::from(stages).is_empty());
+        self.wait_semaphores.push(semaphore.internal_object());
+        self.wait_semaphore_values.push(value);
+        self.destination_stages.push(stages.into());
+        self.has_timeline_semaphores = true;
+    }
+
     /// Adds a command buffer that is executed as part of this command.
     ///
     /// The command buffers are submitted in the order in which they are added.
@@ -189,6 +234,33 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     #[inline]
     pub unsafe fn add_signal_semaphore(&mut self, semaphore: &'a Semaphore) {
         self.signal_semaphores.push(semaphore.internal_object());
+        // Ignored for binary semaphores.
+        self.signal_semaphore_values.push(0);
+    }
+
+    /// Adds a timeline semaphore whose counter is going to be set to `value` at the end of the
+    /// submission.
+    ///
+    /// # Safety
+    ///
+    /// - If you submit this builder, the semaphore must be kept alive until you are guaranteed
+    ///   that the GPU has finished executing this submission.
+    ///
+    /// - `value` must be greater than the value of the counter when this submission is executed,
+    ///   and less than the value of any signal operation on the semaphore that is executed after
+    ///   this submission.
+    ///
+    /// - The fence, command buffers, and semaphores must all belong to the same device.
+    ///
+    #[inline]
+    pub unsafe fn add_signal_timeline_semaphore(
+        &mut self,
+        semaphore: &'a TimelineSemaphore,
+        value: u64,
+    ) {
+        self.signal_semaphores.push(semaphore.internal_object());
+        self.signal_semaphore_values.push(value);
+        self.has_timeline_semaphores = true;
     }
 
     /// Submits the command buffer to the given queue.
@@ -202,8 +274,21 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
             let queue = queue.internal_object_guard();
 
             debug_assert_eq!(self.wait_semaphores.len(), self.destination_stages.len());
+            debug_assert_eq!(self.wait_semaphores.len(), self.wait_semaphore_values.len());
+            debug_assert_eq!(
+                self.signal_semaphores.len(),
+                self.signal_semaphore_values.len()
+            );
+
+            let mut timeline_semaphore_submit_info = ash::vk::TimelineSemaphoreSubmitInfo {
+                wait_semaphore_value_count: self.wait_semaphore_values.len() as u32,
+                p_wait_semaphore_values: self.wait_semaphore_values.as_ptr(),
+                signal_semaphore_value_count: self.signal_semaphore_values.len() as u32,
+                p_signal_semaphore_values: self.signal_semaphore_values.as_ptr(),
+                ..Default::default()
+            };
 
-            let batch = ash::vk::SubmitInfo {
+            let mut batch = ash::vk::SubmitInfo {
                 wait_semaphore_count: self.wait_semaphores.len() as u32,
                 p_wait_semaphores: self.wait_semaphores.as_ptr(),
                 p_wait_dst_stage_mask: self.destination_stages.as_ptr(),
@@ -214,6 +299,10 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
                 ..Default::default()
             };
 
+            if self.has_timeline_semaphores {
+                batch.p_next = &mut timeline_semaphore_submit_info as *mut _ as *mut _;
+            }
+
             check_errors((fns.v1_0.queue_submit)(*queue, 1, &batch, self.fence))?;
             Ok(())
         }
@@ -232,8 +321,13 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
         );
 
         self.wait_semaphores.extend(other.wait_semaphores);
+        self.wait_semaphore_values
+            .extend(other.wait_semaphore_values);
         self.destination_stages.extend(other.destination_stages); // TODO: meh? will be solved if we submit multiple batches
         self.signal_semaphores.extend(other.signal_semaphores);
+        self.signal_semaphore_values
+            .extend(other.signal_semaphore_values);
+        self.has_timeline_semaphores |= other.has_timeline_semaphores;
         self.command_buffers.extend(other.command_buffers);
 
         if self.fence == ash::vk::Fence::null() {
@@ -244,6 +338,120 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     }
 }
 
+/// Parameters to execute command buffers on a queue, as one batch of a
+/// [`Queue::submit_batch`] call.
+#[derive(Clone)]
+pub struct SubmitInfo {
+    /// The semaphores to wait for before beginning the execution of this batch of command
+    /// buffers, and the stages of the command buffers that must wait for each semaphore.
+    ///
+    /// The default value is empty.
+    pub wait_semaphores: Vec<(Arc<Semaphore>, PipelineStages)>,
+
+    /// The command buffers to execute, in order.
+    ///
+    /// The default value is empty.
+    pub command_buffers: Vec<Arc<dyn PrimaryCommandBuffer>>,
+
+    /// The semaphores to signal after the execution of this batch of command buffers has
+    /// completed.
+    ///
+    /// The default value is empty.
+    pub signal_semaphores: Vec<Arc<Semaphore>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for SubmitInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            wait_semaphores: Vec::new(),
+            command_buffers: Vec::new(),
+            signal_semaphores: Vec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Error that can happen when submitting batches of command buffers to a queue.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmitError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The connection to the device has been lost.
+    DeviceLost,
+
+    /// A command buffer was allocated from a pool of a different queue family than the queue.
+    CommandBufferQueueFamilyMismatch {
+        batch_index: usize,
+        command_buffer_index: usize,
+    },
+
+    /// The timeout was reached before the submitted work finished executing.
+    Timeout,
+
+    /// The stages of a wait semaphore were empty.
+    WaitStagesEmpty {
+        batch_index: usize,
+        semaphore_index: usize,
+    },
+}
+
+impl error::Error for SubmitError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SubmitError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::OomError(_) => write!(fmt, "not enough memory"),
+            Self::DeviceLost => write!(fmt, "the connection to the device has been lost"),
+            Self::CommandBufferQueueFamilyMismatch {
+                batch_index,
+                command_buffer_index,
+            } => write!(
+                fmt,
+                "command buffer {} of batch {} was allocated from a pool of a different queue \
+                family than the queue",
+                command_buffer_index, batch_index,
+            ),
+            Self::Timeout => write!(
+                fmt,
+                "the timeout was reached before the submitted work finished executing",
+            ),
+            Self::WaitStagesEmpty {
+                batch_index,
+                semaphore_index,
+            } => write!(
+                fmt,
+                "the stages of wait semaphore {} of batch {} were empty",
+                semaphore_index, batch_index,
+            ),
+        }
+    }
+}
+
+impl From<Error> for SubmitError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            err @ Error::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            Error::DeviceLost => Self::DeviceLost,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
 /// Error that can happen when submitting the prototype.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
@@ -30,6 +30,7 @@ use crate::{
         graphics::{
             color_blend::LogicOp,
             depth_stencil::{CompareOp, StencilOps},
+            fragment_shading_rate::FragmentShadingRate,
             input_assembly::{IndexType, PrimitiveTopology},
             rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
             viewport::{Scissor, Viewport},
@@ -191,6 +192,39 @@ impl SyncCommandBufferBuilder {
         self.current_state = Default::default();
     }
 
+    /// Returns the layout that `image` is in at the current point of the command buffer,
+    /// taking into account the layout transitions of all commands added so far.
+    ///
+    /// Returns `None` if `image` has not been used by any command yet, or if its subresources
+    /// are not all in the same layout.
+    pub fn current_layout(&self, image: &dyn ImageAccess) -> Option<ImageLayout> {
+        let inner = image.inner();
+        let mut subresource_range = image.subresource_range();
+        subresource_range.array_layers.start += inner.first_layer;
+        subresource_range.array_layers.end += inner.first_layer;
+        subresource_range.mip_levels.start += inner.first_mipmap_level;
+        subresource_range.mip_levels.end += inner.first_mipmap_level;
+
+        let range_map = self.images2.get(inner.image)?;
+        let mut current_layout = None;
+
+        for range in inner.image.iter_ranges(subresource_range) {
+            for (_range, state) in range_map.range(&range) {
+                if state.resource_uses.is_empty() {
+                    return None;
+                }
+
+                match current_layout {
+                    None => current_layout = Some(state.current_layout),
+                    Some(layout) if layout != state.current_layout => return None,
+                    Some(_) => (),
+                }
+            }
+        }
+
+        current_layout
+    }
+
     pub(in crate::command_buffer) fn check_resource_conflicts(
         &self,
         resource: &(Cow<'static, str>, Resource),
@@ -964,6 +998,7 @@ pub(in crate::command_buffer) struct CurrentState {
     pub(in crate::command_buffer) depth_test_enable: Option<bool>,
     pub(in crate::command_buffer) depth_write_enable: Option<bool>,
     pub(in crate::command_buffer) discard_rectangle: HashMap<u32, Scissor>,
+    pub(in crate::command_buffer) fragment_shading_rate: Option<FragmentShadingRate>,
     pub(in crate::command_buffer) front_face: Option<FrontFace>,
     pub(in crate::command_buffer) line_stipple: Option<LineStipple>,
     pub(in crate::command_buffer) line_width: Option<f32>,
@@ -1002,7 +1037,7 @@ impl CurrentState {
                 DynamicState::DepthWriteEnable => self.depth_write_enable = None,
                 DynamicState::DiscardRectangle => self.discard_rectangle.clear(),
                 DynamicState::ExclusiveScissor => (), // TODO;
-                DynamicState::FragmentShadingRate => (), // TODO:
+                DynamicState::FragmentShadingRate => self.fragment_shading_rate = None,
                 DynamicState::FrontFace => self.front_face = None,
                 DynamicState::LineStipple => self.line_stipple = None,
                 DynamicState::LineWidth => self.line_width = None,
@@ -1257,6 +1292,12 @@ impl<'a> CommandBufferState<'a> {
         self.current_state.discard_rectangle.get(&num)
     }
 
+    /// Returns the current fragment shading rate, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn fragment_shading_rate(&self) -> Option<FragmentShadingRate> {
+        self.current_state.fragment_shading_rate
+    }
+
     /// Returns the current front face, or `None` if nothing has been set yet.
     #[inline]
     pub fn front_face(&self) -> Option<FrontFace> {
@@ -287,6 +287,9 @@ impl SyncCommandBuffer {
                     buffer_state.gpu_read_unlock(range.clone());
                 }
             }
+
+            drop(buffer_state);
+            buffer.notify_gpu_unlock();
         }
 
         for (image, range_map) in &self.images2 {
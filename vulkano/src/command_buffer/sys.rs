@@ -21,7 +21,7 @@ use crate::{
         CommandBufferInheritanceRenderPassInfo, CommandBufferInheritanceRenderPassType,
         CommandBufferInheritanceRenderingInfo,
     },
-    device::{Device, DeviceOwned},
+    device::{physical::QueueFamily, Device, DeviceOwned},
     OomError, VulkanObject,
 };
 use smallvec::SmallVec;
@@ -42,6 +42,8 @@ pub struct UnsafeCommandBufferBuilder {
     pub(super) handle: ash::vk::CommandBuffer,
     pub(super) device: Arc<Device>,
     usage: CommandBufferUsage,
+    level: CommandBufferLevel,
+    queue_family_index: u32,
 }
 
 impl UnsafeCommandBufferBuilder {
@@ -188,6 +190,8 @@ impl UnsafeCommandBufferBuilder {
             handle: pool_alloc.internal_object(),
             device,
             usage,
+            level: pool_alloc.level(),
+            queue_family_index: pool_alloc.queue_family().id(),
         })
     }
 
@@ -202,6 +206,8 @@ impl UnsafeCommandBufferBuilder {
                 command_buffer: self.handle,
                 device: self.device.clone(),
                 usage: self.usage,
+                level: self.level,
+                queue_family_index: self.queue_family_index,
             })
         }
     }
@@ -262,6 +268,8 @@ pub struct UnsafeCommandBuffer {
     command_buffer: ash::vk::CommandBuffer,
     device: Arc<Device>,
     usage: CommandBufferUsage,
+    level: CommandBufferLevel,
+    queue_family_index: u32,
 }
 
 impl UnsafeCommandBuffer {
@@ -269,6 +277,21 @@ impl UnsafeCommandBuffer {
     pub fn usage(&self) -> CommandBufferUsage {
         self.usage
     }
+
+    /// Returns whether the command buffer is a primary or a secondary command buffer.
+    #[inline]
+    pub fn level(&self) -> CommandBufferLevel {
+        self.level
+    }
+
+    /// Returns the queue family on which the command buffer can be executed.
+    #[inline]
+    pub fn queue_family(&self) -> QueueFamily {
+        self.device
+            .physical_device()
+            .queue_family_by_id(self.queue_family_index)
+            .unwrap()
+    }
 }
 
 unsafe impl DeviceOwned for UnsafeCommandBuffer {
@@ -7,11 +7,188 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use crate::descriptor_set::DescriptorSetWithOffsets;
+use crate::{
+    descriptor_set::{
+        layout::{DescriptorDesc, DescriptorType},
+        DescriptorSetWithOffsets,
+    },
+    pipeline::PipelineLayout,
+};
+use std::{collections::BTreeSet, error, fmt, vec::IntoIter as VecIntoIter};
 
 /// A collection of descriptor set objects.
 pub unsafe trait DescriptorSetsCollection {
     fn into_vec(self) -> Vec<DescriptorSetWithOffsets>;
+
+    /// Returns the descriptor sets of the collection, without consuming it.
+    ///
+    /// The default implementation clones the collection and calls `into_vec` on the clone. For
+    /// the collections that vulkano implements this trait for, this only clones the `Arc`s of the
+    /// descriptor sets.
+    #[inline]
+    fn descriptor_sets(&self) -> Vec<DescriptorSetWithOffsets>
+    where
+        Self: Clone,
+    {
+        self.clone().into_vec()
+    }
+
+    /// Checks whether the descriptor sets of the collection can be bound to `pipeline_layout`,
+    /// with the first set of the collection being bound to set number 0.
+    ///
+    /// Returns the first set and binding whose layout doesn't match the pipeline layout.
+    fn validate_against(
+        &self,
+        pipeline_layout: &PipelineLayout,
+    ) -> Result<(), DescriptorSetsValidationError>
+    where
+        Self: Clone,
+    {
+        let set_layouts = pipeline_layout.set_layouts();
+
+        for (set_num, set) in self.descriptor_sets().iter().enumerate() {
+            let set_num = set_num as u32;
+            let set_layout = set.as_ref().0.layout();
+            let pipeline_set_layout = set_layouts.get(set_num as usize).ok_or(
+                DescriptorSetsValidationError::SetOutOfRange {
+                    set_num,
+                    set_count: set_layouts.len() as u32,
+                },
+            )?;
+
+            if pipeline_set_layout.is_compatible_with(set_layout) {
+                continue;
+            }
+
+            let binding_nums: BTreeSet<u32> = pipeline_set_layout
+                .bindings()
+                .keys()
+                .chain(set_layout.bindings().keys())
+                .copied()
+                .collect();
+
+            for binding_num in binding_nums {
+                match (
+                    pipeline_set_layout.bindings().get(&binding_num),
+                    set_layout.bindings().get(&binding_num),
+                ) {
+                    (Some(_), None) => {
+                        return Err(DescriptorSetsValidationError::MissingBinding {
+                            set_num,
+                            binding_num,
+                        })
+                    }
+                    (None, Some(_)) => {
+                        return Err(DescriptorSetsValidationError::ExtraBinding {
+                            set_num,
+                            binding_num,
+                        })
+                    }
+                    (Some(required), Some(provided)) => {
+                        if required.descriptor_type != provided.descriptor_type {
+                            return Err(DescriptorSetsValidationError::DescriptorTypeMismatch {
+                                set_num,
+                                binding_num,
+                                required: required.descriptor_type,
+                                provided: provided.descriptor_type,
+                            });
+                        }
+
+                        if required != provided {
+                            return Err(DescriptorSetsValidationError::IncompatibleBinding {
+                                set_num,
+                                binding_num,
+                            });
+                        }
+                    }
+                    (None, None) => unreachable!(),
+                }
+            }
+
+            // All the bindings are identical, so the layouts differ in some other way.
+            return Err(DescriptorSetsValidationError::IncompatibleSetLayout { set_num });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the set number, binding number and layout of every binding in the descriptor sets
+    /// of the collection, ordered by set number and then by binding number.
+    fn iter_descriptors(&self) -> VecIntoIter<(u32, u32, DescriptorDesc)>
+    where
+        Self: Clone,
+    {
+        self.descriptor_sets()
+            .iter()
+            .enumerate()
+            .flat_map(|(set_num, set)| {
+                set.as_ref()
+                    .0
+                    .layout()
+                    .bindings()
+                    .iter()
+                    .map(move |(&binding_num, binding)| {
+                        (set_num as u32, binding_num, binding.clone())
+                    })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Returns the layout of binding `binding_num` of set `set_num` in the collection.
+    ///
+    /// Returns `Ok(None)` if the binding number is lower than the highest binding number of the
+    /// set, but no binding is defined for it. Returns an error if the set number or binding number
+    /// is out of range.
+    fn try_descriptor(
+        &self,
+        set_num: u32,
+        binding_num: u32,
+    ) -> Result<Option<DescriptorDesc>, DescriptorSetsIndexError>
+    where
+        Self: Clone,
+    {
+        let descriptor_sets = self.descriptor_sets();
+        let set = descriptor_sets.get(set_num as usize).ok_or(
+            DescriptorSetsIndexError::SetOutOfRange {
+                set_num,
+                set_count: descriptor_sets.len() as u32,
+            },
+        )?;
+        let bindings = set.as_ref().0.layout().bindings();
+        let binding_count = bindings
+            .keys()
+            .next_back()
+            .map_or(0, |&binding_num| binding_num + 1);
+
+        if binding_num >= binding_count {
+            return Err(DescriptorSetsIndexError::BindingOutOfRange {
+                set_num,
+                binding_num,
+                binding_count,
+            });
+        }
+
+        Ok(bindings.get(&binding_num).cloned())
+    }
+
+    /// Returns the number of dynamic offsets that must be provided when binding the descriptor
+    /// sets of the collection, which is the total number of dynamic uniform and storage buffer
+    /// descriptors.
+    fn num_dynamic_offsets(&self) -> usize
+    where
+        Self: Clone,
+    {
+        self.iter_descriptors()
+            .filter(|(_, _, binding)| {
+                matches!(
+                    binding.descriptor_type,
+                    DescriptorType::UniformBufferDynamic | DescriptorType::StorageBufferDynamic
+                )
+            })
+            .map(|(_, _, binding)| binding.descriptor_count as usize)
+            .sum()
+    }
 }
 
 unsafe impl DescriptorSetsCollection for () {
@@ -41,6 +218,55 @@ where
     }
 }
 
+unsafe impl<T, const N: usize> DescriptorSetsCollection for [T; N]
+where
+    T: Into<DescriptorSetWithOffsets>,
+{
+    #[inline]
+    fn into_vec(self) -> Vec<DescriptorSetWithOffsets> {
+        self.into_iter().map(|x| x.into()).collect()
+    }
+}
+
+unsafe impl<'a, T> DescriptorSetsCollection for &'a [T]
+where
+    T: Clone + Into<DescriptorSetWithOffsets>,
+{
+    #[inline]
+    fn into_vec(self) -> Vec<DescriptorSetWithOffsets> {
+        self.iter().cloned().map(|x| x.into()).collect()
+    }
+}
+
+// A blanket implementation for `&T` and `Box<T>` would overlap with the one for
+// `T: Into<DescriptorSetWithOffsets>`, so they are implemented for each collection type instead.
+// References and boxes of single descriptor sets are covered by the `From` implementations of
+// `DescriptorSetWithOffsets`.
+macro_rules! impl_collection_indirect {
+    ([$($param:tt)*] $ty:ty) => (
+        unsafe impl<'a, $($param)*> DescriptorSetsCollection for &'a $ty
+            where $ty: DescriptorSetsCollection + Clone
+        {
+            #[inline]
+            fn into_vec(self) -> Vec<DescriptorSetWithOffsets> {
+                self.clone().into_vec()
+            }
+        }
+
+        unsafe impl<$($param)*> DescriptorSetsCollection for Box<$ty>
+            where $ty: DescriptorSetsCollection
+        {
+            #[inline]
+            fn into_vec(self) -> Vec<DescriptorSetWithOffsets> {
+                (*self).into_vec()
+            }
+        }
+    );
+}
+
+impl_collection_indirect!([T] Vec<T>);
+impl_collection_indirect!([T, const N: usize] [T; N]);
+
 macro_rules! impl_collection {
     ($first:ident $(, $others:ident)+) => (
         unsafe impl<$first$(, $others)+> DescriptorSetsCollection for ($first, $($others),+)
@@ -62,6 +288,7 @@ macro_rules! impl_collection {
             }
         }
 
+        impl_collection_indirect!([$first$(, $others)+] ($first, $($others),+));
         impl_collection!($($others),+);
     );
 
@@ -69,3 +296,410 @@ macro_rules! impl_collection {
 }
 
 impl_collection!(Z, Y, X, W, V, U, T, S, R, Q, P, O, N, M, L, K, J, I, H, G, F, E, D, C, B, A);
+
+/// Error that can happen when validating a `DescriptorSetsCollection` against a pipeline layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DescriptorSetsValidationError {
+    /// The collection contains a descriptor set for a set number that the pipeline layout
+    /// doesn't have.
+    SetOutOfRange { set_num: u32, set_count: u32 },
+
+    /// The pipeline layout has a binding that is missing from the descriptor set.
+    MissingBinding { set_num: u32, binding_num: u32 },
+
+    /// The descriptor set has a binding that the pipeline layout doesn't have.
+    ExtraBinding { set_num: u32, binding_num: u32 },
+
+    /// The descriptor type of a binding of the descriptor set is not the one in the pipeline
+    /// layout.
+    DescriptorTypeMismatch {
+        set_num: u32,
+        binding_num: u32,
+        required: DescriptorType,
+        provided: DescriptorType,
+    },
+
+    /// A binding of the descriptor set has the right descriptor type, but is otherwise defined
+    /// differently than in the pipeline layout.
+    IncompatibleBinding { set_num: u32, binding_num: u32 },
+
+    /// The bindings of the descriptor set match the pipeline layout, but the set layouts are
+    /// still not compatible.
+    IncompatibleSetLayout { set_num: u32 },
+}
+
+impl error::Error for DescriptorSetsValidationError {}
+
+impl fmt::Display for DescriptorSetsValidationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::SetOutOfRange { set_num, set_count } => write!(
+                fmt,
+                "a descriptor set was provided for set {}, but the pipeline layout only has {} sets",
+                set_num, set_count,
+            ),
+            Self::MissingBinding {
+                set_num,
+                binding_num,
+            } => write!(
+                fmt,
+                "the pipeline layout has a descriptor at set {} binding {}, but it is missing from the descriptor set",
+                set_num, binding_num,
+            ),
+            Self::ExtraBinding {
+                set_num,
+                binding_num,
+            } => write!(
+                fmt,
+                "the descriptor set has a descriptor at set {} binding {}, but the pipeline layout doesn't",
+                set_num, binding_num,
+            ),
+            Self::DescriptorTypeMismatch {
+                set_num,
+                binding_num,
+                required,
+                provided,
+            } => write!(
+                fmt,
+                "the descriptor at set {} binding {} has type {:?}, but the pipeline layout requires {:?}",
+                set_num, binding_num, provided, required,
+            ),
+            Self::IncompatibleBinding {
+                set_num,
+                binding_num,
+            } => write!(
+                fmt,
+                "the descriptor at set {} binding {} is not defined identically to the pipeline layout",
+                set_num, binding_num,
+            ),
+            Self::IncompatibleSetLayout { set_num } => write!(
+                fmt,
+                "the layout of the descriptor set for set {} is not compatible with the pipeline layout",
+                set_num,
+            ),
+        }
+    }
+}
+
+/// Error that can happen when indexing into a `DescriptorSetsCollection`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DescriptorSetsIndexError {
+    /// The set number was not less than the number of sets in the collection.
+    SetOutOfRange { set_num: u32, set_count: u32 },
+
+    /// The binding number was higher than the highest binding number of the set.
+    BindingOutOfRange {
+        set_num: u32,
+        binding_num: u32,
+        binding_count: u32,
+    },
+}
+
+impl error::Error for DescriptorSetsIndexError {}
+
+impl fmt::Display for DescriptorSetsIndexError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::SetOutOfRange { set_num, set_count } => write!(
+                fmt,
+                "the set number ({}) was not less than the number of sets in the collection ({})",
+                set_num, set_count,
+            ),
+            Self::BindingOutOfRange {
+                set_num,
+                binding_num,
+                binding_count,
+            } => write!(
+                fmt,
+                "the binding number ({}) was not less than the number of bindings in set {} ({})",
+                binding_num, set_num, binding_count,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DescriptorSetsCollection, DescriptorSetsIndexError, DescriptorSetsValidationError,
+    };
+    use crate::{
+        buffer::{BufferUsage, CpuAccessibleBuffer},
+        descriptor_set::{
+            layout::{
+                DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
+                DescriptorType,
+            },
+            DescriptorSet, PersistentDescriptorSet, WriteDescriptorSet,
+        },
+        device::Device,
+        pipeline::{layout::PipelineLayoutCreateInfo, PipelineLayout},
+        sampler::{Sampler, SamplerCreateInfo},
+        shader::ShaderStages,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn validate_and_iter() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let set_layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::all(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::Sampler)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let other_set_layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::all(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::UniformBuffer)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let pipeline_layout = PipelineLayout::new(
+            device.clone(),
+            PipelineLayoutCreateInfo {
+                set_layouts: [set_layout.clone(), other_set_layout].into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let single_pipeline_layout = PipelineLayout::new(
+            device.clone(),
+            PipelineLayoutCreateInfo {
+                set_layouts: [set_layout.clone()].into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let set = PersistentDescriptorSet::new(
+            set_layout,
+            [WriteDescriptorSet::sampler(
+                0,
+                Sampler::new(device, SamplerCreateInfo::simple_repeat_linear()).unwrap(),
+            )],
+        )
+        .unwrap();
+
+        assert_eq!(set.validate_against(&pipeline_layout), Ok(()));
+        assert_eq!(
+            (set.clone(), set.clone())
+                .iter_descriptors()
+                .map(|(set_num, binding_num, binding)| (
+                    set_num,
+                    binding_num,
+                    binding.descriptor_type
+                ))
+                .collect::<Vec<_>>(),
+            [
+                (0, 0, DescriptorType::Sampler),
+                (1, 0, DescriptorType::Sampler)
+            ],
+        );
+        assert_eq!(
+            (set.clone(), set.clone()).validate_against(&pipeline_layout),
+            Err(DescriptorSetsValidationError::DescriptorTypeMismatch {
+                set_num: 1,
+                binding_num: 0,
+                required: DescriptorType::UniformBuffer,
+                provided: DescriptorType::Sampler,
+            })
+        );
+        assert_eq!(
+            (set.clone(), set).validate_against(&single_pipeline_layout),
+            Err(DescriptorSetsValidationError::SetOutOfRange {
+                set_num: 1,
+                set_count: 1,
+            })
+        );
+    }
+
+    fn sampler_set(device: Arc<Device>) -> Arc<PersistentDescriptorSet> {
+        let set_layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::all(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::Sampler)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        PersistentDescriptorSet::new(
+            set_layout,
+            [WriteDescriptorSet::sampler(
+                0,
+                Sampler::new(device, SamplerCreateInfo::simple_repeat_linear()).unwrap(),
+            )],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn vec_of_dyn_sets() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let set: Arc<dyn DescriptorSet> = sampler_set(device);
+        let sets = vec![set.clone(), set.clone(), set];
+        assert_eq!(sets.clone().iter_descriptors().count(), 3);
+        assert_eq!(sets.into_vec().len(), 3);
+    }
+
+    #[test]
+    fn array_of_dyn_sets() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let set: Arc<dyn DescriptorSet> = sampler_set(device);
+        let sets = [set.clone(), set];
+        assert_eq!(sets.iter_descriptors().count(), 2);
+        assert_eq!(sets.into_vec().len(), 2);
+    }
+
+    #[test]
+    fn references_and_boxes() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let set = sampler_set(device.clone());
+        assert_eq!((&set).into_vec().len(), 1);
+        assert_eq!((&set, &set).into_vec().len(), 2);
+
+        let dyn_set: Arc<dyn DescriptorSet> = set.clone();
+        let sets = vec![dyn_set.clone(), dyn_set.clone()];
+        assert_eq!((&dyn_set).into_vec().len(), 1);
+        assert_eq!(sets.as_slice().iter_descriptors().count(), 2);
+        assert_eq!(sets.as_slice().into_vec().len(), 2);
+
+        let with_offsets = dyn_set.into_vec().pop().unwrap();
+        assert_eq!((&with_offsets).into_vec().len(), 1);
+
+        assert_eq!((&sets).into_vec().len(), 2);
+        assert_eq!((&(set.clone(), set.clone())).iter_descriptors().count(), 2);
+        assert_eq!(Box::new([set.clone(), set.clone()]).into_vec().len(), 2);
+        assert_eq!(Box::new(sets).into_vec().len(), 2);
+
+        let boxed: Box<dyn DescriptorSet> =
+            Box::new(Arc::try_unwrap(sampler_set(device)).ok().unwrap());
+        assert_eq!(boxed.into_vec().len(), 1);
+    }
+
+    #[test]
+    fn try_descriptor() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let set_layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [
+                    (
+                        0,
+                        DescriptorSetLayoutBinding {
+                            stages: ShaderStages::all(),
+                            ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::Sampler)
+                        },
+                    ),
+                    (
+                        2,
+                        DescriptorSetLayoutBinding {
+                            stages: ShaderStages::all(),
+                            ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::Sampler)
+                        },
+                    ),
+                ]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let sampler = Sampler::new(device, SamplerCreateInfo::simple_repeat_linear()).unwrap();
+        let set = PersistentDescriptorSet::new(
+            set_layout,
+            [
+                WriteDescriptorSet::sampler(0, sampler.clone()),
+                WriteDescriptorSet::sampler(2, sampler),
+            ],
+        )
+        .unwrap();
+
+        assert!(set.try_descriptor(0, 0).unwrap().is_some());
+        assert!(set.try_descriptor(0, 1).unwrap().is_none());
+        assert!(set.try_descriptor(0, 2).unwrap().is_some());
+        assert_eq!(
+            set.try_descriptor(0, 3),
+            Err(DescriptorSetsIndexError::BindingOutOfRange {
+                set_num: 0,
+                binding_num: 3,
+                binding_count: 3,
+            })
+        );
+        assert_eq!(
+            set.try_descriptor(1, 0),
+            Err(DescriptorSetsIndexError::SetOutOfRange {
+                set_num: 1,
+                set_count: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn num_dynamic_offsets() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let set_layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::all(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(
+                            DescriptorType::UniformBufferDynamic,
+                        )
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let buffer = CpuAccessibleBuffer::from_data(
+            device.clone(),
+            BufferUsage::uniform_buffer(),
+            false,
+            0u32,
+        )
+        .unwrap();
+        let dynamic_set =
+            PersistentDescriptorSet::new(set_layout, [WriteDescriptorSet::buffer(0, buffer)])
+                .unwrap();
+
+        let sets = (dynamic_set.offsets([0]), sampler_set(device));
+        assert_eq!(sets.num_dynamic_offsets(), 1);
+    }
+}
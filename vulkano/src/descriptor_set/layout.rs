@@ -116,6 +116,15 @@ impl DescriptorSetLayout {
                         },
                     );
                 }
+
+                // VUID-VkDescriptorSetLayoutCreateInfo-flags-03001
+                if binding.update_after_bind {
+                    return Err(
+                        DescriptorSetLayoutCreationError::PushDescriptorUpdateAfterBind {
+                            binding_num,
+                        },
+                    );
+                }
             }
 
             if !binding.immutable_samplers.is_empty() {
@@ -196,6 +205,71 @@ impl DescriptorSetLayout {
                     );
                 }
             }
+
+            if binding.partially_bound {
+                // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingPartiallyBound-03013
+                if !device.enabled_features().descriptor_binding_partially_bound {
+                    return Err(DescriptorSetLayoutCreationError::FeatureNotEnabled {
+                        feature: "descriptor_binding_partially_bound",
+                        reason: "binding is partially bound",
+                    });
+                }
+            }
+
+            if binding.update_after_bind {
+                let features = device.enabled_features();
+                let (enabled, feature) = match binding.descriptor_type {
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingSampledImageUpdateAfterBind-03006
+                    DescriptorType::Sampler
+                    | DescriptorType::CombinedImageSampler
+                    | DescriptorType::SampledImage => (
+                        features.descriptor_binding_sampled_image_update_after_bind,
+                        "descriptor_binding_sampled_image_update_after_bind",
+                    ),
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingStorageImageUpdateAfterBind-03007
+                    DescriptorType::StorageImage => (
+                        features.descriptor_binding_storage_image_update_after_bind,
+                        "descriptor_binding_storage_image_update_after_bind",
+                    ),
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingUniformTexelBufferUpdateAfterBind-03009
+                    DescriptorType::UniformTexelBuffer => (
+                        features.descriptor_binding_uniform_texel_buffer_update_after_bind,
+                        "descriptor_binding_uniform_texel_buffer_update_after_bind",
+                    ),
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingStorageTexelBufferUpdateAfterBind-03010
+                    DescriptorType::StorageTexelBuffer => (
+                        features.descriptor_binding_storage_texel_buffer_update_after_bind,
+                        "descriptor_binding_storage_texel_buffer_update_after_bind",
+                    ),
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingUniformBufferUpdateAfterBind-03005
+                    DescriptorType::UniformBuffer => (
+                        features.descriptor_binding_uniform_buffer_update_after_bind,
+                        "descriptor_binding_uniform_buffer_update_after_bind",
+                    ),
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingStorageBufferUpdateAfterBind-03008
+                    DescriptorType::StorageBuffer => (
+                        features.descriptor_binding_storage_buffer_update_after_bind,
+                        "descriptor_binding_storage_buffer_update_after_bind",
+                    ),
+                    // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-None-03011
+                    DescriptorType::InputAttachment
+                    | DescriptorType::UniformBufferDynamic
+                    | DescriptorType::StorageBufferDynamic => {
+                        return Err(
+                            DescriptorSetLayoutCreationError::UpdateAfterBindDescriptorTypeIncompatible {
+                                binding_num,
+                            },
+                        );
+                    }
+                };
+
+                if !enabled {
+                    return Err(DescriptorSetLayoutCreationError::FeatureNotEnabled {
+                        feature,
+                        reason: "binding can be updated after bind",
+                    });
+                }
+            }
         }
 
         // VUID-VkDescriptorSetLayoutCreateInfo-flags-00281
@@ -263,6 +337,17 @@ impl DescriptorSetLayout {
                 binding_flags |= ash::vk::DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT;
             }
 
+            if binding.partially_bound {
+                binding_flags |= ash::vk::DescriptorBindingFlags::PARTIALLY_BOUND;
+            }
+
+            if binding.update_after_bind {
+                binding_flags |= ash::vk::DescriptorBindingFlags::UPDATE_AFTER_BIND;
+
+                // VUID-VkDescriptorSetLayoutCreateInfo-flags-03000
+                flags |= ash::vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL;
+            }
+
             // VUID-VkDescriptorSetLayoutCreateInfo-binding-00279
             // Guaranteed by BTreeMap
             bindings_vk.push(ash::vk::DescriptorSetLayoutBinding {
@@ -306,7 +391,7 @@ impl DescriptorSetLayout {
             check_errors((fns.v1_0.create_descriptor_set_layout)(
                 device.internal_object(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))
             .map_err(|e| OomError::from(e))?;
@@ -354,6 +439,17 @@ impl DescriptorSetLayout {
             .unwrap_or(0)
     }
 
+    /// Returns whether any binding of the descriptor set layout has `update_after_bind` enabled.
+    ///
+    /// If `true`, descriptor sets with this layout must be allocated from a pool that was created
+    /// with `update_after_bind` enabled.
+    #[inline]
+    pub fn update_after_bind(&self) -> bool {
+        self.bindings
+            .values()
+            .any(|binding| binding.update_after_bind)
+    }
+
     /// Returns whether `self` is compatible with `other`.
     ///
     /// "Compatible" in this sense is defined by the Vulkan specification under the section
@@ -374,7 +470,7 @@ impl Drop for DescriptorSetLayout {
             (fns.v1_0.destroy_descriptor_set_layout)(
                 self.device.internal_object(),
                 self.handle,
-                ptr::null(),
+                self.device.allocation_callbacks(),
             );
         }
     }
@@ -448,6 +544,12 @@ pub enum DescriptorSetLayoutCreationError {
     /// `push_descriptor` is enabled, but a binding has `variable_descriptor_count` enabled.
     PushDescriptorVariableDescriptorCount { binding_num: u32 },
 
+    /// `push_descriptor` is enabled, but a binding has `update_after_bind` enabled.
+    PushDescriptorUpdateAfterBind { binding_num: u32 },
+
+    /// A binding has `update_after_bind` enabled, but it has an incompatible `descriptor_type`.
+    UpdateAfterBindDescriptorTypeIncompatible { binding_num: u32 },
+
     /// A binding has `variable_descriptor_count` enabled, but it is not the highest-numbered
     /// binding.
     VariableDescriptorCountBindingNotHighest {
@@ -511,6 +613,16 @@ impl std::fmt::Display for DescriptorSetLayoutCreationError {
                 "`push_descriptor` is enabled, but binding {} has `variable_descriptor_count` enabled",
                 binding_num,
             ),
+            Self::PushDescriptorUpdateAfterBind { binding_num } => write!(
+                fmt,
+                "`push_descriptor` is enabled, but binding {} has `update_after_bind` enabled",
+                binding_num,
+            ),
+            Self::UpdateAfterBindDescriptorTypeIncompatible { binding_num } => write!(
+                fmt,
+                "binding {} has `update_after_bind` enabled, but it has an incompatible `descriptor_type`",
+                binding_num,
+            ),
             Self::VariableDescriptorCountBindingNotHighest { binding_num, highest_binding_num } => write!(
                 fmt,
                 "binding {} has `variable_descriptor_count` enabled, but it is not the highest-numbered binding ({})",
@@ -546,7 +658,8 @@ pub struct DescriptorSetLayoutCreateInfo {
     /// be enabled on the device, and there are several restrictions:
     /// - There must be no bindings with a type of [`DescriptorType::UniformBufferDynamic`]
     ///   or [`DescriptorType::StorageBufferDynamic`].
-    /// - There must be no bindings with `variable_descriptor_count` enabled.
+    /// - There must be no bindings with `variable_descriptor_count` or `update_after_bind`
+    ///   enabled.
     /// - The total number of descriptors across all bindings must be less than the
     ///   [`max_push_descriptors`](crate::device::Properties::max_push_descriptors) limit.
     ///
@@ -620,6 +733,29 @@ pub struct DescriptorSetLayoutBinding {
     /// The default value is `false`.
     pub variable_descriptor_count: bool,
 
+    /// Whether descriptors in this binding may be left unwritten, as long as they are not
+    /// dynamically accessed by a shader.
+    ///
+    /// If set to `true`, the
+    /// [`descriptor_binding_partially_bound`](crate::device::Features::descriptor_binding_partially_bound)
+    /// feature must be enabled.
+    ///
+    /// The default value is `false`.
+    pub partially_bound: bool,
+
+    /// Whether descriptors in this binding may be updated after the descriptor set has been bound
+    /// to a command buffer, and while the command buffer is pending execution.
+    ///
+    /// If set to `true`, the `descriptor_binding_*_update_after_bind` feature corresponding to
+    /// `descriptor_type` must be enabled, and `push_descriptor` must be `false` for the layout.
+    /// The `descriptor_type` must not be [`DescriptorType::InputAttachment`],
+    /// [`DescriptorType::UniformBufferDynamic`] or [`DescriptorType::StorageBufferDynamic`].
+    /// Descriptor sets with this layout can only be allocated from a pool that was created with
+    /// `update_after_bind` enabled.
+    ///
+    /// The default value is `false`.
+    pub update_after_bind: bool,
+
     /// Which shader stages are going to access the descriptors in this binding.
     ///
     /// The default value is [`ShaderStages::none()`], which must be overridden.
@@ -639,6 +775,12 @@ pub struct DescriptorSetLayoutBinding {
     pub _ne: crate::NonExhaustive,
 }
 
+/// The description of a single descriptor binding, as returned by the inspection methods of
+/// [`DescriptorSetsCollection`](crate::descriptor_set::DescriptorSetsCollection).
+///
+/// This was the name of [`DescriptorSetLayoutBinding`] before version 0.29.
+pub type DescriptorDesc = DescriptorSetLayoutBinding;
+
 impl DescriptorSetLayoutBinding {
     /// Returns a `DescriptorSetLayoutBinding` with the given type.
     #[inline]
@@ -647,6 +789,8 @@ impl DescriptorSetLayoutBinding {
             descriptor_type,
             descriptor_count: 1,
             variable_descriptor_count: false,
+            partially_bound: false,
+            update_after_bind: false,
             stages: ShaderStages::none(),
             immutable_samplers: Vec::new(),
             _ne: crate::NonExhaustive(()),
@@ -663,6 +807,7 @@ impl DescriptorSetLayoutBinding {
         let DescriptorRequirements {
             descriptor_types,
             descriptor_count,
+            buffer_min_size: _,
             image_format,
             image_multisampled,
             image_scalar_type,
@@ -708,6 +853,8 @@ impl From<&DescriptorRequirements> for DescriptorSetLayoutBinding {
             descriptor_type: reqs.descriptor_types[0],
             descriptor_count: reqs.descriptor_count,
             variable_descriptor_count: false,
+            partially_bound: false,
+            update_after_bind: false,
             stages: reqs.stages,
             immutable_samplers: Vec::new(),
             _ne: crate::NonExhaustive(()),
@@ -816,6 +963,7 @@ mod tests {
     use crate::descriptor_set::layout::DescriptorSetLayout;
     use crate::descriptor_set::layout::DescriptorSetLayoutBinding;
     use crate::descriptor_set::layout::DescriptorSetLayoutCreateInfo;
+    use crate::descriptor_set::layout::DescriptorSetLayoutCreationError;
     use crate::descriptor_set::layout::DescriptorType;
     use crate::shader::ShaderStages;
     use std::collections::HashMap;
@@ -853,4 +1001,67 @@ mod tests {
                 .collect::<HashMap<_, _>>(),
         );
     }
+
+    #[test]
+    fn partially_bound_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let result = DescriptorSetLayout::new(
+            device,
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        descriptor_count: 16,
+                        partially_bound: true,
+                        stages: ShaderStages::all_graphics(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::SampledImage)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        );
+
+        match result {
+            Err(DescriptorSetLayoutCreationError::FeatureNotEnabled {
+                feature: "descriptor_binding_partially_bound",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn update_after_bind_input_attachment() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let result = DescriptorSetLayout::new(
+            device,
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        update_after_bind: true,
+                        stages: ShaderStages {
+                            fragment: true,
+                            ..ShaderStages::none()
+                        },
+                        ..DescriptorSetLayoutBinding::descriptor_type(
+                            DescriptorType::InputAttachment,
+                        )
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        );
+
+        match result {
+            Err(DescriptorSetLayoutCreationError::UpdateAfterBindDescriptorTypeIncompatible {
+                binding_num: 0,
+            }) => (),
+            _ => panic!(),
+        }
+    }
 }
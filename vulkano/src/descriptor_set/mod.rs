@@ -73,7 +73,9 @@
 //! - The `DescriptorSetsCollection` trait is implemented on collections of types that implement
 //!   `DescriptorSet`. It is what you pass to the draw functions.
 
-pub use self::collection::DescriptorSetsCollection;
+pub use self::collection::{
+    DescriptorSetsCollection, DescriptorSetsIndexError, DescriptorSetsValidationError,
+};
 use self::layout::DescriptorSetLayout;
 pub use self::persistent::PersistentDescriptorSet;
 pub use self::single_layout_pool::SingleLayoutDescSetPool;
@@ -230,6 +232,45 @@ impl DescriptorSetInner {
         })
     }
 
+    /// Writes a descriptor to the set, without updating the tracked resources.
+    pub(crate) unsafe fn update_untracked(
+        &self,
+        write: &WriteDescriptorSet,
+    ) -> Result<(), DescriptorSetUpdateError> {
+        let layout_binding =
+            check_descriptor_write(write, &self.layout, self.variable_descriptor_count)?;
+
+        let info = write.to_vulkan_info(layout_binding.descriptor_type);
+        let mut write_vk = write.to_vulkan(self.handle, layout_binding.descriptor_type);
+
+        match &info {
+            DescriptorWriteInfo::Image(info) => {
+                write_vk.descriptor_count = info.len() as u32;
+                write_vk.p_image_info = info.as_ptr();
+            }
+            DescriptorWriteInfo::Buffer(info) => {
+                write_vk.descriptor_count = info.len() as u32;
+                write_vk.p_buffer_info = info.as_ptr();
+            }
+            DescriptorWriteInfo::BufferView(info) => {
+                write_vk.descriptor_count = info.len() as u32;
+                write_vk.p_texel_buffer_view = info.as_ptr();
+            }
+        }
+
+        let fns = self.layout.device().fns();
+
+        (fns.v1_0.update_descriptor_sets)(
+            self.layout.device().internal_object(),
+            1,
+            &write_vk,
+            0,
+            ptr::null(),
+        );
+
+        Ok(())
+    }
+
     pub(crate) fn layout(&self) -> &Arc<DescriptorSetLayout> {
         &self.layout
     }
@@ -380,6 +421,14 @@ impl DescriptorBindingResources {
                 DescriptorBindingResources::Buffer(resources),
                 WriteDescriptorSetElements::Buffer(elements),
             ) => write_resources(first, resources, elements),
+            (
+                DescriptorBindingResources::Buffer(resources),
+                WriteDescriptorSetElements::BufferWithRange(elements),
+            ) => {
+                let buffers: SmallVec<[_; 1]> =
+                    elements.iter().map(|(buffer, _)| buffer.clone()).collect();
+                write_resources(first, resources, &buffers)
+            }
             (
                 DescriptorBindingResources::BufferView(resources),
                 WriteDescriptorSetElements::BufferView(elements),
@@ -496,6 +545,54 @@ where
     }
 }
 
+impl From<Arc<dyn DescriptorSet>> for DescriptorSetWithOffsets {
+    #[inline]
+    fn from(descriptor_set: Arc<dyn DescriptorSet>) -> Self {
+        DescriptorSetWithOffsets::new(descriptor_set, std::iter::empty())
+    }
+}
+
+impl<S> From<&Arc<S>> for DescriptorSetWithOffsets
+where
+    S: DescriptorSet + 'static,
+{
+    #[inline]
+    fn from(descriptor_set: &Arc<S>) -> Self {
+        DescriptorSetWithOffsets::new(descriptor_set.clone(), std::iter::empty())
+    }
+}
+
+impl From<&Arc<dyn DescriptorSet>> for DescriptorSetWithOffsets {
+    #[inline]
+    fn from(descriptor_set: &Arc<dyn DescriptorSet>) -> Self {
+        DescriptorSetWithOffsets::new(descriptor_set.clone(), std::iter::empty())
+    }
+}
+
+impl<S> From<Box<S>> for DescriptorSetWithOffsets
+where
+    S: DescriptorSet + 'static,
+{
+    #[inline]
+    fn from(descriptor_set: Box<S>) -> Self {
+        DescriptorSetWithOffsets::new(Arc::<S>::from(descriptor_set), std::iter::empty())
+    }
+}
+
+impl From<Box<dyn DescriptorSet>> for DescriptorSetWithOffsets {
+    #[inline]
+    fn from(descriptor_set: Box<dyn DescriptorSet>) -> Self {
+        DescriptorSetWithOffsets::new(Arc::from(descriptor_set), std::iter::empty())
+    }
+}
+
+impl From<&DescriptorSetWithOffsets> for DescriptorSetWithOffsets {
+    #[inline]
+    fn from(descriptor_set: &DescriptorSetWithOffsets) -> Self {
+        descriptor_set.clone()
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum DescriptorSetCreationError {
     DescriptorSetUpdateError(DescriptorSetUpdateError),
@@ -23,7 +23,7 @@
 
 use crate::descriptor_set::pool::standard::StdDescriptorPoolAlloc;
 use crate::descriptor_set::pool::{DescriptorPool, DescriptorPoolAlloc};
-use crate::descriptor_set::update::WriteDescriptorSet;
+use crate::descriptor_set::update::{DescriptorSetUpdateError, WriteDescriptorSet};
 use crate::descriptor_set::{
     DescriptorSet, DescriptorSetCreationError, DescriptorSetInner, DescriptorSetLayout,
     DescriptorSetResources, UnsafeDescriptorSet,
@@ -112,6 +112,44 @@ impl PersistentDescriptorSet {
     }
 }
 
+impl<P> PersistentDescriptorSet<P>
+where
+    P: DescriptorPoolAlloc,
+{
+    /// Writes a single array element of a binding, leaving the rest of the descriptor set
+    /// untouched.
+    ///
+    /// This is intended for large, partially bound arrays of descriptors, where rewriting the
+    /// whole set whenever one element changes would be wasteful.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `write` does not contain exactly one element.
+    ///
+    /// # Safety
+    ///
+    /// - The written resource is not tracked by the descriptor set, and is not reflected in
+    ///   [`resources`](DescriptorSet::resources). You must keep it alive and synchronize accesses
+    ///   to it for as long as it can be accessed by the device.
+    /// - If the descriptor set is bound in a command buffer that is being recorded, the binding
+    ///   must have been created with
+    ///   [`update_after_bind`](crate::descriptor_set::layout::DescriptorSetLayoutBinding::update_after_bind)
+    ///   enabled.
+    /// - The descriptor set must not be in use by a command buffer that is pending execution.
+    pub unsafe fn update_at_index(
+        &self,
+        write: WriteDescriptorSet,
+    ) -> Result<(), DescriptorSetUpdateError> {
+        assert_eq!(
+            write.elements().len(),
+            1,
+            "the provided write must contain exactly one element",
+        );
+
+        self.inner.update_untracked(&write)
+    }
+}
+
 unsafe impl<P> DescriptorSet for PersistentDescriptorSet<P>
 where
     P: DescriptorPoolAlloc,
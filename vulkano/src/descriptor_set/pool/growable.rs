@@ -0,0 +1,301 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{DescriptorPool, DescriptorPoolAlloc, UnsafeDescriptorPool};
+use crate::{
+    descriptor_set::{
+        layout::{DescriptorSetLayout, DescriptorType},
+        pool::{
+            DescriptorPoolAllocError, DescriptorSetAllocateInfo, UnsafeDescriptorPoolCreateInfo,
+        },
+        sys::UnsafeDescriptorSet,
+    },
+    device::{Device, DeviceOwned},
+    OomError,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A thread-safe descriptor pool that grows on demand.
+///
+/// Descriptor sets are allocated from the most recently created backing pool. Whenever that
+/// allocation fails because the backing pool is full or fragmented, a new backing pool is created
+/// with room for `sets_per_pool` sets of the requested layout, and the allocation is retried from
+/// it. This way, there is no need to know the number of sets and descriptors up front.
+///
+/// Sets are not freed individually when their allocation is dropped. Instead, the memory of all
+/// sets is reclaimed at once by calling [`reset`](GrowableDescriptorPool::reset), or when the
+/// pool is destroyed. The `Arc<GrowableDescriptorPool>` is kept alive by its allocations.
+#[derive(Debug)]
+pub struct GrowableDescriptorPool {
+    device: Arc<Device>,
+    sets_per_pool: u32,
+    inner: Mutex<GrowableDescriptorPoolInner>,
+}
+
+#[derive(Debug, Default)]
+struct GrowableDescriptorPoolInner {
+    // All the backing pools, in the order they were created.
+    pools: Vec<UnsafeDescriptorPool>,
+    // The number of sets allocated since the last reset.
+    set_count: u32,
+    // The number of descriptors of each type allocated since the last reset.
+    descriptor_counts: HashMap<DescriptorType, u32>,
+}
+
+impl GrowableDescriptorPool {
+    /// Builds a new `GrowableDescriptorPool`. Each backing pool will have room for
+    /// `sets_per_pool` sets of the layout that caused it to be created.
+    ///
+    /// No backing pool is created until the first allocation.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `sets_per_pool` is `0`.
+    pub fn new(device: Arc<Device>, sets_per_pool: u32) -> GrowableDescriptorPool {
+        assert!(sets_per_pool != 0);
+
+        GrowableDescriptorPool {
+            device,
+            sets_per_pool,
+            inner: Mutex::new(Default::default()),
+        }
+    }
+
+    /// Returns the number of sets that each backing pool has room for.
+    #[inline]
+    pub fn sets_per_pool(&self) -> u32 {
+        self.sets_per_pool
+    }
+
+    /// Returns the number of backing pools that have been created.
+    #[inline]
+    pub fn pool_count(&self) -> usize {
+        self.inner.lock().unwrap().pools.len()
+    }
+
+    /// Returns the number of sets that have been allocated since the pool was created or last
+    /// reset.
+    #[inline]
+    pub fn set_count(&self) -> u32 {
+        self.inner.lock().unwrap().set_count
+    }
+
+    /// Returns the number of descriptors of each type that have been allocated since the pool was
+    /// created or last reset.
+    #[inline]
+    pub fn descriptor_counts(&self) -> HashMap<DescriptorType, u32> {
+        self.inner.lock().unwrap().descriptor_counts.clone()
+    }
+
+    /// Resets all the backing pools, which frees all the sets that were allocated from them.
+    ///
+    /// The backing pools are kept, so that their memory can be reused by later allocations.
+    ///
+    /// # Safety
+    ///
+    /// - The sets that were allocated from the pool must not be in use by the device, and must
+    ///   not be used again afterwards.
+    pub unsafe fn reset(&self) -> Result<(), OomError> {
+        let mut inner = self.inner.lock().unwrap();
+
+        for pool in inner.pools.iter_mut() {
+            pool.reset()?;
+        }
+
+        inner.set_count = 0;
+        inner.descriptor_counts.clear();
+
+        Ok(())
+    }
+
+    // Creates a new backing pool with room for `sets_per_pool` sets of `layout`.
+    fn create_pool(&self, layout: &DescriptorSetLayout) -> Result<UnsafeDescriptorPool, OomError> {
+        UnsafeDescriptorPool::new(
+            self.device.clone(),
+            UnsafeDescriptorPoolCreateInfo {
+                max_sets: self.sets_per_pool,
+                pool_sizes: layout
+                    .descriptor_counts()
+                    .iter()
+                    .map(|(&ty, &count)| (ty, count * self.sets_per_pool))
+                    .collect(),
+                update_after_bind: layout.update_after_bind(),
+                ..Default::default()
+            },
+        )
+    }
+}
+
+/// A descriptor set allocated from a `GrowableDescriptorPool`.
+///
+/// The set is not freed when this object is dropped, but when the pool is reset or destroyed.
+pub struct GrowableDescriptorPoolAlloc {
+    set: UnsafeDescriptorSet,
+    // We keep the parent of the backing pools alive, otherwise they would be destroyed.
+    pool_parent: Arc<GrowableDescriptorPool>,
+}
+
+unsafe impl DescriptorPool for Arc<GrowableDescriptorPool> {
+    type Alloc = GrowableDescriptorPoolAlloc;
+
+    fn allocate(
+        &mut self,
+        layout: &DescriptorSetLayout,
+        variable_descriptor_count: u32,
+    ) -> Result<GrowableDescriptorPoolAlloc, OomError> {
+        assert!(
+            !layout.push_descriptor(),
+            "the provided descriptor set layout is for push descriptors, and cannot be used to build a descriptor set object",
+        );
+
+        let max_count = layout.variable_descriptor_count();
+
+        assert!(
+            variable_descriptor_count <= max_count,
+            "the provided variable_descriptor_count ({}) is greater than the maximum number of variable count descriptors in the set ({})",
+            variable_descriptor_count,
+            max_count,
+        );
+
+        let mut inner = self.inner.lock().unwrap();
+        let allocate_info = DescriptorSetAllocateInfo {
+            layout,
+            variable_descriptor_count,
+        };
+
+        // Try the most recent backing pool that is compatible with the layout. Update-after-bind
+        // pools are subject to separate limits, so only use them for layouts that need them.
+        let existing = inner
+            .pools
+            .iter_mut()
+            .rev()
+            .find(|pool| pool.update_after_bind() == layout.update_after_bind())
+            .map(|pool| unsafe { pool.allocate_descriptor_sets([allocate_info.clone()]) });
+
+        let set = match existing {
+            Some(Ok(mut sets)) => sets.next().unwrap(),
+            Some(Err(DescriptorPoolAllocError::OutOfHostMemory)) => {
+                return Err(OomError::OutOfHostMemory);
+            }
+            Some(Err(DescriptorPoolAllocError::OutOfDeviceMemory)) => {
+                return Err(OomError::OutOfDeviceMemory);
+            }
+            // The backing pool is full or fragmented, or there is none yet. Create a new one and
+            // retry from it.
+            Some(Err(DescriptorPoolAllocError::FragmentedPool))
+            | Some(Err(DescriptorPoolAllocError::OutOfPoolMemory))
+            | None => {
+                let mut new_pool = self.create_pool(layout)?;
+
+                let set = unsafe {
+                    match new_pool.allocate_descriptor_sets([allocate_info]) {
+                        Ok(mut sets) => sets.next().unwrap(),
+                        Err(DescriptorPoolAllocError::OutOfHostMemory) => {
+                            return Err(OomError::OutOfHostMemory);
+                        }
+                        Err(DescriptorPoolAllocError::OutOfDeviceMemory) => {
+                            return Err(OomError::OutOfDeviceMemory);
+                        }
+                        // A fragmented pool error can't happen at the first ever allocation.
+                        Err(DescriptorPoolAllocError::FragmentedPool) => unreachable!(),
+                        // Out of pool memory cannot happen at the first ever allocation.
+                        Err(DescriptorPoolAllocError::OutOfPoolMemory) => unreachable!(),
+                    }
+                };
+
+                inner.pools.push(new_pool);
+                set
+            }
+        };
+
+        inner.set_count += 1;
+
+        for (&ty, &count) in layout.descriptor_counts() {
+            *inner.descriptor_counts.entry(ty).or_default() += count;
+        }
+
+        Ok(GrowableDescriptorPoolAlloc {
+            set,
+            pool_parent: self.clone(),
+        })
+    }
+}
+
+unsafe impl DeviceOwned for GrowableDescriptorPool {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl DescriptorPoolAlloc for GrowableDescriptorPoolAlloc {
+    #[inline]
+    fn inner(&self) -> &UnsafeDescriptorSet {
+        &self.set
+    }
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut UnsafeDescriptorSet {
+        &mut self.set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::descriptor_set::layout::DescriptorSetLayout;
+    use crate::descriptor_set::layout::DescriptorSetLayoutBinding;
+    use crate::descriptor_set::layout::DescriptorSetLayoutCreateInfo;
+    use crate::descriptor_set::layout::DescriptorType;
+    use crate::descriptor_set::pool::DescriptorPool;
+    use crate::descriptor_set::pool::GrowableDescriptorPool;
+    use crate::shader::ShaderStages;
+    use std::sync::Arc;
+
+    #[test]
+    fn grows_past_initial_size() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::all(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::UniformBuffer)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut pool = Arc::new(GrowableDescriptorPool::new(device, 4));
+        let allocs = (0..100)
+            .map(|_| pool.allocate(&layout, 0).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(allocs.len(), 100);
+        assert!(pool.pool_count() >= 25);
+        assert_eq!(pool.set_count(), 100);
+        assert_eq!(
+            pool.descriptor_counts()[&DescriptorType::UniformBuffer],
+            100
+        );
+
+        drop(allocs);
+        unsafe {
+            pool.reset().unwrap();
+        }
+        assert_eq!(pool.set_count(), 0);
+        assert!(pool.descriptor_counts().is_empty());
+    }
+}
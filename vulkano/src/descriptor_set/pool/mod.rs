@@ -10,6 +10,7 @@
 //! A pool from which descriptor sets can be allocated.
 
 pub use self::{
+    growable::GrowableDescriptorPool,
     standard::StdDescriptorPool,
     sys::{
         DescriptorPoolAllocError, DescriptorSetAllocateInfo, UnsafeDescriptorPool,
@@ -19,6 +20,7 @@ pub use self::{
 use super::{layout::DescriptorSetLayout, sys::UnsafeDescriptorSet};
 use crate::{device::DeviceOwned, OomError};
 
+pub mod growable;
 pub mod standard;
 mod sys;
 
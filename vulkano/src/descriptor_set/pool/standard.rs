@@ -97,6 +97,12 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
                 continue;
             }
 
+            // Update-after-bind pools are subject to separate limits, so only use them for
+            // layouts that need them.
+            if pool.pool.update_after_bind() != layout.update_after_bind() {
+                continue;
+            }
+
             if !layout.descriptor_counts().iter().all(|(ty, &count)| {
                 pool.remaining_capacity.get(ty).copied().unwrap_or_default() >= count
             }) {
@@ -149,6 +155,7 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
                     .map(|(&ty, &count)| (ty, count * 40))
                     .collect(),
                 can_free_descriptor_sets: true,
+                update_after_bind: layout.update_after_bind(),
                 ..Default::default()
             },
         )?;
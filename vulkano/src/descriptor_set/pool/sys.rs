@@ -38,6 +38,7 @@ pub struct UnsafeDescriptorPool {
     max_sets: u32,
     pool_sizes: HashMap<DescriptorType, u32>,
     can_free_descriptor_sets: bool,
+    update_after_bind: bool,
 }
 
 impl UnsafeDescriptorPool {
@@ -56,6 +57,7 @@ impl UnsafeDescriptorPool {
             max_sets,
             pool_sizes,
             can_free_descriptor_sets,
+            update_after_bind,
             _ne: _,
         } = create_info;
 
@@ -85,6 +87,10 @@ impl UnsafeDescriptorPool {
                 flags |= ash::vk::DescriptorPoolCreateFlags::FREE_DESCRIPTOR_SET;
             }
 
+            if update_after_bind {
+                flags |= ash::vk::DescriptorPoolCreateFlags::UPDATE_AFTER_BIND;
+            }
+
             let create_info = ash::vk::DescriptorPoolCreateInfo {
                 flags,
                 max_sets,
@@ -99,7 +105,7 @@ impl UnsafeDescriptorPool {
                 check_errors((fns.v1_0.create_descriptor_pool)(
                     device.internal_object(),
                     &create_info,
-                    ptr::null(),
+                    device.allocation_callbacks(),
                     output.as_mut_ptr(),
                 ))?;
                 output.assume_init()
@@ -113,6 +119,7 @@ impl UnsafeDescriptorPool {
             max_sets,
             pool_sizes,
             can_free_descriptor_sets,
+            update_after_bind,
         })
     }
 
@@ -134,6 +141,13 @@ impl UnsafeDescriptorPool {
         self.can_free_descriptor_sets
     }
 
+    /// Returns whether descriptor sets with `update_after_bind` bindings can be allocated from the
+    /// pool.
+    #[inline]
+    pub fn update_after_bind(&self) -> bool {
+        self.update_after_bind
+    }
+
     /// Allocates descriptor sets from the pool, one for each element in `create_info`.
     /// Returns an iterator to the allocated sets, or an error.
     ///
@@ -149,6 +163,8 @@ impl UnsafeDescriptorPool {
     /// See also the `new` function.
     ///
     /// - The total descriptors of the layouts must fit in the pool.
+    /// - If any of the layouts has a binding with `update_after_bind` enabled, the pool must have
+    ///   been created with `update_after_bind` enabled.
     /// - The total number of descriptor sets allocated from the pool must not overflow the pool.
     /// - You must ensure that the allocated descriptor sets are no longer in use when the pool
     ///   is destroyed, as destroying the pool is equivalent to freeing all the sets.
@@ -166,6 +182,8 @@ impl UnsafeDescriptorPool {
                         info.layout.device().internal_object(),
                     );
                     debug_assert!(!info.layout.push_descriptor());
+                    // VUID-VkDescriptorSetAllocateInfo-pSetLayouts-03044
+                    debug_assert!(!info.layout.update_after_bind() || self.update_after_bind);
                     debug_assert!(
                         info.variable_descriptor_count <= info.layout.variable_descriptor_count()
                     );
@@ -297,7 +315,7 @@ impl Drop for UnsafeDescriptorPool {
             (fns.v1_0.destroy_descriptor_pool)(
                 self.device.internal_object(),
                 self.handle,
-                ptr::null(),
+                self.device.allocation_callbacks(),
             );
         }
     }
@@ -355,6 +373,12 @@ pub struct UnsafeDescriptorPoolCreateInfo {
     /// The default value is `false`.
     pub can_free_descriptor_sets: bool,
 
+    /// Whether descriptor sets whose layout has bindings with `update_after_bind` enabled can be
+    /// allocated from the pool.
+    ///
+    /// The default value is `false`.
+    pub update_after_bind: bool,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -365,6 +389,7 @@ impl Default for UnsafeDescriptorPoolCreateInfo {
             max_sets: 0,
             pool_sizes: HashMap::default(),
             can_free_descriptor_sets: false,
+            update_after_bind: false,
             _ne: crate::NonExhaustive(()),
         }
     }
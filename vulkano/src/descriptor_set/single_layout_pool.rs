@@ -116,6 +116,7 @@ impl SingleLayoutDescSetPool {
                         .iter()
                         .map(|(&ty, &count)| (ty, count * self.set_count as u32))
                         .collect(),
+                    update_after_bind: self.layout.update_after_bind(),
                     ..Default::default()
                 },
             )?;
@@ -16,7 +16,7 @@ use crate::{
     DeviceSize, VulkanObject,
 };
 use smallvec::SmallVec;
-use std::{ptr, sync::Arc};
+use std::{ops::Range, ptr, sync::Arc};
 
 /// Represents a single write operation to the binding of a descriptor set.
 ///
@@ -82,6 +82,40 @@ impl WriteDescriptorSet {
         }
     }
 
+    /// Write a range of a single buffer to array element 0.
+    ///
+    /// See `buffer_with_range_array` for more information.
+    #[inline]
+    pub fn buffer_with_range(
+        binding: u32,
+        buffer: Arc<dyn BufferAccess>,
+        range: Range<DeviceSize>,
+    ) -> Self {
+        Self::buffer_with_range_array(binding, 0, [(buffer, range)])
+    }
+
+    /// Write a number of consecutive buffer elements, each of which only covers `range` of its
+    /// buffer.
+    ///
+    /// The range is relative to the start of the buffer. It must not be empty, must fit within
+    /// the buffer and its start must be a multiple of the device's
+    /// `min_uniform_buffer_offset_alignment` or `min_storage_buffer_offset_alignment`, depending
+    /// on the descriptor type. This is checked when the write is performed.
+    #[inline]
+    pub fn buffer_with_range_array(
+        binding: u32,
+        first_array_element: u32,
+        elements: impl IntoIterator<Item = (Arc<dyn BufferAccess>, Range<DeviceSize>)>,
+    ) -> Self {
+        let elements: SmallVec<_> = elements.into_iter().collect();
+        assert!(!elements.is_empty());
+        Self {
+            binding,
+            first_array_element,
+            elements: WriteDescriptorSetElements::BufferWithRange(elements),
+        }
+    }
+
     /// Write a single buffer view to array element 0.
     #[inline]
     pub fn buffer_view(binding: u32, buffer_view: Arc<dyn BufferViewAbstract>) -> Self {
@@ -247,6 +281,29 @@ impl WriteDescriptorSet {
                         .collect(),
                 )
             }
+            WriteDescriptorSetElements::BufferWithRange(elements) => {
+                debug_assert!(matches!(
+                    descriptor_type,
+                    DescriptorType::UniformBuffer
+                        | DescriptorType::StorageBuffer
+                        | DescriptorType::UniformBufferDynamic
+                        | DescriptorType::StorageBufferDynamic
+                ));
+                DescriptorWriteInfo::Buffer(
+                    elements
+                        .iter()
+                        .map(|(buffer, range)| {
+                            let BufferInner { buffer, offset } = buffer.inner();
+
+                            ash::vk::DescriptorBufferInfo {
+                                buffer: buffer.internal_object(),
+                                offset: offset + range.start,
+                                range: range.end - range.start,
+                            }
+                        })
+                        .collect(),
+                )
+            }
             WriteDescriptorSetElements::BufferView(elements) => {
                 debug_assert!(matches!(
                     descriptor_type,
@@ -344,6 +401,7 @@ impl WriteDescriptorSet {
 pub enum WriteDescriptorSetElements {
     None(u32),
     Buffer(SmallVec<[Arc<dyn BufferAccess>; 1]>),
+    BufferWithRange(SmallVec<[(Arc<dyn BufferAccess>, Range<DeviceSize>); 1]>),
     BufferView(SmallVec<[Arc<dyn BufferViewAbstract>; 1]>),
     ImageView(SmallVec<[Arc<dyn ImageViewAbstract>; 1]>),
     ImageViewSampler(SmallVec<[(Arc<dyn ImageViewAbstract>, Arc<Sampler>); 1]>),
@@ -357,6 +415,7 @@ impl WriteDescriptorSetElements {
         match self {
             Self::None(num_elements) => *num_elements,
             Self::Buffer(elements) => elements.len() as u32,
+            Self::BufferWithRange(elements) => elements.len() as u32,
             Self::BufferView(elements) => elements.len() as u32,
             Self::ImageView(elements) => elements.len() as u32,
             Self::ImageViewSampler(elements) => elements.len() as u32,
@@ -490,6 +549,95 @@ pub(crate) fn check_descriptor_write<'a>(
             //       in case we forget to adjust this code
             assert!(layout.device().enabled_features().robust_buffer_access);
         }
+        WriteDescriptorSetElements::BufferWithRange(elements) => {
+            let storage = match layout_binding.descriptor_type {
+                DescriptorType::StorageBuffer | DescriptorType::StorageBufferDynamic => true,
+                DescriptorType::UniformBuffer | DescriptorType::UniformBufferDynamic => false,
+                _ => {
+                    return Err(DescriptorSetUpdateError::IncompatibleDescriptorType {
+                        binding: write.binding(),
+                    })
+                }
+            };
+
+            let properties = layout.device().physical_device().properties();
+            let (min_offset_alignment, max_range) = if storage {
+                (
+                    properties.min_storage_buffer_offset_alignment,
+                    properties.max_storage_buffer_range,
+                )
+            } else {
+                (
+                    properties.min_uniform_buffer_offset_alignment,
+                    properties.max_uniform_buffer_range,
+                )
+            };
+
+            for (index, (buffer, range)) in elements.iter().enumerate() {
+                assert_eq!(
+                    buffer.device().internal_object(),
+                    layout.device().internal_object(),
+                );
+
+                let usage = buffer.inner().buffer.usage();
+
+                if storage && !usage.storage_buffer {
+                    return Err(DescriptorSetUpdateError::MissingUsage {
+                        binding: write.binding(),
+                        index: descriptor_range_start + index as u32,
+                        usage: "storage_buffer",
+                    });
+                }
+
+                if !storage && !usage.uniform_buffer {
+                    return Err(DescriptorSetUpdateError::MissingUsage {
+                        binding: write.binding(),
+                        index: descriptor_range_start + index as u32,
+                        usage: "uniform_buffer",
+                    });
+                }
+
+                // VUID-VkDescriptorBufferInfo-offset-00340
+                // VUID-VkDescriptorBufferInfo-range-00341
+                // VUID-VkDescriptorBufferInfo-range-00342
+                if range.start >= range.end || range.end > buffer.size() {
+                    return Err(DescriptorSetUpdateError::BufferRangeOutOfBounds {
+                        binding: write.binding(),
+                        index: descriptor_range_start + index as u32,
+                        start: range.start,
+                        end: range.end,
+                        buffer_size: buffer.size(),
+                    });
+                }
+
+                // VUID-VkWriteDescriptorSet-descriptorType-00327
+                // VUID-VkWriteDescriptorSet-descriptorType-00328
+                let offset = buffer.inner().offset + range.start;
+
+                if offset % min_offset_alignment != 0 {
+                    return Err(DescriptorSetUpdateError::BufferOffsetNotAligned {
+                        binding: write.binding(),
+                        index: descriptor_range_start + index as u32,
+                        offset,
+                        required_alignment: min_offset_alignment,
+                    });
+                }
+
+                // VUID-VkWriteDescriptorSet-descriptorType-00332
+                // VUID-VkWriteDescriptorSet-descriptorType-00333
+                if range.end - range.start > max_range as DeviceSize {
+                    return Err(DescriptorSetUpdateError::BufferRangeTooLarge {
+                        binding: write.binding(),
+                        index: descriptor_range_start + index as u32,
+                        range: range.end - range.start,
+                        max: max_range,
+                    });
+                }
+            }
+
+            // See the note about robust buffer access above.
+            assert!(layout.device().enabled_features().robust_buffer_access);
+        }
         WriteDescriptorSetElements::BufferView(elements) => {
             match layout_binding.descriptor_type {
                 DescriptorType::StorageTexelBuffer => {
@@ -908,6 +1056,32 @@ pub enum DescriptorSetUpdateError {
         written_count: u32,
     },
 
+    /// Tried to write a buffer range whose offset was not a multiple of the required alignment.
+    BufferOffsetNotAligned {
+        binding: u32,
+        index: u32,
+        offset: DeviceSize,
+        required_alignment: DeviceSize,
+    },
+
+    /// Tried to write a buffer range that was empty or went past the end of the buffer.
+    BufferRangeOutOfBounds {
+        binding: u32,
+        index: u32,
+        start: DeviceSize,
+        end: DeviceSize,
+        buffer_size: DeviceSize,
+    },
+
+    /// Tried to write a buffer range that was larger than the maximum allowed by the device for
+    /// the descriptor type.
+    BufferRangeTooLarge {
+        binding: u32,
+        index: u32,
+        range: DeviceSize,
+        max: u32,
+    },
+
     /// Tried to write an image view with a 2D type and a 3D underlying image.
     ImageView2dFrom3d { binding: u32, index: u32 },
 
@@ -977,6 +1151,37 @@ impl std::fmt::Display for DescriptorSetUpdateError {
                 "tried to write up to element {} to binding {}, but only {} descriptors are available",
                 written_count, binding, available_count,
             ),
+            Self::BufferOffsetNotAligned {
+                binding,
+                index,
+                offset,
+                required_alignment,
+            } => write!(
+                fmt,
+                "tried to write a buffer range to binding {} index {} whose offset ({}) was not a multiple of the required alignment ({})",
+                binding, index, offset, required_alignment,
+            ),
+            Self::BufferRangeOutOfBounds {
+                binding,
+                index,
+                start,
+                end,
+                buffer_size,
+            } => write!(
+                fmt,
+                "tried to write the buffer range {}..{} to binding {} index {}, but the range was empty or exceeded the size of the buffer ({})",
+                start, end, binding, index, buffer_size,
+            ),
+            Self::BufferRangeTooLarge {
+                binding,
+                index,
+                range,
+                max,
+            } => write!(
+                fmt,
+                "tried to write a buffer range of {} bytes to binding {} index {}, but the maximum for this descriptor type is {}",
+                range, binding, index, max,
+            ),
             Self::ImageView2dFrom3d { binding, index } => write!(
                 fmt,
                 "tried to write an image view to binding {} index {} with a 2D type and a 3D underlying image",
@@ -1039,3 +1244,93 @@ impl std::fmt::Display for DescriptorSetUpdateError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{DescriptorSetUpdateError, WriteDescriptorSet};
+    use crate::{
+        buffer::{BufferAccess, BufferUsage, CpuAccessibleBuffer},
+        descriptor_set::{
+            layout::{
+                DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
+                DescriptorType,
+            },
+            DescriptorSetCreationError, PersistentDescriptorSet,
+        },
+        shader::ShaderStages,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn buffer_with_range() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let set_layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::all(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::UniformBuffer)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let alignment = device
+            .physical_device()
+            .properties()
+            .min_uniform_buffer_offset_alignment;
+        let buffer: Arc<dyn BufferAccess> = CpuAccessibleBuffer::from_iter(
+            device,
+            BufferUsage::uniform_buffer(),
+            false,
+            vec![0u8; alignment as usize * 4],
+        )
+        .unwrap();
+
+        assert!(PersistentDescriptorSet::new(
+            set_layout.clone(),
+            [WriteDescriptorSet::buffer_with_range(
+                0,
+                buffer.clone(),
+                alignment..alignment * 2,
+            )],
+        )
+        .is_ok());
+
+        assert!(matches!(
+            PersistentDescriptorSet::new(
+                set_layout.clone(),
+                [WriteDescriptorSet::buffer_with_range(
+                    0,
+                    buffer.clone(),
+                    alignment * 2..alignment * 5,
+                )],
+            ),
+            Err(DescriptorSetCreationError::DescriptorSetUpdateError(
+                DescriptorSetUpdateError::BufferRangeOutOfBounds { .. }
+            ))
+        ));
+
+        if alignment > 1 {
+            assert!(matches!(
+                PersistentDescriptorSet::new(
+                    set_layout,
+                    [WriteDescriptorSet::buffer_with_range(
+                        0,
+                        buffer,
+                        1..alignment
+                    )],
+                ),
+                Err(DescriptorSetCreationError::DescriptorSetUpdateError(
+                    DescriptorSetUpdateError::BufferOffsetNotAligned { .. }
+                ))
+            ));
+        }
+    }
+}
@@ -95,7 +95,7 @@
 //!
 //! TODO: write
 
-use self::physical::{PhysicalDevice, QueueFamily};
+use self::physical::{PhysicalDevice, QueueFamily, TimeDomain};
 pub(crate) use self::{features::FeaturesFfi, properties::PropertiesFfi};
 pub use self::{
     features::{FeatureRestriction, FeatureRestrictionError, Features},
@@ -103,11 +103,19 @@ pub use self::{
 };
 use crate::{
     check_errors,
-    command_buffer::pool::StandardCommandPool,
+    command_buffer::{
+        pool::{CommandPoolAllocError, StandardCommandPool, StandardCommandPoolThreadHandle},
+        submit::{BindSparseError, BindSparseInfo, SubmitError, SubmitInfo},
+        PrimaryCommandBuffer,
+    },
     descriptor_set::pool::StdDescriptorPool,
     instance::{debug::DebugUtilsLabel, Instance},
-    memory::{pool::StdMemoryPool, ExternalMemoryHandleType},
-    Error, OomError, SynchronizedVulkanObject, Version, VulkanObject,
+    memory::{
+        host::HostAllocationCallbacks, pool::StdMemoryPool, DeviceMemory, ExternalMemoryHandleType,
+        HostAllocator,
+    },
+    sync::{Fence, FenceWaitError},
+    DeviceSize, Error, OomError, SynchronizedVulkanObject, Version, VulkanObject,
 };
 pub use crate::{
     device::extensions::DeviceExtensions,
@@ -127,6 +135,7 @@ use std::{
     ops::Deref,
     ptr,
     sync::{Arc, Mutex, MutexGuard, Weak},
+    time::Duration,
 };
 
 pub(crate) mod extensions;
@@ -146,11 +155,13 @@ pub struct Device {
     api_version: Version,
 
     fns: DeviceFunctions,
+    host_allocation_callbacks: Option<HostAllocationCallbacks>,
     standard_pool: Mutex<Weak<StdMemoryPool>>,
     standard_descriptor_pool: Mutex<Weak<StdDescriptorPool>>,
     standard_command_pools: Mutex<HashMap<u32, Weak<StandardCommandPool>>>,
     enabled_extensions: DeviceExtensions,
     enabled_features: Features,
+    validate_attachment_contents: bool,
     active_queue_families: SmallVec<[u32; 2]>,
     allocation_count: Mutex<u32>,
     fence_pool: Mutex<Vec<ash::vk::Fence>>,
@@ -183,6 +194,8 @@ impl Device {
             mut enabled_extensions,
             mut enabled_features,
             queue_create_infos,
+            host_allocator,
+            validate_attachment_contents,
             _ne: _,
         } = create_info;
 
@@ -381,12 +394,18 @@ impl Device {
             create_info.p_enabled_features = &features_ffi.head_as_ref().features;
         }
 
+        let host_allocation_callbacks = host_allocator.map(HostAllocationCallbacks::new);
+        let allocation_callbacks_vk = match host_allocation_callbacks {
+            Some(ref callbacks) => HostAllocationCallbacks::as_ptr(Some(callbacks)),
+            None => instance.allocation_callbacks(),
+        };
+
         let handle = unsafe {
             let mut output = MaybeUninit::uninit();
             check_errors((fns_i.v1_0.create_device)(
                 physical_device.internal_object(),
                 &create_info,
-                ptr::null(),
+                allocation_callbacks_vk,
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -403,11 +422,13 @@ impl Device {
             physical_device: physical_device.index(),
             api_version,
             fns,
+            host_allocation_callbacks,
             standard_pool: Mutex::new(Weak::new()),
             standard_descriptor_pool: Mutex::new(Weak::new()),
             standard_command_pools: Mutex::new(Default::default()),
             enabled_extensions,
             enabled_features,
+            validate_attachment_contents,
             active_queue_families,
             allocation_count: Mutex::new(0),
             fence_pool: Mutex::new(Vec::new()),
@@ -430,6 +451,7 @@ impl Device {
                         device: device.clone(),
                         family,
                         id,
+                        pending_blocking_submits: Mutex::new(Vec::new()),
                     })
                 })
         };
@@ -495,17 +517,57 @@ impl Device {
     }
 
     /// Returns the extensions that have been enabled on the device.
+    ///
+    /// This is the value of `enabled_extensions` that was passed in [`DeviceCreateInfo`], plus
+    /// `khr_portability_subset` if the physical device supports it, as it must then always be
+    /// enabled.
     #[inline]
     pub fn enabled_extensions(&self) -> &DeviceExtensions {
         &self.enabled_extensions
     }
 
     /// Returns the features that have been enabled on the device.
+    ///
+    /// This is the value of `enabled_features` that was passed in [`DeviceCreateInfo`], plus
+    /// `robust_buffer_access`, which is always enabled.
     #[inline]
     pub fn enabled_features(&self) -> &Features {
         &self.enabled_features
     }
 
+    /// Returns whether the contents of render pass attachments that are loaded are checked when
+    /// beginning a render pass.
+    ///
+    /// This is the value of `validate_attachment_contents` that was passed in
+    /// [`DeviceCreateInfo`].
+    #[inline]
+    pub fn validate_attachment_contents(&self) -> bool {
+        self.validate_attachment_contents
+    }
+
+    /// Returns the host allocator that is used for the device and the objects created from it, if
+    /// any.
+    ///
+    /// If no allocator was specified when creating the device, this is the allocator of the
+    /// instance.
+    #[inline]
+    pub fn host_allocator(&self) -> Option<&Arc<dyn HostAllocator>> {
+        match self.host_allocation_callbacks {
+            Some(ref callbacks) => Some(callbacks.allocator()),
+            None => self.instance.host_allocator(),
+        }
+    }
+
+    /// Returns the allocation callbacks to pass when creating or destroying the device and its
+    /// child objects.
+    #[inline]
+    pub(crate) fn allocation_callbacks(&self) -> *const ash::vk::AllocationCallbacks {
+        match self.host_allocation_callbacks {
+            Some(ref callbacks) => HostAllocationCallbacks::as_ptr(Some(callbacks)),
+            None => self.instance.allocation_callbacks(),
+        }
+    }
+
     /// Returns the standard memory pool used by default if you don't provide any other pool.
     pub fn standard_pool(me: &Arc<Self>) -> Arc<StdMemoryPool> {
         let mut pool = me.standard_pool.lock().unwrap();
@@ -562,6 +624,23 @@ impl Device {
         }
     }
 
+    /// Returns a handle to the current thread's Vulkan pool of the standard command buffer pool,
+    /// which allocates command buffers without looking up the current thread each time.
+    ///
+    /// See [`StandardCommandPool::per_thread_handle`].
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the device and the queue family don't belong to the same physical device.
+    ///
+    #[inline]
+    pub fn command_pool_for_current_thread(
+        me: &Arc<Self>,
+        queue: QueueFamily,
+    ) -> Result<StandardCommandPoolThreadHandle, CommandPoolAllocError> {
+        Device::standard_command_pool(me, queue).per_thread_handle()
+    }
+
     /// Used to track the number of allocations on this device.
     ///
     /// To ensure valid usage of the Vulkan API, we cannot call `vkAllocateMemory` when
@@ -632,6 +711,68 @@ impl Device {
         }
     }
 
+    /// Samples the current time in each of the given time domains, as close together as the
+    /// implementation can manage.
+    ///
+    /// Returns the timestamps in the same order as `time_domains`, together with the maximum
+    /// deviation in nanoseconds between the sampling points of any two timestamps. Timestamps in
+    /// [`TimeDomain::Device`] can be converted to nanoseconds using
+    /// [`PhysicalDevice::timestamp_period`].
+    ///
+    /// The [`ext_calibrated_timestamps`](DeviceExtensions::ext_calibrated_timestamps) extension
+    /// must be enabled on the device, and each of `time_domains` must be supported, as returned
+    /// by [`PhysicalDevice::calibrateable_time_domains`].
+    pub fn calibrated_timestamps(
+        &self,
+        time_domains: &[TimeDomain],
+    ) -> Result<(Vec<u64>, u64), CalibratedTimestampsError> {
+        if !self.enabled_extensions().ext_calibrated_timestamps {
+            return Err(CalibratedTimestampsError::ExtensionNotEnabled {
+                extension: "ext_calibrated_timestamps",
+                reason: "called calibrated_timestamps",
+            });
+        }
+
+        let supported_time_domains = self.physical_device().calibrateable_time_domains()?;
+
+        for &time_domain in time_domains {
+            // VUID-VkCalibratedTimestampInfoEXT-timeDomain-02354
+            if !supported_time_domains.contains(&time_domain) {
+                return Err(CalibratedTimestampsError::TimeDomainNotSupported { time_domain });
+            }
+        }
+
+        if time_domains.is_empty() {
+            return Ok((Vec::new(), 0));
+        }
+
+        let timestamp_infos_vk: SmallVec<[_; 4]> = time_domains
+            .iter()
+            .map(|&time_domain| ash::vk::CalibratedTimestampInfoEXT {
+                time_domain: time_domain.into(),
+                ..Default::default()
+            })
+            .collect();
+
+        let mut timestamps = vec![0; time_domains.len()];
+        let mut max_deviation = 0;
+
+        unsafe {
+            let fns = self.fns();
+            check_errors((fns
+                .ext_calibrated_timestamps
+                .get_calibrated_timestamps_ext)(
+                self.handle,
+                timestamp_infos_vk.len() as u32,
+                timestamp_infos_vk.as_ptr(),
+                timestamps.as_mut_ptr(),
+                &mut max_deviation,
+            ))?;
+        }
+
+        Ok((timestamps, max_deviation))
+    }
+
     /// Assigns a human-readable name to `object` for debugging purposes.
     ///
     /// If `object_name` is `None`, a previously set object name is removed.
@@ -672,15 +813,15 @@ impl Drop for Device {
 
         unsafe {
             for &raw_fence in self.fence_pool.lock().unwrap().iter() {
-                (fns.v1_0.destroy_fence)(self.handle, raw_fence, ptr::null());
+                (fns.v1_0.destroy_fence)(self.handle, raw_fence, self.allocation_callbacks());
             }
             for &raw_sem in self.semaphore_pool.lock().unwrap().iter() {
-                (fns.v1_0.destroy_semaphore)(self.handle, raw_sem, ptr::null());
+                (fns.v1_0.destroy_semaphore)(self.handle, raw_sem, self.allocation_callbacks());
             }
             for &raw_event in self.event_pool.lock().unwrap().iter() {
-                (fns.v1_0.destroy_event)(self.handle, raw_event, ptr::null());
+                (fns.v1_0.destroy_event)(self.handle, raw_event, self.allocation_callbacks());
             }
-            (fns.v1_0.destroy_device)(self.handle, ptr::null());
+            (fns.v1_0.destroy_device)(self.handle, self.allocation_callbacks());
         }
     }
 }
@@ -831,6 +972,25 @@ pub struct DeviceCreateInfo<'qf> {
     /// The default value is empty, which must be overridden.
     pub queue_create_infos: Vec<QueueCreateInfo<'qf>>,
 
+    /// A custom allocator for the host memory used by the Vulkan implementation.
+    ///
+    /// The allocator is used for the device and all objects created from it.
+    ///
+    /// The default value is `None`, which uses the
+    /// [`host_allocator`](crate::instance::InstanceCreateInfo::host_allocator) of the instance.
+    pub host_allocator: Option<Arc<dyn HostAllocator>>,
+
+    /// Whether to check, when beginning a render pass, that the attachments that are loaded with
+    /// [`LoadOp::Load`](crate::render_pass::LoadOp::Load) have defined contents.
+    ///
+    /// Vulkan allows loading an attachment whose contents are undefined, but this is usually a
+    /// bug that results in garbage being rendered. If this is enabled, `begin_render_pass` returns
+    /// an error when an attachment is loaded while its image has not been used by any command
+    /// since it was created.
+    ///
+    /// The default value is `false`.
+    pub validate_attachment_contents: bool,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -841,6 +1001,8 @@ impl Default for DeviceCreateInfo<'static> {
             enabled_extensions: DeviceExtensions::none(),
             enabled_features: Features::none(),
             queue_create_infos: Vec::new(),
+            host_allocator: None,
+            validate_attachment_contents: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -955,14 +1117,79 @@ impl From<Error> for MemoryFdPropertiesError {
     }
 }
 
+/// Error that can happen when calling `calibrated_timestamps`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CalibratedTimestampsError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+
+    /// A requested time domain is not supported by the physical device.
+    TimeDomainNotSupported { time_domain: TimeDomain },
+}
+
+impl error::Error for CalibratedTimestampsError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CalibratedTimestampsError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::OomError(_) => write!(fmt, "not enough memory available"),
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+            Self::TimeDomainNotSupported { time_domain } => write!(
+                fmt,
+                "the time domain {:?} is not supported by the physical device",
+                time_domain,
+            ),
+        }
+    }
+}
+
+impl From<OomError> for CalibratedTimestampsError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<Error> for CalibratedTimestampsError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            err @ Error::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
 /// Represents a queue where commands can be submitted.
 // TODO: should use internal synchronization?
-#[derive(Debug)]
 pub struct Queue {
     handle: Mutex<ash::vk::Queue>,
     device: Arc<Device>,
     family: u32,
     id: u32, // id within family
+
+    // Submissions of `submit_blocking` whose wait timed out. The fence and command buffer are
+    // kept alive until the fence is known to be signaled.
+    pending_blocking_submits: Mutex<Vec<(Fence, Arc<dyn PrimaryCommandBuffer>)>>,
 }
 
 impl Queue {
@@ -1000,15 +1227,676 @@ impl Queue {
         }
     }
 
+    /// Executes a batch of sparse binding operations on this queue, which bind or unbind memory
+    /// to regions of buffers and images that were created with sparse binding enabled.
+    ///
+    /// If `fence` is `Some`, it is signaled once all the operations have completed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the semaphores, resources, memory or fence were not created from the same
+    ///   device as the queue.
+    /// - Panics if the `size` of a buffer or opaque image bind operation is zero.
+    ///
+    /// # Safety
+    ///
+    /// - The wait semaphores must be signaled, or be signaled by a previous submission, and must
+    ///   not be waited upon by any other submission.
+    /// - The signal semaphores and the fence must be unsignaled and must be kept alive until the
+    ///   operations have completed.
+    /// - The buffers and images must not be in use by the device while memory is being
+    ///   (un)bound, and the regions being bound must respect the memory requirements of the
+    ///   resource.
+    /// - The bound memory must be kept alive for as long as it remains bound to a resource that
+    ///   is used by the device.
+    pub unsafe fn bind_sparse(
+        &self,
+        bind_info: BindSparseInfo,
+        fence: Option<&Fence>,
+    ) -> Result<(), BindSparseError> {
+        self.validate_bind_sparse(&bind_info, fence)?;
+
+        let BindSparseInfo {
+            wait_semaphores,
+            buffer_binds,
+            image_opaque_binds,
+            image_binds,
+            signal_semaphores,
+            _ne: _,
+        } = bind_info;
+
+        let memory_bind_vk =
+            |offset: DeviceSize,
+             size: DeviceSize,
+             memory: &Option<(Arc<DeviceMemory>, DeviceSize)>| {
+                let (memory, memory_offset) = memory.as_ref().map_or(
+                    (ash::vk::DeviceMemory::null(), 0),
+                    |(memory, memory_offset)| (memory.internal_object(), *memory_offset),
+                );
+
+                ash::vk::SparseMemoryBind {
+                    resource_offset: offset,
+                    size,
+                    memory,
+                    memory_offset,
+                    flags: ash::vk::SparseMemoryBindFlags::empty(),
+                }
+            };
+
+        let wait_semaphores_vk: SmallVec<[_; 4]> = wait_semaphores
+            .iter()
+            .map(|semaphore| semaphore.internal_object())
+            .collect();
+        let signal_semaphores_vk: SmallVec<[_; 4]> = signal_semaphores
+            .iter()
+            .map(|semaphore| semaphore.internal_object())
+            .collect();
+
+        let buffer_binds_vk: SmallVec<[_; 4]> = buffer_binds
+            .iter()
+            .map(|(buffer, binds)| {
+                (
+                    buffer.internal_object(),
+                    binds
+                        .iter()
+                        .map(|bind| memory_bind_vk(bind.offset, bind.size, &bind.memory))
+                        .collect::<SmallVec<[_; 4]>>(),
+                )
+            })
+            .collect();
+        let buffer_bind_infos_vk: SmallVec<[_; 4]> = buffer_binds_vk
+            .iter()
+            .map(|(buffer, binds)| ash::vk::SparseBufferMemoryBindInfo {
+                buffer: *buffer,
+                bind_count: binds.len() as u32,
+                p_binds: binds.as_ptr(),
+            })
+            .collect();
+
+        let image_opaque_binds_vk: SmallVec<[_; 4]> = image_opaque_binds
+            .iter()
+            .map(|(image, binds)| {
+                (
+                    image.internal_object(),
+                    binds
+                        .iter()
+                        .map(|bind| ash::vk::SparseMemoryBind {
+                            flags: if bind.metadata {
+                                ash::vk::SparseMemoryBindFlags::METADATA
+                            } else {
+                                ash::vk::SparseMemoryBindFlags::empty()
+                            },
+                            ..memory_bind_vk(bind.offset, bind.size, &bind.memory)
+                        })
+                        .collect::<SmallVec<[_; 4]>>(),
+                )
+            })
+            .collect();
+        let image_opaque_bind_infos_vk: SmallVec<[_; 4]> = image_opaque_binds_vk
+            .iter()
+            .map(|(image, binds)| ash::vk::SparseImageOpaqueMemoryBindInfo {
+                image: *image,
+                bind_count: binds.len() as u32,
+                p_binds: binds.as_ptr(),
+            })
+            .collect();
+
+        let image_binds_vk: SmallVec<[_; 4]> = image_binds
+            .iter()
+            .map(|(image, binds)| {
+                (
+                    image.internal_object(),
+                    binds
+                        .iter()
+                        .map(|bind| {
+                            let (memory, memory_offset) = bind.memory.as_ref().map_or(
+                                (ash::vk::DeviceMemory::null(), 0),
+                                |(memory, memory_offset)| {
+                                    (memory.internal_object(), *memory_offset)
+                                },
+                            );
+
+                            ash::vk::SparseImageMemoryBind {
+                                subresource: ash::vk::ImageSubresource {
+                                    aspect_mask: bind.aspects.into(),
+                                    mip_level: bind.mip_level,
+                                    array_layer: bind.array_layer,
+                                },
+                                offset: ash::vk::Offset3D {
+                                    x: bind.offset[0] as i32,
+                                    y: bind.offset[1] as i32,
+                                    z: bind.offset[2] as i32,
+                                },
+                                extent: ash::vk::Extent3D {
+                                    width: bind.extent[0],
+                                    height: bind.extent[1],
+                                    depth: bind.extent[2],
+                                },
+                                memory,
+                                memory_offset,
+                                flags: ash::vk::SparseMemoryBindFlags::empty(),
+                            }
+                        })
+                        .collect::<SmallVec<[_; 4]>>(),
+                )
+            })
+            .collect();
+        let image_bind_infos_vk: SmallVec<[_; 4]> = image_binds_vk
+            .iter()
+            .map(|(image, binds)| ash::vk::SparseImageMemoryBindInfo {
+                image: *image,
+                bind_count: binds.len() as u32,
+                p_binds: binds.as_ptr(),
+            })
+            .collect();
+
+        let bind_info_vk = ash::vk::BindSparseInfo {
+            wait_semaphore_count: wait_semaphores_vk.len() as u32,
+            p_wait_semaphores: wait_semaphores_vk.as_ptr(),
+            buffer_bind_count: buffer_bind_infos_vk.len() as u32,
+            p_buffer_binds: buffer_bind_infos_vk.as_ptr(),
+            image_opaque_bind_count: image_opaque_bind_infos_vk.len() as u32,
+            p_image_opaque_binds: image_opaque_bind_infos_vk.as_ptr(),
+            image_bind_count: image_bind_infos_vk.len() as u32,
+            p_image_binds: image_bind_infos_vk.as_ptr(),
+            signal_semaphore_count: signal_semaphores_vk.len() as u32,
+            p_signal_semaphores: signal_semaphores_vk.as_ptr(),
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+        let handle = self.handle.lock().unwrap();
+        check_errors((fns.v1_0.queue_bind_sparse)(
+            *handle,
+            1,
+            &bind_info_vk,
+            fence.map_or(ash::vk::Fence::null(), |fence| fence.internal_object()),
+        ))?;
+
+        Ok(())
+    }
+
+    fn validate_bind_sparse(
+        &self,
+        bind_info: &BindSparseInfo,
+        fence: Option<&Fence>,
+    ) -> Result<(), BindSparseError> {
+        let &BindSparseInfo {
+            ref wait_semaphores,
+            ref buffer_binds,
+            ref image_opaque_binds,
+            ref image_binds,
+            ref signal_semaphores,
+            _ne: _,
+        } = bind_info;
+
+        // VUID-vkQueueBindSparse-queuetype
+        if !self.family().supports_sparse_binding() {
+            return Err(BindSparseError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkQueueBindSparse-commonparent
+        if let Some(fence) = fence {
+            assert_eq!(self.device(), fence.device());
+        }
+
+        // VUID-VkBindSparseInfo-commonparent
+        for semaphore in wait_semaphores.iter().chain(signal_semaphores.iter()) {
+            assert_eq!(self.device(), semaphore.device());
+        }
+
+        let check_memory_bind = |offset: DeviceSize,
+                                 size: DeviceSize,
+                                 resource_size: DeviceSize,
+                                 memory: &Option<(Arc<DeviceMemory>, DeviceSize)>|
+         -> Result<(), BindSparseError> {
+            // VUID-VkSparseMemoryBind-size-01098
+            assert!(size != 0);
+
+            // VUID-VkSparseMemoryBind-resourceOffset-01099
+            // VUID-VkSparseMemoryBind-size-01100
+            if offset >= resource_size || size > resource_size - offset {
+                return Err(BindSparseError::RegionOutOfRange {
+                    offset,
+                    size,
+                    resource_size,
+                });
+            }
+
+            if let Some((memory, memory_offset)) = memory {
+                assert_eq!(self.device(), memory.device());
+
+                let allocation_size = memory.allocation_size();
+
+                // VUID-VkSparseMemoryBind-memoryOffset-01101
+                // VUID-VkSparseMemoryBind-size-01102
+                if *memory_offset >= allocation_size || size > allocation_size - memory_offset {
+                    return Err(BindSparseError::MemoryOutOfRange {
+                        memory_offset: *memory_offset,
+                        size,
+                        allocation_size,
+                    });
+                }
+            }
+
+            Ok(())
+        };
+
+        for (buffer, binds) in buffer_binds {
+            assert_eq!(self.device(), buffer.device());
+
+            if buffer.sparse().is_none() {
+                return Err(BindSparseError::ResourceNotSparse);
+            }
+
+            let resource_size = buffer.memory_requirements().size;
+
+            for bind in binds {
+                check_memory_bind(bind.offset, bind.size, resource_size, &bind.memory)?;
+            }
+        }
+
+        for (image, binds) in image_opaque_binds {
+            assert_eq!(self.device(), image.device());
+
+            if image.sparse().is_none() {
+                return Err(BindSparseError::ResourceNotSparse);
+            }
+
+            let resource_size = image.memory_requirements().size;
+            let has_metadata = image
+                .sparse_memory_requirements()
+                .iter()
+                .any(|requirements| requirements.format_properties.aspects.metadata);
+
+            for bind in binds {
+                if bind.metadata && !has_metadata {
+                    return Err(BindSparseError::MetadataNotSupported);
+                }
+
+                check_memory_bind(bind.offset, bind.size, resource_size, &bind.memory)?;
+            }
+        }
+
+        for (image, binds) in image_binds {
+            assert_eq!(self.device(), image.device());
+
+            // VUID-VkSparseImageMemoryBindInfo-image-02901
+            match image.sparse() {
+                None => return Err(BindSparseError::ResourceNotSparse),
+                Some(sparse) if !sparse.sparse_residency => {
+                    return Err(BindSparseError::ImageNotSparseResidency)
+                }
+                _ => (),
+            }
+
+            let format_aspects = image.format().unwrap().aspects();
+
+            for bind in binds {
+                // VUID-VkSparseImageMemoryBindInfo-subresource-01106
+                let mut aspects = bind.aspects.iter();
+                match (aspects.next(), aspects.next()) {
+                    (Some(aspect), None) if format_aspects.iter().any(|a| a == aspect) => (),
+                    _ => return Err(BindSparseError::AspectsNotAllowed),
+                }
+
+                // VUID-VkSparseImageMemoryBindInfo-subresource-01722
+                // VUID-VkSparseImageMemoryBindInfo-subresource-01723
+                if bind.mip_level >= image.mip_levels()
+                    || bind.array_layer >= image.dimensions().array_layers()
+                {
+                    return Err(BindSparseError::SubresourceOutOfRange {
+                        mip_level: bind.mip_level,
+                        array_layer: bind.array_layer,
+                    });
+                }
+
+                let image_extent = image
+                    .dimensions()
+                    .mip_level_dimensions(bind.mip_level)
+                    .unwrap()
+                    .width_height_depth();
+
+                if (0..3).any(|i| {
+                    bind.extent[i] == 0
+                        || bind.offset[i] >= image_extent[i]
+                        || bind.extent[i] > image_extent[i] - bind.offset[i]
+                }) {
+                    return Err(BindSparseError::ImageRegionOutOfRange {
+                        offset: bind.offset,
+                        extent: bind.extent,
+                        image_extent,
+                    });
+                }
+
+                if let Some((memory, _)) = &bind.memory {
+                    assert_eq!(self.device(), memory.device());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Executes multiple batches of command buffers on this queue with a single
+    /// `vkQueueSubmit` call, which is cheaper than submitting each batch separately.
+    ///
+    /// Each batch waits on its wait semaphores, executes its command buffers in order, and then
+    /// signals its signal semaphores. If the
+    /// [`synchronization2`](crate::device::Features::synchronization2) feature is enabled on the
+    /// device, `vkQueueSubmit2` is used instead. If `fence` is `Some`, it is signaled once all
+    /// the batches have completed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the semaphores, command buffers or fence were not created from the same device
+    ///   as the queue.
+    ///
+    /// # Safety
+    ///
+    /// This function bypasses the resource tracking of the command buffers, so:
+    ///
+    /// - The command buffers must not be in use by the device, unless they were created for
+    ///   simultaneous use, and the resources they use must be synchronized manually.
+    /// - The wait semaphores must be signaled, or be signaled by a previous submission, and must
+    ///   not be waited upon by any other submission.
+    /// - The signal semaphores and the fence must be unsignaled and must be kept alive until the
+    ///   batches have completed. The command buffers must also be kept alive until then.
+    pub unsafe fn submit_batch(
+        &self,
+        submits: &[SubmitInfo],
+        fence: Option<&Fence>,
+    ) -> Result<(), SubmitError> {
+        self.validate_submit_batch(submits, fence)?;
+
+        let fence_vk = fence.map_or(ash::vk::Fence::null(), |fence| fence.internal_object());
+        let fns = self.device.fns();
+
+        if self.device.enabled_features().synchronization2 {
+            let submits_vk: SmallVec<[_; 4]> = submits
+                .iter()
+                .map(|submit_info| {
+                    let wait_semaphore_infos_vk: SmallVec<[_; 4]> = submit_info
+                        .wait_semaphores
+                        .iter()
+                        .map(|(semaphore, stages)| ash::vk::SemaphoreSubmitInfo {
+                            semaphore: semaphore.internal_object(),
+                            stage_mask: (*stages).into(),
+                            ..Default::default()
+                        })
+                        .collect();
+                    let command_buffer_infos_vk: SmallVec<[_; 4]> = submit_info
+                        .command_buffers
+                        .iter()
+                        .map(|command_buffer| ash::vk::CommandBufferSubmitInfo {
+                            command_buffer: command_buffer.inner().internal_object(),
+                            ..Default::default()
+                        })
+                        .collect();
+                    let signal_semaphore_infos_vk: SmallVec<[_; 4]> = submit_info
+                        .signal_semaphores
+                        .iter()
+                        .map(|semaphore| ash::vk::SemaphoreSubmitInfo {
+                            semaphore: semaphore.internal_object(),
+                            stage_mask: ash::vk::PipelineStageFlags2::ALL_COMMANDS,
+                            ..Default::default()
+                        })
+                        .collect();
+
+                    (
+                        wait_semaphore_infos_vk,
+                        command_buffer_infos_vk,
+                        signal_semaphore_infos_vk,
+                    )
+                })
+                .collect();
+            let submit_infos_vk: SmallVec<[_; 4]> = submits_vk
+                .iter()
+                .map(
+                    |(
+                        wait_semaphore_infos_vk,
+                        command_buffer_infos_vk,
+                        signal_semaphore_infos_vk,
+                    )| {
+                        ash::vk::SubmitInfo2 {
+                            wait_semaphore_info_count: wait_semaphore_infos_vk.len() as u32,
+                            p_wait_semaphore_infos: wait_semaphore_infos_vk.as_ptr(),
+                            command_buffer_info_count: command_buffer_infos_vk.len() as u32,
+                            p_command_buffer_infos: command_buffer_infos_vk.as_ptr(),
+                            signal_semaphore_info_count: signal_semaphore_infos_vk.len() as u32,
+                            p_signal_semaphore_infos: signal_semaphore_infos_vk.as_ptr(),
+                            ..Default::default()
+                        }
+                    },
+                )
+                .collect();
+
+            let handle = self.handle.lock().unwrap();
+
+            if self.device.api_version() >= Version::V1_3 {
+                check_errors((fns.v1_3.queue_submit2)(
+                    *handle,
+                    submit_infos_vk.len() as u32,
+                    submit_infos_vk.as_ptr(),
+                    fence_vk,
+                ))?;
+            } else {
+                check_errors((fns.khr_synchronization2.queue_submit2_khr)(
+                    *handle,
+                    submit_infos_vk.len() as u32,
+                    submit_infos_vk.as_ptr(),
+                    fence_vk,
+                ))?;
+            }
+        } else {
+            let submits_vk: SmallVec<[_; 4]> = submits
+                .iter()
+                .map(|submit_info| {
+                    let (wait_semaphores_vk, wait_dst_stage_mask_vk): (
+                        SmallVec<[_; 4]>,
+                        SmallVec<[_; 4]>,
+                    ) = submit_info
+                        .wait_semaphores
+                        .iter()
+                        .map(|(semaphore, stages)| {
+                            (
+                                semaphore.internal_object(),
+                                ash::vk::PipelineStageFlags::from(*stages),
+                            )
+                        })
+                        .unzip();
+                    let command_buffers_vk: SmallVec<[_; 4]> = submit_info
+                        .command_buffers
+                        .iter()
+                        .map(|command_buffer| command_buffer.inner().internal_object())
+                        .collect();
+                    let signal_semaphores_vk: SmallVec<[_; 4]> = submit_info
+                        .signal_semaphores
+                        .iter()
+                        .map(|semaphore| semaphore.internal_object())
+                        .collect();
+
+                    (
+                        wait_semaphores_vk,
+                        wait_dst_stage_mask_vk,
+                        command_buffers_vk,
+                        signal_semaphores_vk,
+                    )
+                })
+                .collect();
+            let submit_infos_vk: SmallVec<[_; 4]> = submits_vk
+                .iter()
+                .map(
+                    |(
+                        wait_semaphores_vk,
+                        wait_dst_stage_mask_vk,
+                        command_buffers_vk,
+                        signal_semaphores_vk,
+                    )| ash::vk::SubmitInfo {
+                        wait_semaphore_count: wait_semaphores_vk.len() as u32,
+                        p_wait_semaphores: wait_semaphores_vk.as_ptr(),
+                        p_wait_dst_stage_mask: wait_dst_stage_mask_vk.as_ptr(),
+                        command_buffer_count: command_buffers_vk.len() as u32,
+                        p_command_buffers: command_buffers_vk.as_ptr(),
+                        signal_semaphore_count: signal_semaphores_vk.len() as u32,
+                        p_signal_semaphores: signal_semaphores_vk.as_ptr(),
+                        ..Default::default()
+                    },
+                )
+                .collect();
+
+            let handle = self.handle.lock().unwrap();
+            check_errors((fns.v1_0.queue_submit)(
+                *handle,
+                submit_infos_vk.len() as u32,
+                submit_infos_vk.as_ptr(),
+                fence_vk,
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_submit_batch(
+        &self,
+        submits: &[SubmitInfo],
+        fence: Option<&Fence>,
+    ) -> Result<(), SubmitError> {
+        // VUID-vkQueueSubmit-commonparent
+        if let Some(fence) = fence {
+            assert_eq!(self.device(), fence.device());
+        }
+
+        for (batch_index, submit_info) in submits.iter().enumerate() {
+            let &SubmitInfo {
+                ref wait_semaphores,
+                ref command_buffers,
+                ref signal_semaphores,
+                _ne: _,
+            } = submit_info;
+
+            for (semaphore_index, (semaphore, stages)) in wait_semaphores.iter().enumerate() {
+                // VUID-VkSubmitInfo-commonparent
+                assert_eq!(self.device(), semaphore.device());
+
+                // VUID-VkSubmitInfo-pWaitDstStageMask-requiredbitmask
+                if ash::vk::PipelineStageFlags::from(*stages).is_empty() {
+                    return Err(SubmitError::WaitStagesEmpty {
+                        batch_index,
+                        semaphore_index,
+                    });
+                }
+            }
+
+            for (command_buffer_index, command_buffer) in command_buffers.iter().enumerate() {
+                // VUID-VkSubmitInfo-commonparent
+                assert_eq!(self.device(), command_buffer.device());
+
+                // VUID-vkQueueSubmit-pCommandBuffers-00074
+                if command_buffer.inner().queue_family() != self.family() {
+                    return Err(SubmitError::CommandBufferQueueFamilyMismatch {
+                        batch_index,
+                        command_buffer_index,
+                    });
+                }
+            }
+
+            for semaphore in signal_semaphores {
+                // VUID-VkSubmitInfo-commonparent
+                assert_eq!(self.device(), semaphore.device());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Submits a command buffer to this queue, and blocks until it has finished executing or
+    /// until `timeout` has elapsed.
+    ///
+    /// The fence used to wait is taken from the device's fence pool, so that repeated calls don't
+    /// create new fences. If the timeout is reached, [`SubmitError::Timeout`] is returned and the
+    /// fence and the command buffer are kept alive by the queue until the execution has finished.
+    /// They are then recycled by a later call to this function, or when the queue is dropped.
+    ///
+    /// This is meant for one-off operations during setup and teardown, such as uploading initial
+    /// data. It should not be used every frame, as it stalls the CPU until the device has caught
+    /// up.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `command_buffer` was not created from the same device as the queue.
+    ///
+    /// # Safety
+    ///
+    /// This function bypasses the resource tracking of the command buffer, so the same
+    /// requirements as for [`submit_batch`](Self::submit_batch) apply.
+    pub unsafe fn submit_blocking(
+        &self,
+        command_buffer: Arc<dyn PrimaryCommandBuffer>,
+        timeout: Option<Duration>,
+    ) -> Result<(), SubmitError> {
+        self.cleanup_blocking_submits()
+            .map_err(SubmitError::OomError)?;
+
+        let fence = Fence::from_pool(self.device.clone()).map_err(SubmitError::OomError)?;
+        self.submit_batch(
+            &[SubmitInfo {
+                command_buffers: vec![command_buffer.clone()],
+                ..Default::default()
+            }],
+            Some(&fence),
+        )?;
+
+        match fence.wait(timeout) {
+            Ok(()) => Ok(()),
+            Err(FenceWaitError::Timeout) => {
+                self.pending_blocking_submits
+                    .lock()
+                    .unwrap()
+                    .push((fence, command_buffer));
+                Err(SubmitError::Timeout)
+            }
+            Err(FenceWaitError::OomError(err)) => Err(SubmitError::OomError(err)),
+            Err(FenceWaitError::DeviceLostError) => Err(SubmitError::DeviceLost),
+        }
+    }
+
+    // Releases the submissions of `submit_blocking` that have finished executing, which puts
+    // their fences back into the pool.
+    fn cleanup_blocking_submits(&self) -> Result<(), OomError> {
+        let mut pending = self.pending_blocking_submits.lock().unwrap();
+        let mut index = 0;
+
+        while index < pending.len() {
+            if pending[index].0.is_signaled()? {
+                pending.swap_remove(index);
+            } else {
+                index += 1;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Opens a queue debug label region.
     ///
-    /// The [`ext_debug_utils`](crate::instance::InstanceExtensions::ext_debug_utils) must be
-    /// enabled on the instance.
+    /// This does nothing if the
+    /// [`ext_debug_utils`](crate::instance::InstanceExtensions::ext_debug_utils) extension is not
+    /// enabled on the instance, so that labels can be left in place in release builds.
     #[inline]
     pub fn begin_debug_utils_label(
         &self,
         mut label_info: DebugUtilsLabel,
     ) -> Result<(), DebugUtilsError> {
+        if !self
+            .device()
+            .instance()
+            .enabled_extensions()
+            .ext_debug_utils
+        {
+            return Ok(());
+        }
+
         self.validate_begin_debug_utils_label(&mut label_info)?;
 
         let DebugUtilsLabel {
@@ -1037,25 +1925,14 @@ impl Queue {
         &self,
         label_info: &mut DebugUtilsLabel,
     ) -> Result<(), DebugUtilsError> {
-        if !self
-            .device()
-            .instance()
-            .enabled_extensions()
-            .ext_debug_utils
-        {
-            return Err(DebugUtilsError::ExtensionNotEnabled {
-                extension: "ext_debug_utils",
-                reason: "tried to submit a debug utils command",
-            });
-        }
-
         Ok(())
     }
 
     /// Closes a queue debug label region.
     ///
-    /// The [`ext_debug_utils`](crate::instance::InstanceExtensions::ext_debug_utils) must be
-    /// enabled on the instance.
+    /// This does nothing if the
+    /// [`ext_debug_utils`](crate::instance::InstanceExtensions::ext_debug_utils) extension is not
+    /// enabled on the instance, so that labels can be left in place in release builds.
     ///
     /// # Safety
     ///
@@ -1063,6 +1940,15 @@ impl Queue {
     ///   the queue.
     #[inline]
     pub unsafe fn end_debug_utils_label(&self) -> Result<(), DebugUtilsError> {
+        if !self
+            .device()
+            .instance()
+            .enabled_extensions()
+            .ext_debug_utils
+        {
+            return Ok(());
+        }
+
         self.validate_end_debug_utils_label()?;
 
         {
@@ -1075,18 +1961,6 @@ impl Queue {
     }
 
     fn validate_end_debug_utils_label(&self) -> Result<(), DebugUtilsError> {
-        if !self
-            .device()
-            .instance()
-            .enabled_extensions()
-            .ext_debug_utils
-        {
-            return Err(DebugUtilsError::ExtensionNotEnabled {
-                extension: "ext_debug_utils",
-                reason: "tried to submit a debug utils command",
-            });
-        }
-
         // VUID-vkQueueEndDebugUtilsLabelEXT-None-01911
         // TODO: not checked, so unsafe for now
 
@@ -1095,13 +1969,23 @@ impl Queue {
 
     /// Inserts a queue debug label.
     ///
-    /// The [`ext_debug_utils`](crate::instance::InstanceExtensions::ext_debug_utils) must be
-    /// enabled on the instance.
+    /// This does nothing if the
+    /// [`ext_debug_utils`](crate::instance::InstanceExtensions::ext_debug_utils) extension is not
+    /// enabled on the instance, so that labels can be left in place in release builds.
     #[inline]
     pub fn insert_debug_utils_label(
         &mut self,
         mut label_info: DebugUtilsLabel,
     ) -> Result<(), DebugUtilsError> {
+        if !self
+            .device()
+            .instance()
+            .enabled_extensions()
+            .ext_debug_utils
+        {
+            return Ok(());
+        }
+
         self.validate_insert_debug_utils_label(&mut label_info)?;
 
         let DebugUtilsLabel {
@@ -1130,19 +2014,29 @@ impl Queue {
         &self,
         label_info: &mut DebugUtilsLabel,
     ) -> Result<(), DebugUtilsError> {
-        if !self
-            .device()
-            .instance()
-            .enabled_extensions()
-            .ext_debug_utils
-        {
-            return Err(DebugUtilsError::ExtensionNotEnabled {
-                extension: "ext_debug_utils",
-                reason: "tried to submit a debug utils command",
-            });
+        Ok(())
+    }
+}
+
+impl Drop for Queue {
+    #[inline]
+    fn drop(&mut self) {
+        // The fences can only be put back into the pool once they are no longer in use.
+        for (fence, _) in self.pending_blocking_submits.get_mut().unwrap().drain(..) {
+            let _ = fence.wait(None);
         }
+    }
+}
 
-        Ok(())
+impl fmt::Debug for Queue {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("Queue")
+            .field("handle", &self.handle)
+            .field("device", &self.device)
+            .field("family", &self.family)
+            .field("id", &self.id)
+            .finish()
     }
 }
 
@@ -1202,10 +2096,24 @@ impl fmt::Display for DebugUtilsError {
 
 #[cfg(test)]
 mod tests {
-    use crate::device::physical::PhysicalDevice;
-    use crate::device::{Device, DeviceCreateInfo, DeviceCreationError, QueueCreateInfo};
+    use crate::buffer::sys::{UnsafeBuffer, UnsafeBufferCreateInfo};
+    use crate::buffer::BufferUsage;
+    use crate::command_buffer::submit::{
+        BindSparseError, BindSparseInfo, SparseBufferMemoryBind, SubmitInfo,
+    };
+    use crate::command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage};
+    use crate::device::physical::{MemoryBudgetError, PhysicalDevice, TimeDomain};
+    use crate::device::{
+        CalibratedTimestampsError, Device, DeviceCreateInfo, DeviceCreationError, QueueCreateInfo,
+    };
     use crate::device::{FeatureRestriction, FeatureRestrictionError, Features};
+    use crate::format::{Format, FormatFeatures};
+    use crate::image::ImageTiling;
+    use crate::instance::debug::DebugUtilsLabel;
+    use crate::sync::{Fence, PipelineStages, Semaphore};
+    use crate::Version;
     use std::sync::Arc;
+    use std::time::Duration;
 
     #[test]
     fn one_ref() {
@@ -1213,6 +2121,63 @@ mod tests {
         assert!(Arc::get_mut(&mut device).is_some());
     }
 
+    #[test]
+    fn subgroup_properties() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let subgroup_properties = physical.subgroup_properties();
+
+        if physical.api_version() >= Version::V1_1 {
+            assert!(subgroup_properties.subgroup_size.unwrap() >= 1);
+            assert!(subgroup_properties.supported_operations.unwrap().basic);
+        } else {
+            assert!(subgroup_properties.subgroup_size.is_none());
+            assert!(subgroup_properties.supported_operations.is_none());
+        }
+    }
+
+    #[test]
+    fn memory_budget() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        match physical.memory_budget() {
+            Ok(budgets) => assert_eq!(budgets.len(), physical.memory_heaps().len()),
+            Err(MemoryBudgetError::ExtensionNotSupported { .. }) => {
+                assert!(!physical.supported_extensions().ext_memory_budget)
+            }
+            Err(MemoryBudgetError::InstanceExtensionNotEnabled { .. }) => (),
+        }
+    }
+
+    #[test]
+    fn supports_image_format_features() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let format_properties = physical.format_properties(Format::R8G8B8A8_UNORM);
+        assert!(physical.supports_image_format_features(
+            Format::R8G8B8A8_UNORM,
+            ImageTiling::Optimal,
+            &format_properties.optimal_tiling_features,
+        ));
+        assert!(physical.supports_image_format_features(
+            Format::R8G8B8A8_UNORM,
+            ImageTiling::Linear,
+            &FormatFeatures::default(),
+        ));
+    }
+
     #[test]
     fn too_many_queues() {
         let instance = instance!();
@@ -1307,4 +2272,186 @@ mod tests {
             )
         });
     }
+
+    #[test]
+    fn bind_sparse_resource_not_sparse() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer = UnsafeBuffer::new(
+            device.clone(),
+            UnsafeBufferCreateInfo {
+                size: 128,
+                usage: BufferUsage::all(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let res = unsafe {
+            queue.bind_sparse(
+                BindSparseInfo {
+                    buffer_binds: vec![(
+                        buffer,
+                        vec![SparseBufferMemoryBind {
+                            size: 128,
+                            ..Default::default()
+                        }],
+                    )],
+                    ..Default::default()
+                },
+                None,
+            )
+        };
+
+        match res {
+            Err(BindSparseError::ResourceNotSparse) => (),
+            Err(BindSparseError::NotSupportedByQueueFamily) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn submit_batch() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let command_buffer = Arc::new(
+            AutoCommandBufferBuilder::primary(
+                device.clone(),
+                queue.family(),
+                CommandBufferUsage::SimultaneousUse,
+            )
+            .unwrap()
+            .build()
+            .unwrap(),
+        );
+        let semaphore = Arc::new(Semaphore::from_pool(device.clone()).unwrap());
+        let fence = Fence::new(device.clone(), Default::default()).unwrap();
+
+        unsafe {
+            queue
+                .submit_batch(
+                    &[
+                        SubmitInfo {
+                            command_buffers: vec![command_buffer.clone()],
+                            signal_semaphores: vec![semaphore.clone()],
+                            ..Default::default()
+                        },
+                        SubmitInfo {
+                            wait_semaphores: vec![(
+                                semaphore,
+                                PipelineStages {
+                                    all_commands: true,
+                                    ..PipelineStages::none()
+                                },
+                            )],
+                            command_buffers: vec![command_buffer.clone(), command_buffer],
+                            ..Default::default()
+                        },
+                    ],
+                    Some(&fence),
+                )
+                .unwrap();
+        }
+
+        fence.wait(Some(Duration::from_secs(5))).unwrap();
+    }
+
+    #[test]
+    fn submit_blocking() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let command_buffer = Arc::new(
+            AutoCommandBufferBuilder::primary(
+                device.clone(),
+                queue.family(),
+                CommandBufferUsage::SimultaneousUse,
+            )
+            .unwrap()
+            .build()
+            .unwrap(),
+        );
+
+        unsafe {
+            queue
+                .submit_blocking(command_buffer.clone(), Some(Duration::from_secs(5)))
+                .unwrap();
+            queue.submit_blocking(command_buffer, None).unwrap();
+        }
+    }
+
+    #[test]
+    fn debug_utils_label_without_extension() {
+        let (_, queue) = gfx_dev_and_queue!();
+
+        // The test instance does not enable `ext_debug_utils`, so these must be no-ops.
+        queue
+            .begin_debug_utils_label(DebugUtilsLabel {
+                label_name: "label".to_owned(),
+                ..Default::default()
+            })
+            .unwrap();
+        unsafe {
+            queue.end_debug_utils_label().unwrap();
+        }
+    }
+
+    #[test]
+    fn calibrated_timestamps_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match device.calibrated_timestamps(&[TimeDomain::Device]) {
+            Err(CalibratedTimestampsError::ExtensionNotEnabled {
+                extension: "ext_calibrated_timestamps",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn enabled_features() {
+        let (device, _) = gfx_dev_and_queue!(robust_buffer_access);
+
+        let enabled_features = device.enabled_features();
+        assert!(enabled_features.robust_buffer_access);
+        assert_eq!(*device.enabled_extensions(), {
+            let mut extensions = crate::device::DeviceExtensions::none();
+            extensions.khr_portability_subset = device
+                .physical_device()
+                .supported_extensions()
+                .khr_portability_subset;
+            extensions
+        });
+    }
+
+    #[test]
+    fn vulkan12_features() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance)
+            .find(|p| p.api_version() >= Version::V1_2 && p.supported_features().timeline_semaphore)
+        {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = physical.queue_families().next().unwrap();
+
+        let (device, _) = Device::new(
+            physical,
+            DeviceCreateInfo {
+                enabled_features: Features {
+                    timeline_semaphore: true,
+                    ..Features::none()
+                },
+                queue_create_infos: vec![QueueCreateInfo::family(family)],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // Features from the Vulkan 1.2 core features struct are requested through the same
+        // chain as they are queried.
+        assert!(device.enabled_features().timeline_semaphore);
+        assert!(!device.enabled_features().buffer_device_address);
+    }
 }
@@ -11,9 +11,10 @@ use crate::{
     buffer::{BufferUsage, ExternalBufferInfo, ExternalBufferProperties},
     check_errors,
     device::{DeviceExtensions, Features, FeaturesFfi, Properties, PropertiesFfi},
-    format::{Format, FormatProperties},
-    image::{ImageCreateFlags, ImageFormatInfo, ImageFormatProperties, ImageUsage},
+    format::{Format, FormatFeatures, FormatProperties},
+    image::{ImageCreateFlags, ImageFormatInfo, ImageFormatProperties, ImageTiling, ImageUsage},
     instance::{Instance, InstanceCreationError},
+    shader::ShaderStages,
     swapchain::{
         ColorSpace, FullScreenExclusive, PresentMode, SupportedSurfaceTransforms, Surface,
         SurfaceApi, SurfaceCapabilities, SurfaceInfo,
@@ -408,6 +409,36 @@ impl<'a> PhysicalDevice<'a> {
         &self.info.properties
     }
 
+    /// Returns the number of nanoseconds it takes for the value of a timestamp query to be
+    /// incremented by 1.
+    #[inline]
+    pub fn timestamp_period(&self) -> f32 {
+        self.properties().timestamp_period
+    }
+
+    /// Returns the subgroup properties reported by the device.
+    ///
+    /// The properties are only known if the device supports Vulkan 1.1. Otherwise, all fields
+    /// are `None`.
+    #[inline]
+    pub fn subgroup_properties(&self) -> SubgroupProperties {
+        let properties = self.properties();
+
+        SubgroupProperties {
+            subgroup_size: properties.subgroup_size,
+            supported_stages: properties
+                .supported_stages
+                .or(properties.subgroup_supported_stages),
+            supported_operations: properties
+                .supported_operations
+                .or(properties.subgroup_supported_operations),
+            quad_operations_in_all_stages: properties
+                .quad_operations_in_all_stages
+                .or(properties.subgroup_quad_operations_in_all_stages),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
     /// Returns the features that are supported by this physical device.
     #[inline]
     pub fn supported_features(&self) -> &'a Features {
@@ -549,6 +580,26 @@ impl<'a> PhysicalDevice<'a> {
         }
     }
 
+    /// Returns whether images with the given `format` and `tiling` support all of the format
+    /// features in `features` on this physical device.
+    ///
+    /// This is a convenience wrapper around [`format_properties`](Self::format_properties), which
+    /// can be used to pick a fallback format before trying to create an image.
+    #[inline]
+    pub fn supports_image_format_features(
+        &self,
+        format: Format,
+        tiling: ImageTiling,
+        features: &FormatFeatures,
+    ) -> bool {
+        let format_properties = self.format_properties(format);
+
+        match tiling {
+            ImageTiling::Linear => format_properties.linear_tiling_features.contains(features),
+            ImageTiling::Optimal => format_properties.optimal_tiling_features.contains(features),
+        }
+    }
+
     /// Retrieves the external handle properties supported for semaphores with a given
     /// configuration.
     ///
@@ -848,6 +899,68 @@ impl<'a> PhysicalDevice<'a> {
         }
     }
 
+    /// Queries the current memory budget and usage of each memory heap of the physical device.
+    ///
+    /// The values can change at any time, for example when other processes allocate memory, so
+    /// this function performs a new query every time it is called. One element is returned per
+    /// memory heap, in the same order as [`memory_heaps`](PhysicalDevice::memory_heaps).
+    ///
+    /// The physical device must support the
+    /// [`ext_memory_budget`](crate::device::DeviceExtensions::ext_memory_budget) extension, and
+    /// the instance API version must be at least 1.1 or the
+    /// [`khr_get_physical_device_properties2`](crate::instance::InstanceExtensions::khr_get_physical_device_properties2)
+    /// extension must be enabled on the instance.
+    pub fn memory_budget(&self) -> Result<Vec<MemoryHeapBudget>, MemoryBudgetError> {
+        if !self.supported_extensions().ext_memory_budget {
+            return Err(MemoryBudgetError::ExtensionNotSupported {
+                extension: "ext_memory_budget",
+            });
+        }
+
+        if !(self.instance.api_version() >= Version::V1_1
+            || self
+                .instance
+                .enabled_extensions()
+                .khr_get_physical_device_properties2)
+        {
+            return Err(MemoryBudgetError::InstanceExtensionNotEnabled {
+                extension: "khr_get_physical_device_properties2",
+            });
+        }
+
+        let mut memory_budget_properties =
+            ash::vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
+        let mut memory_properties2 = ash::vk::PhysicalDeviceMemoryProperties2 {
+            p_next: &mut memory_budget_properties as *mut _ as *mut _,
+            ..Default::default()
+        };
+
+        unsafe {
+            let fns = self.instance.fns();
+
+            if self.instance.api_version() >= Version::V1_1 {
+                (fns.v1_1.get_physical_device_memory_properties2)(
+                    self.info.handle,
+                    &mut memory_properties2,
+                );
+            } else {
+                (fns.khr_get_physical_device_properties2
+                    .get_physical_device_memory_properties2_khr)(
+                    self.info.handle,
+                    &mut memory_properties2,
+                );
+            }
+        }
+
+        let heap_count = memory_properties2.memory_properties.memory_heap_count as usize;
+
+        Ok(memory_budget_properties.heap_budget[..heap_count]
+            .iter()
+            .zip(&memory_budget_properties.heap_usage[..heap_count])
+            .map(|(&budget, &usage)| MemoryHeapBudget { budget, usage })
+            .collect())
+    }
+
     /// Builds an iterator that enumerates all the queue families on this physical device.
     #[inline]
     pub fn queue_families(&self) -> impl ExactSizeIterator<Item = QueueFamily<'a>> {
@@ -877,6 +990,20 @@ impl<'a> PhysicalDevice<'a> {
         }
     }
 
+    /// Returns whether any queue family of the physical device can present to the given surface.
+    fn is_surface_supported<W>(
+        &self,
+        surface: &Surface<W>,
+    ) -> Result<bool, SurfacePropertiesError> {
+        for queue_family in self.queue_families() {
+            if queue_family.supports_surface(surface)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
     /// Returns the capabilities that are supported by the physical device for the given surface.
     ///
     /// # Panic
@@ -892,6 +1019,12 @@ impl<'a> PhysicalDevice<'a> {
             surface.instance().internal_object(),
         );
 
+        // VUID-vkGetPhysicalDeviceSurfaceCapabilitiesKHR-surface-06211
+        // VUID-vkGetPhysicalDeviceSurfaceCapabilities2KHR-pSurfaceInfo-06210
+        if !self.is_surface_supported(surface)? {
+            return Err(SurfacePropertiesError::SurfaceNotSupported);
+        }
+
         /* Input */
 
         let SurfaceInfo {
@@ -1098,6 +1231,12 @@ impl<'a> PhysicalDevice<'a> {
             surface.instance().internal_object(),
         );
 
+        // VUID-vkGetPhysicalDeviceSurfaceFormatsKHR-surface-06525
+        // VUID-vkGetPhysicalDeviceSurfaceFormats2KHR-pSurfaceInfo-06521
+        if !self.is_surface_supported(surface)? {
+            return Err(SurfacePropertiesError::SurfaceNotSupported);
+        }
+
         if self
             .instance
             .enabled_extensions()
@@ -1258,6 +1397,11 @@ impl<'a> PhysicalDevice<'a> {
             surface.instance().internal_object(),
         );
 
+        // VUID-vkGetPhysicalDeviceSurfacePresentModesKHR-surface-06525
+        if !self.is_surface_supported(surface)? {
+            return Err(SurfacePropertiesError::SurfaceNotSupported);
+        }
+
         let fns = self.instance.fns();
 
         let modes = unsafe {
@@ -1299,6 +1443,51 @@ impl<'a> PhysicalDevice<'a> {
             .into_iter()
             .filter_map(|mode_vk| mode_vk.try_into().ok()))
     }
+
+    /// Returns the time domains that can be used with
+    /// [`Device::calibrated_timestamps`](crate::device::Device::calibrated_timestamps).
+    ///
+    /// Returns an empty list if the
+    /// [`ext_calibrated_timestamps`](crate::device::DeviceExtensions::ext_calibrated_timestamps)
+    /// extension is not supported by the physical device.
+    pub fn calibrateable_time_domains(&self) -> Result<Vec<TimeDomain>, OomError> {
+        if !self.supported_extensions().ext_calibrated_timestamps {
+            return Ok(Vec::new());
+        }
+
+        // This is a physical-device-level function of a device extension, so it must be loaded
+        // from the instance.
+        let fns = ash::vk::ExtCalibratedTimestampsFn::load(|name| self.instance.proc_addr(name));
+
+        let time_domains = unsafe {
+            loop {
+                let mut count = 0;
+                check_errors((fns.get_physical_device_calibrateable_time_domains_ext)(
+                    self.internal_object(),
+                    &mut count,
+                    ptr::null_mut(),
+                ))?;
+
+                let mut time_domains = Vec::with_capacity(count as usize);
+                let result = check_errors((fns
+                    .get_physical_device_calibrateable_time_domains_ext)(
+                    self.internal_object(),
+                    &mut count,
+                    time_domains.as_mut_ptr(),
+                ))?;
+
+                if !matches!(result, Success::Incomplete) {
+                    time_domains.set_len(count as usize);
+                    break time_domains;
+                }
+            }
+        };
+
+        Ok(time_domains
+            .into_iter()
+            .filter_map(|time_domain_vk| time_domain_vk.try_into().ok())
+            .collect())
+    }
 }
 
 unsafe impl<'a> VulkanObject for PhysicalDevice<'a> {
@@ -1349,6 +1538,46 @@ impl TryFrom<ash::vk::PhysicalDeviceType> for PhysicalDeviceType {
     }
 }
 
+/// A time domain that timestamps can be taken in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum TimeDomain {
+    /// The time domain of timestamp queries on the device. Values can be converted to
+    /// nanoseconds using [`PhysicalDevice::timestamp_period`].
+    Device = ash::vk::TimeDomainEXT::DEVICE.as_raw(),
+
+    /// The `CLOCK_MONOTONIC` clock of POSIX systems.
+    ClockMonotonic = ash::vk::TimeDomainEXT::CLOCK_MONOTONIC.as_raw(),
+
+    /// The `CLOCK_MONOTONIC_RAW` clock of POSIX systems.
+    ClockMonotonicRaw = ash::vk::TimeDomainEXT::CLOCK_MONOTONIC_RAW.as_raw(),
+
+    /// The performance counter of Windows, as returned by `QueryPerformanceCounter`.
+    QueryPerformanceCounter = ash::vk::TimeDomainEXT::QUERY_PERFORMANCE_COUNTER.as_raw(),
+}
+
+impl From<TimeDomain> for ash::vk::TimeDomainEXT {
+    #[inline]
+    fn from(val: TimeDomain) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
+
+impl TryFrom<ash::vk::TimeDomainEXT> for TimeDomain {
+    type Error = ();
+
+    #[inline]
+    fn try_from(val: ash::vk::TimeDomainEXT) -> Result<Self, Self::Error> {
+        Ok(match val {
+            ash::vk::TimeDomainEXT::DEVICE => Self::Device,
+            ash::vk::TimeDomainEXT::CLOCK_MONOTONIC => Self::ClockMonotonic,
+            ash::vk::TimeDomainEXT::CLOCK_MONOTONIC_RAW => Self::ClockMonotonicRaw,
+            ash::vk::TimeDomainEXT::QUERY_PERFORMANCE_COUNTER => Self::QueryPerformanceCounter,
+            _ => return Err(()),
+        })
+    }
+}
+
 /// Represents a memory type in a physical device.
 #[derive(Debug, Copy, Clone)]
 pub struct MemoryType<'a> {
@@ -1480,6 +1709,19 @@ impl<'a> MemoryHeap<'a> {
     }
 }
 
+/// The memory budget and usage of a memory heap, as returned by
+/// [`PhysicalDevice::memory_budget`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryHeapBudget {
+    /// An estimate of how much memory, in bytes, the current process can allocate from the heap
+    /// before allocations may fail or cause performance degradation.
+    pub budget: DeviceSize,
+
+    /// An estimate of how much memory, in bytes, the current process is currently using in the
+    /// heap.
+    pub usage: DeviceSize,
+}
+
 /// Represents a queue family in a physical device.
 ///
 /// A queue family is group of one or multiple queues. All queues of one family have the same
@@ -1684,6 +1926,28 @@ impl TryFrom<ash::vk::DriverId> for DriverId {
     }
 }
 
+/// The properties of the subgroups of a physical device.
+///
+/// Each field is `None` if the value is unknown, because the physical device doesn't support
+/// Vulkan 1.1.
+#[derive(Clone, Copy, Debug)]
+pub struct SubgroupProperties {
+    /// The default number of invocations in each subgroup.
+    pub subgroup_size: Option<u32>,
+
+    /// The shader stages in which subgroup operations are supported.
+    pub supported_stages: Option<ShaderStages>,
+
+    /// The subgroup operations that are supported.
+    pub supported_operations: Option<SubgroupFeatures>,
+
+    /// Whether quad subgroup operations are supported in all stages, rather than only the
+    /// fragment and compute stages.
+    pub quad_operations_in_all_stages: Option<bool>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
 /// Specifies which subgroup operations are supported.
 #[derive(Clone, Copy, Debug)]
 pub struct SubgroupFeatures {
@@ -1777,6 +2041,34 @@ impl From<ash::vk::ShaderCorePropertiesFlagsAMD> for ShaderCoreProperties {
     }
 }
 
+/// Error that can happen when calling [`PhysicalDevice::memory_budget`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MemoryBudgetError {
+    /// A required device extension is not supported by the physical device.
+    ExtensionNotSupported { extension: &'static str },
+
+    /// A required instance extension is not enabled on the instance.
+    InstanceExtensionNotEnabled { extension: &'static str },
+}
+
+impl error::Error for MemoryBudgetError {}
+
+impl fmt::Display for MemoryBudgetError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::ExtensionNotSupported { extension } => write!(
+                fmt,
+                "the device extension {} is not supported by the physical device",
+                extension
+            ),
+            Self::InstanceExtensionNotEnabled { extension } => {
+                write!(fmt, "the instance extension {} must be enabled", extension)
+            }
+        }
+    }
+}
+
 /// Error that can happen when retrieving properties of a surface.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
@@ -1789,6 +2081,9 @@ pub enum SurfacePropertiesError {
 
     // The given `SurfaceInfo` values are not supported for the surface by the physical device.
     NotSupported,
+
+    /// None of the queue families of the physical device can present to the surface.
+    SurfaceNotSupported,
 }
 
 impl error::Error for SurfacePropertiesError {
@@ -1811,6 +2106,7 @@ impl fmt::Display for SurfacePropertiesError {
                 Self::OomError(_) => "not enough memory",
                 Self::SurfaceLost => "the surface is no longer valid",
                 Self::NotSupported => "the given `SurfaceInfo` values are not supported for the surface by the physical device",
+                Self::SurfaceNotSupported => "none of the queue families of the physical device can present to the surface",
             }
         )
     }
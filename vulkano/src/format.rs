@@ -880,6 +880,14 @@ impl Default for FormatFeatures {
     }
 }
 
+impl FormatFeatures {
+    /// Returns whether all features in `other` are also present in `self`.
+    #[inline]
+    pub fn contains(&self, other: &FormatFeatures) -> bool {
+        &(self | other) == self
+    }
+}
+
 impl BitOr for &FormatFeatures {
     type Output = FormatFeatures;
 
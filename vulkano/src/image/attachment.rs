@@ -104,6 +104,7 @@ impl AttachmentImage {
             format,
             ImageUsage::none(),
             SampleCount::Sample1,
+            false,
         )
     }
 
@@ -128,6 +129,7 @@ impl AttachmentImage {
             format,
             base_usage,
             SampleCount::Sample1,
+            false,
         )
     }
 
@@ -142,7 +144,15 @@ impl AttachmentImage {
         samples: SampleCount,
         format: Format,
     ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        AttachmentImage::new_impl(device, dimensions, 1, format, ImageUsage::none(), samples)
+        AttachmentImage::new_impl(
+            device,
+            dimensions,
+            1,
+            format,
+            ImageUsage::none(),
+            samples,
+            false,
+        )
     }
 
     /// Same as `multisampled`, but creates an image that can be used as an input attachment.
@@ -160,7 +170,7 @@ impl AttachmentImage {
             ..ImageUsage::none()
         };
 
-        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples)
+        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples, false)
     }
 
     /// Same as `new`, but lets you specify additional usages.
@@ -175,7 +185,15 @@ impl AttachmentImage {
         format: Format,
         usage: ImageUsage,
     ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        AttachmentImage::new_impl(device, dimensions, 1, format, usage, SampleCount::Sample1)
+        AttachmentImage::new_impl(
+            device,
+            dimensions,
+            1,
+            format,
+            usage,
+            SampleCount::Sample1,
+            false,
+        )
     }
 
     /// Same as `with_usage`, but creates a multisampled image.
@@ -190,7 +208,7 @@ impl AttachmentImage {
         format: Format,
         usage: ImageUsage,
     ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        AttachmentImage::new_impl(device, dimensions, 1, format, usage, samples)
+        AttachmentImage::new_impl(device, dimensions, 1, format, usage, samples, false)
     }
 
     /// Same as `multisampled_with_usage`, but creates an image with multiple layers.
@@ -206,7 +224,15 @@ impl AttachmentImage {
         format: Format,
         usage: ImageUsage,
     ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
-        AttachmentImage::new_impl(device, dimensions, array_layers, format, usage, samples)
+        AttachmentImage::new_impl(
+            device,
+            dimensions,
+            array_layers,
+            format,
+            usage,
+            samples,
+            false,
+        )
     }
 
     /// Same as `new`, except that the image can later be sampled.
@@ -230,6 +256,7 @@ impl AttachmentImage {
             format,
             base_usage,
             SampleCount::Sample1,
+            false,
         )
     }
 
@@ -255,6 +282,7 @@ impl AttachmentImage {
             format,
             base_usage,
             SampleCount::Sample1,
+            false,
         )
     }
 
@@ -276,7 +304,7 @@ impl AttachmentImage {
             ..ImageUsage::none()
         };
 
-        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples)
+        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples, false)
     }
 
     /// Same as `sampled_multisampled`, but creates an image that can be used as an input
@@ -296,7 +324,7 @@ impl AttachmentImage {
             ..ImageUsage::none()
         };
 
-        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples)
+        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples, false)
     }
 
     /// Same as `new`, except that the image will be transient.
@@ -323,6 +351,41 @@ impl AttachmentImage {
             format,
             base_usage,
             SampleCount::Sample1,
+            false,
+        )
+    }
+
+    /// Same as `transient`, except that the image is backed by lazily allocated memory if the
+    /// device supports it, and can also be used as an input attachment.
+    ///
+    /// Lazily allocated memory is only committed by the implementation when it is actually
+    /// needed. On tile-based GPUs, this often means that the image's content stays entirely in
+    /// on-chip tile memory and never uses any bandwidth or device memory. If no memory type with
+    /// the `lazily_allocated` property can hold the image, device local memory is used instead.
+    ///
+    /// The image must only ever be used as a color, depth/stencil or input attachment within a
+    /// render pass. Its content is undefined outside of the render pass, so the attachment's
+    /// `load_op` should be `Clear` or `DontCare` and its `store_op` should be `DontCare`.
+    #[inline]
+    pub fn transient_lazy(
+        device: Arc<Device>,
+        dimensions: [u32; 2],
+        format: Format,
+    ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
+        let base_usage = ImageUsage {
+            transient_attachment: true,
+            input_attachment: true,
+            ..ImageUsage::none()
+        };
+
+        AttachmentImage::new_impl(
+            device,
+            dimensions,
+            1,
+            format,
+            base_usage,
+            SampleCount::Sample1,
+            true,
         )
     }
 
@@ -348,6 +411,7 @@ impl AttachmentImage {
             format,
             base_usage,
             SampleCount::Sample1,
+            false,
         )
     }
 
@@ -369,7 +433,7 @@ impl AttachmentImage {
             ..ImageUsage::none()
         };
 
-        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples)
+        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples, false)
     }
 
     /// Same as `transient_multisampled`, but creates an image that can be used as an input
@@ -389,7 +453,7 @@ impl AttachmentImage {
             ..ImageUsage::none()
         };
 
-        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples)
+        AttachmentImage::new_impl(device, dimensions, 1, format, base_usage, samples, false)
     }
 
     // All constructors dispatch to this one.
@@ -400,6 +464,7 @@ impl AttachmentImage {
         format: Format,
         base_usage: ImageUsage,
         samples: SampleCount,
+        prefer_lazily_allocated: bool,
     ) -> Result<Arc<AttachmentImage>, ImageCreationError> {
         let physical_device = device.physical_device();
         let device_properties = physical_device.properties();
@@ -441,6 +506,14 @@ impl AttachmentImage {
         )?;
 
         let mem_reqs = image.memory_requirements();
+
+        // Only prefer lazily allocated memory if the image can actually be bound to it,
+        // otherwise fall back to preferring device local memory.
+        let lazily_allocated = prefer_lazily_allocated
+            && physical_device
+                .memory_types()
+                .any(|t| t.is_lazily_allocated() && mem_reqs.memory_type_bits & (1 << t.id()) != 0);
+
         let memory = MemoryPool::alloc_from_requirements(
             &Device::standard_pool(&device),
             &mem_reqs,
@@ -448,7 +521,13 @@ impl AttachmentImage {
             MappingRequirement::DoNotMap,
             Some(DedicatedAllocation::Image(&image)),
             |t| {
-                if t.is_device_local() {
+                let preferred = if lazily_allocated {
+                    t.is_lazily_allocated()
+                } else {
+                    t.is_device_local()
+                };
+
+                if preferred {
                     AllocFromRequirementsFilter::Preferred
                 } else {
                     AllocFromRequirementsFilter::Allowed
@@ -659,6 +738,10 @@ where
 mod tests {
     use super::AttachmentImage;
     use crate::format::Format;
+    use crate::image::view::{
+        ImageView, ImageViewAbstract, ImageViewCreateInfo, ImageViewCreationError,
+    };
+    use crate::image::{ImageAccess, ImageAspects, ImageSubresourceRange};
 
     #[test]
     fn create_regular() {
@@ -672,9 +755,68 @@ mod tests {
         let _img = AttachmentImage::transient(device, [32, 32], Format::R8G8B8A8_UNORM).unwrap();
     }
 
+    #[test]
+    fn create_transient_lazy() {
+        let (device, _) = gfx_dev_and_queue!();
+        let img =
+            AttachmentImage::transient_lazy(device, [32, 32], Format::R8G8B8A8_UNORM).unwrap();
+        assert!(img.inner().image.usage().transient_attachment);
+    }
+
     #[test]
     fn d16_unorm_always_supported() {
         let (device, _) = gfx_dev_and_queue!();
         let _img = AttachmentImage::new(device, [32, 32], Format::D16_UNORM).unwrap();
     }
+
+    #[test]
+    fn stencil_only_view() {
+        let (device, _) = gfx_dev_and_queue!();
+        let format = Format::D24_UNORM_S8_UINT;
+
+        // Support for this format is optional.
+        let format_features = device
+            .physical_device()
+            .format_properties(format)
+            .optimal_tiling_features;
+        if !(format_features.sampled_image && format_features.depth_stencil_attachment) {
+            return;
+        }
+
+        let img = AttachmentImage::sampled(device, [32, 32], format).unwrap();
+
+        let view = ImageView::new(
+            img.clone(),
+            ImageViewCreateInfo {
+                subresource_range: ImageSubresourceRange {
+                    aspects: ImageAspects {
+                        stencil: true,
+                        ..ImageAspects::none()
+                    },
+                    ..img.subresource_range()
+                },
+                ..ImageViewCreateInfo::from_image(&img)
+            },
+        )
+        .unwrap();
+        assert!(view.subresource_range().aspects.stencil);
+        assert!(!view.subresource_range().aspects.depth);
+
+        assert!(matches!(
+            ImageView::new(
+                img.clone(),
+                ImageViewCreateInfo {
+                    subresource_range: ImageSubresourceRange {
+                        aspects: ImageAspects {
+                            color: true,
+                            ..ImageAspects::none()
+                        },
+                        ..img.subresource_range()
+                    },
+                    ..ImageViewCreateInfo::from_image(&img)
+                },
+            ),
+            Err(ImageViewCreationError::ImageAspectsNotCompatible { .. })
+        ));
+    }
 }
@@ -9,15 +9,14 @@
 
 use super::{
     sys::UnsafeImage, traits::ImageContent, ImageAccess, ImageCreateFlags, ImageCreationError,
-    ImageDescriptorLayouts, ImageDimensions, ImageInner, ImageLayout, ImageSubresourceLayers,
-    ImageUsage, MipmapsCount,
+    ImageDescriptorLayouts, ImageDimensions, ImageInner, ImageLayout, ImageUsage, MipmapsCount,
 };
 use crate::{
     buffer::{BufferAccess, BufferContents, BufferUsage, CpuAccessibleBuffer},
     command_buffer::{
-        AutoCommandBufferBuilder, BlitImageInfo, CommandBufferBeginError, CommandBufferExecFuture,
-        CommandBufferUsage, CopyBufferToImageInfo, ImageBlit, PrimaryAutoCommandBuffer,
-        PrimaryCommandBuffer,
+        AutoCommandBufferBuilder, CommandBufferBeginError, CommandBufferExecFuture,
+        CommandBufferUsage, CopyBufferToImageInfo, CopyError, CopyImageInfo,
+        PrimaryAutoCommandBuffer, PrimaryCommandBuffer,
     },
     device::{physical::QueueFamily, Device, DeviceOwned, Queue},
     format::Format,
@@ -31,7 +30,7 @@ use crate::{
     },
     sampler::Filter,
     sync::{NowFuture, Sharing},
-    OomError,
+    DeviceSize, OomError,
 };
 use smallvec::SmallVec;
 use std::{
@@ -61,44 +60,6 @@ fn has_mipmaps(mipmaps: MipmapsCount) -> bool {
     }
 }
 
-fn generate_mipmaps<L>(
-    cbb: &mut AutoCommandBufferBuilder<L>,
-    image: Arc<dyn ImageAccess>,
-    dimensions: ImageDimensions,
-    layout: ImageLayout,
-) {
-    for level in 1..image.mip_levels() {
-        let src_size = dimensions
-            .mip_level_dimensions(level - 1)
-            .unwrap()
-            .width_height_depth();
-        let dst_size = dimensions
-            .mip_level_dimensions(level)
-            .unwrap()
-            .width_height_depth();
-
-        cbb.blit_image(BlitImageInfo {
-            regions: [ImageBlit {
-                src_subresource: ImageSubresourceLayers {
-                    mip_level: level - 1,
-                    ..image.subresource_layers()
-                },
-                src_offsets: [[0; 3], src_size],
-                dst_subresource: ImageSubresourceLayers {
-                    mip_level: level,
-                    ..image.subresource_layers()
-                },
-                dst_offsets: [[0; 3], dst_size],
-                ..Default::default()
-            }]
-            .into(),
-            filter: Filter::Linear,
-            ..BlitImageInfo::images(image.clone(), image.clone())
-        })
-        .expect("failed to blit a mip map to image!");
-    }
-}
-
 impl ImmutableImage {
     #[deprecated(note = "use ImmutableImage::uninitialized instead")]
     #[inline]
@@ -240,6 +201,8 @@ impl ImmutableImage {
     }
 
     /// Construct an ImmutableImage from the contents of `iter`.
+    ///
+    /// See [`from_buffer`](ImmutableImage::from_buffer) for the requirements on the data.
     #[inline]
     pub fn from_iter<Px, I>(
         iter: I,
@@ -269,6 +232,14 @@ impl ImmutableImage {
     }
 
     /// Construct an ImmutableImage containing a copy of the data in `source`.
+    ///
+    /// The data is copied into the first mip level of all array layers, tightly packed. For
+    /// compressed formats, the data is made of whole texel blocks, so its size in bytes must be
+    /// `ceil(width / block_width) * ceil(height / block_height) * ceil(depth / block_depth) *
+    /// array_layers * block_size`, as given by [`Format::block_extent`] and
+    /// [`Format::block_size`]. For uncompressed formats the block extent is `[1, 1, 1]`, and this
+    /// is simply the number of texels times the size of a texel. If the size of `source` is
+    /// different, `SourceSizeMismatch` is returned.
     pub fn from_buffer(
         source: Arc<dyn BufferAccess>,
         dimensions: ImageDimensions,
@@ -282,6 +253,28 @@ impl ImmutableImage {
         ),
         ImmutableImageCreationError,
     > {
+        if let Some(block_size) = format.block_size() {
+            let num_blocks = dimensions
+                .width_height_depth()
+                .into_iter()
+                .zip(format.block_extent())
+                .map(|(extent, block_extent)| {
+                    let extent = extent as DeviceSize;
+                    let block_extent = block_extent as DeviceSize;
+                    (extent + block_extent - 1) / block_extent
+                })
+                .product::<DeviceSize>()
+                * dimensions.array_layers() as DeviceSize;
+            let required_size = num_blocks * block_size;
+
+            if source.size() != required_size {
+                return Err(ImmutableImageCreationError::SourceSizeMismatch {
+                    required_size,
+                    provided_size: source.size(),
+                });
+            }
+        }
+
         let need_to_generate_mipmaps = has_mipmaps(mip_levels);
         let usage = ImageUsage {
             transfer_dst: true,
@@ -311,13 +304,72 @@ impl ImmutableImage {
         cbb.copy_buffer_to_image(CopyBufferToImageInfo::buffer_image(source, initializer))
             .unwrap();
 
-        if need_to_generate_mipmaps {
-            generate_mipmaps(
-                &mut cbb,
-                image.clone(),
-                image.dimensions,
-                ImageLayout::ShaderReadOnlyOptimal,
-            );
+        if image.mip_levels() > 1 {
+            cbb.generate_mipmaps(image.clone(), Filter::Linear)
+                .expect("failed to blit a mip map to image!");
+        }
+
+        let cb = cbb.build().unwrap();
+
+        let future = match cb.execute(queue) {
+            Ok(f) => f,
+            Err(e) => unreachable!("{:?}", e),
+        };
+
+        Ok((image, future))
+    }
+
+    /// Construct an ImmutableImage containing a copy of the first mip level of `source`, without
+    /// going through host memory.
+    ///
+    /// The new image has the same dimensions and format as `source`. `source` must have been
+    /// created with the `transfer_src` usage, and `queue` must belong to a queue family that
+    /// supports transfer operations. The new image is shared between all the active queue
+    /// families of the device, so it can be used on any queue afterwards without an ownership
+    /// transfer.
+    pub fn from_image(
+        source: Arc<dyn ImageAccess>,
+        mip_levels: MipmapsCount,
+        queue: Arc<Queue>,
+    ) -> Result<
+        (
+            Arc<Self>,
+            CommandBufferExecFuture<NowFuture, PrimaryAutoCommandBuffer>,
+        ),
+        ImmutableImageCreationError,
+    > {
+        let need_to_generate_mipmaps = has_mipmaps(mip_levels);
+        let usage = ImageUsage {
+            transfer_dst: true,
+            transfer_src: need_to_generate_mipmaps,
+            sampled: true,
+            ..ImageUsage::none()
+        };
+        let flags = ImageCreateFlags::none();
+        let layout = ImageLayout::ShaderReadOnlyOptimal;
+
+        let dimensions = source.dimensions();
+        let (image, initializer) = ImmutableImage::uninitialized(
+            source.device().clone(),
+            dimensions,
+            source.format(),
+            mip_levels,
+            usage,
+            flags,
+            layout,
+            source.device().active_queue_families(),
+        )?;
+
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            source.device().clone(),
+            queue.family(),
+            CommandBufferUsage::MultipleSubmit,
+        )?;
+        cbb.copy_image(CopyImageInfo::images(source, initializer))?;
+
+        if image.mip_levels() > 1 {
+            cbb.generate_mipmaps(image.clone(), Filter::Linear)
+                .expect("failed to blit a mip map to image!");
         }
 
         let cb = cbb.build().unwrap();
@@ -475,6 +527,15 @@ pub enum ImmutableImageCreationError {
     ImageCreationError(ImageCreationError),
     DeviceMemoryAllocationError(DeviceMemoryAllocationError),
     CommandBufferBeginError(CommandBufferBeginError),
+
+    /// Recording the copy from the source image failed.
+    CopyError(CopyError),
+
+    /// The size of the source buffer doesn't match the size of the image data.
+    SourceSizeMismatch {
+        required_size: DeviceSize,
+        provided_size: DeviceSize,
+    },
 }
 
 impl error::Error for ImmutableImageCreationError {
@@ -484,6 +545,8 @@ impl error::Error for ImmutableImageCreationError {
             Self::ImageCreationError(err) => Some(err),
             Self::DeviceMemoryAllocationError(err) => Some(err),
             Self::CommandBufferBeginError(err) => Some(err),
+            Self::CopyError(err) => Some(err),
+            _ => None,
         }
     }
 }
@@ -495,6 +558,16 @@ impl fmt::Display for ImmutableImageCreationError {
             Self::ImageCreationError(err) => err.fmt(f),
             Self::DeviceMemoryAllocationError(err) => err.fmt(f),
             Self::CommandBufferBeginError(err) => err.fmt(f),
+            Self::CopyError(err) => err.fmt(f),
+            Self::SourceSizeMismatch {
+                required_size,
+                provided_size,
+            } => write!(
+                f,
+                "the size of the source buffer ({} bytes) doesn't match the size of the image \
+                data ({} bytes)",
+                provided_size, required_size,
+            ),
         }
     }
 }
@@ -526,3 +599,65 @@ impl From<CommandBufferBeginError> for ImmutableImageCreationError {
         Self::CommandBufferBeginError(err)
     }
 }
+
+impl From<CopyError> for ImmutableImageCreationError {
+    #[inline]
+    fn from(err: CopyError) -> Self {
+        Self::CopyError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImmutableImage, ImmutableImageCreationError};
+    use crate::{
+        format::Format,
+        image::{ImageAccess, ImageDimensions, MipmapsCount, StorageImage},
+    };
+
+    #[test]
+    fn from_iter_size_mismatch() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        match ImmutableImage::from_iter(
+            [0u8; 4 * 4 * 4 - 1],
+            ImageDimensions::Dim2d {
+                width: 4,
+                height: 4,
+                array_layers: 1,
+            },
+            MipmapsCount::One,
+            Format::R8G8B8A8_UNORM,
+            queue,
+        ) {
+            Err(ImmutableImageCreationError::SourceSizeMismatch {
+                required_size: 64,
+                provided_size: 63,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn from_image() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let dimensions = ImageDimensions::Dim2d {
+            width: 4,
+            height: 4,
+            array_layers: 1,
+        };
+        let source = StorageImage::new(
+            device.clone(),
+            dimensions,
+            Format::R8G8B8A8_UNORM,
+            Some(queue.family()),
+        )
+        .unwrap();
+
+        let (image, _future) =
+            ImmutableImage::from_image(source, MipmapsCount::One, queue).unwrap();
+        assert_eq!(image.dimensions(), dimensions);
+        assert_eq!(image.format(), Format::R8G8B8A8_UNORM);
+    }
+}
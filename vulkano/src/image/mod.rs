@@ -833,11 +833,98 @@ impl From<ash::vk::ImageFormatProperties> for ImageFormatProperties {
     }
 }
 
+/// The memory requirements of one aspect of a sparse image.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct SparseImageMemoryRequirements {
+    /// The properties of the image's format that apply to sparse binding.
+    pub format_properties: SparseImageFormatProperties,
+
+    /// The first mip level at which subresources are included in the mip tail region.
+    pub image_mip_tail_first_lod: u32,
+
+    /// The size in bytes of the mip tail region. If `single_miptail` is not set, this is the size
+    /// of the mip tail region of a single array layer.
+    pub image_mip_tail_size: DeviceSize,
+
+    /// The offset in bytes into the opaque memory range of the image, where the mip tail region
+    /// begins. This offset must be used when binding memory to the mip tail region through
+    /// [`Queue::bind_sparse`](crate::device::Queue::bind_sparse).
+    pub image_mip_tail_offset: DeviceSize,
+
+    /// The stride in bytes between the mip tail regions of consecutive array layers. This is only
+    /// meaningful if `single_miptail` is not set.
+    pub image_mip_tail_stride: DeviceSize,
+}
+
+impl From<ash::vk::SparseImageMemoryRequirements> for SparseImageMemoryRequirements {
+    #[inline]
+    fn from(val: ash::vk::SparseImageMemoryRequirements) -> Self {
+        Self {
+            format_properties: val.format_properties.into(),
+            image_mip_tail_first_lod: val.image_mip_tail_first_lod,
+            image_mip_tail_size: val.image_mip_tail_size,
+            image_mip_tail_offset: val.image_mip_tail_offset,
+            image_mip_tail_stride: val.image_mip_tail_stride,
+        }
+    }
+}
+
+/// The properties of an image format that apply to sparse binding.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct SparseImageFormatProperties {
+    /// The aspects of the image that these properties apply to.
+    pub aspects: ImageAspects,
+
+    /// The size in texels (or for compressed images, texel blocks) of the regions that memory
+    /// can be bound to with sparse residency.
+    pub image_granularity: [u32; 3],
+
+    /// Whether the image uses a single mip tail region for all array layers, instead of one mip
+    /// tail region per array layer.
+    pub single_miptail: bool,
+
+    /// Whether the first mip level that is not a multiple of `image_granularity` begins the mip
+    /// tail region.
+    pub aligned_mip_size: bool,
+
+    /// Whether the image uses a non-standard sparse image block size, and therefore
+    /// `image_granularity` does not match the standard sparse image block dimensions.
+    pub nonstandard_block_size: bool,
+}
+
+impl From<ash::vk::SparseImageFormatProperties> for SparseImageFormatProperties {
+    #[inline]
+    fn from(val: ash::vk::SparseImageFormatProperties) -> Self {
+        Self {
+            aspects: val.aspect_mask.into(),
+            image_granularity: [
+                val.image_granularity.width,
+                val.image_granularity.height,
+                val.image_granularity.depth,
+            ],
+            single_miptail: val
+                .flags
+                .intersects(ash::vk::SparseImageFormatFlags::SINGLE_MIPTAIL),
+            aligned_mip_size: val
+                .flags
+                .intersects(ash::vk::SparseImageFormatFlags::ALIGNED_MIP_SIZE),
+            nonstandard_block_size: val
+                .flags
+                .intersects(ash::vk::SparseImageFormatFlags::NONSTANDARD_BLOCK_SIZE),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::format::Format;
     use crate::image::ImageAccess;
     use crate::image::ImageDimensions;
+    use crate::image::ImageFormatInfo;
+    use crate::image::ImageType;
+    use crate::image::ImageUsage;
     use crate::image::ImmutableImage;
     use crate::image::MipmapsCount;
 
@@ -981,4 +1068,42 @@ mod tests {
             assert_eq!(image.mip_levels(), 10);
         }
     }
+
+    #[test]
+    fn image_format_properties() {
+        let (device, _) = gfx_dev_and_queue!();
+        let physical_device = device.physical_device();
+
+        // Sampled 2D images of this format are required to be supported.
+        let properties = physical_device
+            .image_format_properties(ImageFormatInfo {
+                format: Some(Format::R8G8B8A8_UNORM),
+                image_type: ImageType::Dim2d,
+                usage: ImageUsage {
+                    sampled: true,
+                    ..ImageUsage::none()
+                },
+                ..Default::default()
+            })
+            .unwrap()
+            .unwrap();
+        assert!(properties.max_extent[0] >= 1 && properties.max_extent[1] >= 1);
+        assert_eq!(properties.max_extent[2], 1);
+        assert!(properties.max_mip_levels >= 1);
+        assert!(properties.max_array_layers >= 1);
+        assert!(properties.sample_counts.sample1);
+
+        // A color format can't be used as a depth/stencil attachment.
+        assert!(physical_device
+            .image_format_properties(ImageFormatInfo {
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage {
+                    depth_stencil_attachment: true,
+                    ..ImageUsage::none()
+                },
+                ..Default::default()
+            })
+            .unwrap()
+            .is_none());
+    }
 }
@@ -16,12 +16,15 @@
 use super::{
     ImageAspect, ImageAspects, ImageCreateFlags, ImageDimensions, ImageLayout,
     ImageSubresourceLayers, ImageSubresourceRange, ImageTiling, ImageUsage, SampleCount,
-    SampleCounts,
+    SampleCounts, SparseImageMemoryRequirements,
 };
 use crate::image::view::ImageViewCreationError;
 use crate::range_map::RangeMap;
 use crate::{
-    buffer::cpu_access::{ReadLockError, WriteLockError},
+    buffer::{
+        cpu_access::{ReadLockError, WriteLockError},
+        sys::SparseLevel,
+    },
     check_errors,
     device::{Device, DeviceOwned},
     format::{ChromaSampling, Format, FormatFeatures, NumericType},
@@ -75,6 +78,7 @@ pub struct UnsafeImage {
     cube_compatible: bool,
     array_2d_compatible: bool,
     block_texel_view_compatible: bool,
+    sparse: Option<SparseLevel>,
 
     aspect_list: SmallVec<[ImageAspect; 4]>,
     aspect_size: DeviceSize,
@@ -122,6 +126,7 @@ impl UnsafeImage {
             cube_compatible,
             array_2d_compatible,
             block_texel_view_compatible,
+            sparse,
             _ne: _,
         } = create_info;
 
@@ -147,6 +152,7 @@ impl UnsafeImage {
             cube_compatible,
             array_2d_compatible,
             block_texel_view_compatible,
+            sparse,
 
             aspect_list,
             aspect_size,
@@ -177,6 +183,7 @@ impl UnsafeImage {
             cube_compatible,
             array_2d_compatible,
             block_texel_view_compatible,
+            sparse,
             _ne: _,
         } = create_info;
 
@@ -444,6 +451,85 @@ impl UnsafeImage {
             }
         }
 
+        if let Some(sparse_level) = sparse {
+            // VUID-VkImageCreateInfo-flags-00969
+            if !device.enabled_features().sparse_binding {
+                return Err(ImageCreationError::FeatureNotEnabled {
+                    feature: "sparse_binding",
+                    reason: "sparse was `Some`",
+                });
+            }
+
+            if sparse_level.sparse_residency {
+                // VUID-VkImageCreateInfo-tiling-04121
+                if tiling == ImageTiling::Linear {
+                    return Err(ImageCreationError::SparseResidencyLinearTiling);
+                }
+
+                match image_type {
+                    // VUID-VkImageCreateInfo-imageType-00970
+                    ImageType::Dim1d => return Err(ImageCreationError::SparseResidencyDim1d),
+                    ImageType::Dim2d => {
+                        // VUID-VkImageCreateInfo-imageType-00971
+                        if !device.enabled_features().sparse_residency_image2_d {
+                            return Err(ImageCreationError::FeatureNotEnabled {
+                                feature: "sparse_residency_image2_d",
+                                reason: "sparse was `Some`, `sparse_residency` was set and the image type was 2D",
+                            });
+                        }
+
+                        // VUID-VkImageCreateInfo-imageType-00973
+                        // VUID-VkImageCreateInfo-imageType-00974
+                        // VUID-VkImageCreateInfo-imageType-00975
+                        // VUID-VkImageCreateInfo-imageType-00976
+                        let (enabled, feature) = match samples {
+                            SampleCount::Sample2 => (
+                                device.enabled_features().sparse_residency2_samples,
+                                "sparse_residency2_samples",
+                            ),
+                            SampleCount::Sample4 => (
+                                device.enabled_features().sparse_residency4_samples,
+                                "sparse_residency4_samples",
+                            ),
+                            SampleCount::Sample8 => (
+                                device.enabled_features().sparse_residency8_samples,
+                                "sparse_residency8_samples",
+                            ),
+                            SampleCount::Sample16 => (
+                                device.enabled_features().sparse_residency16_samples,
+                                "sparse_residency16_samples",
+                            ),
+                            _ => (true, ""),
+                        };
+
+                        if !enabled {
+                            return Err(ImageCreationError::FeatureNotEnabled {
+                                feature,
+                                reason: "sparse was `Some`, `sparse_residency` was set and the image was multisampled",
+                            });
+                        }
+                    }
+                    ImageType::Dim3d => {
+                        // VUID-VkImageCreateInfo-imageType-00972
+                        if !device.enabled_features().sparse_residency_image3_d {
+                            return Err(ImageCreationError::FeatureNotEnabled {
+                                feature: "sparse_residency_image3_d",
+                                reason: "sparse was `Some`, `sparse_residency` was set and the image type was 3D",
+                            });
+                        }
+                    }
+                }
+            }
+
+            // VUID-VkImageCreateInfo-flags-01924
+            if sparse_level.sparse_aliased && !device.enabled_features().sparse_residency_aliased {
+                return Err(ImageCreationError::FeatureNotEnabled {
+                    feature: "sparse_residency_aliased",
+                    reason: "sparse was `Some` and `sparse_aliased` was set",
+                });
+            }
+        }
+
         /* Check sharing mode and queue families */
 
         match sharing {
@@ -755,10 +841,14 @@ impl UnsafeImage {
             cube_compatible,
             array_2d_compatible,
             block_texel_view_compatible,
+            sparse,
             _ne: _,
         } = create_info;
 
         let flags = ImageCreateFlags {
+            sparse_binding: sparse.is_some(),
+            sparse_residency: sparse.map_or(false, |sparse| sparse.sparse_residency),
+            sparse_aliased: sparse.map_or(false, |sparse| sparse.sparse_aliased),
             mutable_format,
             cube_compatible,
             array_2d_compatible,
@@ -825,7 +915,7 @@ impl UnsafeImage {
             check_errors((fns.v1_0.create_image)(
                 device.internal_object(),
                 &create_info.build(),
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -889,6 +979,7 @@ impl UnsafeImage {
             cube_compatible: flags.cube_compatible,
             array_2d_compatible: flags.array_2d_compatible,
             block_texel_view_compatible: flags.block_texel_view_compatible,
+            sparse: None,
 
             aspect_list,
             aspect_size,
@@ -961,6 +1052,39 @@ impl UnsafeImage {
         }
     }
 
+    /// Returns the sparse memory requirements for this image, one element for each aspect or
+    /// group of aspects that has its own requirements.
+    ///
+    /// Returns an empty list if the image was not created with the `sparse_residency` flag set.
+    pub fn sparse_memory_requirements(&self) -> Vec<SparseImageMemoryRequirements> {
+        if !self.sparse.map_or(false, |sparse| sparse.sparse_residency) {
+            return Vec::new();
+        }
+
+        unsafe {
+            let fns = self.device.fns();
+
+            let mut count = 0;
+            (fns.v1_0.get_image_sparse_memory_requirements)(
+                self.device.internal_object(),
+                self.handle,
+                &mut count,
+                ptr::null_mut(),
+            );
+
+            let mut requirements = Vec::with_capacity(count as usize);
+            (fns.v1_0.get_image_sparse_memory_requirements)(
+                self.device.internal_object(),
+                self.handle,
+                &mut count,
+                requirements.as_mut_ptr(),
+            );
+            requirements.set_len(count as usize);
+
+            requirements.into_iter().map(Into::into).collect()
+        }
+    }
+
     pub unsafe fn bind_memory(
         &self,
         memory: &DeviceMemory,
@@ -968,6 +1092,9 @@ impl UnsafeImage {
     ) -> Result<(), OomError> {
         let fns = self.device.fns();
 
+        // VUID-vkBindImageMemory-image-01045
+        debug_assert!(self.sparse.is_none());
+
         // We check for correctness in debug mode.
         debug_assert!({
             let mut mem_reqs = MaybeUninit::uninit();
@@ -1169,6 +1296,12 @@ impl UnsafeImage {
         self.block_texel_view_compatible
     }
 
+    /// Returns the level of sparse binding that the image was created with, if any.
+    #[inline]
+    pub fn sparse(&self) -> Option<SparseLevel> {
+        self.sparse
+    }
+
     /// Returns an `ImageSubresourceLayers` covering the first mip level of the image. All aspects
     /// of the image are selected, or `plane0` if the image is multi-planar.
     #[inline]
@@ -1339,7 +1472,11 @@ impl Drop for UnsafeImage {
 
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_image)(self.device.internal_object(), self.handle, ptr::null());
+            (fns.v1_0.destroy_image)(
+                self.device.internal_object(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
         }
     }
 }
@@ -1464,6 +1601,14 @@ pub struct UnsafeImageCreateInfo {
     /// The default value is `false`.
     pub block_texel_view_compatible: bool,
 
+    /// Create an image with sparsely bound memory.
+    ///
+    /// Memory is not bound to a sparse image when it is created. Instead, it must be bound with
+    /// [`Queue::bind_sparse`](crate::device::Queue::bind_sparse).
+    ///
+    /// The default value is `None`.
+    pub sparse: Option<SparseLevel>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1487,6 +1632,7 @@ impl Default for UnsafeImageCreateInfo {
             cube_compatible: false,
             array_2d_compatible: false,
             block_texel_view_compatible: false,
+            sparse: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1592,6 +1738,12 @@ pub enum ImageCreationError {
         id: u32,
     },
 
+    /// The `sparse_residency` flag was set, but the image type was 1D.
+    SparseResidencyDim1d,
+
+    /// The `sparse_residency` flag was set, and tiling was `Linear`.
+    SparseResidencyLinearTiling,
+
     /// A YCbCr format was given, but the specified width and/or height was not a multiple of 2
     /// as required by the format's chroma subsampling.
     YcbcrFormatInvalidDimensions,
@@ -1718,6 +1870,18 @@ impl fmt::Display for ImageCreationError {
             Self::SharingInvalidQueueFamilyId { id } => {
                 write!(fmt, "the sharing mode was set to `Concurrent`, but one of the specified queue family ids was not valid")
             }
+            Self::SparseResidencyDim1d => {
+                write!(
+                    fmt,
+                    "the `sparse_residency` flag was set, but the image type was 1D"
+                )
+            }
+            Self::SparseResidencyLinearTiling => {
+                write!(
+                    fmt,
+                    "the `sparse_residency` flag was set, and tiling was `Linear`"
+                )
+            }
             Self::YcbcrFormatInvalidDimensions => {
                 write!(fmt, "a YCbCr format was given, but the specified width and/or height was not a multiple of 2 as required by the format's chroma subsampling")
             }
@@ -2172,6 +2336,7 @@ mod tests {
     use super::ImageUsage;
     use super::UnsafeImage;
     use super::UnsafeImageCreateInfo;
+    use crate::buffer::sys::SparseLevel;
     use crate::format::Format;
     use crate::image::sys::SubresourceRangeIterator;
     use crate::image::ImageAspect;
@@ -2314,6 +2479,72 @@ mod tests {
         };
     }
 
+    #[test]
+    fn missing_feature_sparse_binding() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = UnsafeImage::new(
+            device,
+            UnsafeImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage {
+                    sampled: true,
+                    ..ImageUsage::none()
+                },
+                sparse: Some(SparseLevel::none()),
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageCreationError::FeatureNotEnabled {
+                feature: "sparse_binding",
+                ..
+            }) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn missing_feature_sparse_residency() {
+        let (device, _) = gfx_dev_and_queue!(sparse_binding);
+
+        let res = UnsafeImage::new(
+            device,
+            UnsafeImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage {
+                    sampled: true,
+                    ..ImageUsage::none()
+                },
+                sparse: Some(SparseLevel {
+                    sparse_residency: true,
+                    sparse_aliased: false,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageCreationError::FeatureNotEnabled {
+                feature: "sparse_residency_image2_d",
+                ..
+            }) => (),
+            _ => panic!(),
+        };
+    }
+
     #[test]
     fn compressed_not_color_attachment() {
         let (device, _) = gfx_dev_and_queue!();
@@ -26,7 +26,6 @@ use std::{
     error, fmt,
     hash::{Hash, Hasher},
     mem::MaybeUninit,
-    ptr,
     sync::Arc,
 };
 
@@ -415,8 +414,8 @@ where
             }
         }
         // VUID-VkImageViewCreateInfo-image-01762
-        else if Some(format) != image_inner.format() {
-            return Err(ImageViewCreationError::FormatNotCompatible);
+        else if !image_inner.mutable_format() && Some(format) != image_inner.format() {
+            return Err(ImageViewCreationError::ImageNotMutableFormat);
         }
 
         // VUID-VkImageViewCreateInfo-imageViewType-04973
@@ -528,7 +527,7 @@ where
             check_errors((fns.v1_0.create_image_view)(
                 image_inner.device().internal_object(),
                 &create_info,
-                ptr::null(),
+                image_inner.device().allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -561,7 +560,11 @@ where
         unsafe {
             let device = self.device();
             let fns = device.fns();
-            (fns.v1_0.destroy_image_view)(device.internal_object(), self.handle, ptr::null());
+            (fns.v1_0.destroy_image_view)(
+                device.internal_object(),
+                self.handle,
+                device.allocation_callbacks(),
+            );
         }
     }
 }
@@ -625,18 +628,28 @@ pub struct ImageViewCreateInfo {
     /// The format of the image view.
     ///
     /// If this is set to a format that is different from the image, the image must be created with
-    /// the `mutable_format` flag.
+    /// the `mutable_format` flag, and the format must be in the same compatibility class as the
+    /// format of the image. This can be used to reinterpret the data of the image, for example to
+    /// view an `R8G8B8A8_UNORM` image as `R8G8B8A8_SRGB`.
     ///
     /// The default value is `None`, which must be overridden.
     pub format: Option<Format>,
 
     /// How to map components of each pixel.
     ///
+    /// This can be used to swizzle the components of the image, for example to read an image
+    /// with BGRA data as RGBA in a shader.
+    ///
     /// The default value is [`ComponentMapping::identity()`].
     pub component_mapping: ComponentMapping,
 
     /// The subresource range of the image that the view should cover.
     ///
+    /// For an image with a depth/stencil format, a view can select only the `depth` or only the
+    /// `stencil` aspect, for example to read the stencil values in a shader. A view that selects
+    /// both can be used as an attachment, but can't be written to a sampled or storage image
+    /// descriptor.
+    ///
     /// The default value is empty, which must be overridden.
     pub subresource_range: ImageSubresourceRange,
 
@@ -765,6 +778,10 @@ pub enum ImageViewCreationError {
     /// `cube_compatible` flag.
     ImageNotCubeCompatible,
 
+    /// A format was requested that is different from the format of the image, but the image was
+    /// not created with the `mutable_format` flag.
+    ImageNotMutableFormat,
+
     /// The given image view type was not compatible with the type of the image.
     ImageTypeNotCompatible,
 
@@ -872,6 +889,10 @@ impl fmt::Display for ImageViewCreationError {
                 fmt,
                 "a cube image view type was requested, but the image was not created with the `cube_compatible` flag",
             ),
+            Self::ImageNotMutableFormat => write!(
+                fmt,
+                "a format was requested that is different from the format of the image, but the image was not created with the `mutable_format` flag",
+            ),
             Self::ImageTypeNotCompatible => write!(
                 fmt,
                 "the given image view type was not compatible with the type of the image",
@@ -50,7 +50,7 @@ use std::{
     fmt,
     mem::MaybeUninit,
     panic::{catch_unwind, AssertUnwindSafe, RefUnwindSafe},
-    ptr,
+    slice,
     sync::Arc,
 };
 
@@ -154,7 +154,7 @@ impl DebugUtilsMessenger {
             check_errors((fns.ext_debug_utils.create_debug_utils_messenger_ext)(
                 instance.internal_object(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -172,7 +172,7 @@ impl Drop for DebugUtilsMessenger {
             (fns.ext_debug_utils.destroy_debug_utils_messenger_ext)(
                 self.instance.internal_object(),
                 self.handle,
-                ptr::null(),
+                self.instance.allocation_callbacks(),
             );
         }
     }
@@ -218,11 +218,45 @@ pub(super) unsafe extern "system" fn trampoline(
             .to_str()
             .expect("debug callback message not utf-8");
 
+        let queue_labels = slice_from_raw_parts(
+            (*callback_data).p_queue_labels,
+            (*callback_data).queue_label_count,
+        )
+        .iter()
+        .map(|label| DebugUtilsMessengerCallbackLabel::from_vk(label))
+        .collect();
+
+        let cmd_buf_labels = slice_from_raw_parts(
+            (*callback_data).p_cmd_buf_labels,
+            (*callback_data).cmd_buf_label_count,
+        )
+        .iter()
+        .map(|label| DebugUtilsMessengerCallbackLabel::from_vk(label))
+        .collect();
+
+        let objects =
+            slice_from_raw_parts((*callback_data).p_objects, (*callback_data).object_count)
+                .iter()
+                .map(|object| DebugUtilsMessengerCallbackObjectNameInfo {
+                    object_type: object.object_type,
+                    object_handle: object.object_handle,
+                    object_name: object.p_object_name.as_ref().map(|object_name| {
+                        CStr::from_ptr(object_name)
+                            .to_str()
+                            .expect("debug callback object name not utf-8")
+                    }),
+                })
+                .collect();
+
         let message = Message {
             severity: message_severity.into(),
             ty: message_types.into(),
             layer_prefix,
+            message_id_number: (*callback_data).message_id_number,
             description,
+            queue_labels,
+            cmd_buf_labels,
+            objects,
         };
 
         user_callback(&message);
@@ -231,6 +265,16 @@ pub(super) unsafe extern "system" fn trampoline(
     ash::vk::FALSE
 }
 
+// The arrays in the callback data can be null if their count is 0, which `slice::from_raw_parts`
+// doesn't allow.
+unsafe fn slice_from_raw_parts<'a, T>(ptr: *const T, count: u32) -> &'a [T] {
+    if count == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr, count as usize)
+    }
+}
+
 /// Error that can happen when creating a `DebugUtilsMessenger`.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum DebugUtilsMessengerCreationError {
@@ -325,9 +369,57 @@ pub struct Message<'a> {
     /// Type of message,
     pub ty: DebugUtilsMessageType,
     /// Prefix of the layer that reported this message or `None` if unknown.
+    ///
+    /// For messages reported by the validation layers, this is the name of the message
+    /// identifier, usually the VUID of the check that failed.
     pub layer_prefix: Option<&'a str>,
+    /// Numeric identifier of the message, or `0` if unknown.
+    ///
+    /// Together with `layer_prefix`, this can be used to filter out specific messages.
+    pub message_id_number: i32,
     /// Description of the message.
     pub description: &'a str,
+    /// The labels of the queues that were active when the message was reported, starting with the
+    /// most recent one.
+    pub queue_labels: Vec<DebugUtilsMessengerCallbackLabel<'a>>,
+    /// The labels of the command buffers that were active when the message was reported,
+    /// starting with the most recent one.
+    pub cmd_buf_labels: Vec<DebugUtilsMessengerCallbackLabel<'a>>,
+    /// The objects that are related to the message.
+    pub objects: Vec<DebugUtilsMessengerCallbackObjectNameInfo<'a>>,
+}
+
+/// A label of a queue or command buffer, as received by the callback.
+#[derive(Clone, Copy, Debug)]
+pub struct DebugUtilsMessengerCallbackLabel<'a> {
+    /// The name of the label.
+    pub label_name: &'a str,
+    /// The RGBA color value that is associated with the label, or `[0.0; 4]` if none was
+    /// provided.
+    pub color: [f32; 4],
+}
+
+impl<'a> DebugUtilsMessengerCallbackLabel<'a> {
+    unsafe fn from_vk(label: &'a ash::vk::DebugUtilsLabelEXT) -> Self {
+        Self {
+            label_name: CStr::from_ptr(label.p_label_name)
+                .to_str()
+                .expect("debug callback label name not utf-8"),
+            color: label.color,
+        }
+    }
+}
+
+/// An object that is related to a message received by the callback.
+#[derive(Clone, Copy, Debug)]
+pub struct DebugUtilsMessengerCallbackObjectNameInfo<'a> {
+    /// The type of the object.
+    pub object_type: ash::vk::ObjectType,
+    /// The raw handle of the object.
+    pub object_handle: u64,
+    /// The name that was given to the object with `set_debug_utils_object_name`, or `None` if it
+    /// has no name.
+    pub object_name: Option<&'a str>,
 }
 
 /// Severity of message.
@@ -595,7 +687,7 @@ impl Default for DebugUtilsLabel {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::thread;
+    use std::{ffi::CString, ptr, sync::Mutex, thread};
 
     #[test]
     fn ensure_sendable() {
@@ -616,4 +708,76 @@ mod tests {
             let _ = callback;
         });
     }
+
+    #[test]
+    fn structured_message() {
+        let message_id_name = CString::new("VUID-test").unwrap();
+        let message = CString::new("test message").unwrap();
+        let label_name = CString::new("test label").unwrap();
+        let object_name = CString::new("test object").unwrap();
+
+        let queue_labels = [ash::vk::DebugUtilsLabelEXT {
+            p_label_name: label_name.as_ptr(),
+            color: [1.0, 0.0, 0.0, 1.0],
+            ..Default::default()
+        }];
+        let objects = [
+            ash::vk::DebugUtilsObjectNameInfoEXT {
+                object_type: ash::vk::ObjectType::BUFFER,
+                object_handle: 1,
+                p_object_name: object_name.as_ptr(),
+                ..Default::default()
+            },
+            ash::vk::DebugUtilsObjectNameInfoEXT {
+                object_type: ash::vk::ObjectType::IMAGE,
+                object_handle: 2,
+                p_object_name: ptr::null(),
+                ..Default::default()
+            },
+        ];
+        let callback_data = ash::vk::DebugUtilsMessengerCallbackDataEXT {
+            p_message_id_name: message_id_name.as_ptr(),
+            message_id_number: 42,
+            p_message: message.as_ptr(),
+            queue_label_count: queue_labels.len() as u32,
+            p_queue_labels: queue_labels.as_ptr(),
+            cmd_buf_label_count: 0,
+            p_cmd_buf_labels: ptr::null(),
+            object_count: objects.len() as u32,
+            p_objects: objects.as_ptr(),
+            ..Default::default()
+        };
+
+        let received = Arc::new(Mutex::new(false));
+        let user_callback: Box<UserCallback> = Box::new({
+            let received = received.clone();
+            Arc::new(move |msg: &Message| {
+                assert_eq!(msg.layer_prefix, Some("VUID-test"));
+                assert_eq!(msg.message_id_number, 42);
+                assert_eq!(msg.description, "test message");
+                assert_eq!(msg.queue_labels.len(), 1);
+                assert_eq!(msg.queue_labels[0].label_name, "test label");
+                assert_eq!(msg.queue_labels[0].color, [1.0, 0.0, 0.0, 1.0]);
+                assert!(msg.cmd_buf_labels.is_empty());
+                assert_eq!(msg.objects.len(), 2);
+                assert_eq!(msg.objects[0].object_type, ash::vk::ObjectType::BUFFER);
+                assert_eq!(msg.objects[0].object_handle, 1);
+                assert_eq!(msg.objects[0].object_name, Some("test object"));
+                assert_eq!(msg.objects[1].object_name, None);
+                *received.lock().unwrap() = true;
+            })
+        });
+
+        unsafe {
+            trampoline(
+                ash::vk::DebugUtilsMessageSeverityFlagsEXT::WARNING,
+                ash::vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION,
+                &callback_data,
+                &*user_callback as &Arc<_> as *const Arc<_> as *const c_void as *mut _,
+            );
+        }
+
+        // Panics in the callback are caught, so check that it ran to the end.
+        assert!(*received.lock().unwrap());
+    }
 }
@@ -64,6 +64,7 @@ use crate::{
     check_errors,
     device::physical::{init_physical_devices, PhysicalDeviceInfo},
     instance::debug::{trampoline, DebugUtilsMessageSeverity, DebugUtilsMessageType},
+    memory::{host::HostAllocationCallbacks, HostAllocator},
     Error, OomError, VulkanObject,
 };
 pub use crate::{
@@ -74,7 +75,7 @@ pub use crate::{
 use smallvec::SmallVec;
 use std::{
     error,
-    ffi::{c_void, CString},
+    ffi::{c_void, CStr, CString},
     fmt,
     hash::{Hash, Hasher},
     mem::MaybeUninit,
@@ -231,6 +232,7 @@ pub struct Instance {
     enabled_extensions: InstanceExtensions,
     enabled_layers: Vec<String>,
     function_pointers: OwnedOrRef<FunctionPointers<Box<dyn Loader>>>,
+    host_allocation_callbacks: Option<HostAllocationCallbacks>,
     max_api_version: Version,
     user_callbacks: Vec<Box<UserCallback>>,
 }
@@ -282,6 +284,7 @@ impl Instance {
             engine_name,
             engine_version,
             function_pointers,
+            host_allocator,
             max_api_version,
             enumerate_portability,
             _ne: _,
@@ -419,13 +422,15 @@ impl Instance {
             create_info.p_next = info as *const _ as *const _;
         }
 
+        let host_allocation_callbacks = host_allocator.map(HostAllocationCallbacks::new);
+
         // Creating the Vulkan instance.
         let handle = {
             let mut output = MaybeUninit::uninit();
             let fns = function_pointers.fns();
             check_errors((fns.v1_0.create_instance)(
                 &create_info,
-                ptr::null(),
+                HostAllocationCallbacks::as_ptr(host_allocation_callbacks.as_ref()),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -447,6 +452,7 @@ impl Instance {
             enabled_extensions,
             enabled_layers,
             function_pointers,
+            host_allocation_callbacks,
             max_api_version,
             user_callbacks,
         };
@@ -479,6 +485,16 @@ impl Instance {
         &self.fns
     }
 
+    /// Loads an instance-level function pointer by name.
+    ///
+    /// This is needed for the physical-device-level functions of device extensions, which are not
+    /// part of `InstanceFunctions` and can't be loaded through `vkGetDeviceProcAddr`.
+    #[inline]
+    pub(crate) fn proc_addr(&self, name: &CStr) -> *const c_void {
+        self.function_pointers
+            .get_instance_proc_addr(self.handle, name.as_ptr())
+    }
+
     /// Returns the extensions that have been enabled on the instance.
     #[inline]
     pub fn enabled_extensions(&self) -> &InstanceExtensions {
@@ -490,6 +506,21 @@ impl Instance {
     pub fn enabled_layers(&self) -> &[String] {
         &self.enabled_layers
     }
+
+    /// Returns the host allocator that was specified when creating the instance, if any.
+    #[inline]
+    pub fn host_allocator(&self) -> Option<&Arc<dyn HostAllocator>> {
+        self.host_allocation_callbacks
+            .as_ref()
+            .map(HostAllocationCallbacks::allocator)
+    }
+
+    /// Returns the allocation callbacks to pass when creating or destroying the instance and its
+    /// child objects.
+    #[inline]
+    pub(crate) fn allocation_callbacks(&self) -> *const ash::vk::AllocationCallbacks {
+        HostAllocationCallbacks::as_ptr(self.host_allocation_callbacks.as_ref())
+    }
 }
 
 impl Drop for Instance {
@@ -498,7 +529,7 @@ impl Drop for Instance {
         let fns = self.fns();
 
         unsafe {
-            (fns.v1_0.destroy_instance)(self.handle, ptr::null());
+            (fns.v1_0.destroy_instance)(self.handle, self.allocation_callbacks());
         }
     }
 }
@@ -538,6 +569,7 @@ impl fmt::Debug for Instance {
             enabled_extensions,
             enabled_layers,
             function_pointers,
+            host_allocation_callbacks,
             max_api_version,
             user_callbacks: _,
         } = self;
@@ -550,6 +582,7 @@ impl fmt::Debug for Instance {
             .field("enabled_extensions", enabled_extensions)
             .field("enabled_layers", enabled_layers)
             .field("function_pointers", function_pointers)
+            .field("host_allocation_callbacks", host_allocation_callbacks)
             .field("max_api_version", max_api_version)
             .finish_non_exhaustive()
     }
@@ -593,6 +626,14 @@ pub struct InstanceCreateInfo {
     /// default.
     pub function_pointers: Option<FunctionPointers<Box<dyn Loader>>>,
 
+    /// A custom allocator for the host memory used by the Vulkan implementation.
+    ///
+    /// The allocator is used for the instance and all objects created from it, including devices
+    /// unless they specify their own allocator.
+    ///
+    /// The default value is `None`, which lets the implementation use its own allocator.
+    pub host_allocator: Option<Arc<dyn HostAllocator>>,
+
     /// The highest Vulkan API version that the application will use with the instance.
     ///
     /// Usually, you will want to leave this at the default.
@@ -630,6 +671,7 @@ impl Default for InstanceCreateInfo {
             engine_name: None,
             engine_version: Version::major_minor(0, 0),
             function_pointers: None,
+            host_allocator: None,
             max_api_version: None,
             enumerate_portability: false,
             _ne: crate::NonExhaustive(()),
@@ -771,13 +813,100 @@ impl<T> Deref for OwnedOrRef<T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::device::physical::PhysicalDevice;
+    use super::{Instance, InstanceCreateInfo};
+    use crate::{
+        device::physical::PhysicalDevice,
+        memory::{HostAllocationScope, HostAllocator},
+    };
+    use std::{
+        alloc::{self, Layout},
+        collections::HashMap,
+        ffi::c_void,
+        ptr,
+        sync::{Arc, Mutex},
+    };
 
     #[test]
     fn create_instance() {
         let _ = instance!();
     }
 
+    #[test]
+    fn create_instance_with_host_allocator() {
+        #[derive(Default)]
+        struct TrackingAllocator {
+            allocations: Mutex<HashMap<usize, Layout>>,
+        }
+
+        unsafe impl HostAllocator for TrackingAllocator {
+            fn allocate(
+                &self,
+                size: usize,
+                alignment: usize,
+                _scope: HostAllocationScope,
+            ) -> *mut c_void {
+                let layout = Layout::from_size_align(size, alignment).unwrap();
+                let memory = unsafe { alloc::alloc(layout) };
+
+                if !memory.is_null() {
+                    self.allocations
+                        .lock()
+                        .unwrap()
+                        .insert(memory as usize, layout);
+                }
+
+                memory as *mut c_void
+            }
+
+            fn reallocate(
+                &self,
+                original: *mut c_void,
+                size: usize,
+                alignment: usize,
+                scope: HostAllocationScope,
+            ) -> *mut c_void {
+                let original_layout = self.allocations.lock().unwrap()[&(original as usize)];
+                let memory = self.allocate(size, alignment, scope);
+
+                if !memory.is_null() {
+                    unsafe {
+                        ptr::copy_nonoverlapping(
+                            original as *const u8,
+                            memory as *mut u8,
+                            original_layout.size().min(size),
+                        );
+                    }
+                    self.free(original);
+                }
+
+                memory
+            }
+
+            fn free(&self, memory: *mut c_void) {
+                let layout = self
+                    .allocations
+                    .lock()
+                    .unwrap()
+                    .remove(&(memory as usize))
+                    .unwrap();
+                unsafe { alloc::dealloc(memory as *mut u8, layout) };
+            }
+        }
+
+        let allocator = Arc::new(TrackingAllocator::default());
+        let instance = match Instance::new(InstanceCreateInfo {
+            host_allocator: Some(allocator.clone()),
+            ..Default::default()
+        }) {
+            Ok(instance) => instance,
+            Err(_) => return,
+        };
+        assert!(instance.host_allocator().is_some());
+
+        drop(instance);
+        assert!(allocator.allocations.lock().unwrap().is_empty());
+    }
+
     #[test]
     fn queue_family_by_id() {
         let instance = instance!();
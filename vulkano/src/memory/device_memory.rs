@@ -9,6 +9,7 @@
 
 use super::DedicatedAllocation;
 use crate::{
+    buffer::ExternalBufferInfo,
     check_errors,
     device::{physical::MemoryType, Device, DeviceOwned},
     DeviceSize, Error, OomError, Version, VulkanObject,
@@ -21,7 +22,7 @@ use std::{
     hash::{Hash, Hasher},
     mem::MaybeUninit,
     ops::{BitOr, Range},
-    ptr, slice,
+    slice,
     sync::{Arc, Mutex},
 };
 
@@ -82,6 +83,8 @@ impl DeviceMemory {
             memory_type_index,
             dedicated_allocation,
             export_handle_types,
+            flags: _,
+            priority: _,
             _ne: _,
         } = allocate_info;
 
@@ -121,6 +124,8 @@ impl DeviceMemory {
             memory_type_index,
             dedicated_allocation,
             export_handle_types,
+            flags: _,
+            priority: _,
             _ne: _,
         } = allocate_info;
 
@@ -146,6 +151,8 @@ impl DeviceMemory {
             memory_type_index,
             ref mut dedicated_allocation,
             export_handle_types,
+            flags,
+            priority,
             _ne: _,
         } = allocate_info;
 
@@ -202,6 +209,29 @@ impl DeviceMemory {
                             },
                         );
                     }
+
+                    // VUID-VkExportMemoryAllocateInfo-handleTypes-00656
+                    for handle_type in export_handle_types.iter() {
+                        let external_buffer_properties = device
+                            .physical_device()
+                            .external_buffer_properties(ExternalBufferInfo {
+                                usage: *buffer.usage(),
+                                ..ExternalBufferInfo::handle_type(handle_type)
+                            });
+
+                        if let Some(external_buffer_properties) = external_buffer_properties {
+                            if !external_buffer_properties
+                                .external_memory_properties
+                                .exportable
+                            {
+                                return Err(
+                                    DeviceMemoryAllocationError::ExportHandleTypeNotSupported {
+                                        handle_type,
+                                    },
+                                );
+                            }
+                        }
+                    }
                 }
                 DedicatedAllocation::Image(image) => {
                     // VUID-VkMemoryDedicatedAllocateInfo-commonparent
@@ -235,6 +265,15 @@ impl DeviceMemory {
             });
         }
 
+        if (export_handle_types.opaque_win32 || export_handle_types.opaque_win32_kmt)
+            && !device.enabled_extensions().khr_external_memory_win32
+        {
+            return Err(DeviceMemoryAllocationError::ExtensionNotEnabled {
+                extension: "khr_external_memory_win32",
+                reason: "`export_handle_types.opaque_win32` or `export_handle_types.opaque_win32_kmt` was set",
+            });
+        }
+
         if export_handle_types.dma_buf && !device.enabled_extensions().ext_external_memory_dma_buf {
             return Err(DeviceMemoryAllocationError::ExtensionNotEnabled {
                 extension: "ext_external_memory_dma_buf",
@@ -242,6 +281,58 @@ impl DeviceMemory {
             });
         }
 
+        if !flags.is_empty() {
+            if !(device.api_version() >= Version::V1_1
+                || device.enabled_extensions().khr_device_group)
+            {
+                return Err(DeviceMemoryAllocationError::ExtensionNotEnabled {
+                    extension: "khr_device_group",
+                    reason: "`flags` was not empty",
+                });
+            }
+
+            // VUID-VkMemoryAllocateInfo-flags-03331
+            if flags.device_address && !device.enabled_features().buffer_device_address {
+                return Err(DeviceMemoryAllocationError::FeatureNotEnabled {
+                    feature: "buffer_device_address",
+                    reason: "`flags.device_address` was set",
+                });
+            }
+
+            // VUID-VkMemoryAllocateInfo-flags-03330
+            if flags.device_address_capture_replay
+                && !device
+                    .enabled_features()
+                    .buffer_device_address_capture_replay
+            {
+                return Err(DeviceMemoryAllocationError::FeatureNotEnabled {
+                    feature: "buffer_device_address_capture_replay",
+                    reason: "`flags.device_address_capture_replay` was set",
+                });
+            }
+        }
+
+        if priority != 0.5 {
+            if !device.enabled_extensions().ext_memory_priority {
+                return Err(DeviceMemoryAllocationError::ExtensionNotEnabled {
+                    extension: "ext_memory_priority",
+                    reason: "`priority` was not 0.5",
+                });
+            }
+
+            if !device.enabled_features().memory_priority {
+                return Err(DeviceMemoryAllocationError::FeatureNotEnabled {
+                    feature: "memory_priority",
+                    reason: "`priority` was not 0.5",
+                });
+            }
+
+            // VUID-VkMemoryPriorityAllocateInfoEXT-priority-02602
+            if !(0.0..=1.0).contains(&priority) {
+                return Err(DeviceMemoryAllocationError::PriorityOutOfRange);
+            }
+        }
+
         if let Some(import_info) = import_info {
             match import_info {
                 &mut MemoryImportInfo::Fd {
@@ -311,6 +402,8 @@ impl DeviceMemory {
             memory_type_index,
             dedicated_allocation,
             export_handle_types,
+            flags,
+            priority,
             _ne: _,
         } = allocate_info;
 
@@ -351,6 +444,32 @@ impl DeviceMemory {
             allocate_info = allocate_info.push_next(info);
         }
 
+        let mut flags_info = if !flags.is_empty() {
+            Some(ash::vk::MemoryAllocateFlagsInfo {
+                flags: flags.into(),
+                ..Default::default()
+            })
+        } else {
+            None
+        };
+
+        if let Some(info) = flags_info.as_mut() {
+            allocate_info = allocate_info.push_next(info);
+        }
+
+        let mut priority_allocate_info = if priority != 0.5 {
+            Some(ash::vk::MemoryPriorityAllocateInfoEXT {
+                priority,
+                ..Default::default()
+            })
+        } else {
+            None
+        };
+
+        if let Some(info) = priority_allocate_info.as_mut() {
+            allocate_info = allocate_info.push_next(info);
+        }
+
         #[cfg(unix)]
         let mut import_fd_info = match import_info {
             Some(MemoryImportInfo::Fd { handle_type, file }) => {
@@ -389,7 +508,7 @@ impl DeviceMemory {
             check_errors((fns.v1_0.allocate_memory)(
                 device.internal_object(),
                 &allocate_info.build(),
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -415,6 +534,31 @@ impl DeviceMemory {
         self.allocation_size
     }
 
+    /// Returns the number of bytes of lazily-allocated memory that are currently committed to
+    /// this allocation.
+    ///
+    /// The value may change over time, as the implementation commits more memory when needed.
+    ///
+    /// Returns an error if the memory type of this allocation is not lazily allocated.
+    #[inline]
+    pub fn commitment(&self) -> Result<DeviceSize, DeviceMemoryCommitmentError> {
+        // VUID-vkGetDeviceMemoryCommitment-memory-00690
+        if !self.memory_type().is_lazily_allocated() {
+            return Err(DeviceMemoryCommitmentError::NotLazilyAllocated);
+        }
+
+        unsafe {
+            let fns = self.device.fns();
+            let mut output: DeviceSize = 0;
+            (fns.v1_0.get_device_memory_commitment)(
+                self.device.internal_object(),
+                self.handle,
+                &mut output,
+            );
+            Ok(output)
+        }
+    }
+
     /// Exports the device memory into a Unix file descriptor. The caller owns the returned `File`.
     ///
     /// # Panic
@@ -470,6 +614,53 @@ impl DeviceMemory {
             Ok(file)
         }
     }
+
+    /// Exports the device memory into a Win32 handle.
+    ///
+    /// For the `OpaqueWin32` handle type, the caller owns the returned handle, and must close it
+    /// with `CloseHandle` when it is no longer needed. For the `OpaqueWin32Kmt` handle type, the
+    /// handle is not owned by the caller and must not be closed.
+    #[inline]
+    pub fn export_win32_handle(
+        &self,
+        handle_type: ExternalMemoryHandleType,
+    ) -> Result<*mut c_void, DeviceMemoryExportError> {
+        // VUID-VkMemoryGetWin32HandleInfoKHR-handleType-00664
+        if !matches!(
+            handle_type,
+            ExternalMemoryHandleType::OpaqueWin32 | ExternalMemoryHandleType::OpaqueWin32Kmt
+        ) {
+            return Err(DeviceMemoryExportError::HandleTypeNotSupported { handle_type });
+        }
+
+        // VUID-VkMemoryGetWin32HandleInfoKHR-handleType-00662
+        if !ash::vk::ExternalMemoryHandleTypeFlags::from(self.export_handle_types)
+            .intersects(ash::vk::ExternalMemoryHandleTypeFlags::from(handle_type))
+        {
+            return Err(DeviceMemoryExportError::HandleTypeNotSupported { handle_type });
+        }
+
+        debug_assert!(self.device().enabled_extensions().khr_external_memory_win32);
+
+        let handle = unsafe {
+            let fns = self.device.fns();
+            let info = ash::vk::MemoryGetWin32HandleInfoKHR {
+                memory: self.handle,
+                handle_type: handle_type.into(),
+                ..Default::default()
+            };
+
+            let mut output = MaybeUninit::uninit();
+            check_errors((fns.khr_external_memory_win32.get_memory_win32_handle_khr)(
+                self.device.internal_object(),
+                &info,
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(handle)
+    }
 }
 
 impl Drop for DeviceMemory {
@@ -477,7 +668,11 @@ impl Drop for DeviceMemory {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.free_memory)(self.device.internal_object(), self.handle, ptr::null());
+            (fns.v1_0.free_memory)(
+                self.device.internal_object(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
             let mut allocation_count = self
                 .device
                 .allocation_count()
@@ -549,6 +744,12 @@ pub enum DeviceMemoryAllocationError {
         required_size: DeviceSize,
     },
 
+    /// `dedicated_allocation` was `Some`, but one of the provided `export_handle_types` can't be
+    /// exported for the buffer, according to the physical device's external buffer properties.
+    ExportHandleTypeNotSupported {
+        handle_type: ExternalMemoryHandleType,
+    },
+
     /// The provided `MemoryImportInfo::Fd::handle_type` is not supported for file descriptors.
     ImportFdHandleTypeNotSupported {
         handle_type: ExternalMemoryHandleType,
@@ -567,6 +768,9 @@ pub enum DeviceMemoryAllocationError {
         memory_type_count: u32,
     },
 
+    /// The provided `priority` was not between 0.0 and 1.0 inclusive.
+    PriorityOutOfRange,
+
     /// Spec violation, containing the Valid Usage ID (VUID) from the Vulkan spec.
     // TODO: Remove
     SpecViolation(u32),
@@ -609,6 +813,11 @@ impl fmt::Display for DeviceMemoryAllocationError {
                 "`dedicated_allocation` was `Some`, but the provided `allocation_size` ({}) was different from the required size of the buffer or image ({})",
                 allocation_size, required_size,
             ),
+            Self::ExportHandleTypeNotSupported { handle_type } => write!(
+                fmt,
+                "`dedicated_allocation` was `Some`, but the export handle type {:?} can't be exported for the buffer",
+                handle_type,
+            ),
             Self::ImportFdHandleTypeNotSupported { handle_type } => write!(
                 fmt,
                 "the provided `MemoryImportInfo::Fd::handle_type` ({:?}) is not supported for file descriptors",
@@ -624,6 +833,10 @@ impl fmt::Display for DeviceMemoryAllocationError {
                 "the provided `memory_type_index` ({}) was not less than the number of memory types in the physical device ({})",
                 memory_type_index, memory_type_count,
             ),
+            Self::PriorityOutOfRange => write!(
+                fmt,
+                "the provided `priority` was not between 0.0 and 1.0 inclusive",
+            ),
 
             Self::SpecViolation(u) => {
                 write!(fmt, "valid usage ID check {} failed", u)
@@ -684,6 +897,27 @@ pub struct MemoryAllocateInfo<'d> {
     /// The handle types that can be exported from the allocated memory.
     pub export_handle_types: ExternalMemoryHandleTypes,
 
+    /// Additional properties of the allocation.
+    ///
+    /// If not empty, the device API version must be at least 1.1, or the
+    /// [`khr_device_group`](crate::device::DeviceExtensions::khr_device_group) extension must be
+    /// enabled on the device.
+    ///
+    /// The default value is [`MemoryAllocateFlags::none()`].
+    pub flags: MemoryAllocateFlags,
+
+    /// The priority of the allocation relative to other allocations, between 0.0 and 1.0. The
+    /// implementation may use this to decide which allocations to keep in device-local memory
+    /// when it is oversubscribed.
+    ///
+    /// If set to a value other than 0.5, the
+    /// [`ext_memory_priority`](crate::device::DeviceExtensions::ext_memory_priority) extension
+    /// and the [`memory_priority`](crate::device::Features::memory_priority) feature must be
+    /// enabled on the device.
+    ///
+    /// The default value is `0.5`.
+    pub priority: f32,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -695,6 +929,8 @@ impl Default for MemoryAllocateInfo<'static> {
             memory_type_index: u32::MAX,
             dedicated_allocation: None,
             export_handle_types: ExternalMemoryHandleTypes::none(),
+            flags: MemoryAllocateFlags::none(),
+            priority: 0.5,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -708,11 +944,69 @@ impl<'d> MemoryAllocateInfo<'d> {
             memory_type_index: u32::MAX,
             dedicated_allocation: Some(dedicated_allocation),
             export_handle_types: ExternalMemoryHandleTypes::none(),
+            flags: MemoryAllocateFlags::none(),
+            priority: 0.5,
             _ne: crate::NonExhaustive(()),
         }
     }
 }
 
+/// Flags specifying additional properties of a device memory allocation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MemoryAllocateFlags {
+    /// The memory can be bound to buffers with the
+    /// [`device_address`](crate::buffer::BufferUsage::device_address) usage, whose device
+    /// address can then be queried.
+    ///
+    /// The [`buffer_device_address`](crate::device::Features::buffer_device_address) feature
+    /// must be enabled on the device.
+    pub device_address: bool,
+
+    /// The device address of the memory can be saved and reused on a subsequent run, for trace
+    /// capture and replay.
+    ///
+    /// The
+    /// [`buffer_device_address_capture_replay`](crate::device::Features::buffer_device_address_capture_replay)
+    /// feature must be enabled on the device.
+    pub device_address_capture_replay: bool,
+}
+
+impl MemoryAllocateFlags {
+    /// Builds a `MemoryAllocateFlags` with all values set to false.
+    #[inline]
+    pub fn none() -> Self {
+        MemoryAllocateFlags {
+            device_address: false,
+            device_address_capture_replay: false,
+        }
+    }
+
+    /// Returns whether none of the fields are set.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        let MemoryAllocateFlags {
+            device_address,
+            device_address_capture_replay,
+        } = *self;
+
+        !(device_address || device_address_capture_replay)
+    }
+}
+
+impl From<MemoryAllocateFlags> for ash::vk::MemoryAllocateFlags {
+    #[inline]
+    fn from(val: MemoryAllocateFlags) -> Self {
+        let mut result = ash::vk::MemoryAllocateFlags::empty();
+        if val.device_address {
+            result |= ash::vk::MemoryAllocateFlags::DEVICE_ADDRESS;
+        }
+        if val.device_address_capture_replay {
+            result |= ash::vk::MemoryAllocateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY;
+        }
+        result
+    }
+}
+
 /// Parameters to import memory from an external source.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -1054,6 +1348,27 @@ impl From<OomError> for DeviceMemoryExportError {
     }
 }
 
+/// Error type returned by [`DeviceMemory::commitment`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DeviceMemoryCommitmentError {
+    /// The memory type of the allocation is not lazily allocated.
+    NotLazilyAllocated,
+}
+
+impl error::Error for DeviceMemoryCommitmentError {}
+
+impl fmt::Display for DeviceMemoryCommitmentError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::NotLazilyAllocated => write!(
+                fmt,
+                "the memory type of the allocation is not lazily allocated",
+            ),
+        }
+    }
+}
+
 /// Represents device memory that has been mapped in a CPU-accessible space.
 ///
 /// In order to access the contents of the allocated memory, you can use the `read` and `write`
@@ -1189,6 +1504,27 @@ impl MappedDeviceMemory {
         self.memory
     }
 
+    /// Expands `range` so that it can be passed to [`flush_range`](Self::flush_range) and
+    /// [`invalidate_range`](Self::invalidate_range).
+    ///
+    /// If the memory is not host-coherent, the start of `range` is rounded down and the end is
+    /// rounded up to a multiple of the
+    /// [`non_coherent_atom_size`](crate::device::Properties::non_coherent_atom_size) device
+    /// property, without going past the end of the memory mapping. If the memory is
+    /// host-coherent, `range` is returned unchanged.
+    #[inline]
+    pub fn atom_aligned_range(&self, range: Range<DeviceSize>) -> Range<DeviceSize> {
+        if self.coherent {
+            return range;
+        }
+
+        let start = range.start - range.start % self.atom_size;
+        let end = ((range.end + self.atom_size - 1) / self.atom_size * self.atom_size)
+            .min(self.range.end);
+
+        start..end
+    }
+
     /// Invalidates the host (CPU) cache for a range of mapped memory.
     ///
     /// If the mapped memory is not host-coherent, you must call this function before the memory is
@@ -1475,9 +1811,12 @@ impl From<OomError> for MemoryMapError {
 
 #[cfg(test)]
 mod tests {
-    use super::MemoryAllocateInfo;
+    use super::{ExternalMemoryHandleTypes, MemoryAllocateFlags, MemoryAllocateInfo};
+    use crate::memory::pool::StdHostVisibleMemoryTypePool;
     use crate::memory::DeviceMemory;
     use crate::memory::DeviceMemoryAllocationError;
+    use crate::memory::DeviceMemoryCommitmentError;
+    use crate::memory::MappedDeviceMemory;
     use crate::OomError;
 
     #[test]
@@ -1495,6 +1834,37 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn commitment() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        for memory_type in device.physical_device().memory_types() {
+            // Allocating protected memory requires the `protected_memory` feature.
+            if memory_type.is_protected() {
+                continue;
+            }
+
+            let memory = DeviceMemory::allocate(
+                device.clone(),
+                MemoryAllocateInfo {
+                    allocation_size: 256,
+                    memory_type_index: memory_type.id(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            if memory_type.is_lazily_allocated() {
+                assert!(memory.commitment().unwrap() <= memory.allocation_size());
+            } else {
+                assert_eq!(
+                    memory.commitment(),
+                    Err(DeviceMemoryCommitmentError::NotLazilyAllocated)
+                );
+            }
+        }
+    }
+
     #[test]
     fn zero_size() {
         let (device, _) = gfx_dev_and_queue!();
@@ -1536,6 +1906,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn priority_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_type = device.physical_device().memory_types().next().unwrap();
+
+        match DeviceMemory::allocate(
+            device.clone(),
+            MemoryAllocateInfo {
+                allocation_size: 256,
+                memory_type_index: memory_type.id(),
+                priority: 1.0,
+                ..Default::default()
+            },
+        ) {
+            Err(DeviceMemoryAllocationError::ExtensionNotEnabled {
+                extension: "ext_memory_priority",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn export_win32_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_type = device.physical_device().memory_types().next().unwrap();
+
+        match DeviceMemory::allocate(
+            device.clone(),
+            MemoryAllocateInfo {
+                allocation_size: 256,
+                memory_type_index: memory_type.id(),
+                export_handle_types: ExternalMemoryHandleTypes {
+                    opaque_win32: true,
+                    ..ExternalMemoryHandleTypes::none()
+                },
+                ..Default::default()
+            },
+        ) {
+            Err(DeviceMemoryAllocationError::ExtensionNotEnabled {
+                extension: "khr_external_memory_win32",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn device_address_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_type = device.physical_device().memory_types().next().unwrap();
+
+        match DeviceMemory::allocate(
+            device.clone(),
+            MemoryAllocateInfo {
+                allocation_size: 256,
+                memory_type_index: memory_type.id(),
+                flags: MemoryAllocateFlags {
+                    device_address: true,
+                    ..MemoryAllocateFlags::none()
+                },
+                ..Default::default()
+            },
+        ) {
+            Err(DeviceMemoryAllocationError::FeatureNotEnabled {
+                feature: "buffer_device_address",
+                ..
+            })
+            | Err(DeviceMemoryAllocationError::ExtensionNotEnabled {
+                extension: "khr_device_group",
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     #[ignore] // TODO: test fails for now on Mesa+Intel
     fn oom_multi() {
@@ -1597,4 +2043,61 @@ mod tests {
         }
         assert_eq!(*device.allocation_count().lock().unwrap(), 1);
     }
+
+    #[test]
+    fn non_coherent_round_trip() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_type = match device
+            .physical_device()
+            .memory_types()
+            .find(|t| t.is_host_visible() && !t.is_host_coherent())
+        {
+            Some(t) => t,
+            None => return,
+        };
+        let atom_size = device.physical_device().properties().non_coherent_atom_size;
+
+        let memory = DeviceMemory::allocate(
+            device.clone(),
+            MemoryAllocateInfo {
+                allocation_size: atom_size * 4,
+                memory_type_index: memory_type.id(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let memory = MappedDeviceMemory::new(memory, 0..atom_size * 4).unwrap();
+
+        let range = atom_size / 2 + 1..atom_size * 2 + 3;
+        let aligned_range = memory.atom_aligned_range(range.clone());
+        assert!(aligned_range.start <= range.start && aligned_range.end >= range.end);
+        assert_eq!(aligned_range.start % atom_size, 0);
+        assert_eq!(aligned_range.end % atom_size, 0);
+
+        let offset = (range.start - aligned_range.start) as usize;
+        let len = (range.end - range.start) as usize;
+
+        unsafe {
+            let bytes = memory.write(aligned_range.clone()).unwrap();
+            for (i, byte) in bytes[offset..offset + len].iter_mut().enumerate() {
+                *byte = i as u8;
+            }
+            memory.flush_range(aligned_range.clone()).unwrap();
+
+            memory.invalidate_range(aligned_range.clone()).unwrap();
+            let bytes = memory.read(aligned_range).unwrap();
+            assert!(bytes[offset..offset + len]
+                .iter()
+                .enumerate()
+                .all(|(i, &byte)| byte == i as u8));
+        }
+
+        // Allocations from the pool don't share any atom.
+        let pool = StdHostVisibleMemoryTypePool::new(device.clone(), memory_type);
+        let first = StdHostVisibleMemoryTypePool::alloc(&pool, 1, 1).unwrap();
+        let second = StdHostVisibleMemoryTypePool::alloc(&pool, 1, 1).unwrap();
+        assert_eq!(first.offset() % atom_size, 0);
+        assert_eq!(second.offset() % atom_size, 0);
+        assert_ne!(first.offset(), second.offset());
+    }
 }
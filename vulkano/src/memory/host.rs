@@ -0,0 +1,188 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Custom allocation of the host memory used by the Vulkan implementation.
+//!
+//! By default, the Vulkan implementation allocates host memory for its internal objects with its
+//! own allocator. If you provide a [`HostAllocator`] when creating an instance or a device, the
+//! implementation will use it instead, for the object itself and for every object created from
+//! it.
+
+use std::{ffi::c_void, fmt, ptr, sync::Arc};
+
+/// An allocator of host memory, to be used by the Vulkan implementation.
+///
+/// # Safety
+///
+/// - `allocate` and `reallocate` must return either a null pointer, if the allocation failed, or
+///   a pointer to a block of memory of at least `size` bytes, aligned to `alignment`, that stays
+///   valid until it is reallocated or freed.
+/// - `reallocate` must preserve the contents of the original allocation, up to the smaller of the
+///   original and the new size.
+pub unsafe trait HostAllocator: Send + Sync {
+    /// Allocates `size` bytes of memory, aligned to `alignment`. Returns a null pointer if the
+    /// allocation failed.
+    fn allocate(&self, size: usize, alignment: usize, scope: HostAllocationScope) -> *mut c_void;
+
+    /// Changes the size of the allocation `original` to `size` bytes, aligned to `alignment`.
+    /// Returns a null pointer if the reallocation failed, in which case `original` must be left
+    /// untouched.
+    ///
+    /// `original` is never null, and `size` is never zero.
+    fn reallocate(
+        &self,
+        original: *mut c_void,
+        size: usize,
+        alignment: usize,
+        scope: HostAllocationScope,
+    ) -> *mut c_void;
+
+    /// Frees the allocation `memory`, which was returned by `allocate` or `reallocate`.
+    ///
+    /// `memory` is never null.
+    fn free(&self, memory: *mut c_void);
+}
+
+impl fmt::Debug for dyn HostAllocator {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("dyn HostAllocator").finish_non_exhaustive()
+    }
+}
+
+/// Describes how long a host memory allocation is expected to live.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum HostAllocationScope {
+    /// The allocation only lives for the duration of a Vulkan command.
+    Command = ash::vk::SystemAllocationScope::COMMAND.as_raw(),
+
+    /// The allocation lives as long as a Vulkan object.
+    Object = ash::vk::SystemAllocationScope::OBJECT.as_raw(),
+
+    /// The allocation lives as long as a pipeline cache or validation cache.
+    Cache = ash::vk::SystemAllocationScope::CACHE.as_raw(),
+
+    /// The allocation lives as long as the device.
+    Device = ash::vk::SystemAllocationScope::DEVICE.as_raw(),
+
+    /// The allocation lives as long as the instance.
+    Instance = ash::vk::SystemAllocationScope::INSTANCE.as_raw(),
+}
+
+impl From<ash::vk::SystemAllocationScope> for HostAllocationScope {
+    #[inline]
+    fn from(val: ash::vk::SystemAllocationScope) -> Self {
+        match val {
+            ash::vk::SystemAllocationScope::COMMAND => Self::Command,
+            ash::vk::SystemAllocationScope::CACHE => Self::Cache,
+            ash::vk::SystemAllocationScope::DEVICE => Self::Device,
+            ash::vk::SystemAllocationScope::INSTANCE => Self::Instance,
+            // This is called from the Vulkan implementation, so it must not panic.
+            _ => Self::Object,
+        }
+    }
+}
+
+/// Owns a `HostAllocator` and the `VkAllocationCallbacks` that forward to it.
+pub(crate) struct HostAllocationCallbacks {
+    // Boxed so that the address given to the implementation as user data never changes.
+    allocator: Box<Arc<dyn HostAllocator>>,
+    callbacks_vk: ash::vk::AllocationCallbacks,
+}
+
+// The raw pointers in `callbacks_vk` only point to `allocator`, which is `Send` and `Sync`.
+unsafe impl Send for HostAllocationCallbacks {}
+unsafe impl Sync for HostAllocationCallbacks {}
+
+impl HostAllocationCallbacks {
+    pub(crate) fn new(allocator: Arc<dyn HostAllocator>) -> Self {
+        let allocator = Box::new(allocator);
+        let callbacks_vk = ash::vk::AllocationCallbacks {
+            p_user_data: &*allocator as *const Arc<dyn HostAllocator> as *mut c_void,
+            pfn_allocation: Some(allocation_trampoline),
+            pfn_reallocation: Some(reallocation_trampoline),
+            pfn_free: Some(free_trampoline),
+            pfn_internal_allocation: None,
+            pfn_internal_free: None,
+        };
+
+        Self {
+            allocator,
+            callbacks_vk,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn allocator(&self) -> &Arc<dyn HostAllocator> {
+        &self.allocator
+    }
+
+    /// Returns a pointer to pass as the `pAllocator` parameter of Vulkan functions, or null if
+    /// `callbacks` is `None`.
+    #[inline]
+    pub(crate) fn as_ptr(callbacks: Option<&Self>) -> *const ash::vk::AllocationCallbacks {
+        callbacks.map_or(ptr::null(), |callbacks| &callbacks.callbacks_vk)
+    }
+}
+
+impl fmt::Debug for HostAllocationCallbacks {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("HostAllocationCallbacks")
+            .field("allocator", &self.allocator)
+            .finish_non_exhaustive()
+    }
+}
+
+unsafe extern "system" fn allocation_trampoline(
+    user_data: *mut c_void,
+    size: usize,
+    alignment: usize,
+    scope: ash::vk::SystemAllocationScope,
+) -> *mut c_void {
+    let allocator = &*(user_data as *const Arc<dyn HostAllocator>);
+    allocator.allocate(size, alignment, scope.into())
+}
+
+unsafe extern "system" fn reallocation_trampoline(
+    user_data: *mut c_void,
+    original: *mut c_void,
+    size: usize,
+    alignment: usize,
+    scope: ash::vk::SystemAllocationScope,
+) -> *mut c_void {
+    let allocator = &*(user_data as *const Arc<dyn HostAllocator>);
+
+    // "If pOriginal is NULL, then pfnReallocation must behave equivalently to a call to
+    // PFN_vkAllocationFunction with the same parameter values (without pOriginal)."
+    if original.is_null() {
+        return allocator.allocate(size, alignment, scope.into());
+    }
+
+    // "If size is zero, then pfnReallocation must behave equivalently to a call to
+    // PFN_vkFreeFunction with the same pUserData parameter value, and pMemory equal to
+    // pOriginal."
+    if size == 0 {
+        allocator.free(original);
+        return ptr::null_mut();
+    }
+
+    allocator.reallocate(original, size, alignment, scope.into())
+}
+
+unsafe extern "system" fn free_trampoline(user_data: *mut c_void, memory: *mut c_void) {
+    // "pMemory may be NULL, which the callback must handle safely."
+    if memory.is_null() {
+        return;
+    }
+
+    let allocator = &*(user_data as *const Arc<dyn HostAllocator>);
+    allocator.free(memory)
+}
@@ -94,15 +94,18 @@
 
 pub use self::{
     device_memory::{
-        DeviceMemory, DeviceMemoryAllocationError, DeviceMemoryExportError,
-        ExternalMemoryHandleType, ExternalMemoryHandleTypes, MappedDeviceMemory,
-        MemoryAllocateInfo, MemoryImportInfo, MemoryMapError,
+        DeviceMemory, DeviceMemoryAllocationError, DeviceMemoryCommitmentError,
+        DeviceMemoryExportError, ExternalMemoryHandleType, ExternalMemoryHandleTypes,
+        MappedDeviceMemory, MemoryAllocateFlags, MemoryAllocateInfo, MemoryImportInfo,
+        MemoryMapError,
     },
+    host::{HostAllocationScope, HostAllocator},
     pool::MemoryPool,
 };
 use crate::{buffer::sys::UnsafeBuffer, image::sys::UnsafeImage, DeviceSize};
 
 mod device_memory;
+pub mod host;
 pub mod pool;
 
 /// Represents requirements expressed by the Vulkan implementation when it comes to binding memory
@@ -11,6 +11,7 @@ use crate::device::physical::MemoryType;
 use crate::device::Device;
 use crate::instance::Instance;
 use crate::memory::device_memory::MemoryAllocateInfo;
+use crate::memory::pool::pool_allocate_flags;
 use crate::memory::DeviceMemory;
 use crate::memory::DeviceMemoryAllocationError;
 use crate::memory::MappedDeviceMemory;
@@ -76,6 +77,19 @@ impl StdHostVisibleMemoryTypePool {
             al * (1 + (val - 1) / al)
         }
 
+        // If the memory is not host-coherent, flushes and invalidations operate on whole atoms.
+        // Align the allocations to the atom size, so that these never touch another allocation.
+        let (alignment, occupied_size) = if me.memory_type().is_host_coherent() {
+            (alignment, size)
+        } else {
+            let atom_size = me
+                .device
+                .physical_device()
+                .properties()
+                .non_coherent_atom_size;
+            (alignment.max(atom_size), align(size, atom_size))
+        };
+
         // Find a location.
         let mut occupied = me.occupied.lock().unwrap();
 
@@ -86,8 +100,8 @@ impl StdHostVisibleMemoryTypePool {
                 let entry1 = entries[i].clone();
                 let entry1_end = align(entry1.end, alignment);
                 let entry2 = entries[i + 1].clone();
-                if entry1_end + size <= entry2.start {
-                    entries.insert(i + 1, entry1_end..entry1_end + size);
+                if entry1_end + occupied_size <= entry2.start {
+                    entries.insert(i + 1, entry1_end..entry1_end + occupied_size);
                     return Ok(StdHostVisibleMemoryTypePoolAlloc {
                         pool: me.clone(),
                         memory: dev_mem.clone(),
@@ -99,8 +113,8 @@ impl StdHostVisibleMemoryTypePool {
 
             // Try append at the end.
             let last_end = entries.last().map(|e| align(e.end, alignment)).unwrap_or(0);
-            if last_end + size <= (**dev_mem).as_ref().allocation_size() {
-                entries.push(last_end..last_end + size);
+            if last_end + occupied_size <= (**dev_mem).as_ref().allocation_size() {
+                entries.push(last_end..last_end + occupied_size);
                 return Ok(StdHostVisibleMemoryTypePoolAlloc {
                     pool: me.clone(),
                     memory: dev_mem.clone(),
@@ -113,12 +127,13 @@ impl StdHostVisibleMemoryTypePool {
         // We need to allocate a new block.
         let new_block = {
             const MIN_BLOCK_SIZE: DeviceSize = 8 * 1024 * 1024; // 8 MB
-            let allocation_size = cmp::max(MIN_BLOCK_SIZE, size.next_power_of_two());
+            let allocation_size = cmp::max(MIN_BLOCK_SIZE, occupied_size.next_power_of_two());
             let memory = DeviceMemory::allocate(
                 me.device.clone(),
                 MemoryAllocateInfo {
                     allocation_size,
                     memory_type_index: me.memory_type().id(),
+                    flags: pool_allocate_flags(&me.device),
                     ..Default::default()
                 },
             )?;
@@ -126,7 +141,7 @@ impl StdHostVisibleMemoryTypePool {
             Arc::new(new_block)
         };
 
-        occupied.push((new_block.clone(), vec![0..size]));
+        occupied.push((new_block.clone(), vec![0..occupied_size]));
         Ok(StdHostVisibleMemoryTypePoolAlloc {
             pool: me.clone(),
             memory: new_block,
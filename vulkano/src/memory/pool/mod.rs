@@ -21,8 +21,11 @@ use crate::memory::DeviceMemory;
 use crate::memory::DeviceMemoryAllocationError;
 use crate::memory::ExternalMemoryHandleTypes;
 use crate::memory::MappedDeviceMemory;
+use crate::memory::MemoryAllocateFlags;
 use crate::memory::MemoryRequirements;
 use crate::DeviceSize;
+use crate::Version;
+use smallvec::SmallVec;
 use std::sync::Arc;
 
 mod host_visible;
@@ -42,30 +45,57 @@ fn choose_allocation_memory_type<'s, F>(
 where
     F: FnMut(MemoryType) -> AllocFromRequirementsFilter,
 {
-    let mem_ty = {
-        let mut filter = |ty: MemoryType| {
-            if map == MappingRequirement::Map && !ty.is_host_visible() {
-                return AllocFromRequirementsFilter::Forbidden;
-            }
-            filter(ty)
-        };
-        let first_loop = device
-            .physical_device()
-            .memory_types()
-            .map(|t| (t, AllocFromRequirementsFilter::Preferred));
-        let second_loop = device
+    let mut filter = |ty: MemoryType| {
+        if map == MappingRequirement::Map && !ty.is_host_visible() {
+            return AllocFromRequirementsFilter::Forbidden;
+        }
+        filter(ty)
+    };
+    let id = choose_memory_type_id(
+        device
             .physical_device()
             .memory_types()
-            .map(|t| (t, AllocFromRequirementsFilter::Allowed));
-        first_loop
-            .chain(second_loop)
-            .filter(|&(t, _)| (requirements.memory_type_bits & (1 << t.id())) != 0)
-            .filter(|&(t, rq)| filter(t) == rq)
-            .next()
-            .expect("Couldn't find a memory type to allocate from")
-            .0
-    };
-    mem_ty
+            .map(|t| (t.id(), filter(t)))
+            .collect::<SmallVec<[_; 32]>>(),
+        requirements.memory_type_bits,
+    )
+    .expect("Couldn't find a memory type to allocate from");
+
+    device.physical_device().memory_type_by_id(id).unwrap()
+}
+
+// Returns the id of the first memory type permitted by `memory_type_bits` whose filter result is
+// `Preferred`, or if there is none, the first one whose filter result is `Allowed`.
+fn choose_memory_type_id<I>(memory_types: I, memory_type_bits: u32) -> Option<u32>
+where
+    I: IntoIterator<Item = (u32, AllocFromRequirementsFilter)>,
+    I::IntoIter: Clone,
+{
+    let memory_types = memory_types
+        .into_iter()
+        .filter(|&(id, _)| (memory_type_bits & (1 << id)) != 0);
+
+    [
+        AllocFromRequirementsFilter::Preferred,
+        AllocFromRequirementsFilter::Allowed,
+    ]
+    .into_iter()
+    .find_map(|rq| memory_types.clone().find(|&(_, f)| f == rq))
+    .map(|(id, _)| id)
+}
+
+// Memory allocated by the pools may be bound to a buffer with the `device_address` usage. If the
+// `buffer_device_address` feature was enabled through Vulkan 1.2 or `khr_buffer_device_address`,
+// such memory must be allocated with the `device_address` flag.
+pub(crate) fn pool_allocate_flags(device: &Device) -> MemoryAllocateFlags {
+    MemoryAllocateFlags {
+        device_address: device.enabled_features().buffer_device_address
+            && (device.api_version() >= Version::V1_2
+                || device.enabled_extensions().khr_buffer_device_address)
+            && (device.api_version() >= Version::V1_1
+                || device.enabled_extensions().khr_device_group),
+        ..MemoryAllocateFlags::none()
+    }
 }
 
 /// Allocate dedicated memory with exportable fd.
@@ -94,6 +124,7 @@ where
                 opaque_fd: true,
                 ..ExternalMemoryHandleTypes::none()
             },
+            flags: pool_allocate_flags(&device),
             ..MemoryAllocateInfo::dedicated_allocation(dedicated_allocation)
         },
     )?;
@@ -214,6 +245,7 @@ pub unsafe trait MemoryPool: DeviceOwned {
                 allocation_size: requirements.size,
                 memory_type_index: memory_type.id(),
                 dedicated_allocation,
+                flags: pool_allocate_flags(self.device()),
                 ..Default::default()
             },
         )?;
@@ -235,6 +267,83 @@ pub enum AllocFromRequirementsFilter {
     Forbidden,
 }
 
+/// Hint describing how an allocation is going to be used, which can be turned into a filter for
+/// [`MemoryPool::alloc_from_requirements`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MemoryUsage {
+    /// The memory is only accessed by the device. Device-local memory types are preferred, but
+    /// others are allowed.
+    GpuOnly,
+
+    /// The memory is written by the host and read by the device, for example a staging buffer.
+    /// Only host-visible memory types are allowed, and uncached ones are preferred.
+    Upload,
+
+    /// The memory is written by the device and read back by the host. Only host-visible memory
+    /// types are allowed, and cached ones are preferred.
+    Download,
+
+    /// The memory is updated frequently by the host and read by the device, for example a uniform
+    /// buffer that changes every frame. Only host-visible memory types are allowed, and memory
+    /// types that are also device-local are preferred.
+    ///
+    /// On systems where device memory is exposed to the host (e.g. with resizable BAR), this
+    /// lets the device read the data directly from its own memory. Otherwise, a host-visible
+    /// memory type that is not device-local is chosen, and the data is read across the bus like
+    /// it would be from a staging buffer.
+    UploadToDeviceLocal,
+}
+
+impl MemoryUsage {
+    /// Returns how suitable `ty` is for this usage.
+    #[inline]
+    pub fn filter(self, ty: MemoryType) -> AllocFromRequirementsFilter {
+        self.filter_properties(
+            ty.is_device_local(),
+            ty.is_host_visible(),
+            ty.is_host_cached(),
+        )
+    }
+
+    fn filter_properties(
+        self,
+        device_local: bool,
+        host_visible: bool,
+        host_cached: bool,
+    ) -> AllocFromRequirementsFilter {
+        let preferred = match self {
+            MemoryUsage::GpuOnly => device_local,
+            MemoryUsage::Upload => {
+                if !host_visible {
+                    return AllocFromRequirementsFilter::Forbidden;
+                }
+
+                !host_cached
+            }
+            MemoryUsage::Download => {
+                if !host_visible {
+                    return AllocFromRequirementsFilter::Forbidden;
+                }
+
+                host_cached
+            }
+            MemoryUsage::UploadToDeviceLocal => {
+                if !host_visible {
+                    return AllocFromRequirementsFilter::Forbidden;
+                }
+
+                device_local
+            }
+        };
+
+        if preferred {
+            AllocFromRequirementsFilter::Preferred
+        } else {
+            AllocFromRequirementsFilter::Allowed
+        }
+    }
+}
+
 /// Object that represents a single allocation. Its destructor should free the chunk.
 pub unsafe trait MemoryPoolAlloc: Send + Sync {
     /// Returns the memory object from which this is allocated. Returns `None` if the memory is
@@ -247,6 +356,21 @@ pub unsafe trait MemoryPoolAlloc: Send + Sync {
     /// Returns the offset at the start of the memory where the first byte of this allocation
     /// resides.
     fn offset(&self) -> DeviceSize;
+
+    /// Returns the size in bytes of this allocation.
+    ///
+    /// This is the amount of memory that is actually reserved for the allocation. For a resource,
+    /// it is the size given by its memory requirements, which the implementation may have
+    /// rounded up compared to the size of the resource itself. For a dedicated allocation, it is
+    /// the size of the whole `DeviceMemory` object.
+    ///
+    /// The default implementation returns the size of the memory object after `offset()`. This is
+    /// exact for a dedicated allocation, but overestimates the size of an allocation that shares
+    /// its memory object with others.
+    #[inline]
+    fn allocation_size(&self) -> DeviceSize {
+        self.memory().allocation_size() - self.offset()
+    }
 }
 
 /// Whether an allocation should map the memory or not.
@@ -306,6 +430,17 @@ where
             PotentialDedicatedAllocation::DedicatedMapped(_) => 0,
         }
     }
+
+    #[inline]
+    fn allocation_size(&self) -> DeviceSize {
+        match *self {
+            PotentialDedicatedAllocation::Generic(ref alloc) => alloc.allocation_size(),
+            PotentialDedicatedAllocation::Dedicated(ref mem) => mem.allocation_size(),
+            PotentialDedicatedAllocation::DedicatedMapped(ref mem) => {
+                mem.as_ref().allocation_size()
+            }
+        }
+    }
 }
 
 impl<A> From<A> for PotentialDedicatedAllocation<A> {
@@ -314,3 +449,70 @@ impl<A> From<A> for PotentialDedicatedAllocation<A> {
         PotentialDedicatedAllocation::Generic(alloc)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{choose_memory_type_id, MemoryUsage};
+
+    // (device_local, host_visible, host_cached)
+    const RESIZABLE_BAR: [(bool, bool, bool); 4] = [
+        (true, false, false),
+        (false, true, false),
+        (false, true, true),
+        (true, true, false),
+    ];
+    const NO_RESIZABLE_BAR: [(bool, bool, bool); 3] = [
+        (true, false, false),
+        (false, true, false),
+        (false, true, true),
+    ];
+
+    fn choose(
+        usage: MemoryUsage,
+        memory_types: &[(bool, bool, bool)],
+        memory_type_bits: u32,
+    ) -> Option<u32> {
+        choose_memory_type_id(
+            memory_types.iter().enumerate().map(
+                |(id, &(device_local, host_visible, host_cached))| {
+                    (
+                        id as u32,
+                        usage.filter_properties(device_local, host_visible, host_cached),
+                    )
+                },
+            ),
+            memory_type_bits,
+        )
+    }
+
+    #[test]
+    fn upload_to_device_local() {
+        assert_eq!(
+            choose(MemoryUsage::UploadToDeviceLocal, &RESIZABLE_BAR, !0),
+            Some(3)
+        );
+        assert_eq!(
+            choose(MemoryUsage::UploadToDeviceLocal, &NO_RESIZABLE_BAR, !0),
+            Some(1)
+        );
+        assert_eq!(
+            choose(MemoryUsage::UploadToDeviceLocal, &RESIZABLE_BAR, 0b0001),
+            None
+        );
+    }
+
+    #[test]
+    fn usage_preferences() {
+        assert_eq!(choose(MemoryUsage::GpuOnly, &RESIZABLE_BAR, !0), Some(0));
+        assert_eq!(
+            choose(MemoryUsage::GpuOnly, &RESIZABLE_BAR, 0b0110),
+            Some(1)
+        );
+        assert_eq!(choose(MemoryUsage::Upload, &RESIZABLE_BAR, !0), Some(1));
+        assert_eq!(choose(MemoryUsage::Download, &RESIZABLE_BAR, !0), Some(2));
+        assert_eq!(
+            choose(MemoryUsage::Download, &RESIZABLE_BAR, 0b1010),
+            Some(1)
+        );
+    }
+}
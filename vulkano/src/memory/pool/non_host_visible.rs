@@ -11,6 +11,7 @@ use crate::device::physical::MemoryType;
 use crate::device::Device;
 use crate::instance::Instance;
 use crate::memory::device_memory::MemoryAllocateInfo;
+use crate::memory::pool::pool_allocate_flags;
 use crate::memory::DeviceMemory;
 use crate::memory::DeviceMemoryAllocationError;
 use crate::DeviceSize;
@@ -119,6 +120,7 @@ impl StdNonHostVisibleMemoryTypePool {
                 MemoryAllocateInfo {
                     allocation_size,
                     memory_type_index: me.memory_type().id(),
+                    flags: pool_allocate_flags(&me.device),
                     ..Default::default()
                 },
             )?;
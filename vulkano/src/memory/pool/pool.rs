@@ -174,6 +174,11 @@ unsafe impl MemoryPoolAlloc for StdMemoryPoolAlloc {
             StdMemoryPoolAllocInner::HostVisible(ref mem) => mem.offset(),
         }
     }
+
+    #[inline]
+    fn allocation_size(&self) -> DeviceSize {
+        self.size()
+    }
 }
 
 #[derive(Debug)]
@@ -26,6 +26,8 @@ use crate::device::Device;
 use crate::OomError;
 use crate::Success;
 use crate::VulkanObject;
+use std::error;
+use std::fmt;
 use std::mem::MaybeUninit;
 use std::ptr;
 use std::sync::Arc;
@@ -47,11 +49,16 @@ impl PipelineCache {
     /// implementation. Therefore you can easily crash your application or the system by passing
     /// wrong data. Hence why this function is unsafe.
     ///
+    /// The header at the start of the data is checked against the physical device of `device`.
+    /// If the data is too short, has an unknown header version, or was created for a different
+    /// vendor, device or pipeline cache UUID, then `PipelineCacheError::Incompatible` is returned
+    /// instead of creating the cache. In that case you should discard the data and create an
+    /// empty cache instead. The rest of the data is not checked.
+    ///
     /// # Example
     ///
     /// This example loads a cache from a file, if it exists.
     /// See [`get_data`](#method.get_data) for how to store the data in a file.
-    /// TODO: there's a header in the cached data that must be checked ; talk about this
     ///
     /// ```
     /// # use std::sync::Arc;
@@ -82,8 +89,10 @@ impl PipelineCache {
     pub unsafe fn with_data(
         device: Arc<Device>,
         initial_data: &[u8],
-    ) -> Result<Arc<PipelineCache>, OomError> {
-        PipelineCache::new_impl(device, Some(initial_data))
+    ) -> Result<Arc<PipelineCache>, PipelineCacheError> {
+        check_header(&device, initial_data)?;
+
+        Ok(PipelineCache::new_impl(device, Some(initial_data))?)
     }
 
     /// Builds a new empty pipeline cache.
@@ -123,7 +132,7 @@ impl PipelineCache {
             check_errors((fns.v1_0.create_pipeline_cache)(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -231,6 +240,44 @@ impl PipelineCache {
     }
 }
 
+// Checks the header that is at the start of the data returned by `vkGetPipelineCacheData`.
+fn check_header(device: &Device, data: &[u8]) -> Result<(), PipelineCacheError> {
+    const HEADER_SIZE: usize = 16 + ash::vk::UUID_SIZE;
+
+    if data.len() < HEADER_SIZE {
+        return Err(PipelineCacheError::Incompatible);
+    }
+
+    let read_u32 = |offset: usize| {
+        u32::from_ne_bytes([
+            data[offset],
+            data[offset + 1],
+            data[offset + 2],
+            data[offset + 3],
+        ])
+    };
+
+    let header_size = read_u32(0) as usize;
+    let header_version = read_u32(4);
+    let vendor_id = read_u32(8);
+    let device_id = read_u32(12);
+    let pipeline_cache_uuid = &data[16..HEADER_SIZE];
+
+    let properties = device.physical_device().properties();
+
+    if header_size < HEADER_SIZE
+        || header_size > data.len()
+        || header_version != ash::vk::PipelineCacheHeaderVersion::ONE.as_raw() as u32
+        || vendor_id != properties.vendor_id
+        || device_id != properties.device_id
+        || pipeline_cache_uuid != properties.pipeline_cache_uuid
+    {
+        return Err(PipelineCacheError::Incompatible);
+    }
+
+    Ok(())
+}
+
 unsafe impl VulkanObject for PipelineCache {
     type Object = ash::vk::PipelineCache;
 
@@ -248,15 +295,56 @@ impl Drop for PipelineCache {
             (fns.v1_0.destroy_pipeline_cache)(
                 self.device.internal_object(),
                 self.cache,
-                ptr::null(),
+                self.device.allocation_callbacks(),
             );
         }
     }
 }
 
+/// Error that can happen when creating a pipeline cache from existing data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PipelineCacheError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The data was not created by a device compatible with this one, or is not pipeline cache
+    /// data at all.
+    Incompatible,
+}
+
+impl error::Error for PipelineCacheError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PipelineCacheError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::OomError(_) => write!(fmt, "not enough memory available"),
+            Self::Incompatible => write!(
+                fmt,
+                "the data was not created by a device compatible with this one",
+            ),
+        }
+    }
+}
+
+impl From<OomError> for PipelineCacheError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::pipeline::cache::PipelineCache;
+    use crate::pipeline::cache::{PipelineCache, PipelineCacheError};
     use crate::pipeline::ComputePipeline;
     use crate::shader::ShaderModule;
     use std::sync::Arc;
@@ -454,4 +542,38 @@ mod tests {
 
         assert_eq!(cache_data, second_data);
     }
+
+    #[test]
+    fn with_data_roundtrip() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cache = PipelineCache::empty(device.clone()).unwrap();
+        let data = cache.get_data().unwrap();
+
+        unsafe {
+            PipelineCache::with_data(device.clone(), &data).unwrap();
+        }
+    }
+
+    #[test]
+    fn with_data_incompatible() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cache = PipelineCache::empty(device.clone()).unwrap();
+        let mut data = cache.get_data().unwrap();
+
+        // Corrupt the pipeline cache UUID.
+        data[16] = !data[16];
+
+        unsafe {
+            assert_eq!(
+                PipelineCache::with_data(device.clone(), &data).unwrap_err(),
+                PipelineCacheError::Incompatible,
+            );
+            assert_eq!(
+                PipelineCache::with_data(device.clone(), &[0; 8]).unwrap_err(),
+                PipelineCacheError::Incompatible,
+            );
+        }
+    }
 }
@@ -29,21 +29,29 @@ use crate::descriptor_set::layout::{
 };
 use crate::device::{Device, DeviceOwned};
 use crate::pipeline::cache::PipelineCache;
+use crate::pipeline::executable::{
+    self, PipelineExecutableError, PipelineExecutableInfo, PipelineExecutableInternalRepresentation,
+};
 use crate::pipeline::layout::{
     PipelineLayout, PipelineLayoutCreationError, PipelineLayoutSupersetError,
 };
 use crate::pipeline::{Pipeline, PipelineBindPoint};
-use crate::shader::{DescriptorRequirements, EntryPoint, SpecializationConstants};
+use crate::shader::{
+    DescriptorRequirements, EntryPoint, ShaderExecution, SpecializationConstant,
+    SpecializationConstants, SpecializationMapEntry,
+};
 use crate::DeviceSize;
 use crate::Error;
 use crate::OomError;
 use crate::VulkanObject;
+use smallvec::SmallVec;
 use std::collections::HashMap;
 use std::error;
 use std::fmt;
 use std::mem;
 use std::mem::MaybeUninit;
 use std::ptr;
+use std::slice;
 use std::sync::Arc;
 
 /// A pipeline object that describes to the Vulkan implementation how it should perform compute
@@ -60,6 +68,8 @@ pub struct ComputePipeline {
     layout: Arc<PipelineLayout>,
     descriptor_requirements: HashMap<(u32, u32), DescriptorRequirements>,
     num_used_descriptor_sets: u32,
+    local_size: Option<[u32; 3]>,
+    allow_derivatives: bool,
 }
 
 impl ComputePipeline {
@@ -78,6 +88,308 @@ impl ComputePipeline {
     where
         Css: SpecializationConstants,
         F: FnOnce(&mut [DescriptorSetLayoutCreateInfo]),
+    {
+        let layout = Self::layout_from_shader(&device, &shader, func)?;
+
+        unsafe {
+            ComputePipeline::with_unchecked_pipeline_layout(
+                device,
+                shader,
+                specialization_constants,
+                layout,
+                cache,
+            )
+        }
+    }
+
+    /// Builds a new `ComputePipeline`, with the values of the specialization constants provided
+    /// at runtime.
+    ///
+    /// `specialization_constants` maps the ID of a specialization constant to its value. Each ID
+    /// must be declared as a specialization constant by the shader, and the value must have the
+    /// same type as the declaration. Constants of the shader that are not given a value keep
+    /// their default value.
+    ///
+    /// `func` is the same as in [`new`](ComputePipeline::new).
+    pub fn with_specialization<F>(
+        device: Arc<Device>,
+        shader: EntryPoint,
+        specialization_constants: &HashMap<u32, SpecializationConstant>,
+        cache: Option<Arc<PipelineCache>>,
+        func: F,
+    ) -> Result<Arc<ComputePipeline>, ComputePipelineCreationError>
+    where
+        F: FnOnce(&mut [DescriptorSetLayoutCreateInfo]),
+    {
+        let layout = Self::layout_from_shader(&device, &shader, func)?;
+
+        let mut pipelines = ComputePipeline::new_batch(
+            device,
+            vec![ComputePipelineCreateInfo {
+                specialization_constants: specialization_constants.clone(),
+                ..ComputePipelineCreateInfo::shader(shader, layout)
+            }],
+            cache,
+        )?;
+
+        Ok(pipelines.pop().unwrap())
+    }
+
+    /// Builds several `ComputePipeline`s at once, with a single call to the Vulkan
+    /// implementation.
+    ///
+    /// Each pipeline can be derived from a previously created pipeline, or from a pipeline that
+    /// comes earlier in `create_infos`, by setting its `base_pipeline`. Implementations may be
+    /// able to create derivatives faster than unrelated pipelines. The base pipeline must have
+    /// been created with `allow_derivatives` set.
+    ///
+    /// The returned pipelines are in the same order as `create_infos`.
+    pub fn new_batch(
+        device: Arc<Device>,
+        create_infos: Vec<ComputePipelineCreateInfo>,
+        cache: Option<Arc<PipelineCache>>,
+    ) -> Result<Vec<Arc<ComputePipeline>>, ComputePipelineCreationError> {
+        let mut local_sizes = Vec::with_capacity(create_infos.len());
+        let mut specializations = Vec::with_capacity(create_infos.len());
+
+        for (index, create_info) in create_infos.iter().enumerate() {
+            let &ComputePipelineCreateInfo {
+                ref shader,
+                ref specialization_constants,
+                ref layout,
+                allow_derivatives: _,
+                ref base_pipeline,
+                _ne: _,
+            } = create_info;
+
+            // VUID-VkComputePipelineCreateInfo-commonparent
+            assert_eq!(&device, layout.device());
+
+            // VUID-VkPipelineShaderStageCreateInfo-pName-00707
+            local_sizes.push(match shader.execution() {
+                ShaderExecution::Compute(execution) => execution.local_size,
+                _ => return Err(ComputePipelineCreationError::WrongShaderType),
+            });
+
+            specializations.push(Self::specialization_info(shader, specialization_constants)?);
+
+            // VUID-VkComputePipelineCreateInfo-layout-00703
+            // VUID-VkComputePipelineCreateInfo-layout-00704
+            layout.ensure_compatible_with_shader(
+                shader.descriptor_requirements(),
+                shader.push_constant_requirements(),
+            )?;
+
+            match base_pipeline {
+                Some(ComputeBasePipeline::Pipeline(base_pipeline)) => {
+                    // VUID-VkComputePipelineCreateInfo-commonparent
+                    assert_eq!(&device, base_pipeline.device());
+
+                    // VUID-vkCreateComputePipelines-flags-00696
+                    if !base_pipeline.allow_derivatives {
+                        return Err(
+                            ComputePipelineCreationError::BasePipelineDerivativesNotAllowed {
+                                index,
+                            },
+                        );
+                    }
+                }
+                &Some(ComputeBasePipeline::Index(base_index)) => {
+                    // VUID-vkCreateComputePipelines-flags-00695
+                    if base_index >= index {
+                        return Err(ComputePipelineCreationError::BasePipelineIndexOutOfRange {
+                            index,
+                        });
+                    }
+
+                    // VUID-vkCreateComputePipelines-flags-00696
+                    if !create_infos[base_index].allow_derivatives {
+                        return Err(
+                            ComputePipelineCreationError::BasePipelineDerivativesNotAllowed {
+                                index,
+                            },
+                        );
+                    }
+                }
+                None => (),
+            }
+        }
+
+        let handles = unsafe {
+            let specializations_vk: SmallVec<[_; 4]> = specializations
+                .iter()
+                .map(
+                    |(spec_descriptors, spec_data)| ash::vk::SpecializationInfo {
+                        map_entry_count: spec_descriptors.len() as u32,
+                        p_map_entries: spec_descriptors.as_ptr() as *const _,
+                        data_size: spec_data.len(),
+                        p_data: spec_data.as_ptr() as *const _,
+                    },
+                )
+                .collect();
+
+            let create_infos_vk: SmallVec<[_; 4]> = create_infos
+                .iter()
+                .zip(&specializations_vk)
+                .map(|(create_info, specialization)| {
+                    let mut flags = executable::create_flags(&device);
+
+                    if create_info.allow_derivatives {
+                        flags |= ash::vk::PipelineCreateFlags::ALLOW_DERIVATIVES;
+                    }
+
+                    let (base_pipeline_handle, base_pipeline_index) =
+                        match create_info.base_pipeline {
+                            Some(ComputeBasePipeline::Pipeline(ref base_pipeline)) => {
+                                flags |= ash::vk::PipelineCreateFlags::DERIVATIVE;
+                                (base_pipeline.handle, -1)
+                            }
+                            Some(ComputeBasePipeline::Index(base_index)) => {
+                                flags |= ash::vk::PipelineCreateFlags::DERIVATIVE;
+                                (ash::vk::Pipeline::null(), base_index as i32)
+                            }
+                            None => (ash::vk::Pipeline::null(), -1),
+                        };
+
+                    ash::vk::ComputePipelineCreateInfo {
+                        flags,
+                        stage: ash::vk::PipelineShaderStageCreateInfo {
+                            flags: ash::vk::PipelineShaderStageCreateFlags::empty(),
+                            stage: ash::vk::ShaderStageFlags::COMPUTE,
+                            module: create_info.shader.module().internal_object(),
+                            p_name: create_info.shader.name().as_ptr(),
+                            p_specialization_info: if specialization.data_size == 0 {
+                                ptr::null()
+                            } else {
+                                specialization
+                            },
+                            ..Default::default()
+                        },
+                        layout: create_info.layout.internal_object(),
+                        base_pipeline_handle,
+                        base_pipeline_index,
+                        ..Default::default()
+                    }
+                })
+                .collect();
+
+            let cache_handle = match cache {
+                Some(ref cache) => cache.internal_object(),
+                None => ash::vk::PipelineCache::null(),
+            };
+
+            let fns = device.fns();
+            let mut output = Vec::with_capacity(create_infos_vk.len());
+            let result = check_errors((fns.v1_0.create_compute_pipelines)(
+                device.internal_object(),
+                cache_handle,
+                create_infos_vk.len() as u32,
+                create_infos_vk.as_ptr(),
+                device.allocation_callbacks(),
+                output.as_mut_ptr(),
+            ));
+            output.set_len(create_infos_vk.len());
+
+            if let Err(err) = result {
+                // The pipelines that could not be created are set to a null handle, but the
+                // others have to be destroyed.
+                for handle in output {
+                    if handle != ash::vk::Pipeline::null() {
+                        (fns.v1_0.destroy_pipeline)(
+                            device.internal_object(),
+                            handle,
+                            device.allocation_callbacks(),
+                        );
+                    }
+                }
+
+                return Err(err.into());
+            }
+
+            output
+        };
+
+        Ok(create_infos
+            .into_iter()
+            .zip(handles)
+            .zip(local_sizes)
+            .map(|((create_info, handle), local_size)| {
+                let descriptor_requirements: HashMap<_, _> = create_info
+                    .shader
+                    .descriptor_requirements()
+                    .map(|(loc, reqs)| (loc, reqs.clone()))
+                    .collect();
+                let num_used_descriptor_sets = descriptor_requirements
+                    .keys()
+                    .map(|loc| loc.0)
+                    .max()
+                    .map(|x| x + 1)
+                    .unwrap_or(0);
+
+                Arc::new(ComputePipeline {
+                    handle,
+                    device: device.clone(),
+                    layout: create_info.layout,
+                    descriptor_requirements,
+                    num_used_descriptor_sets,
+                    local_size,
+                    allow_derivatives: create_info.allow_derivatives,
+                })
+            })
+            .collect())
+    }
+
+    // Checks runtime specialization constant values against the requirements of `shader`, and
+    // converts them to map entries and data.
+    fn specialization_info(
+        shader: &EntryPoint,
+        specialization_constants: &HashMap<u32, SpecializationConstant>,
+    ) -> Result<(Vec<SpecializationMapEntry>, Vec<u8>), ComputePipelineCreationError> {
+        for (&constant_id, value) in specialization_constants {
+            let reqs = shader
+                .specialization_constant_requirements()
+                .find_map(|(id, reqs)| (id == constant_id).then(|| reqs))
+                .ok_or(
+                    ComputePipelineCreationError::SpecializationConstantNotFound { constant_id },
+                )?;
+
+            if reqs.ty != value.ty() || reqs.size != value.size() {
+                return Err(
+                    ComputePipelineCreationError::SpecializationConstantTypeMismatch {
+                        constant_id,
+                    },
+                );
+            }
+        }
+
+        // Sort by ID, so that the same constants always produce the same specialization info.
+        let mut constant_ids: Vec<u32> = specialization_constants.keys().copied().collect();
+        constant_ids.sort_unstable();
+
+        let mut spec_descriptors = Vec::with_capacity(constant_ids.len());
+        let mut spec_data = Vec::with_capacity(constant_ids.len() * 4);
+
+        for constant_id in constant_ids {
+            let value = &specialization_constants[&constant_id];
+            spec_descriptors.push(SpecializationMapEntry {
+                constant_id,
+                offset: spec_data.len() as u32,
+                size: value.size() as usize,
+            });
+            spec_data.extend_from_slice(&value.to_ne_bytes());
+        }
+
+        Ok((spec_descriptors, spec_data))
+    }
+
+    // Creates a pipeline layout from the requirements of `shader`.
+    fn layout_from_shader<F>(
+        device: &Arc<Device>,
+        shader: &EntryPoint,
+        func: F,
+    ) -> Result<Arc<PipelineLayout>, ComputePipelineCreationError>
+    where
+        F: FnOnce(&mut [DescriptorSetLayoutCreateInfo]),
     {
         let mut set_layout_create_infos =
             DescriptorSetLayoutCreateInfo::from_requirements(shader.descriptor_requirements());
@@ -87,7 +399,7 @@ impl ComputePipeline {
             .map(|desc| DescriptorSetLayout::new(device.clone(), desc.clone()))
             .collect::<Result<Vec<_>, _>>()?;
 
-        let layout = PipelineLayout::new(
+        Ok(PipelineLayout::new(
             device.clone(),
             PipelineLayoutCreateInfo {
                 set_layouts,
@@ -98,17 +410,7 @@ impl ComputePipeline {
                     .collect(),
                 ..Default::default()
             },
-        )?;
-
-        unsafe {
-            ComputePipeline::with_unchecked_pipeline_layout(
-                device,
-                shader,
-                specialization_constants,
-                layout,
-                cache,
-            )
-        }
+        )?)
     }
 
     /// Builds a new `ComputePipeline` with a specific pipeline layout.
@@ -166,15 +468,36 @@ impl ComputePipeline {
     where
         Css: SpecializationConstants,
     {
+        let spec_data = slice::from_raw_parts(
+            specialization_constants as *const Css as *const u8,
+            mem::size_of_val(specialization_constants),
+        );
+
+        ComputePipeline::new_unchecked(device, shader, Css::descriptors(), spec_data, layout, cache)
+    }
+
+    unsafe fn new_unchecked(
+        device: Arc<Device>,
+        shader: EntryPoint,
+        spec_descriptors: &[SpecializationMapEntry],
+        spec_data: &[u8],
+        layout: Arc<PipelineLayout>,
+        cache: Option<Arc<PipelineCache>>,
+    ) -> Result<Arc<ComputePipeline>, ComputePipelineCreationError> {
+        // VUID-VkPipelineShaderStageCreateInfo-pName-00707
+        let local_size = match shader.execution() {
+            ShaderExecution::Compute(execution) => execution.local_size,
+            _ => return Err(ComputePipelineCreationError::WrongShaderType),
+        };
+
         let fns = device.fns();
 
         let handle = {
-            let spec_descriptors = Css::descriptors();
             let specialization = ash::vk::SpecializationInfo {
                 map_entry_count: spec_descriptors.len() as u32,
                 p_map_entries: spec_descriptors.as_ptr() as *const _,
-                data_size: mem::size_of_val(specialization_constants),
-                p_data: specialization_constants as *const Css as *const _,
+                data_size: spec_data.len(),
+                p_data: spec_data.as_ptr() as *const _,
             };
 
             let stage = ash::vk::PipelineShaderStageCreateInfo {
@@ -191,7 +514,7 @@ impl ComputePipeline {
             };
 
             let infos = ash::vk::ComputePipelineCreateInfo {
-                flags: ash::vk::PipelineCreateFlags::empty(),
+                flags: executable::create_flags(&device),
                 stage,
                 layout: layout.internal_object(),
                 base_pipeline_handle: ash::vk::Pipeline::null(),
@@ -210,7 +533,7 @@ impl ComputePipeline {
                 cache_handle,
                 1,
                 &infos,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -233,6 +556,8 @@ impl ComputePipeline {
             layout,
             descriptor_requirements,
             num_used_descriptor_sets,
+            local_size,
+            allow_derivatives: false,
         }))
     }
 
@@ -251,6 +576,45 @@ impl ComputePipeline {
             .iter()
             .map(|(loc, reqs)| (*loc, reqs))
     }
+
+    /// Returns the number of invocations in each local workgroup of the shader, in the X, Y and
+    /// Z dimensions.
+    ///
+    /// This is `None` if the size is given by specialization constants in the shader.
+    #[inline]
+    pub fn local_size(&self) -> Option<[u32; 3]> {
+        self.local_size
+    }
+
+    /// Returns whether other pipelines can be derived from this pipeline.
+    #[inline]
+    pub fn allow_derivatives(&self) -> bool {
+        self.allow_derivatives
+    }
+
+    /// Returns the executables that the pipeline was compiled into, with their statistics.
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device.
+    #[inline]
+    pub fn executable_properties(
+        &self,
+    ) -> Result<Vec<PipelineExecutableInfo>, PipelineExecutableError> {
+        executable::executable_properties(&self.device, self.handle)
+    }
+
+    /// Returns the internal representations of the executable `executable_index`, such as its
+    /// disassembly.
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device.
+    #[inline]
+    pub fn executable_internal_representations(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, PipelineExecutableError> {
+        executable::executable_internal_representations(&self.device, self.handle, executable_index)
+    }
 }
 
 impl Pipeline for ComputePipeline {
@@ -307,16 +671,82 @@ impl Drop for ComputePipeline {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_pipeline)(self.device.internal_object(), self.handle, ptr::null());
+            (fns.v1_0.destroy_pipeline)(
+                self.device.internal_object(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
         }
     }
 }
 
+/// Parameters to create a compute pipeline with [`ComputePipeline::new_batch`].
+#[derive(Clone, Debug)]
+pub struct ComputePipelineCreateInfo<'a> {
+    /// The compute shader to execute.
+    ///
+    /// There is no default value.
+    pub shader: EntryPoint<'a>,
+
+    /// The values of the specialization constants of the shader, mapped by their ID. Constants
+    /// that are not given a value keep their default value.
+    ///
+    /// The default value is empty.
+    pub specialization_constants: HashMap<u32, SpecializationConstant>,
+
+    /// The pipeline layout to use. It must be a superset of what the shader uses.
+    ///
+    /// There is no default value.
+    pub layout: Arc<PipelineLayout>,
+
+    /// Whether other pipelines can be derived from this pipeline.
+    ///
+    /// The default value is `false`.
+    pub allow_derivatives: bool,
+
+    /// The pipeline that this pipeline is derived from, if any.
+    ///
+    /// The default value is `None`.
+    pub base_pipeline: Option<ComputeBasePipeline>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl<'a> ComputePipelineCreateInfo<'a> {
+    /// Returns a `ComputePipelineCreateInfo` with the specified `shader` and `layout`.
+    #[inline]
+    pub fn shader(shader: EntryPoint<'a>, layout: Arc<PipelineLayout>) -> Self {
+        Self {
+            shader,
+            specialization_constants: HashMap::default(),
+            layout,
+            allow_derivatives: false,
+            base_pipeline: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// The pipeline that a compute pipeline is derived from.
+#[derive(Clone, Debug)]
+pub enum ComputeBasePipeline {
+    /// A pipeline that was created before.
+    Pipeline(Arc<ComputePipeline>),
+
+    /// The pipeline at this index in the same batch. It must come before the derived pipeline.
+    Index(usize),
+}
+
 /// Error that can happen when creating a compute pipeline.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ComputePipelineCreationError {
     /// Not enough memory.
     OomError(OomError),
+    /// The base pipeline of the pipeline at index `index` was not created with
+    /// `allow_derivatives` set.
+    BasePipelineDerivativesNotAllowed { index: usize },
+    /// The base pipeline index of the pipeline at index `index` is not less than `index`.
+    BasePipelineIndexOutOfRange { index: usize },
     /// Error while creating a descriptor set layout object.
     DescriptorSetLayoutCreationError(DescriptorSetLayoutCreationError),
     /// Error while creating the pipeline layout object.
@@ -325,6 +755,13 @@ pub enum ComputePipelineCreationError {
     IncompatiblePipelineLayout(PipelineLayoutSupersetError),
     /// The provided specialization constants are not compatible with what the shader expects.
     IncompatibleSpecializationConstants,
+    /// A value was provided for a specialization constant that the shader doesn't declare.
+    SpecializationConstantNotFound { constant_id: u32 },
+    /// The value provided for a specialization constant doesn't have the type that the shader
+    /// declares for it.
+    SpecializationConstantTypeMismatch { constant_id: u32 },
+    /// The shader is not a compute shader.
+    WrongShaderType,
 }
 
 impl error::Error for ComputePipelineCreationError {
@@ -332,10 +769,15 @@ impl error::Error for ComputePipelineCreationError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
             Self::OomError(ref err) => Some(err),
+            Self::BasePipelineDerivativesNotAllowed { .. } => None,
+            Self::BasePipelineIndexOutOfRange { .. } => None,
             Self::DescriptorSetLayoutCreationError(ref err) => Some(err),
             Self::PipelineLayoutCreationError(ref err) => Some(err),
             Self::IncompatiblePipelineLayout(ref err) => Some(err),
             Self::IncompatibleSpecializationConstants => None,
+            Self::SpecializationConstantNotFound { .. } => None,
+            Self::SpecializationConstantTypeMismatch { .. } => None,
+            Self::WrongShaderType => None,
         }
     }
 }
@@ -348,6 +790,12 @@ impl fmt::Display for ComputePipelineCreationError {
             "{}",
             match *self {
                 ComputePipelineCreationError::OomError(_) => "not enough memory available",
+                ComputePipelineCreationError::BasePipelineDerivativesNotAllowed { .. } => {
+                    "the base pipeline was not created with derivatives allowed"
+                }
+                ComputePipelineCreationError::BasePipelineIndexOutOfRange { .. } => {
+                    "the base pipeline index is not less than the index of the pipeline"
+                }
                 ComputePipelineCreationError::DescriptorSetLayoutCreationError(_) => {
                     "error while creating a descriptor set layout object"
                 }
@@ -360,6 +808,15 @@ impl fmt::Display for ComputePipelineCreationError {
                 ComputePipelineCreationError::IncompatibleSpecializationConstants => {
                     "the provided specialization constants are not compatible with what the shader expects"
                 }
+                ComputePipelineCreationError::SpecializationConstantNotFound { .. } => {
+                    "a value was provided for a specialization constant that the shader doesn't declare"
+                }
+                ComputePipelineCreationError::SpecializationConstantTypeMismatch { .. } => {
+                    "the value provided for a specialization constant doesn't have the type that the shader declares for it"
+                }
+                ComputePipelineCreationError::WrongShaderType => {
+                    "the shader is not a compute shader"
+                }
             }
         )
     }
@@ -410,29 +867,36 @@ mod tests {
     use crate::buffer::CpuAccessibleBuffer;
     use crate::command_buffer::AutoCommandBufferBuilder;
     use crate::command_buffer::CommandBufferUsage;
+    use crate::command_buffer::DispatchIndirectCommand;
     use crate::descriptor_set::PersistentDescriptorSet;
     use crate::descriptor_set::WriteDescriptorSet;
+    use crate::device::Device;
+    use crate::pipeline::compute::ComputeBasePipeline;
+    use crate::pipeline::compute::ComputePipelineCreateInfo;
+    use crate::pipeline::compute::ComputePipelineCreationError;
+    use crate::pipeline::executable::PipelineExecutableError;
     use crate::pipeline::ComputePipeline;
     use crate::pipeline::Pipeline;
     use crate::pipeline::PipelineBindPoint;
+    use crate::shader::spirv::ExecutionModel;
+    use crate::shader::ComputeShaderExecution;
+    use crate::shader::ShaderExecution;
     use crate::shader::ShaderModule;
+    use crate::shader::SpecializationConstant;
     use crate::shader::SpecializationConstants;
     use crate::shader::SpecializationMapEntry;
     use crate::sync::now;
     use crate::sync::GpuFuture;
+    use std::collections::HashMap;
+    use std::sync::Arc;
 
     // TODO: test for basic creation
     // TODO: test for pipeline layout error
 
-    #[test]
-    fn specialization_constants() {
-        // This test checks whether specialization constants work.
-        // It executes a single compute shader (one invocation) that writes the value of a spec.
-        // constant to a buffer. The buffer content is then checked for the right value.
-
-        let (device, queue) = gfx_dev_and_queue!();
-
-        let module = unsafe {
+    // A compute shader that writes the value of the specialization constant with ID 83, an `int`,
+    // to the buffer at binding 0.
+    fn spec_constant_module(device: Arc<Device>) -> Arc<ShaderModule> {
+        unsafe {
             /*
             #version 450
 
@@ -469,8 +933,44 @@ mod tests {
                 0, 5, 0, 0, 0, 65, 0, 5, 0, 12, 0, 0, 0, 13, 0, 0, 0, 9, 0, 0, 0, 10, 0, 0, 0, 62,
                 0, 3, 0, 13, 0, 0, 0, 11, 0, 0, 0, 253, 0, 1, 0, 56, 0, 1, 0,
             ];
-            ShaderModule::from_bytes(device.clone(), &MODULE).unwrap()
-        };
+            ShaderModule::from_bytes(device, &MODULE).unwrap()
+        }
+    }
+
+    #[test]
+    fn entry_points_reflection() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let module = spec_constant_module(device);
+        let entry_points: Vec<_> = module.entry_points().collect();
+        assert_eq!(entry_points.len(), 1);
+
+        let (execution_model, entry_point) = &entry_points[0];
+        assert_eq!(*execution_model, ExecutionModel::GLCompute);
+        assert_eq!(entry_point.name().to_str().unwrap(), "main");
+        assert_eq!(
+            *entry_point.execution(),
+            ShaderExecution::Compute(ComputeShaderExecution {
+                local_size: Some([1, 1, 1]),
+            })
+        );
+
+        let descriptor_requirements: Vec<_> = entry_point.descriptor_requirements().collect();
+        assert_eq!(descriptor_requirements.len(), 1);
+        assert_eq!(descriptor_requirements[0].0, (0, 0));
+        assert_eq!(descriptor_requirements[0].1.descriptor_count, 1);
+        assert!(entry_point.push_constant_requirements().is_none());
+    }
+
+    #[test]
+    fn specialization_constants() {
+        // This test checks whether specialization constants work.
+        // It executes a single compute shader (one invocation) that writes the value of a spec.
+        // constant to a buffer. The buffer content is then checked for the right value.
+
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let module = spec_constant_module(device.clone());
 
         #[derive(Debug, Copy, Clone)]
         #[allow(non_snake_case)]
@@ -497,6 +997,82 @@ mod tests {
             |_| {},
         )
         .unwrap();
+        assert_eq!(pipeline.local_size(), Some([1, 1, 1]));
+
+        let data_buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0).unwrap();
+
+        let set = PersistentDescriptorSet::new(
+            pipeline.layout().set_layouts().get(0).unwrap().clone(),
+            [WriteDescriptorSet::buffer(0, data_buffer.clone())],
+        )
+        .unwrap();
+
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        cbb.bind_pipeline_compute(pipeline.clone())
+            .bind_descriptor_sets(
+                PipelineBindPoint::Compute,
+                pipeline.layout().clone(),
+                0,
+                set,
+            )
+            .dispatch([1, 1, 1])
+            .unwrap();
+        let cb = cbb.build().unwrap();
+
+        let future = now(device.clone())
+            .then_execute(queue.clone(), cb)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        future.wait(None).unwrap();
+
+        let data_buffer_content = data_buffer.read().unwrap();
+        assert_eq!(*data_buffer_content, 0x12345678);
+    }
+
+    #[test]
+    fn runtime_specialization_constants() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let module = spec_constant_module(device.clone());
+
+        assert_eq!(
+            ComputePipeline::with_specialization(
+                device.clone(),
+                module.entry_point("main").unwrap(),
+                &HashMap::from([(84, SpecializationConstant::I32(0x12345678))]),
+                None,
+                |_| {},
+            )
+            .unwrap_err(),
+            ComputePipelineCreationError::SpecializationConstantNotFound { constant_id: 84 },
+        );
+
+        assert_eq!(
+            ComputePipeline::with_specialization(
+                device.clone(),
+                module.entry_point("main").unwrap(),
+                &HashMap::from([(83, SpecializationConstant::F32(1.0))]),
+                None,
+                |_| {},
+            )
+            .unwrap_err(),
+            ComputePipelineCreationError::SpecializationConstantTypeMismatch { constant_id: 83 },
+        );
+
+        let pipeline = ComputePipeline::with_specialization(
+            device.clone(),
+            module.entry_point("main").unwrap(),
+            &HashMap::from([(83, SpecializationConstant::I32(0x12345678))]),
+            None,
+            |_| {},
+        )
+        .unwrap();
 
         let data_buffer =
             CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0).unwrap();
@@ -534,4 +1110,202 @@ mod tests {
         let data_buffer_content = data_buffer.read().unwrap();
         assert_eq!(*data_buffer_content, 0x12345678);
     }
+
+    #[test]
+    fn executable_properties_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let module = spec_constant_module(device.clone());
+
+        let pipeline = ComputePipeline::new(
+            device.clone(),
+            module.entry_point("main").unwrap(),
+            &(),
+            None,
+            |_| {},
+        )
+        .unwrap();
+
+        assert!(matches!(
+            pipeline.executable_properties(),
+            Err(PipelineExecutableError::FeatureNotEnabled {
+                feature: "pipeline_executable_info",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn executable_properties() {
+        let (device, _) = gfx_dev_and_queue!(pipeline_executable_info);
+        let module = spec_constant_module(device.clone());
+
+        let pipeline = ComputePipeline::new(
+            device.clone(),
+            module.entry_point("main").unwrap(),
+            &(),
+            None,
+            |_| {},
+        )
+        .unwrap();
+
+        let executables = pipeline.executable_properties().unwrap();
+        assert!(!executables.is_empty());
+        assert!(executables
+            .iter()
+            .any(|executable| executable.stages.compute));
+    }
+
+    #[test]
+    fn batch_derivatives() {
+        let (device, _) = gfx_dev_and_queue!();
+        let module = spec_constant_module(device.clone());
+
+        let base = ComputePipeline::new(
+            device.clone(),
+            module.entry_point("main").unwrap(),
+            &(),
+            None,
+            |_| {},
+        )
+        .unwrap();
+        assert!(!base.allow_derivatives());
+        let layout = base.layout().clone();
+
+        assert_eq!(
+            ComputePipeline::new_batch(
+                device.clone(),
+                vec![ComputePipelineCreateInfo {
+                    base_pipeline: Some(ComputeBasePipeline::Pipeline(base.clone())),
+                    ..ComputePipelineCreateInfo::shader(
+                        module.entry_point("main").unwrap(),
+                        layout.clone(),
+                    )
+                }],
+                None,
+            )
+            .unwrap_err(),
+            ComputePipelineCreationError::BasePipelineDerivativesNotAllowed { index: 0 },
+        );
+
+        assert_eq!(
+            ComputePipeline::new_batch(
+                device.clone(),
+                vec![ComputePipelineCreateInfo {
+                    allow_derivatives: true,
+                    base_pipeline: Some(ComputeBasePipeline::Index(0)),
+                    ..ComputePipelineCreateInfo::shader(
+                        module.entry_point("main").unwrap(),
+                        layout.clone(),
+                    )
+                }],
+                None,
+            )
+            .unwrap_err(),
+            ComputePipelineCreationError::BasePipelineIndexOutOfRange { index: 0 },
+        );
+
+        let pipelines = ComputePipeline::new_batch(
+            device.clone(),
+            vec![
+                ComputePipelineCreateInfo {
+                    allow_derivatives: true,
+                    ..ComputePipelineCreateInfo::shader(
+                        module.entry_point("main").unwrap(),
+                        layout.clone(),
+                    )
+                },
+                ComputePipelineCreateInfo {
+                    specialization_constants: HashMap::from([(83, SpecializationConstant::I32(1))]),
+                    base_pipeline: Some(ComputeBasePipeline::Index(0)),
+                    ..ComputePipelineCreateInfo::shader(
+                        module.entry_point("main").unwrap(),
+                        layout.clone(),
+                    )
+                },
+            ],
+            None,
+        )
+        .unwrap();
+        assert_eq!(pipelines.len(), 2);
+        assert!(pipelines[0].allow_derivatives());
+        assert!(!pipelines[1].allow_derivatives());
+    }
+
+    #[test]
+    fn dispatch_indirect_from_compute_output() {
+        // The first dispatch writes `1` to the `x` member of a `DispatchIndirectCommand`, which
+        // is then used for an indirect dispatch that writes to a second buffer.
+
+        let (device, queue) = gfx_dev_and_queue!();
+        let module = spec_constant_module(device.clone());
+
+        let pipeline = ComputePipeline::with_specialization(
+            device.clone(),
+            module.entry_point("main").unwrap(),
+            &HashMap::from([(83, SpecializationConstant::I32(1))]),
+            None,
+            |_| {},
+        )
+        .unwrap();
+
+        let indirect_buffer = CpuAccessibleBuffer::from_iter(
+            device.clone(),
+            BufferUsage::all(),
+            false,
+            [DispatchIndirectCommand { x: 0, y: 1, z: 1 }],
+        )
+        .unwrap();
+        let data_buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0).unwrap();
+
+        let layout = pipeline.layout().set_layouts().get(0).unwrap();
+        let indirect_set = PersistentDescriptorSet::new(
+            layout.clone(),
+            [WriteDescriptorSet::buffer(0, indirect_buffer.clone())],
+        )
+        .unwrap();
+        let data_set = PersistentDescriptorSet::new(
+            layout.clone(),
+            [WriteDescriptorSet::buffer(0, data_buffer.clone())],
+        )
+        .unwrap();
+
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            device.clone(),
+            queue.family(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        cbb.bind_pipeline_compute(pipeline.clone())
+            .bind_descriptor_sets(
+                PipelineBindPoint::Compute,
+                pipeline.layout().clone(),
+                0,
+                indirect_set,
+            )
+            .dispatch([1, 1, 1])
+            .unwrap()
+            .bind_descriptor_sets(
+                PipelineBindPoint::Compute,
+                pipeline.layout().clone(),
+                0,
+                data_set,
+            )
+            .dispatch_indirect(indirect_buffer.clone())
+            .unwrap();
+        let cb = cbb.build().unwrap();
+
+        let future = now(device.clone())
+            .then_execute(queue.clone(), cb)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        future.wait(None).unwrap();
+
+        assert_eq!(
+            indirect_buffer.read().unwrap()[0],
+            DispatchIndirectCommand { x: 1, y: 1, z: 1 }
+        );
+        assert_eq!(*data_buffer.read().unwrap(), 1);
+    }
 }
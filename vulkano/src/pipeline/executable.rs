@@ -0,0 +1,406 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Information about the executables that a pipeline was compiled into.
+//!
+//! When a pipeline is created, the implementation compiles its shaders into one or more
+//! executables. If the
+//! [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info) feature is
+//! enabled on the device, vulkano asks the implementation to keep statistics and internal
+//! representations of these executables, which can be queried afterwards. This is mostly useful
+//! to analyze the performance of shaders, for example to look at their register usage or their
+//! disassembly.
+
+use crate::{
+    check_errors, device::Device, shader::ShaderStages, Error, OomError, Success, VulkanObject,
+};
+use std::{error, ffi::CStr, fmt, os::raw::c_char, ptr};
+
+/// Information about an executable of a pipeline.
+#[derive(Clone, Debug)]
+pub struct PipelineExecutableInfo {
+    /// A short name of the executable.
+    pub name: String,
+
+    /// A human-readable description of the executable.
+    pub description: String,
+
+    /// The shader stages that were compiled into the executable.
+    pub stages: ShaderStages,
+
+    /// The subgroup size that the executable is dispatched with, or 0 if it has no subgroup size.
+    pub subgroup_size: u32,
+
+    /// The statistics that were compiled by the implementation for the executable, such as the
+    /// number of registers that are used.
+    pub statistics: Vec<PipelineExecutableStatistic>,
+}
+
+/// A statistic of a pipeline executable.
+#[derive(Clone, Debug)]
+pub struct PipelineExecutableStatistic {
+    /// A short name of the statistic.
+    pub name: String,
+
+    /// A human-readable description of the statistic.
+    pub description: String,
+
+    /// The value of the statistic.
+    pub value: PipelineExecutableStatisticValue,
+}
+
+/// The value of a statistic of a pipeline executable.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PipelineExecutableStatisticValue {
+    Bool(bool),
+    Int(i64),
+    Uint(u64),
+    Float(f64),
+}
+
+/// An internal representation of a pipeline executable, such as its disassembly.
+#[derive(Clone, Debug)]
+pub struct PipelineExecutableInternalRepresentation {
+    /// A short name of the internal representation.
+    pub name: String,
+
+    /// A human-readable description of the internal representation.
+    pub description: String,
+
+    /// Whether `data` contains text. If so, it is null-terminated UTF-8.
+    pub is_text: bool,
+
+    /// The data of the internal representation.
+    pub data: Vec<u8>,
+}
+
+/// Returns the flags to create pipelines with, so that their executables can be queried.
+pub(crate) fn create_flags(device: &Device) -> ash::vk::PipelineCreateFlags {
+    if device.enabled_features().pipeline_executable_info {
+        ash::vk::PipelineCreateFlags::CAPTURE_STATISTICS_KHR
+            | ash::vk::PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS_KHR
+    } else {
+        ash::vk::PipelineCreateFlags::empty()
+    }
+}
+
+// Checks that executable properties can be queried on `device`.
+fn validate_executable_properties(device: &Device) -> Result<(), PipelineExecutableError> {
+    // VUID-vkGetPipelineExecutablePropertiesKHR-pipelineExecutableInfo-03270
+    // VUID-vkGetPipelineExecutableStatisticsKHR-pipelineExecutableInfo-03272
+    // VUID-vkGetPipelineExecutableInternalRepresentationsKHR-pipelineExecutableInfo-03276
+    if !device.enabled_features().pipeline_executable_info {
+        return Err(PipelineExecutableError::FeatureNotEnabled {
+            feature: "pipeline_executable_info",
+            reason: "queried the executables of a pipeline",
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns the executables of `pipeline`, with their statistics.
+pub(crate) fn executable_properties(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+) -> Result<Vec<PipelineExecutableInfo>, PipelineExecutableError> {
+    validate_executable_properties(device)?;
+
+    let fns = device.fns();
+    let pipeline_info = ash::vk::PipelineInfoKHR {
+        pipeline,
+        ..Default::default()
+    };
+
+    let properties_vk = unsafe {
+        loop {
+            let mut count = 0;
+            check_errors((fns
+                .khr_pipeline_executable_properties
+                .get_pipeline_executable_properties_khr)(
+                device.internal_object(),
+                &pipeline_info,
+                &mut count,
+                ptr::null_mut(),
+            ))?;
+
+            let mut properties_vk =
+                vec![ash::vk::PipelineExecutablePropertiesKHR::default(); count as usize];
+            let result = check_errors((fns
+                .khr_pipeline_executable_properties
+                .get_pipeline_executable_properties_khr)(
+                device.internal_object(),
+                &pipeline_info,
+                &mut count,
+                properties_vk.as_mut_ptr(),
+            ))?;
+
+            if !matches!(result, Success::Incomplete) {
+                properties_vk.set_len(count as usize);
+                break properties_vk;
+            }
+        }
+    };
+
+    properties_vk
+        .into_iter()
+        .enumerate()
+        .map(|(executable_index, properties_vk)| {
+            let executable_info = ash::vk::PipelineExecutableInfoKHR {
+                pipeline,
+                executable_index: executable_index as u32,
+                ..Default::default()
+            };
+
+            let statistics_vk = unsafe {
+                loop {
+                    let mut count = 0;
+                    check_errors((fns
+                        .khr_pipeline_executable_properties
+                        .get_pipeline_executable_statistics_khr)(
+                        device.internal_object(),
+                        &executable_info,
+                        &mut count,
+                        ptr::null_mut(),
+                    ))?;
+
+                    let mut statistics_vk =
+                        vec![ash::vk::PipelineExecutableStatisticKHR::default(); count as usize];
+                    let result = check_errors((fns
+                        .khr_pipeline_executable_properties
+                        .get_pipeline_executable_statistics_khr)(
+                        device.internal_object(),
+                        &executable_info,
+                        &mut count,
+                        statistics_vk.as_mut_ptr(),
+                    ))?;
+
+                    if !matches!(result, Success::Incomplete) {
+                        statistics_vk.set_len(count as usize);
+                        break statistics_vk;
+                    }
+                }
+            };
+
+            let statistics = statistics_vk
+                .into_iter()
+                .map(|statistic_vk| PipelineExecutableStatistic {
+                    name: string_from_vk(&statistic_vk.name),
+                    description: string_from_vk(&statistic_vk.description),
+                    value: unsafe {
+                        match statistic_vk.format {
+                            ash::vk::PipelineExecutableStatisticFormatKHR::BOOL32 => {
+                                PipelineExecutableStatisticValue::Bool(
+                                    statistic_vk.value.b32 != ash::vk::FALSE,
+                                )
+                            }
+                            ash::vk::PipelineExecutableStatisticFormatKHR::INT64 => {
+                                PipelineExecutableStatisticValue::Int(statistic_vk.value.i64)
+                            }
+                            ash::vk::PipelineExecutableStatisticFormatKHR::UINT64 => {
+                                PipelineExecutableStatisticValue::Uint(statistic_vk.value.u64)
+                            }
+                            ash::vk::PipelineExecutableStatisticFormatKHR::FLOAT64 => {
+                                PipelineExecutableStatisticValue::Float(statistic_vk.value.f64)
+                            }
+                            _ => unreachable!(),
+                        }
+                    },
+                })
+                .collect();
+
+            Ok(PipelineExecutableInfo {
+                name: string_from_vk(&properties_vk.name),
+                description: string_from_vk(&properties_vk.description),
+                stages: properties_vk.stages.into(),
+                subgroup_size: properties_vk.subgroup_size,
+                statistics,
+            })
+        })
+        .collect()
+}
+
+/// Returns the internal representations of the executable `executable_index` of `pipeline`.
+pub(crate) fn executable_internal_representations(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+    executable_index: u32,
+) -> Result<Vec<PipelineExecutableInternalRepresentation>, PipelineExecutableError> {
+    validate_executable_properties(device)?;
+
+    let fns = device.fns();
+    let executable_info = ash::vk::PipelineExecutableInfoKHR {
+        pipeline,
+        executable_index,
+        ..Default::default()
+    };
+
+    // VUID-VkPipelineExecutableInfoKHR-executableIndex-03275
+    let executable_count = unsafe {
+        let mut count = 0;
+        check_errors((fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_properties_khr)(
+            device.internal_object(),
+            &ash::vk::PipelineInfoKHR {
+                pipeline,
+                ..Default::default()
+            },
+            &mut count,
+            ptr::null_mut(),
+        ))?;
+        count
+    };
+
+    if executable_index >= executable_count {
+        return Err(PipelineExecutableError::ExecutableIndexOutOfRange {
+            executable_index,
+            executable_count,
+        });
+    }
+
+    let (representations_vk, data) = unsafe {
+        loop {
+            let mut count = 0;
+            check_errors((fns
+                .khr_pipeline_executable_properties
+                .get_pipeline_executable_internal_representations_khr)(
+                device.internal_object(),
+                &executable_info,
+                &mut count,
+                ptr::null_mut(),
+            ))?;
+
+            // Query the size of the data of each representation first.
+            let mut representations_vk = vec![
+                    ash::vk::PipelineExecutableInternalRepresentationKHR::default();
+                    count as usize
+                ];
+            check_errors((fns
+                .khr_pipeline_executable_properties
+                .get_pipeline_executable_internal_representations_khr)(
+                device.internal_object(),
+                &executable_info,
+                &mut count,
+                representations_vk.as_mut_ptr(),
+            ))?;
+            representations_vk.truncate(count as usize);
+
+            let mut data: Vec<Vec<u8>> = representations_vk
+                .iter()
+                .map(|representation_vk| vec![0; representation_vk.data_size])
+                .collect();
+
+            for (representation_vk, data) in representations_vk.iter_mut().zip(data.iter_mut()) {
+                representation_vk.p_data = data.as_mut_ptr() as *mut _;
+            }
+
+            let result = check_errors((fns
+                .khr_pipeline_executable_properties
+                .get_pipeline_executable_internal_representations_khr)(
+                device.internal_object(),
+                &executable_info,
+                &mut count,
+                representations_vk.as_mut_ptr(),
+            ))?;
+
+            if !matches!(result, Success::Incomplete) {
+                break (representations_vk, data);
+            }
+        }
+    };
+
+    Ok(representations_vk
+        .into_iter()
+        .zip(data)
+        .map(|(representation_vk, mut data)| {
+            data.truncate(representation_vk.data_size);
+
+            PipelineExecutableInternalRepresentation {
+                name: string_from_vk(&representation_vk.name),
+                description: string_from_vk(&representation_vk.description),
+                is_text: representation_vk.is_text != ash::vk::FALSE,
+                data,
+            }
+        })
+        .collect())
+}
+
+fn string_from_vk(chars: &[c_char]) -> String {
+    unsafe { CStr::from_ptr(chars.as_ptr()) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Error that can happen when querying the executables of a pipeline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PipelineExecutableError {
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+
+    /// The provided executable index is not less than the number of executables of the
+    /// pipeline.
+    ExecutableIndexOutOfRange {
+        executable_index: u32,
+        executable_count: u32,
+    },
+
+    /// Not enough memory.
+    OomError(OomError),
+}
+
+impl error::Error for PipelineExecutableError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PipelineExecutableError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(f, "the feature {} must be enabled: {}", feature, reason)
+            }
+            Self::ExecutableIndexOutOfRange {
+                executable_index,
+                executable_count,
+            } => write!(
+                f,
+                "the executable index {} is not less than the number of executables of the pipeline ({})",
+                executable_index, executable_count,
+            ),
+            Self::OomError(_) => write!(f, "not enough memory available"),
+        }
+    }
+}
+
+impl From<OomError> for PipelineExecutableError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<Error> for PipelineExecutableError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            err @ Error::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
@@ -17,10 +17,14 @@ use super::{
     },
     depth_stencil::DepthStencilState,
     discard_rectangle::DiscardRectangleState,
+    fragment_shading_rate::{
+        FragmentShadingRate, FragmentShadingRateCombinerOp, FragmentShadingRateState,
+    },
     input_assembly::{InputAssemblyState, PrimitiveTopology, PrimitiveTopologyClass},
     multisample::MultisampleState,
     rasterization::{
-        CullMode, DepthBiasState, FrontFace, LineRasterizationMode, PolygonMode, RasterizationState,
+        ConservativeRasterizationMode, ConservativeRasterizationState, CullMode, DepthBiasState,
+        FrontFace, LineRasterizationMode, PolygonMode, RasterizationState,
     },
     render_pass::{PipelineRenderPassType, PipelineRenderingCreateInfo},
     tessellation::TessellationState,
@@ -35,6 +39,7 @@ use crate::{
     format::NumericType,
     pipeline::{
         cache::PipelineCache,
+        executable,
         graphics::{
             color_blend::BlendFactor,
             depth_stencil::{DepthBoundsState, DepthState, StencilOpState, StencilState},
@@ -62,6 +67,8 @@ use std::{
 pub struct GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss> {
     render_pass: Option<PipelineRenderPassType>,
     cache: Option<Arc<PipelineCache>>,
+    allow_derivatives: bool,
+    base_pipeline: Option<Arc<GraphicsPipeline>>,
 
     vertex_shader: Option<(EntryPoint<'vs>, Vss)>,
     tessellation_shaders: Option<TessellationShaders<'tcs, 'tes, Tcss, Tess>>,
@@ -73,6 +80,7 @@ pub struct GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, T
     tessellation_state: TessellationState,
     viewport_state: ViewportState,
     discard_rectangle_state: DiscardRectangleState,
+    fragment_shading_rate_state: FragmentShadingRateState,
     rasterization_state: RasterizationState,
     multisample_state: MultisampleState,
     depth_stencil_state: DepthStencilState,
@@ -106,6 +114,8 @@ impl
         GraphicsPipelineBuilder {
             render_pass: None,
             cache: None,
+            allow_derivatives: false,
+            base_pipeline: None,
 
             vertex_shader: None,
             tessellation_shaders: None,
@@ -117,6 +127,7 @@ impl
             tessellation_state: Default::default(),
             viewport_state: Default::default(),
             discard_rectangle_state: Default::default(),
+            fragment_shading_rate_state: Default::default(),
             rasterization_state: Default::default(),
             multisample_state: Default::default(),
             depth_stencil_state: Default::default(),
@@ -294,6 +305,8 @@ where
             let &Self {
                 ref render_pass,
                 ref cache,
+                allow_derivatives: _,
+                base_pipeline: _,
 
                 ref vertex_shader,
                 ref tessellation_shaders,
@@ -305,6 +318,7 @@ where
                 ref tessellation_state,
                 ref viewport_state,
                 ref discard_rectangle_state,
+                ref fragment_shading_rate_state,
                 ref rasterization_state,
                 ref multisample_state,
                 ref depth_stencil_state,
@@ -365,6 +379,8 @@ where
         let Self {
             mut render_pass,
             cache,
+            allow_derivatives,
+            base_pipeline: _,
             vertex_shader,
             tessellation_shaders,
             geometry_shader,
@@ -374,6 +390,7 @@ where
             tessellation_state,
             viewport_state,
             discard_rectangle_state,
+            fragment_shading_rate_state,
             rasterization_state,
             multisample_state,
             depth_stencil_state,
@@ -392,6 +409,7 @@ where
             device,
             layout: pipeline_layout,
             render_pass: render_pass.take().expect("Missing render pass"),
+            allow_derivatives,
             shaders,
             descriptor_requirements,
             num_used_descriptor_sets,
@@ -403,6 +421,9 @@ where
             discard_rectangle_state: has
                 .pre_rasterization_shader_state
                 .then(|| discard_rectangle_state),
+            fragment_shading_rate_state: has
+                .fragment_shader_state
+                .then(|| fragment_shading_rate_state),
             rasterization_state,
             multisample_state: has.fragment_output_state.then(|| multisample_state),
             depth_stencil_state: has.depth_stencil_state.then(|| depth_stencil_state),
@@ -424,6 +445,8 @@ where
         let &Self {
             ref render_pass,
             ref cache,
+            allow_derivatives: _,
+            ref base_pipeline,
 
             ref vertex_shader,
             ref tessellation_shaders,
@@ -435,6 +458,7 @@ where
             ref tessellation_state,
             ref viewport_state,
             ref discard_rectangle_state,
+            ref fragment_shading_rate_state,
             ref rasterization_state,
             ref multisample_state,
             ref depth_stencil_state,
@@ -448,6 +472,20 @@ where
         // VUID-VkGraphicsPipelineCreateInfo-layout-01688
         // Checked at pipeline layout creation time.
 
+        /*
+            Base pipeline
+        */
+
+        if let Some(base_pipeline) = base_pipeline {
+            // VUID-VkGraphicsPipelineCreateInfo-commonparent
+            assert_eq!(device, base_pipeline.device().as_ref());
+
+            // VUID-vkCreateGraphicsPipelines-flags-00721
+            if !base_pipeline.allow_derivatives() {
+                return Err(GraphicsPipelineCreationError::BasePipelineDerivativesNotAllowed);
+            }
+        }
+
         /*
             Render pass
         */
@@ -1055,6 +1093,7 @@ where
                     line_width,
                     line_rasterization_mode,
                     line_stipple,
+                    conservative_rasterization,
                 } = rasterization_state;
 
                 // VUID-VkPipelineRasterizationStateCreateInfo-depthClampEnable-00782
@@ -1226,8 +1265,13 @@ where
 
                         if let StateMode::Fixed(line_stipple) = line_stipple {
                             // VUID-VkGraphicsPipelineCreateInfo-stippledLineEnable-02767
-                            assert!(line_stipple.factor >= 1 && line_stipple.factor <= 256);
-                            // TODO: return error?
+                            if !(1..=256).contains(&line_stipple.factor) {
+                                return Err(
+                                    GraphicsPipelineCreationError::LineStippleFactorOutOfRange {
+                                        factor: line_stipple.factor,
+                                    },
+                                );
+                            }
                         }
                     }
                 } else {
@@ -1245,6 +1289,30 @@ where
                         });
                     }
                 }
+
+                let ConservativeRasterizationState {
+                    mode,
+                    extra_primitive_overestimation_size: _,
+                } = conservative_rasterization;
+
+                if mode != ConservativeRasterizationMode::Disabled {
+                    if !device.enabled_extensions().ext_conservative_rasterization {
+                        return Err(GraphicsPipelineCreationError::ExtensionNotEnabled {
+                            extension: "ext_conservative_rasterization",
+                            reason: "ConservativeRasterizationState::mode was not Disabled",
+                        });
+                    }
+
+                    if mode == ConservativeRasterizationMode::Underestimate
+                        && !properties.primitive_underestimation.unwrap_or(false)
+                    {
+                        return Err(
+                            GraphicsPipelineCreationError::ConservativeRasterizationModeNotSupported {
+                                mode,
+                            },
+                        );
+                    }
+                }
             }
 
             // Discard rectangle state
@@ -1572,6 +1640,98 @@ where
                 // VUID?
             }
 
+            // Fragment shading rate state
+            {
+                let &FragmentShadingRateState { rate } = fragment_shading_rate_state;
+
+                let fixed_rate = match rate {
+                    StateMode::Fixed(rate) => Some(rate),
+                    StateMode::Dynamic => None,
+                };
+
+                if fixed_rate != Some(FragmentShadingRate::default()) {
+                    if !device.enabled_extensions().khr_fragment_shading_rate {
+                        return Err(GraphicsPipelineCreationError::ExtensionNotEnabled {
+                            extension: "khr_fragment_shading_rate",
+                            reason: "FragmentShadingRateState::rate was Dynamic or not the default value",
+                        });
+                    }
+                }
+
+                if let Some(FragmentShadingRate {
+                    fragment_size,
+                    combiner_ops,
+                }) = fixed_rate
+                {
+                    // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04494
+                    // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04495
+                    // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04496
+                    // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04497
+                    // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04498
+                    // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04499
+                    if fragment_size.iter().any(|&size| !matches!(size, 1 | 2 | 4)) {
+                        return Err(GraphicsPipelineCreationError::FragmentSizeNotSupported {
+                            fragment_size,
+                        });
+                    }
+
+                    if fragment_size != [1, 1] {
+                        // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04500
+                        if !device.enabled_features().pipeline_fragment_shading_rate {
+                            return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                                feature: "pipeline_fragment_shading_rate",
+                                reason: "FragmentShadingRate::fragment_size was not [1, 1]",
+                            });
+                        }
+
+                        // VUID?
+                        let max_fragment_size = properties.max_fragment_size.unwrap();
+
+                        if fragment_size[0] > max_fragment_size[0]
+                            || fragment_size[1] > max_fragment_size[1]
+                        {
+                            return Err(GraphicsPipelineCreationError::FragmentSizeNotSupported {
+                                fragment_size,
+                            });
+                        }
+                    }
+
+                    // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04501
+                    if combiner_ops[0] != FragmentShadingRateCombinerOp::Keep
+                        && !device.enabled_features().primitive_fragment_shading_rate
+                    {
+                        return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                            feature: "primitive_fragment_shading_rate",
+                            reason: "FragmentShadingRate::combiner_ops[0] was not Keep",
+                        });
+                    }
+
+                    // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04502
+                    if combiner_ops[1] != FragmentShadingRateCombinerOp::Keep
+                        && !device.enabled_features().attachment_fragment_shading_rate
+                    {
+                        return Err(GraphicsPipelineCreationError::FeatureNotEnabled {
+                            feature: "attachment_fragment_shading_rate",
+                            reason: "FragmentShadingRate::combiner_ops[1] was not Keep",
+                        });
+                    }
+
+                    // VUID-VkGraphicsPipelineCreateInfo-fragmentShadingRateNonTrivialCombinerOps-04506
+                    if !properties
+                        .fragment_shading_rate_non_trivial_combiner_ops
+                        .unwrap_or(false)
+                    {
+                        if let Some(&op) = combiner_ops.iter().find(|op| !op.is_trivial()) {
+                            return Err(
+                                GraphicsPipelineCreationError::FragmentShadingRateCombinerOpNotSupported {
+                                    op,
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+
             // TODO:
             // VUID-VkGraphicsPipelineCreateInfo-renderPass-06038
         }
@@ -2060,6 +2220,8 @@ where
         let Self {
             render_pass,
             cache,
+            allow_derivatives,
+            base_pipeline,
 
             vertex_shader,
             tessellation_shaders,
@@ -2071,6 +2233,7 @@ where
             tessellation_state,
             viewport_state,
             discard_rectangle_state,
+            fragment_shading_rate_state,
             rasterization_state,
             multisample_state,
             depth_stencil_state,
@@ -2263,6 +2426,7 @@ where
         let mut scissors_vk: SmallVec<[_; 2]> = SmallVec::new();
         let mut viewport_state_vk = None;
         let mut rasterization_line_state_vk = None;
+        let mut rasterization_conservative_state_vk = None;
         let mut rasterization_state_vk = None;
         let mut discard_rectangles: SmallVec<[_; 2]> = SmallVec::new();
         let mut discard_rectangle_state_vk = None;
@@ -2439,6 +2603,7 @@ where
                     line_width,
                     line_rasterization_mode,
                     line_stipple,
+                    conservative_rasterization,
                 } = rasterization_state;
 
                 let rasterizer_discard_enable = match rasterizer_discard_enable {
@@ -2558,6 +2723,37 @@ where
                         },
                     ) as *const _ as *const _;
                 }
+
+                if device.enabled_extensions().ext_conservative_rasterization {
+                    let ConservativeRasterizationState {
+                        mode,
+                        extra_primitive_overestimation_size,
+                    } = conservative_rasterization;
+
+                    // VUID-VkPipelineRasterizationConservativeStateCreateInfoEXT-extraPrimitiveOverestimationSize-01769
+                    let extra_primitive_overestimation_size = extra_primitive_overestimation_size
+                        .min(
+                            device
+                                .physical_device()
+                                .properties()
+                                .max_extra_primitive_overestimation_size
+                                .unwrap_or(0.0),
+                        )
+                        .max(0.0);
+
+                    let rasterization_conservative_state = rasterization_conservative_state_vk
+                        .insert(
+                            ash::vk::PipelineRasterizationConservativeStateCreateInfoEXT {
+                                flags: ash::vk::PipelineRasterizationConservativeStateCreateFlagsEXT::empty(),
+                                conservative_rasterization_mode: mode.into(),
+                                extra_primitive_overestimation_size,
+                                ..Default::default()
+                            },
+                        );
+                    rasterization_conservative_state.p_next = rasterization_state.p_next;
+                    rasterization_state.p_next =
+                        rasterization_conservative_state as *const _ as *const _;
+                }
             }
 
             // Discard rectangle state
@@ -2723,6 +2919,7 @@ where
         */
 
         let mut fragment_shader_specialization_vk = None;
+        let mut fragment_shading_rate_state_vk = None;
         let mut depth_stencil_state_vk = None;
 
         if has.fragment_shader_state {
@@ -2764,6 +2961,36 @@ where
                     ..Default::default()
                 });
             }
+
+            // Fragment shading rate state
+            if device.enabled_extensions().khr_fragment_shading_rate {
+                let &FragmentShadingRateState { rate } = fragment_shading_rate_state;
+
+                let FragmentShadingRate {
+                    fragment_size,
+                    combiner_ops,
+                } = match rate {
+                    StateMode::Fixed(rate) => {
+                        dynamic_state.insert(DynamicState::FragmentShadingRate, false);
+                        rate
+                    }
+                    StateMode::Dynamic => {
+                        dynamic_state.insert(DynamicState::FragmentShadingRate, true);
+                        Default::default()
+                    }
+                };
+
+                let _ = fragment_shading_rate_state_vk.insert(
+                    ash::vk::PipelineFragmentShadingRateStateCreateInfoKHR {
+                        fragment_size: ash::vk::Extent2D {
+                            width: fragment_size[0],
+                            height: fragment_size[1],
+                        },
+                        combiner_ops: [combiner_ops[0].into(), combiner_ops[1].into()],
+                        ..Default::default()
+                    },
+                );
+            }
         }
 
         // Depth/stencil state
@@ -3129,8 +3356,22 @@ where
             Create
         */
 
+        let mut flags = executable::create_flags(device);
+
+        if *allow_derivatives {
+            flags |= ash::vk::PipelineCreateFlags::ALLOW_DERIVATIVES;
+        }
+
+        let base_pipeline_handle = match base_pipeline {
+            Some(base_pipeline) => {
+                flags |= ash::vk::PipelineCreateFlags::DERIVATIVE;
+                base_pipeline.internal_object()
+            }
+            None => ash::vk::Pipeline::null(),
+        };
+
         let mut create_info = ash::vk::GraphicsPipelineCreateInfo {
-            flags: ash::vk::PipelineCreateFlags::empty(), // TODO: some flags are available but none are critical
+            flags,
             stage_count: stages_vk.len() as u32,
             p_stages: stages_vk.as_ptr(),
             p_vertex_input_state: vertex_input_state_vk
@@ -3172,8 +3413,8 @@ where
             layout: pipeline_layout.internal_object(),
             render_pass: render_pass_vk,
             subpass: subpass_vk,
-            base_pipeline_handle: ash::vk::Pipeline::null(), // TODO:
-            base_pipeline_index: -1,                         // TODO:
+            base_pipeline_handle,
+            base_pipeline_index: -1,
             ..Default::default()
         };
 
@@ -3182,6 +3423,11 @@ where
             create_info.p_next = info as *const _ as *const _;
         }
 
+        if let Some(info) = fragment_shading_rate_state_vk.as_mut() {
+            info.p_next = create_info.p_next;
+            create_info.p_next = info as *const _ as *const _;
+        }
+
         if let Some(info) = rendering_create_info_vk.as_mut() {
             info.p_next = create_info.p_next;
             create_info.p_next = info as *const _ as *const _;
@@ -3200,7 +3446,7 @@ where
                 cache_handle,
                 1,
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -3228,8 +3474,6 @@ struct ShaderStageInfo<'a> {
 impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
 {
-    // TODO: add pipeline derivate system
-
     /// Sets the vertex shader to use.
     // TODO: correct specialization constants
     #[inline]
@@ -3244,6 +3488,8 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: Some((shader, specialization_constants)),
             tessellation_shaders: self.tessellation_shaders,
@@ -3255,6 +3501,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
@@ -3279,6 +3526,8 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: Some(TessellationShaders {
@@ -3293,6 +3542,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
@@ -3314,6 +3564,8 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
@@ -3325,6 +3577,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
@@ -3348,6 +3601,8 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
@@ -3359,6 +3614,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
@@ -3380,6 +3636,8 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
@@ -3391,6 +3649,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
             depth_stencil_state: self.depth_stencil_state,
@@ -3438,6 +3697,18 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         self
     }
 
+    /// Sets the fragment shading rate state.
+    ///
+    /// The default value is [`FragmentShadingRateState::default()`].
+    #[inline]
+    pub fn fragment_shading_rate_state(
+        mut self,
+        fragment_shading_rate_state: FragmentShadingRateState,
+    ) -> Self {
+        self.fragment_shading_rate_state = fragment_shading_rate_state;
+        self
+    }
+
     /// Sets the rasterization state.
     ///
     /// The default value is [`RasterizationState::default()`].
@@ -4043,6 +4314,8 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         GraphicsPipelineBuilder {
             render_pass: Some(render_pass.into()),
             cache: self.cache,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
@@ -4059,9 +4332,28 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             color_blend_state: self.color_blend_state,
 
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
         }
     }
 
+    /// Sets whether other pipelines can be derived from this pipeline. The default is `false`.
+    #[inline]
+    pub fn allow_derivatives(mut self, allow_derivatives: bool) -> Self {
+        self.allow_derivatives = allow_derivatives;
+        self
+    }
+
+    /// Sets the pipeline that this pipeline is derived from. Implementations may be able to
+    /// create derivatives faster than unrelated pipelines.
+    ///
+    /// The base pipeline must have been created with
+    /// [`allow_derivatives`](GraphicsPipelineBuilder::allow_derivatives) set.
+    #[inline]
+    pub fn base_pipeline(mut self, base_pipeline: Arc<GraphicsPipeline>) -> Self {
+        self.base_pipeline = Some(base_pipeline);
+        self
+    }
+
     /// Enable caching of this pipeline via a PipelineCache object.
     ///
     /// If this pipeline already exists in the cache it will be used, if this is a new
@@ -4088,6 +4380,8 @@ where
         GraphicsPipelineBuilder {
             render_pass: self.render_pass.clone(),
             cache: self.cache.clone(),
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline.clone(),
 
             vertex_shader: self.vertex_shader.clone(),
             tessellation_shaders: self.tessellation_shaders.clone(),
@@ -4104,6 +4398,7 @@ where
             color_blend_state: self.color_blend_state.clone(),
 
             discard_rectangle_state: self.discard_rectangle_state.clone(),
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
         }
     }
 }
@@ -7,7 +7,10 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use super::vertex_input::IncompatibleVertexDefinitionError;
+use super::{
+    fragment_shading_rate::FragmentShadingRateCombinerOp,
+    rasterization::ConservativeRasterizationMode, vertex_input::IncompatibleVertexDefinitionError,
+};
 use crate::{
     descriptor_set::layout::DescriptorSetLayoutCreationError,
     format::{Format, NumericType},
@@ -32,12 +35,18 @@ pub enum GraphicsPipelineCreationError {
         reason: &'static str,
     },
 
+    /// The base pipeline was not created with derivatives allowed.
+    BasePipelineDerivativesNotAllowed,
+
     /// A color attachment has a format that does not support blending.
     ColorAttachmentFormatBlendNotSupported { attachment_index: u32 },
 
     /// A color attachment has a format that does not support that usage.
     ColorAttachmentFormatUsageNotSupported { attachment_index: u32 },
 
+    /// The requested conservative rasterization mode is not supported by the device.
+    ConservativeRasterizationModeNotSupported { mode: ConservativeRasterizationMode },
+
     /// The depth attachment has a format that does not support that usage.
     DepthAttachmentFormatUsageNotSupported,
 
@@ -48,6 +57,13 @@ pub enum GraphicsPipelineCreationError {
     /// expects.
     FragmentShaderRenderPassIncompatible,
 
+    /// The fragment shading rate uses a combiner operation other than `Keep` or `Replace`, but
+    /// the device does not support non-trivial combiner operations.
+    FragmentShadingRateCombinerOpNotSupported { op: FragmentShadingRateCombinerOp },
+
+    /// The fragment size of the fragment shading rate is not supported by the device.
+    FragmentSizeNotSupported { fragment_size: [u32; 2] },
+
     /// The pipeline layout is not compatible with what the shaders expect.
     IncompatiblePipelineLayout(PipelineLayoutSupersetError),
 
@@ -64,6 +80,9 @@ pub enum GraphicsPipelineCreationError {
     /// `patch_control_points` was not greater than 0 and less than or equal to the `max_tessellation_patch_size` limit.
     InvalidNumPatchControlPoints,
 
+    /// The `factor` of a fixed line stipple was not between 1 and 256 inclusive.
+    LineStippleFactorOutOfRange { factor: u32 },
+
     /// The maximum number of discard rectangles has been exceeded.
     MaxDiscardRectanglesExceeded {
         /// Maximum allowed value.
@@ -228,6 +247,10 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 "the feature {} must be enabled: {}",
                 feature, reason
             ),
+            Self::BasePipelineDerivativesNotAllowed => write!(
+                f,
+                "the base pipeline was not created with derivatives allowed",
+            ),
             Self::ColorAttachmentFormatBlendNotSupported { attachment_index } => write!(
                 f,
                 "color attachment {} has a format that does not support blending",
@@ -238,6 +261,11 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 "color attachment {} has a format that does not support that usage",
                 attachment_index,
             ),
+            Self::ConservativeRasterizationModeNotSupported { mode } => write!(
+                f,
+                "the conservative rasterization mode {:?} is not supported by the device",
+                mode,
+            ),
             Self::DepthAttachmentFormatUsageNotSupported => write!(
                 f,
                 "the depth attachment has a format that does not support that usage",
@@ -250,6 +278,16 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 f,
                 "the output of the fragment shader is not compatible with what the render pass subpass expects",
             ),
+            Self::FragmentShadingRateCombinerOpNotSupported { op } => write!(
+                f,
+                "the fragment shading rate combiner operation {:?} is not supported by the device",
+                op,
+            ),
+            Self::FragmentSizeNotSupported { fragment_size } => write!(
+                f,
+                "the fragment size {:?} is not supported by the device",
+                fragment_size,
+            ),
             Self::IncompatiblePipelineLayout(_) => write!(
                 f,
                 "the pipeline layout is not compatible with what the shaders expect",
@@ -270,6 +308,11 @@ impl fmt::Display for GraphicsPipelineCreationError {
                 f,
                 "patch_control_points was not greater than 0 and less than or equal to the max_tessellation_patch_size limit",
             ),
+            Self::LineStippleFactorOutOfRange { factor } => write!(
+                f,
+                "the line stipple factor ({}) was not between 1 and 256 inclusive",
+                factor,
+            ),
             Self::MaxDiscardRectanglesExceeded { .. } => write!(
                 f,
                 "the maximum number of discard rectangles has been exceeded",
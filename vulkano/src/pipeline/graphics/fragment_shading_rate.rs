@@ -0,0 +1,132 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Configures the rate at which the fragment shader is invoked.
+//!
+//! Normally, the fragment shader is invoked once for every pixel (or sample) that is covered by a
+//! primitive. With a coarser fragment shading rate, a single invocation covers a block of several
+//! pixels, which reduces the shading cost in areas where less detail is needed.
+//!
+//! The final shading rate of a fragment is obtained by combining the rate of the pipeline (or of
+//! the draw call, if set dynamically) with the rate of the primitive and the rate read from a
+//! fragment shading rate attachment, using the combiner operations.
+
+use crate::pipeline::StateMode;
+
+/// The state in a graphics pipeline describing the fragment shading rate.
+#[derive(Clone, Copy, Debug)]
+pub struct FragmentShadingRateState {
+    /// The fragment shading rate of the pipeline, and how it is combined with the other rates.
+    ///
+    /// If set to `Dynamic`, or to a value other than the default, the
+    /// [`khr_fragment_shading_rate`](crate::device::DeviceExtensions::khr_fragment_shading_rate)
+    /// extension must be enabled on the device.
+    pub rate: StateMode<FragmentShadingRate>,
+}
+
+impl FragmentShadingRateState {
+    /// Creates a `FragmentShadingRateState` with a fragment size of 1x1, and the combiner
+    /// operations set to `Keep`.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            rate: StateMode::Fixed(Default::default()),
+        }
+    }
+}
+
+impl Default for FragmentShadingRateState {
+    /// Returns [`FragmentShadingRateState::new()`].
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A fragment shading rate, and the operations that combine it with the other shading rates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FragmentShadingRate {
+    /// The width and height in pixels of the fragments that are generated. Each value must be
+    /// 1, 2 or 4, and must not exceed the
+    /// [`max_fragment_size`](crate::device::Properties::max_fragment_size) device property.
+    ///
+    /// If this is not `[1, 1]`, the
+    /// [`pipeline_fragment_shading_rate`](crate::device::Features::pipeline_fragment_shading_rate)
+    /// feature must be enabled on the device.
+    pub fragment_size: [u32; 2],
+
+    /// The operations that combine the shading rates. The first operation combines the rate
+    /// given in `fragment_size` with the rate of the primitive, the second combines that result
+    /// with the rate read from the fragment shading rate attachment.
+    ///
+    /// If the first operation is not `Keep`, the
+    /// [`primitive_fragment_shading_rate`](crate::device::Features::primitive_fragment_shading_rate)
+    /// feature must be enabled on the device. If the second operation is not `Keep`, the
+    /// [`attachment_fragment_shading_rate`](crate::device::Features::attachment_fragment_shading_rate)
+    /// feature must be enabled on the device. Operations other than `Keep` and `Replace` require
+    /// the
+    /// [`fragment_shading_rate_non_trivial_combiner_ops`](crate::device::Properties::fragment_shading_rate_non_trivial_combiner_ops)
+    /// device property to be `true`.
+    pub combiner_ops: [FragmentShadingRateCombinerOp; 2],
+}
+
+impl Default for FragmentShadingRate {
+    /// Returns a fragment size of `[1, 1]`, with both combiner operations set to `Keep`.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            fragment_size: [1, 1],
+            combiner_ops: [FragmentShadingRateCombinerOp::Keep; 2],
+        }
+    }
+}
+
+/// An operation that combines two fragment shading rates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum FragmentShadingRateCombinerOp {
+    /// The first rate is kept, the second is ignored.
+    Keep = ash::vk::FragmentShadingRateCombinerOpKHR::KEEP.as_raw(),
+
+    /// The second rate replaces the first.
+    Replace = ash::vk::FragmentShadingRateCombinerOpKHR::REPLACE.as_raw(),
+
+    /// The minimum of the two rates is used, for each dimension.
+    Min = ash::vk::FragmentShadingRateCombinerOpKHR::MIN.as_raw(),
+
+    /// The maximum of the two rates is used, for each dimension.
+    Max = ash::vk::FragmentShadingRateCombinerOpKHR::MAX.as_raw(),
+
+    /// The product of the two rates is used, for each dimension.
+    Mul = ash::vk::FragmentShadingRateCombinerOpKHR::MUL.as_raw(),
+}
+
+impl FragmentShadingRateCombinerOp {
+    /// Returns whether this operation is `Keep` or `Replace`, which are supported whenever the
+    /// corresponding feature is enabled.
+    #[inline]
+    pub(crate) fn is_trivial(&self) -> bool {
+        matches!(self, Self::Keep | Self::Replace)
+    }
+}
+
+impl Default for FragmentShadingRateCombinerOp {
+    /// Returns `FragmentShadingRateCombinerOp::Keep`.
+    #[inline]
+    fn default() -> Self {
+        Self::Keep
+    }
+}
+
+impl From<FragmentShadingRateCombinerOp> for ash::vk::FragmentShadingRateCombinerOpKHR {
+    #[inline]
+    fn from(val: FragmentShadingRateCombinerOp) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
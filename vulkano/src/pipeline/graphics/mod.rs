@@ -59,12 +59,18 @@
 pub use self::{builder::GraphicsPipelineBuilder, creation_error::GraphicsPipelineCreationError};
 use self::{
     color_blend::ColorBlendState, depth_stencil::DepthStencilState,
-    discard_rectangle::DiscardRectangleState, input_assembly::InputAssemblyState,
-    multisample::MultisampleState, rasterization::RasterizationState,
-    render_pass::PipelineRenderPassType, tessellation::TessellationState,
-    vertex_input::VertexInputState, viewport::ViewportState,
+    discard_rectangle::DiscardRectangleState, fragment_shading_rate::FragmentShadingRateState,
+    input_assembly::InputAssemblyState, multisample::MultisampleState,
+    rasterization::RasterizationState, render_pass::PipelineRenderPassType,
+    tessellation::TessellationState, vertex_input::VertexInputState, viewport::ViewportState,
+};
+use super::{
+    executable::{
+        self, PipelineExecutableError, PipelineExecutableInfo,
+        PipelineExecutableInternalRepresentation,
+    },
+    DynamicState, Pipeline, PipelineBindPoint, PipelineLayout,
 };
-use super::{DynamicState, Pipeline, PipelineBindPoint, PipelineLayout};
 use crate::{
     device::{Device, DeviceOwned},
     shader::{DescriptorRequirements, ShaderStage},
@@ -74,7 +80,6 @@ use std::{
     collections::HashMap,
     fmt,
     hash::{Hash, Hasher},
-    ptr,
     sync::Arc,
 };
 
@@ -83,6 +88,7 @@ pub mod color_blend;
 mod creation_error;
 pub mod depth_stencil;
 pub mod discard_rectangle;
+pub mod fragment_shading_rate;
 pub mod input_assembly;
 pub mod multisample;
 pub mod rasterization;
@@ -102,6 +108,7 @@ pub struct GraphicsPipeline {
     device: Arc<Device>,
     layout: Arc<PipelineLayout>,
     render_pass: PipelineRenderPassType,
+    allow_derivatives: bool,
 
     // TODO: replace () with an object that describes the shaders in some way.
     shaders: HashMap<ShaderStage, ()>,
@@ -113,6 +120,7 @@ pub struct GraphicsPipeline {
     tessellation_state: Option<TessellationState>,
     viewport_state: Option<ViewportState>,
     discard_rectangle_state: Option<DiscardRectangleState>,
+    fragment_shading_rate_state: Option<FragmentShadingRateState>,
     rasterization_state: RasterizationState,
     multisample_state: Option<MultisampleState>,
     depth_stencil_state: Option<DepthStencilState>,
@@ -151,6 +159,13 @@ impl GraphicsPipeline {
         &self.render_pass
     }
 
+    /// Returns the number of views that this graphics pipeline renders to with multiview
+    /// rendering, or `0` if multiview is not enabled.
+    #[inline]
+    pub fn view_count(&self) -> u32 {
+        self.render_pass.view_count()
+    }
+
     /// Returns information about a particular shader.
     ///
     /// `None` is returned if the pipeline does not contain this shader.
@@ -202,6 +217,12 @@ impl GraphicsPipeline {
         self.discard_rectangle_state.as_ref()
     }
 
+    /// Returns the fragment shading rate state used to create this pipeline.
+    #[inline]
+    pub fn fragment_shading_rate_state(&self) -> Option<&FragmentShadingRateState> {
+        self.fragment_shading_rate_state.as_ref()
+    }
+
     /// Returns the rasterization state used to create this pipeline.
     #[inline]
     pub fn rasterization_state(&self) -> &RasterizationState {
@@ -238,6 +259,36 @@ impl GraphicsPipeline {
     pub fn dynamic_states(&self) -> impl ExactSizeIterator<Item = (DynamicState, bool)> + '_ {
         self.dynamic_state.iter().map(|(k, v)| (*k, *v))
     }
+
+    /// Returns whether other pipelines can be derived from this pipeline.
+    #[inline]
+    pub fn allow_derivatives(&self) -> bool {
+        self.allow_derivatives
+    }
+
+    /// Returns the executables that the pipeline was compiled into, with their statistics.
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device.
+    #[inline]
+    pub fn executable_properties(
+        &self,
+    ) -> Result<Vec<PipelineExecutableInfo>, PipelineExecutableError> {
+        executable::executable_properties(&self.device, self.handle)
+    }
+
+    /// Returns the internal representations of the executable `executable_index`, such as its
+    /// disassembly.
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device.
+    #[inline]
+    pub fn executable_internal_representations(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, PipelineExecutableError> {
+        executable::executable_internal_representations(&self.device, self.handle, executable_index)
+    }
 }
 
 impl Pipeline for GraphicsPipeline {
@@ -285,7 +336,11 @@ impl Drop for GraphicsPipeline {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_pipeline)(self.device.internal_object(), self.handle, ptr::null());
+            (fns.v1_0.destroy_pipeline)(
+                self.device.internal_object(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
         }
     }
 }
@@ -78,11 +78,18 @@ pub struct RasterizationState {
     /// [`ext_line_rasterization`](crate::device::DeviceExtensions::ext_line_rasterization)
     /// extension and an additional feature must be enabled on the device.
     pub line_stipple: Option<StateMode<LineStipple>>,
+
+    /// Enables and sets the parameters for conservative rasterization.
+    ///
+    /// If the mode is not `Disabled`, the
+    /// [`ext_conservative_rasterization`](crate::device::DeviceExtensions::ext_conservative_rasterization)
+    /// extension must be enabled on the device.
+    pub conservative_rasterization: ConservativeRasterizationState,
 }
 
 impl RasterizationState {
-    /// Creates a `RasterizationState` with depth clamping, discard, depth biasing and line
-    /// stippling disabled, filled polygons, no culling, counterclockwise front face, and the
+    /// Creates a `RasterizationState` with depth clamping, discard, depth biasing, line
+    /// stippling and conservative rasterization disabled, filled polygons, no culling, counterclockwise front face, and the
     /// default line width and line rasterization mode.
     #[inline]
     pub fn new() -> Self {
@@ -96,6 +103,7 @@ impl RasterizationState {
             line_width: StateMode::Fixed(1.0),
             line_rasterization_mode: Default::default(),
             line_stipple: None,
+            conservative_rasterization: Default::default(),
         }
     }
 
@@ -133,6 +141,16 @@ impl RasterizationState {
         self.front_face = StateMode::Dynamic;
         self
     }
+
+    /// Sets the conservative rasterization state.
+    #[inline]
+    pub fn conservative_rasterization(
+        mut self,
+        conservative_rasterization: ConservativeRasterizationState,
+    ) -> Self {
+        self.conservative_rasterization = conservative_rasterization;
+        self
+    }
 }
 
 impl Default for RasterizationState {
@@ -316,3 +334,65 @@ pub struct LineStipple {
     /// The bit pattern used in stippled line rasterization.
     pub pattern: u16,
 }
+
+/// The state in a graphics pipeline describing how conservative rasterization should behave.
+#[derive(Clone, Copy, Debug)]
+pub struct ConservativeRasterizationState {
+    /// The conservative rasterization mode to use.
+    ///
+    /// The default value is [`ConservativeRasterizationMode::Disabled`].
+    pub mode: ConservativeRasterizationMode,
+
+    /// The extra size in pixels to increase the generating primitive during conservative
+    /// rasterization, when `mode` is `Overestimate`.
+    ///
+    /// The value is clamped to the range from 0.0 to the
+    /// [`max_extra_primitive_overestimation_size`](crate::device::Properties::max_extra_primitive_overestimation_size)
+    /// device property.
+    ///
+    /// The default value is 0.0.
+    pub extra_primitive_overestimation_size: f32,
+}
+
+impl Default for ConservativeRasterizationState {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            mode: ConservativeRasterizationMode::Disabled,
+            extra_primitive_overestimation_size: 0.0,
+        }
+    }
+}
+
+/// The conservative rasterization mode to use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum ConservativeRasterizationMode {
+    /// Conservative rasterization is disabled; primitives are rasterized normally.
+    Disabled = ash::vk::ConservativeRasterizationModeEXT::DISABLED.as_raw(),
+
+    /// Fragments are generated for every pixel that is at least partially covered by the
+    /// primitive.
+    Overestimate = ash::vk::ConservativeRasterizationModeEXT::OVERESTIMATE.as_raw(),
+
+    /// Fragments are generated only for pixels that are fully covered by the primitive.
+    ///
+    /// The [`primitive_underestimation`](crate::device::Properties::primitive_underestimation)
+    /// device property must be `true`.
+    Underestimate = ash::vk::ConservativeRasterizationModeEXT::UNDERESTIMATE.as_raw(),
+}
+
+impl Default for ConservativeRasterizationMode {
+    /// Returns `ConservativeRasterizationMode::Disabled`.
+    #[inline]
+    fn default() -> Self {
+        Self::Disabled
+    }
+}
+
+impl From<ConservativeRasterizationMode> for ash::vk::ConservativeRasterizationModeEXT {
+    #[inline]
+    fn from(val: ConservativeRasterizationMode) -> Self {
+        Self::from_raw(val as i32)
+    }
+}
@@ -17,6 +17,26 @@ pub enum PipelineRenderPassType {
     BeginRendering(PipelineRenderingCreateInfo),
 }
 
+impl PipelineRenderPassType {
+    /// Returns the view mask that the pipeline renders with. This is `0` if multiview rendering
+    /// is not enabled.
+    #[inline]
+    pub fn view_mask(&self) -> u32 {
+        match self {
+            Self::BeginRenderPass(subpass) => subpass.subpass_desc().view_mask,
+            Self::BeginRendering(rendering_info) => rendering_info.view_mask,
+        }
+    }
+
+    /// Returns the number of views that the pipeline renders to, which is the index of the
+    /// highest bit set in the view mask plus one. This is `0` if multiview rendering is not
+    /// enabled.
+    #[inline]
+    pub fn view_count(&self) -> u32 {
+        u32::BITS - self.view_mask().leading_zeros()
+    }
+}
+
 impl From<Subpass> for PipelineRenderPassType {
     #[inline]
     fn from(val: Subpass) -> Self {
@@ -75,7 +75,6 @@ use std::{
     error, fmt,
     hash::{Hash, Hasher},
     mem::MaybeUninit,
-    ptr,
     sync::Arc,
 };
 
@@ -500,7 +499,7 @@ impl PipelineLayout {
             check_errors((fns.v1_0.create_pipeline_layout)(
                 device.internal_object(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -626,7 +625,7 @@ impl Drop for PipelineLayout {
             (fns.v1_0.destroy_pipeline_layout)(
                 self.device.internal_object(),
                 self.handle,
-                ptr::null(),
+                self.device.allocation_callbacks(),
             );
         }
     }
@@ -25,6 +25,7 @@ use std::sync::Arc;
 
 pub mod cache;
 pub mod compute;
+pub mod executable;
 pub mod graphics;
 pub mod layout;
 
@@ -18,6 +18,7 @@ use crate::{
     device::{Device, DeviceOwned},
     DeviceSize, Error, OomError, Success, VulkanObject,
 };
+use smallvec::SmallVec;
 use std::{
     error,
     ffi::c_void,
@@ -25,7 +26,6 @@ use std::{
     hash::{Hash, Hasher},
     mem::{size_of_val, MaybeUninit},
     ops::Range,
-    ptr,
     sync::Arc,
 };
 
@@ -87,7 +87,7 @@ impl QueryPool {
             check_errors((fns.v1_0.create_query_pool)(
                 device.internal_object(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -102,6 +102,30 @@ impl QueryPool {
         }))
     }
 
+    /// Creates a new `QueryPool` of `PipelineStatistics` queries, counting the statistics enabled
+    /// in `flags`.
+    ///
+    /// The [`pipeline_statistics_query`](crate::device::Features::pipeline_statistics_query)
+    /// feature must be enabled on the device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `query_count` is `0`.
+    #[inline]
+    pub fn new_pipeline_statistics(
+        device: Arc<Device>,
+        flags: QueryPipelineStatisticFlags,
+        query_count: u32,
+    ) -> Result<Arc<QueryPool>, QueryPoolCreationError> {
+        Self::new(
+            device,
+            QueryPoolCreateInfo {
+                query_count,
+                ..QueryPoolCreateInfo::query_type(QueryType::PipelineStatistics(flags))
+            },
+        )
+    }
+
     /// Returns the query type of the pool.
     #[inline]
     pub fn query_type(&self) -> QueryType {
@@ -139,6 +163,80 @@ impl QueryPool {
             None
         }
     }
+
+    /// Retrieves the results of a range of queries, together with their availability.
+    ///
+    /// One [`QueryResult`] is returned for each query in `range`, containing
+    /// [`self.query_type().result_len()`](QueryType::result_len) values. If a query's results are
+    /// not yet available, `available` is `false` and its values are zero, unless
+    /// [`QueryResultFlags::partial`] is enabled. The availability is always retrieved, so
+    /// [`QueryResultFlags::with_availability`] is ignored.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the range is empty.
+    pub fn get_results_typed<T>(
+        &self,
+        range: Range<u32>,
+        flags: QueryResultFlags,
+    ) -> Result<Vec<QueryResult<T>>, GetResultsError>
+    where
+        T: QueryResultElement + Copy + Default + PartialEq,
+    {
+        let queries_range =
+            self.queries_range(range.clone())
+                .ok_or(GetResultsError::RangeOutOfBounds {
+                    range_end: range.end,
+                    query_count: self.query_count,
+                })?;
+
+        let result_len = self.query_type.result_len() as usize;
+        let per_query_len = result_len + 1;
+        let mut data = vec![T::default(); per_query_len * range.len()];
+
+        queries_range.get_results(
+            &mut data,
+            QueryResultFlags {
+                with_availability: true,
+                ..flags
+            },
+        )?;
+
+        Ok(data
+            .chunks_exact(per_query_len)
+            .map(|chunk| QueryResult {
+                values: chunk[..result_len].iter().copied().collect(),
+                available: chunk[result_len] != T::default(),
+            })
+            .collect())
+    }
+
+    /// Retrieves the results of a range of `PipelineStatistics` queries.
+    ///
+    /// One [`PipelineStatistics`] is returned for each query in `range`. Only the fields
+    /// corresponding to the statistics that the pool was created with are `Some`.
+    ///
+    /// See [`get_results_typed`](QueryPool::get_results_typed) for the meaning of `flags`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the range is empty.
+    pub fn get_pipeline_statistics(
+        &self,
+        range: Range<u32>,
+        flags: QueryResultFlags,
+    ) -> Result<Vec<PipelineStatistics>, GetResultsError> {
+        let statistic_flags = match self.query_type {
+            QueryType::PipelineStatistics(flags) => flags,
+            _ => return Err(GetResultsError::QueryTypeMismatch),
+        };
+
+        Ok(self
+            .get_results_typed::<u64>(range, flags)?
+            .into_iter()
+            .map(|result| PipelineStatistics::from_values(statistic_flags, &result))
+            .collect())
+    }
 }
 
 impl Drop for QueryPool {
@@ -146,7 +244,11 @@ impl Drop for QueryPool {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_query_pool)(self.device.internal_object(), self.handle, ptr::null());
+            (fns.v1_0.destroy_query_pool)(
+                self.device.internal_object(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
         }
     }
 }
@@ -416,6 +518,15 @@ pub enum GetResultsError {
     InvalidFlags,
     /// Not enough memory.
     OomError(OomError),
+    /// The operation is not supported for the type of the query pool.
+    QueryTypeMismatch,
+    /// The requested range of queries is not within the query pool.
+    RangeOutOfBounds {
+        /// End of the requested range.
+        range_end: u32,
+        /// Number of queries in the query pool.
+        query_count: u32,
+    },
 }
 
 impl From<Error> for GetResultsError {
@@ -453,6 +564,12 @@ impl fmt::Display for GetResultsError {
                     "the provided flags are not allowed for this type of query"
                 }
                 Self::OomError(_) => "not enough memory available",
+                Self::QueryTypeMismatch => {
+                    "the operation is not supported for the type of the query pool"
+                }
+                Self::RangeOutOfBounds { .. } => {
+                    "the requested range of queries is not within the query pool"
+                }
             }
         )
     }
@@ -485,6 +602,89 @@ unsafe impl QueryResultElement for u64 {
     const FLAG: ash::vk::QueryResultFlags = ash::vk::QueryResultFlags::TYPE_64;
 }
 
+/// The results of a single query, as returned by [`QueryPool::get_results_typed`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueryResult<T> {
+    /// The values written by the query. For `Occlusion` and `Timestamp` queries this contains
+    /// a single value, for `PipelineStatistics` queries it contains one value per enabled flag.
+    pub values: SmallVec<[T; 1]>,
+
+    /// Whether the results of the query were available.
+    pub available: bool,
+}
+
+impl<T> QueryResult<T>
+where
+    T: Copy,
+{
+    /// Returns the first value of the query, which is the only value for `Occlusion` and
+    /// `Timestamp` queries.
+    #[inline]
+    pub fn value(&self) -> T {
+        self.values[0]
+    }
+}
+
+/// The results of a single `PipelineStatistics` query, as returned by
+/// [`QueryPool::get_pipeline_statistics`].
+///
+/// Each field is `Some` only if the corresponding flag was enabled in the
+/// [`QueryPipelineStatisticFlags`] of the query pool.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PipelineStatistics {
+    /// The number of vertices processed by the input assembly.
+    pub input_assembly_vertices: Option<u64>,
+    /// The number of primitives processed by the input assembly.
+    pub input_assembly_primitives: Option<u64>,
+    /// The number of times a vertex shader was invoked.
+    pub vertex_shader_invocations: Option<u64>,
+    /// The number of times a geometry shader was invoked.
+    pub geometry_shader_invocations: Option<u64>,
+    /// The number of primitives generated by geometry shaders.
+    pub geometry_shader_primitives: Option<u64>,
+    /// The number of times the clipping stage was invoked on a primitive.
+    pub clipping_invocations: Option<u64>,
+    /// The number of primitives that were output by the clipping stage.
+    pub clipping_primitives: Option<u64>,
+    /// The number of times a fragment shader was invoked.
+    pub fragment_shader_invocations: Option<u64>,
+    /// The number of patches processed by a tessellation control shader.
+    pub tessellation_control_shader_patches: Option<u64>,
+    /// The number of times a tessellation evaluation shader was invoked.
+    pub tessellation_evaluation_shader_invocations: Option<u64>,
+    /// The number of times a compute shader was invoked.
+    pub compute_shader_invocations: Option<u64>,
+
+    /// Whether the results of the query were available.
+    pub available: bool,
+}
+
+impl PipelineStatistics {
+    // The values are written by Vulkan in the order of the bits of the enabled flags, which is
+    // the same as the order of the fields of `QueryPipelineStatisticFlags`.
+    fn from_values(flags: QueryPipelineStatisticFlags, result: &QueryResult<u64>) -> Self {
+        let mut values = result.values.iter().copied();
+        let mut next = |enabled: bool| if enabled { values.next() } else { None };
+
+        PipelineStatistics {
+            input_assembly_vertices: next(flags.input_assembly_vertices),
+            input_assembly_primitives: next(flags.input_assembly_primitives),
+            vertex_shader_invocations: next(flags.vertex_shader_invocations),
+            geometry_shader_invocations: next(flags.geometry_shader_invocations),
+            geometry_shader_primitives: next(flags.geometry_shader_primitives),
+            clipping_invocations: next(flags.clipping_invocations),
+            clipping_primitives: next(flags.clipping_primitives),
+            fragment_shader_invocations: next(flags.fragment_shader_invocations),
+            tessellation_control_shader_patches: next(flags.tessellation_control_shader_patches),
+            tessellation_evaluation_shader_invocations: next(
+                flags.tessellation_evaluation_shader_invocations,
+            ),
+            compute_shader_invocations: next(flags.compute_shader_invocations),
+            available: result.available,
+        }
+    }
+}
+
 /// The type of query that a query pool should perform.
 #[derive(Debug, Copy, Clone)]
 pub enum QueryType {
@@ -736,12 +936,90 @@ impl From<QueryResultFlags> for ash::vk::QueryResultFlags {
 
 #[cfg(test)]
 mod tests {
+    use super::GetResultsError;
     use super::QueryPoolCreateInfo;
+    use super::QueryResultFlags;
     use crate::query::QueryPipelineStatisticFlags;
     use crate::query::QueryPool;
     use crate::query::QueryPoolCreationError;
     use crate::query::QueryType;
 
+    #[test]
+    fn get_results_typed_out_of_range() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = QueryPool::new(
+            device,
+            QueryPoolCreateInfo {
+                query_count: 4,
+                ..QueryPoolCreateInfo::query_type(QueryType::Timestamp)
+            },
+        )
+        .unwrap();
+
+        match pool.get_results_typed::<u64>(2..5, QueryResultFlags::default()) {
+            Err(GetResultsError::RangeOutOfBounds {
+                range_end: 5,
+                query_count: 4,
+            }) => (),
+            _ => panic!(),
+        };
+
+        match pool.get_results_typed::<u32>(
+            0..4,
+            QueryResultFlags {
+                partial: true,
+                ..Default::default()
+            },
+        ) {
+            Err(GetResultsError::InvalidFlags) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn pipeline_statistics_results() {
+        let (device, _) = gfx_dev_and_queue!(pipeline_statistics_query);
+        let flags = QueryPipelineStatisticFlags {
+            vertex_shader_invocations: true,
+            fragment_shader_invocations: true,
+            ..QueryPipelineStatisticFlags::none()
+        };
+        let pool = QueryPool::new_pipeline_statistics(device, flags, 2).unwrap();
+
+        let results = pool
+            .get_pipeline_statistics(0..2, QueryResultFlags::default())
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        for result in results {
+            assert!(result.input_assembly_vertices.is_none());
+            assert!(result.compute_shader_invocations.is_none());
+
+            if result.available {
+                assert!(result.vertex_shader_invocations.is_some());
+                assert!(result.fragment_shader_invocations.is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn pipeline_statistics_query_type_mismatch() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = QueryPool::new(
+            device,
+            QueryPoolCreateInfo {
+                query_count: 1,
+                ..QueryPoolCreateInfo::query_type(QueryType::Occlusion)
+            },
+        )
+        .unwrap();
+
+        match pool.get_pipeline_statistics(0..1, QueryResultFlags::default()) {
+            Err(GetResultsError::QueryTypeMismatch) => (),
+            _ => panic!(),
+        };
+    }
+
     #[test]
     fn pipeline_statistics_feature() {
         let (device, _) = gfx_dev_and_queue!();
@@ -1055,14 +1055,14 @@ impl RenderPass {
                 (fns.v1_2.create_render_pass2)(
                     device.internal_object(),
                     &create_info,
-                    ptr::null(),
+                    device.allocation_callbacks(),
                     output.as_mut_ptr(),
                 )
             } else {
                 (fns.khr_create_renderpass2.create_render_pass2_khr)(
                     device.internal_object(),
                     &create_info,
-                    ptr::null(),
+                    device.allocation_callbacks(),
                     output.as_mut_ptr(),
                 )
             })?;
@@ -1321,7 +1321,7 @@ impl RenderPass {
             check_errors((fns.v1_0.create_render_pass)(
                 device.internal_object(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -21,7 +21,6 @@ use std::{
     hash::{Hash, Hasher},
     mem::MaybeUninit,
     ops::Range,
-    ptr,
     sync::Arc,
 };
 
@@ -312,7 +311,7 @@ impl Framebuffer {
             check_errors((fns.v1_0.create_framebuffer)(
                 device.internal_object(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -370,7 +369,7 @@ impl Drop for Framebuffer {
             (fns.v1_0.destroy_framebuffer)(
                 self.device().internal_object(),
                 self.handle,
-                ptr::null(),
+                self.device().allocation_callbacks(),
             );
         }
     }
@@ -39,9 +39,9 @@ use crate::{
 };
 use std::cmp::max;
 use std::{
+    error, fmt,
     hash::{Hash, Hasher},
     mem::MaybeUninit,
-    ptr,
     sync::Arc,
 };
 
@@ -245,9 +245,22 @@ impl RenderPass {
 
     /// Returns `true` if this render pass is compatible with the other render pass,
     /// as defined in the [`Render Pass Compatibility` section of the Vulkan specs](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/html/chap8.html#renderpass-compatibility).
+    ///
+    /// See [`check_compatibility`](RenderPass::check_compatibility) for a version that reports
+    /// the reason why the render passes are not compatible.
+    #[inline]
     pub fn is_compatible_with(&self, other: &RenderPass) -> bool {
+        self.check_compatibility(other).is_ok()
+    }
+
+    /// Checks whether this render pass is compatible with the other render pass, and returns
+    /// the first mismatch found if it is not.
+    ///
+    /// Load and store operations, initial and final layouts, and the layouts of attachment
+    /// references are ignored, as required by the specification.
+    pub fn check_compatibility(&self, other: &RenderPass) -> Result<(), CompatibilityMismatch> {
         if self == other {
-            return true;
+            return Ok(());
         }
 
         let Self {
@@ -272,167 +285,194 @@ impl RenderPass {
         } = other;
 
         if attachments1.len() != attachments2.len() {
-            return false;
+            return Err(CompatibilityMismatch::AttachmentCount {
+                count: attachments1.len() as u32,
+                other_count: attachments2.len() as u32,
+            });
         }
 
-        if !attachments1
-            .iter()
-            .zip(attachments2)
-            .all(|(attachment_desc1, attachment_desc2)| {
-                let AttachmentDescription {
-                    format: format1,
-                    samples: samples1,
-                    load_op: _,
-                    store_op: _,
-                    stencil_load_op: _,
-                    stencil_store_op: _,
-                    initial_layout: _,
-                    final_layout: _,
-                    _ne: _,
-                } = attachment_desc1;
-                let AttachmentDescription {
-                    format: format2,
-                    samples: samples2,
-                    load_op: _,
-                    store_op: _,
-                    stencil_load_op: _,
-                    stencil_store_op: _,
-                    initial_layout: _,
-                    final_layout: _,
-                    _ne: _,
-                } = attachment_desc2;
-
-                format1 == format2 && samples1 == samples2
-            })
+        for (attachment, (attachment_desc1, attachment_desc2)) in
+            attachments1.iter().zip(attachments2).enumerate()
         {
-            return false;
+            let AttachmentDescription {
+                format: format1,
+                samples: samples1,
+                load_op: _,
+                store_op: _,
+                stencil_load_op: _,
+                stencil_store_op: _,
+                initial_layout: _,
+                final_layout: _,
+                _ne: _,
+            } = attachment_desc1;
+            let AttachmentDescription {
+                format: format2,
+                samples: samples2,
+                load_op: _,
+                store_op: _,
+                stencil_load_op: _,
+                stencil_store_op: _,
+                initial_layout: _,
+                final_layout: _,
+                _ne: _,
+            } = attachment_desc2;
+
+            if format1 != format2 || samples1 != samples2 {
+                return Err(CompatibilityMismatch::Attachment {
+                    attachment: attachment as u32,
+                });
+            }
         }
 
-        let are_atch_refs_compatible = |atch_ref1, atch_ref2| match (atch_ref1, atch_ref2) {
-            (None, None) => true,
-            (Some(atch_ref1), Some(atch_ref2)) => {
-                let &AttachmentReference {
-                    attachment: attachment1,
-                    layout: _,
-                    aspects: aspects1,
-                    _ne: _,
-                } = atch_ref1;
-                let AttachmentDescription {
-                    format: format1,
-                    samples: samples1,
-                    load_op: _,
-                    store_op: _,
-                    stencil_load_op: _,
-                    stencil_store_op: _,
-                    initial_layout: _,
-                    final_layout: _,
-                    _ne: _,
-                } = &attachments1[attachment1 as usize];
-
-                let &AttachmentReference {
-                    attachment: attachment2,
-                    layout: _,
-                    aspects: aspects2,
-                    _ne: _,
-                } = atch_ref2;
-                let AttachmentDescription {
-                    format: format2,
-                    samples: samples2,
-                    load_op: _,
-                    store_op: _,
-                    stencil_load_op: _,
-                    stencil_store_op: _,
-                    initial_layout: _,
-                    final_layout: _,
-                    _ne: _,
-                } = &attachments2[attachment2 as usize];
-
-                format1 == format2 && samples1 == samples2 && aspects1 == aspects2
-            }
-            _ => false,
-        };
+        let are_atch_refs_compatible =
+            |atch_ref1: Option<&AttachmentReference>, atch_ref2: Option<&AttachmentReference>| {
+                match (atch_ref1, atch_ref2) {
+                    (None, None) => true,
+                    (Some(atch_ref1), Some(atch_ref2)) => {
+                        let &AttachmentReference {
+                            attachment: attachment1,
+                            layout: _,
+                            aspects: aspects1,
+                            _ne: _,
+                        } = atch_ref1;
+                        let AttachmentDescription {
+                            format: format1,
+                            samples: samples1,
+                            load_op: _,
+                            store_op: _,
+                            stencil_load_op: _,
+                            stencil_store_op: _,
+                            initial_layout: _,
+                            final_layout: _,
+                            _ne: _,
+                        } = &attachments1[attachment1 as usize];
+
+                        let &AttachmentReference {
+                            attachment: attachment2,
+                            layout: _,
+                            aspects: aspects2,
+                            _ne: _,
+                        } = atch_ref2;
+                        let AttachmentDescription {
+                            format: format2,
+                            samples: samples2,
+                            load_op: _,
+                            store_op: _,
+                            stencil_load_op: _,
+                            stencil_store_op: _,
+                            initial_layout: _,
+                            final_layout: _,
+                            _ne: _,
+                        } = &attachments2[attachment2 as usize];
+
+                        format1 == format2 && samples1 == samples2 && aspects1 == aspects2
+                    }
+                    _ => false,
+                }
+            };
+
+        // Returns the index of the first pair of references that are not compatible.
+        fn first_incompatible_ref(
+            atch_refs1: &[Option<AttachmentReference>],
+            atch_refs2: &[Option<AttachmentReference>],
+            are_atch_refs_compatible: impl Fn(
+                Option<&AttachmentReference>,
+                Option<&AttachmentReference>,
+            ) -> bool,
+        ) -> Option<usize> {
+            (0..max(atch_refs1.len(), atch_refs2.len())).find(|&i| {
+                !are_atch_refs_compatible(
+                    atch_refs1.get(i).and_then(|x| x.as_ref()),
+                    atch_refs2.get(i).and_then(|x| x.as_ref()),
+                )
+            })
+        }
 
         if subpasses1.len() != subpasses2.len() {
-            return false;
+            return Err(CompatibilityMismatch::SubpassCount {
+                count: subpasses1.len() as u32,
+                other_count: subpasses2.len() as u32,
+            });
         }
 
-        if !(subpasses1.iter())
-            .zip(subpasses2.iter())
-            .all(|(subpass1, subpass2)| {
-                let &SubpassDescription {
-                    view_mask: view_mask1,
-                    input_attachments: ref input_attachments1,
-                    color_attachments: ref color_attachments1,
-                    resolve_attachments: ref resolve_attachments1,
-                    depth_stencil_attachment: ref depth_stencil_attachment1,
-                    preserve_attachments: _,
-                    _ne: _,
-                } = subpass1;
-                let &SubpassDescription {
-                    view_mask: view_mask2,
-                    input_attachments: ref input_attachments2,
-                    color_attachments: ref color_attachments2,
-                    resolve_attachments: ref resolve_attachments2,
-                    depth_stencil_attachment: ref depth_stencil_attachment2,
-                    preserve_attachments: _,
-                    _ne: _,
-                } = subpass2;
-
-                if !(0..max(input_attachments1.len(), input_attachments2.len())).all(|i| {
-                    are_atch_refs_compatible(
-                        input_attachments1.get(i).and_then(|x| x.as_ref()),
-                        input_attachments2.get(i).and_then(|x| x.as_ref()),
-                    )
-                }) {
-                    return false;
-                }
-
-                if !(0..max(color_attachments1.len(), color_attachments2.len())).all(|i| {
-                    are_atch_refs_compatible(
-                        color_attachments1.get(i).and_then(|x| x.as_ref()),
-                        color_attachments2.get(i).and_then(|x| x.as_ref()),
-                    )
-                }) {
-                    return false;
-                }
+        for (subpass, (subpass1, subpass2)) in subpasses1.iter().zip(subpasses2).enumerate() {
+            let subpass = subpass as u32;
+            let &SubpassDescription {
+                view_mask: view_mask1,
+                input_attachments: ref input_attachments1,
+                color_attachments: ref color_attachments1,
+                resolve_attachments: ref resolve_attachments1,
+                depth_stencil_attachment: ref depth_stencil_attachment1,
+                preserve_attachments: _,
+                _ne: _,
+            } = subpass1;
+            let &SubpassDescription {
+                view_mask: view_mask2,
+                input_attachments: ref input_attachments2,
+                color_attachments: ref color_attachments2,
+                resolve_attachments: ref resolve_attachments2,
+                depth_stencil_attachment: ref depth_stencil_attachment2,
+                preserve_attachments: _,
+                _ne: _,
+            } = subpass2;
+
+            if let Some(index) = first_incompatible_ref(
+                input_attachments1,
+                input_attachments2,
+                &are_atch_refs_compatible,
+            ) {
+                return Err(CompatibilityMismatch::InputAttachment {
+                    subpass,
+                    index: index as u32,
+                });
+            }
 
-                if subpasses1.len() > 1
-                    && !(0..max(resolve_attachments1.len(), resolve_attachments2.len())).all(|i| {
-                        are_atch_refs_compatible(
-                            resolve_attachments1.get(i).and_then(|x| x.as_ref()),
-                            resolve_attachments2.get(i).and_then(|x| x.as_ref()),
-                        )
-                    })
-                {
-                    return false;
-                }
+            if let Some(index) = first_incompatible_ref(
+                color_attachments1,
+                color_attachments2,
+                &are_atch_refs_compatible,
+            ) {
+                return Err(CompatibilityMismatch::ColorAttachment {
+                    subpass,
+                    index: index as u32,
+                });
+            }
 
-                if !are_atch_refs_compatible(
-                    depth_stencil_attachment1.as_ref(),
-                    depth_stencil_attachment2.as_ref(),
+            // Resolve attachments only matter if there is more than one subpass.
+            if subpasses1.len() > 1 {
+                if let Some(index) = first_incompatible_ref(
+                    resolve_attachments1,
+                    resolve_attachments2,
+                    &are_atch_refs_compatible,
                 ) {
-                    return false;
+                    return Err(CompatibilityMismatch::ResolveAttachment {
+                        subpass,
+                        index: index as u32,
+                    });
                 }
+            }
 
-                if view_mask1 != view_mask2 {
-                    return false;
-                }
+            if !are_atch_refs_compatible(
+                depth_stencil_attachment1.as_ref(),
+                depth_stencil_attachment2.as_ref(),
+            ) {
+                return Err(CompatibilityMismatch::DepthStencilAttachment { subpass });
+            }
 
-                true
-            })
-        {
-            return false;
+            if view_mask1 != view_mask2 {
+                return Err(CompatibilityMismatch::ViewMask { subpass });
+            }
         }
 
         if dependencies1 != dependencies2 {
-            return false;
+            return Err(CompatibilityMismatch::Dependencies);
         }
 
         if correlated_view_masks1 != correlated_view_masks2 {
-            return false;
+            return Err(CompatibilityMismatch::CorrelatedViewMasks);
         }
 
-        true
+        Ok(())
     }
 
     /// Returns `true` if the subpass of this description is compatible with the shader's fragment
@@ -475,7 +515,11 @@ impl Drop for RenderPass {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_render_pass)(self.device.internal_object(), self.handle, ptr::null());
+            (fns.v1_0.destroy_render_pass)(
+                self.device.internal_object(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
         }
     }
 }
@@ -513,6 +557,93 @@ impl Hash for RenderPass {
     }
 }
 
+/// The reason why two render passes are not compatible, as returned by
+/// [`RenderPass::check_compatibility`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompatibilityMismatch {
+    /// An attachment has a different format or sample count in the two render passes.
+    Attachment { attachment: u32 },
+
+    /// The render passes have a different number of attachments.
+    AttachmentCount { count: u32, other_count: u32 },
+
+    /// A color attachment reference of a subpass is not compatible.
+    ColorAttachment { subpass: u32, index: u32 },
+
+    /// The correlated view masks are not identical.
+    CorrelatedViewMasks,
+
+    /// The subpass dependencies are not identical.
+    Dependencies,
+
+    /// The depth/stencil attachment reference of a subpass is not compatible.
+    DepthStencilAttachment { subpass: u32 },
+
+    /// An input attachment reference of a subpass is not compatible.
+    InputAttachment { subpass: u32, index: u32 },
+
+    /// A resolve attachment reference of a subpass is not compatible.
+    ResolveAttachment { subpass: u32, index: u32 },
+
+    /// The render passes have a different number of subpasses.
+    SubpassCount { count: u32, other_count: u32 },
+
+    /// The view mask of a subpass is not identical.
+    ViewMask { subpass: u32 },
+}
+
+impl error::Error for CompatibilityMismatch {}
+
+impl fmt::Display for CompatibilityMismatch {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::Attachment { attachment } => write!(
+                fmt,
+                "attachment {} has a different format or sample count",
+                attachment,
+            ),
+            Self::AttachmentCount { count, other_count } => write!(
+                fmt,
+                "the render passes have a different number of attachments ({} and {})",
+                count, other_count,
+            ),
+            Self::ColorAttachment { subpass, index } => write!(
+                fmt,
+                "color attachment reference {} of subpass {} is not compatible",
+                index, subpass,
+            ),
+            Self::CorrelatedViewMasks => {
+                write!(fmt, "the correlated view masks are not identical")
+            }
+            Self::Dependencies => write!(fmt, "the subpass dependencies are not identical"),
+            Self::DepthStencilAttachment { subpass } => write!(
+                fmt,
+                "the depth/stencil attachment reference of subpass {} is not compatible",
+                subpass,
+            ),
+            Self::InputAttachment { subpass, index } => write!(
+                fmt,
+                "input attachment reference {} of subpass {} is not compatible",
+                index, subpass,
+            ),
+            Self::ResolveAttachment { subpass, index } => write!(
+                fmt,
+                "resolve attachment reference {} of subpass {} is not compatible",
+                index, subpass,
+            ),
+            Self::SubpassCount { count, other_count } => write!(
+                fmt,
+                "the render passes have a different number of subpasses ({} and {})",
+                count, other_count,
+            ),
+            Self::ViewMask { subpass } => {
+                write!(fmt, "the view mask of subpass {} is not identical", subpass)
+            }
+        }
+    }
+}
+
 /// Represents a subpass within a `RenderPass` object.
 ///
 /// This struct doesn't correspond to anything in Vulkan. It is simply an equivalent to a
@@ -1191,6 +1322,7 @@ impl From<ash::vk::ResolveModeFlags> for ResolveModes {
 #[cfg(test)]
 mod tests {
     use crate::format::Format;
+    use crate::render_pass::CompatibilityMismatch;
     use crate::render_pass::RenderPass;
     use crate::render_pass::RenderPassCreationError;
 
@@ -1254,4 +1386,51 @@ mod tests {
         assert_ne!(granularity[0], 0);
         assert_ne!(granularity[1], 0);
     }
+
+    #[test]
+    fn compatibility_ignores_load_store() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp1 = single_pass_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: Clear, store: Store, format: Format::R8G8B8A8_UNORM, samples: 1, }
+            },
+            pass: {
+                color: [a],
+                depth_stencil: {}
+            }
+        }
+        .unwrap();
+        let rp2 = single_pass_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: Load, store: DontCare, format: Format::R8G8B8A8_UNORM, samples: 1, }
+            },
+            pass: {
+                color: [a],
+                depth_stencil: {}
+            }
+        }
+        .unwrap();
+        let rp3 = single_pass_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: Clear, store: Store, format: Format::B8G8R8A8_UNORM, samples: 1, }
+            },
+            pass: {
+                color: [a],
+                depth_stencil: {}
+            }
+        }
+        .unwrap();
+
+        assert!(rp1.is_compatible_with(&rp2));
+        assert_eq!(rp1.check_compatibility(&rp2), Ok(()));
+        assert!(!rp1.is_compatible_with(&rp3));
+        assert_eq!(
+            rp1.check_compatibility(&rp3),
+            Err(CompatibilityMismatch::Attachment { attachment: 0 }),
+        );
+    }
 }
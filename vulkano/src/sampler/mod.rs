@@ -60,7 +60,6 @@ use std::{
     hash::{Hash, Hasher},
     mem::MaybeUninit,
     ops::RangeInclusive,
-    ptr,
     sync::Arc,
 };
 
@@ -396,7 +395,7 @@ impl Sampler {
             check_errors((fns.v1_0.create_sampler)(
                 device.internal_object(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -462,6 +461,22 @@ impl Sampler {
                     return Err(SamplerImageViewIncompatibleError::MipmapModeLinearNotSupported);
                 }
             }
+
+            if matches!(
+                self.reduction_mode,
+                SamplerReductionMode::Min | SamplerReductionMode::Max
+            ) && !image_view.format_features().sampled_image_filter_minmax
+            {
+                // VUID-vkCmdDispatch-magFilter-09598
+                if self.mag_filter == Filter::Linear || self.min_filter == Filter::Linear {
+                    return Err(SamplerImageViewIncompatibleError::FilterMinmaxNotSupported);
+                }
+
+                // VUID-vkCmdDispatch-mipmapMode-09599
+                if self.mipmap_mode == SamplerMipmapMode::Linear {
+                    return Err(SamplerImageViewIncompatibleError::FilterMinmaxNotSupported);
+                }
+            }
         }
 
         if self.mag_filter == Filter::Cubic || self.min_filter == Filter::Cubic {
@@ -657,7 +672,11 @@ impl Drop for Sampler {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_sampler)(self.device.internal_object(), self.handle, ptr::null());
+            (fns.v1_0.destroy_sampler)(
+                self.device.internal_object(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
         }
     }
 }
@@ -989,6 +1008,12 @@ pub struct SamplerCreateInfo {
     /// How the value sampled from a mipmap should be calculated from the selected
     /// pixels, for the `Linear` and `Cubic` filters.
     ///
+    /// If set to anything other than `WeightedAverage`, the
+    /// [`sampler_filter_minmax`](crate::device::Features::sampler_filter_minmax) feature or the
+    /// [`ext_sampler_filter_minmax`](crate::device::DeviceExtensions::ext_sampler_filter_minmax)
+    /// extension must be enabled on the device. When sampling with a linear filter, the format of
+    /// the image view must also support the `sampled_image_filter_minmax` format feature.
+    ///
     /// The default value is [`WeightedAverage`](SamplerReductionMode::WeightedAverage).
     pub reduction_mode: SamplerReductionMode,
 
@@ -1399,6 +1424,10 @@ pub enum SamplerImageViewIncompatibleError {
     /// supported by the image view's format features.
     FilterCubicMinmaxNotSupported,
 
+    /// The sampler uses linear filtering with a `Min` or `Max` reduction mode, but this is not
+    /// supported by the image view's format features.
+    FilterMinmaxNotSupported,
+
     /// The sampler uses a linear mipmap mode, but this is not supported by the image view's format
     /// features.
     MipmapModeLinearNotSupported,
@@ -1423,6 +1452,7 @@ impl fmt::Display for SamplerImageViewIncompatibleError {
             Self::FilterLinearNotSupported => write!(fmt, "the sampler uses a linear filter, but this is not supported by the image view's format features"),
             Self::FilterCubicNotSupported => write!(fmt, "the sampler uses a cubic filter, but this is not supported by the image view's format features"),
             Self::FilterCubicMinmaxNotSupported => write!(fmt, "the sampler uses a cubic filter with a `Min` or `Max` reduction mode, but this is not supported by the image view's format features"),
+            Self::FilterMinmaxNotSupported => write!(fmt, "the sampler uses linear filtering with a `Min` or `Max` reduction mode, but this is not supported by the image view's format features"),
             Self::MipmapModeLinearNotSupported => write!(fmt, "the sampler uses a linear mipmap mode, but this is not supported by the image view's format features"),
             Self::UnnormalizedCoordinatesMultipleMipLevels => write!(fmt, "the sampler uses unnormalized coordinates, but the image view has multiple mip levels"),
             Self::UnnormalizedCoordinatesViewTypeNotCompatible => write!(fmt, "the sampler uses unnormalized coordinates, but the image view has a type other than `Dim1d` or `Dim2d`"),
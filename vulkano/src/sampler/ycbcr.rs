@@ -726,7 +726,11 @@ impl From<ChromaLocation> for ash::vk::ChromaLocation {
 
 #[cfg(test)]
 mod tests {
-    use super::{SamplerYcbcrConversion, SamplerYcbcrConversionCreationError};
+    use super::{
+        SamplerYcbcrConversion, SamplerYcbcrConversionCreateInfo,
+        SamplerYcbcrConversionCreationError,
+    };
+    use crate::format::Format;
 
     #[test]
     fn feature_not_enabled() {
@@ -742,4 +746,34 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn format_missing() {
+        let (device, queue) = gfx_dev_and_queue!(sampler_ycbcr_conversion);
+
+        let r = SamplerYcbcrConversion::new(device, Default::default());
+
+        match r {
+            Err(SamplerYcbcrConversionCreationError::FormatMissing) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn format_not_unorm() {
+        let (device, queue) = gfx_dev_and_queue!(sampler_ycbcr_conversion);
+
+        let r = SamplerYcbcrConversion::new(
+            device,
+            SamplerYcbcrConversionCreateInfo {
+                format: Some(Format::R8G8B8A8_SINT),
+                ..Default::default()
+            },
+        );
+
+        match r {
+            Err(SamplerYcbcrConversionCreationError::FormatNotUnorm) => (),
+            _ => panic!(),
+        }
+    }
 }
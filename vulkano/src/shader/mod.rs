@@ -24,7 +24,7 @@ use crate::format::{Format, NumericType};
 use crate::image::view::ImageViewType;
 use crate::pipeline::graphics::input_assembly::PrimitiveTopology;
 use crate::pipeline::layout::PushConstantRange;
-use crate::shader::spirv::{Capability, Spirv, SpirvError};
+use crate::shader::spirv::{Capability, Instruction, Spirv, SpirvError};
 use crate::sync::PipelineStages;
 use crate::DeviceSize;
 use crate::OomError;
@@ -41,7 +41,6 @@ use std::fmt::Display;
 use std::mem;
 use std::mem::MaybeUninit;
 use std::ops::BitOr;
-use std::ptr;
 use std::sync::Arc;
 
 pub mod reflect;
@@ -83,6 +82,57 @@ impl ShaderModule {
         )
     }
 
+    /// As `from_words`, but first checks that every entry point of the module has an execution
+    /// model that can be used in a Vulkan pipeline. On success, returns the shader module together
+    /// with the name and shader stage of each of its entry points.
+    ///
+    /// Entry points are checked again against the stage they are used in when creating a
+    /// pipeline, so pointing a stage at an entry point with the wrong execution model returns an
+    /// error instead of creating an invalid pipeline.
+    ///
+    /// # Safety
+    ///
+    /// - The SPIR-V code is not validated beyond the minimum needed to extract the information.
+    pub unsafe fn from_words_checked(
+        device: Arc<Device>,
+        words: &[u32],
+    ) -> Result<(Arc<ShaderModule>, Vec<(String, ShaderStage)>), ShaderCreationError> {
+        let spirv = Spirv::new(words)?;
+
+        let stages = spirv
+            .iter_entry_point()
+            .filter_map(|instruction| match instruction {
+                Instruction::EntryPoint {
+                    execution_model,
+                    name,
+                    ..
+                } => Some((name, *execution_model)),
+                _ => None,
+            })
+            .map(|(name, execution_model)| {
+                entry_point_stage(execution_model)
+                    .map(|stage| (name.clone(), stage))
+                    .ok_or_else(
+                        || ShaderCreationError::EntryPointExecutionModelNotSupported {
+                            name: name.clone(),
+                            execution_model,
+                        },
+                    )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let module = Self::from_words_with_data(
+            device,
+            words,
+            spirv.version(),
+            reflect::spirv_capabilities(&spirv),
+            reflect::spirv_extensions(&spirv),
+            reflect::entry_points(&spirv),
+        )?;
+
+        Ok((module, stages))
+    }
+
     /// As `from_words`, but takes a slice of bytes.
     ///
     /// # Panics
@@ -156,7 +206,7 @@ impl ShaderModule {
             check_errors((fns.v1_0.create_shader_module)(
                 device.internal_object(),
                 &infos,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -219,6 +269,23 @@ impl ShaderModule {
         )
     }
 
+    /// Returns information about all entry points in the shader module, together with their
+    /// execution model.
+    pub fn entry_points(&self) -> impl Iterator<Item = (ExecutionModel, EntryPoint<'_>)> {
+        self.entry_points.iter().flat_map(move |(name, infos)| {
+            infos.iter().map(move |(execution, info)| {
+                (
+                    *execution,
+                    EntryPoint {
+                        module: self,
+                        name: CString::new(name.as_str()).unwrap(),
+                        info,
+                    },
+                )
+            })
+        })
+    }
+
     /// Returns information about the entry point with the provided name. Returns `None` if no entry
     /// point with that name exists in the shader module or if multiple entry points with the same
     /// name exist.
@@ -262,6 +329,26 @@ unsafe impl VulkanObject for ShaderModule {
     }
 }
 
+// Returns the shader stage that an entry point with `execution_model` runs in, or `None` if it
+// can't be used in a Vulkan pipeline.
+fn entry_point_stage(execution_model: ExecutionModel) -> Option<ShaderStage> {
+    Some(match execution_model {
+        ExecutionModel::Vertex => ShaderStage::Vertex,
+        ExecutionModel::TessellationControl => ShaderStage::TessellationControl,
+        ExecutionModel::TessellationEvaluation => ShaderStage::TessellationEvaluation,
+        ExecutionModel::Geometry => ShaderStage::Geometry,
+        ExecutionModel::Fragment => ShaderStage::Fragment,
+        ExecutionModel::GLCompute => ShaderStage::Compute,
+        ExecutionModel::RayGenerationKHR => ShaderStage::Raygen,
+        ExecutionModel::IntersectionKHR => ShaderStage::Intersection,
+        ExecutionModel::AnyHitKHR => ShaderStage::AnyHit,
+        ExecutionModel::ClosestHitKHR => ShaderStage::ClosestHit,
+        ExecutionModel::MissKHR => ShaderStage::Miss,
+        ExecutionModel::CallableKHR => ShaderStage::Callable,
+        ExecutionModel::Kernel | ExecutionModel::TaskNV | ExecutionModel::MeshNV => return None,
+    })
+}
+
 impl Drop for ShaderModule {
     #[inline]
     fn drop(&mut self) {
@@ -270,7 +357,7 @@ impl Drop for ShaderModule {
             (fns.v1_0.destroy_shader_module)(
                 self.device.internal_object(),
                 self.handle,
-                ptr::null(),
+                self.device.allocation_callbacks(),
             );
         }
     }
@@ -280,6 +367,10 @@ impl Drop for ShaderModule {
 #[derive(Clone, Debug)]
 pub enum ShaderCreationError {
     OomError(OomError),
+    EntryPointExecutionModelNotSupported {
+        name: String,
+        execution_model: ExecutionModel,
+    },
     SpirvCapabilityNotSupported {
         capability: Capability,
         reason: ShaderSupportError,
@@ -300,6 +391,7 @@ impl Error for ShaderCreationError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
             Self::OomError(err) => Some(err),
+            Self::EntryPointExecutionModelNotSupported { .. } => None,
             Self::SpirvCapabilityNotSupported { reason, .. } => Some(reason),
             Self::SpirvError(err) => Some(err),
             Self::SpirvExtensionNotSupported { reason, .. } => Some(reason),
@@ -312,6 +404,14 @@ impl Display for ShaderCreationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::EntryPointExecutionModelNotSupported {
+                name,
+                execution_model,
+            } => write!(
+                f,
+                "the entry point {} has the execution model {:?}, which cannot be used in a pipeline",
+                name, execution_model,
+            ),
             Self::SpirvCapabilityNotSupported { capability, .. } => write!(
                 f,
                 "the SPIR-V capability {:?} enabled by the shader is not supported by the device",
@@ -456,7 +556,7 @@ pub enum ShaderExecution {
     TessellationEvaluation,
     Geometry(GeometryShaderExecution),
     Fragment,
-    Compute,
+    Compute(ComputeShaderExecution),
     RayGeneration,
     AnyHit,
     ClosestHit,
@@ -488,6 +588,16 @@ pub struct GeometryShaderExecution {
     pub output: GeometryShaderOutput,*/
 }
 
+/// The mode in which a compute shader executes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ComputeShaderExecution {
+    /// The number of invocations in each local workgroup, in the X, Y and Z dimensions.
+    ///
+    /// This is `None` if the size is given by specialization constants, and therefore not known
+    /// until the pipeline is created.
+    pub local_size: Option<[u32; 3]>,
+}
+
 /// The input primitive type that is expected by a geometry shader.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum GeometryShaderInput {
@@ -546,6 +656,12 @@ pub struct DescriptorRequirements {
     /// layout can declare more than this, but never less.
     pub descriptor_count: u32,
 
+    /// For uniform buffer bindings, the size in bytes of the block that the shader declares, as
+    /// determined from its `Offset` and `ArrayStride` decorations. Buffers bound to this
+    /// descriptor must be at least this large. This is `None` for other descriptor types, or if
+    /// the size could not be determined.
+    pub buffer_min_size: Option<DeviceSize>,
+
     /// The image format that is required for image views bound to this descriptor. If this is
     /// `None`, then any image format is allowed.
     pub image_format: Option<Format>,
@@ -650,6 +766,10 @@ impl DescriptorRequirements {
         Ok(Self {
             descriptor_types,
             descriptor_count: self.descriptor_count.max(other.descriptor_count),
+            buffer_min_size: match (self.buffer_min_size, other.buffer_min_size) {
+                (Some(first), Some(second)) => Some(first.max(second)),
+                (first, second) => first.or(second),
+            },
             image_format: self.image_format.or(other.image_format),
             image_multisampled: self.image_multisampled,
             image_scalar_type: self.image_scalar_type.or(other.image_scalar_type),
@@ -714,6 +834,89 @@ impl Display for DescriptorRequirementsIncompatible {
 #[derive(Clone, Copy, Debug)]
 pub struct SpecializationConstantRequirements {
     pub size: DeviceSize,
+    pub ty: SpecializationConstantType,
+}
+
+/// The type of a specialization constant, as declared in a shader.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SpecializationConstantType {
+    Bool,
+    Float,
+    Sint,
+    Uint,
+}
+
+/// The value of a single specialization constant, provided at runtime.
+///
+/// This can be used instead of a type implementing [`SpecializationConstants`], when the values
+/// of the constants are only known at runtime. See
+/// [`ComputePipeline::with_specialization`](crate::pipeline::ComputePipeline::with_specialization).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpecializationConstant {
+    Bool(bool),
+    I32(i32),
+    U32(u32),
+    F32(f32),
+}
+
+impl SpecializationConstant {
+    /// Returns the type of the constant.
+    #[inline]
+    pub fn ty(&self) -> SpecializationConstantType {
+        match self {
+            Self::Bool(_) => SpecializationConstantType::Bool,
+            Self::I32(_) => SpecializationConstantType::Sint,
+            Self::U32(_) => SpecializationConstantType::Uint,
+            Self::F32(_) => SpecializationConstantType::Float,
+        }
+    }
+
+    /// Returns the size in bytes of the constant, as it is provided to Vulkan.
+    #[inline]
+    pub fn size(&self) -> DeviceSize {
+        4
+    }
+
+    /// Returns the bytes of the constant, as they are provided to Vulkan.
+    ///
+    /// Booleans are stored as a `VkBool32`.
+    #[inline]
+    pub fn to_ne_bytes(&self) -> [u8; 4] {
+        match *self {
+            Self::Bool(value) => (value as ash::vk::Bool32).to_ne_bytes(),
+            Self::I32(value) => value.to_ne_bytes(),
+            Self::U32(value) => value.to_ne_bytes(),
+            Self::F32(value) => value.to_ne_bytes(),
+        }
+    }
+}
+
+impl From<bool> for SpecializationConstant {
+    #[inline]
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i32> for SpecializationConstant {
+    #[inline]
+    fn from(value: i32) -> Self {
+        Self::I32(value)
+    }
+}
+
+impl From<u32> for SpecializationConstant {
+    #[inline]
+    fn from(value: u32) -> Self {
+        Self::U32(value)
+    }
+}
+
+impl From<f32> for SpecializationConstant {
+    #[inline]
+    fn from(value: f32) -> Self {
+        Self::F32(value)
+    }
 }
 
 /// Trait for types that contain specialization data for shaders.
@@ -1067,7 +1270,7 @@ impl From<ShaderExecution> for ShaderStage {
             ShaderExecution::TessellationEvaluation => Self::TessellationEvaluation,
             ShaderExecution::Geometry(_) => Self::Geometry,
             ShaderExecution::Fragment => Self::Fragment,
-            ShaderExecution::Compute => Self::Compute,
+            ShaderExecution::Compute(_) => Self::Compute,
             ShaderExecution::RayGeneration => Self::Raygen,
             ShaderExecution::AnyHit => Self::AnyHit,
             ShaderExecution::ClosestHit => Self::ClosestHit,
@@ -17,12 +17,13 @@ use crate::{
     pipeline::layout::PushConstantRange,
     shader::{
         spirv::{
-            Capability, Decoration, Dim, ExecutionMode, ExecutionModel, Id, Instruction, Spirv,
-            StorageClass,
+            BuiltIn, Capability, Decoration, Dim, ExecutionMode, ExecutionModel, Id, Instruction,
+            Spirv, StorageClass,
         },
-        DescriptorIdentifier, DescriptorRequirements, EntryPointInfo, GeometryShaderExecution,
-        GeometryShaderInput, ShaderExecution, ShaderInterface, ShaderInterfaceEntry,
-        ShaderInterfaceEntryType, ShaderStage, SpecializationConstantRequirements,
+        ComputeShaderExecution, DescriptorIdentifier, DescriptorRequirements, EntryPointInfo,
+        GeometryShaderExecution, GeometryShaderInput, ShaderExecution, ShaderInterface,
+        ShaderInterfaceEntry, ShaderInterfaceEntryType, ShaderStage,
+        SpecializationConstantRequirements, SpecializationConstantType,
     },
 };
 use std::borrow::Cow;
@@ -152,7 +153,9 @@ fn shader_execution(
 
         ExecutionModel::Fragment => ShaderExecution::Fragment,
 
-        ExecutionModel::GLCompute => ShaderExecution::Compute,
+        ExecutionModel::GLCompute => ShaderExecution::Compute(ComputeShaderExecution {
+            local_size: compute_local_size(spirv, function_id),
+        }),
 
         ExecutionModel::RayGenerationKHR => ShaderExecution::RayGeneration,
         ExecutionModel::IntersectionKHR => ShaderExecution::Intersection,
@@ -165,6 +168,79 @@ fn shader_execution(
     }
 }
 
+/// Extracts the local workgroup size of the compute entry point `function_id` from `spirv`.
+/// Returns `None` if the size is given by specialization constants.
+fn compute_local_size(spirv: &Spirv, function_id: Id) -> Option<[u32; 3]> {
+    // A constant decorated with the `WorkgroupSize` built-in overrides the execution mode.
+    let workgroup_size_id = spirv
+        .iter_decoration()
+        .find_map(|instruction| match instruction {
+            Instruction::Decorate {
+                target,
+                decoration:
+                    Decoration::BuiltIn {
+                        built_in: BuiltIn::WorkgroupSize,
+                    },
+            } => Some(*target),
+            _ => None,
+        });
+
+    if let Some(workgroup_size_id) = workgroup_size_id {
+        return match spirv.id(workgroup_size_id).instruction() {
+            Instruction::ConstantComposite { constituents, .. } => {
+                constant_local_size(spirv, constituents)
+            }
+            _ => None,
+        };
+    }
+
+    spirv
+        .iter_execution_mode()
+        .find_map(|instruction| match instruction {
+            Instruction::ExecutionMode {
+                entry_point,
+                mode:
+                    ExecutionMode::LocalSize {
+                        x_size,
+                        y_size,
+                        z_size,
+                    },
+            } if *entry_point == function_id => Some(Some([*x_size, *y_size, *z_size])),
+            Instruction::ExecutionModeId {
+                entry_point,
+                mode:
+                    ExecutionMode::LocalSizeId {
+                        x_size,
+                        y_size,
+                        z_size,
+                    },
+            } if *entry_point == function_id => {
+                Some(constant_local_size(spirv, &[*x_size, *y_size, *z_size]))
+            }
+            _ => None,
+        })
+        .flatten()
+}
+
+/// Returns the values of the three integer constants in `ids`, or `None` if any of them is not
+/// a regular constant.
+fn constant_local_size(spirv: &Spirv, ids: &[Id]) -> Option<[u32; 3]> {
+    if ids.len() != 3 {
+        return None;
+    }
+
+    let mut local_size = [0; 3];
+
+    for (size, &id) in local_size.iter_mut().zip(ids) {
+        *size = match spirv.id(id).instruction() {
+            Instruction::Constant { value, .. } => value[0],
+            _ => return None,
+        };
+    }
+
+    Some(local_size)
+}
+
 #[derive(Clone, Debug, Default)]
 struct InterfaceVariables {
     descriptor: HashMap<Id, DescriptorVariable>,
@@ -752,6 +828,7 @@ fn descriptor_requirements_of(spirv: &Spirv, variable_id: Id) -> DescriptorVaria
                         DescriptorType::UniformBuffer,
                         DescriptorType::UniformBufferDynamic,
                     ];
+                    reqs.buffer_min_size = size_of_type(spirv, id);
                 };
 
                 None
@@ -983,7 +1060,17 @@ fn specialization_constant_requirements(
                             _ => size_of_type(spirv, result_type_id)
                                 .expect("Found runtime-sized specialization constant"),
                         };
-                        Some((constant_id, SpecializationConstantRequirements { size }))
+                        let ty = match *spirv.id(result_type_id).instruction() {
+                            Instruction::TypeBool { .. } => SpecializationConstantType::Bool,
+                            Instruction::TypeInt { signedness, .. } => match signedness {
+                                0 => SpecializationConstantType::Uint,
+                                1 => SpecializationConstantType::Sint,
+                                _ => unreachable!(),
+                            },
+                            Instruction::TypeFloat { .. } => SpecializationConstantType::Float,
+                            _ => panic!("Found non-scalar specialization constant"),
+                        };
+                        Some((constant_id, SpecializationConstantRequirements { size, ty }))
                     }),
                 _ => None,
             }
@@ -313,6 +313,7 @@
 
 pub use self::present_region::PresentRegion;
 pub use self::present_region::RectangleLayer;
+pub use self::surface::choose_hdr_or_srgb;
 pub use self::surface::ColorSpace;
 pub use self::surface::CompositeAlpha;
 pub use self::surface::PresentMode;
@@ -328,11 +329,15 @@ pub use self::swapchain::acquire_next_image;
 pub use self::swapchain::acquire_next_image_raw;
 pub use self::swapchain::present;
 pub use self::swapchain::present_incremental;
+pub use self::swapchain::present_with_id;
 pub use self::swapchain::AcquireError;
 pub use self::swapchain::AcquiredImage;
 pub use self::swapchain::FullScreenExclusive;
 pub use self::swapchain::FullScreenExclusiveError;
+pub use self::swapchain::HdrMetadata;
+pub use self::swapchain::HdrMetadataError;
 pub use self::swapchain::PresentFuture;
+pub use self::swapchain::PresentWaitError;
 pub use self::swapchain::Swapchain;
 pub use self::swapchain::SwapchainAcquireFuture;
 pub use self::swapchain::SwapchainCreateInfo;
@@ -10,6 +10,8 @@
 use super::FullScreenExclusive;
 use super::Win32Monitor;
 use crate::check_errors;
+use crate::device::physical::{PhysicalDevice, SurfacePropertiesError};
+use crate::format::Format;
 use crate::image::ImageUsage;
 use crate::instance::Instance;
 use crate::swapchain::display::DisplayMode;
@@ -18,14 +20,17 @@ use crate::swapchain::SurfaceSwapchainLock;
 use crate::Error;
 use crate::OomError;
 use crate::VulkanObject;
+#[cfg(feature = "raw-window-handle")]
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
 use std::error;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::mem::MaybeUninit;
 use std::os::raw::c_ulong;
-use std::ptr;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+#[cfg(feature = "raw-window-handle")]
+use std::{ffi::c_void, ptr};
 
 /// Represents a surface on the screen.
 ///
@@ -118,7 +123,7 @@ impl<W> Surface<W> {
             check_errors((fns.khr_display.create_display_plane_surface_khr)(
                 instance.internal_object(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -164,7 +169,7 @@ impl<W> Surface<W> {
             check_errors((fns.khr_android_surface.create_android_surface_khr)(
                 instance.internal_object(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -211,7 +216,7 @@ impl<W> Surface<W> {
             check_errors((fns.mvk_ios_surface.create_ios_surface_mvk)(
                 instance.internal_object(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -258,7 +263,7 @@ impl<W> Surface<W> {
             check_errors((fns.mvk_macos_surface.create_mac_os_surface_mvk)(
                 instance.internal_object(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -304,7 +309,7 @@ impl<W> Surface<W> {
             check_errors((fns.ext_metal_surface.create_metal_surface_ext)(
                 instance.internal_object(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -350,7 +355,7 @@ impl<W> Surface<W> {
             check_errors((fns.nn_vi_surface.create_vi_surface_nn)(
                 instance.internal_object(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -400,7 +405,7 @@ impl<W> Surface<W> {
             check_errors((fns.khr_wayland_surface.create_wayland_surface_khr)(
                 instance.internal_object(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -450,7 +455,7 @@ impl<W> Surface<W> {
             check_errors((fns.khr_win32_surface.create_win32_surface_khr)(
                 instance.internal_object(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -500,7 +505,7 @@ impl<W> Surface<W> {
             check_errors((fns.khr_xcb_surface.create_xcb_surface_khr)(
                 instance.internal_object(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -550,7 +555,7 @@ impl<W> Surface<W> {
             check_errors((fns.khr_xlib_surface.create_xlib_surface_khr)(
                 instance.internal_object(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -566,6 +571,55 @@ impl<W> Surface<W> {
         }))
     }
 
+    /// Creates a `Surface` from a raw window handle.
+    ///
+    /// On macOS and iOS, if the
+    /// [`ext_metal_surface`](crate::instance::InstanceExtensions::ext_metal_surface) extension is
+    /// enabled and the layer of the view is a `CAMetalLayer`, the surface is created from the
+    /// layer with [`from_metal`](Self::from_metal). Otherwise it is created from the view.
+    ///
+    /// Returns [`SurfaceCreationError::UnsupportedWindowHandle`] if vulkano can't create a surface
+    /// from this kind of handle.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid handle.
+    /// - The objects referred to by `handle` must outlive the created `Surface`.
+    ///   The `win` parameter can be used to ensure this.
+    #[cfg(feature = "raw-window-handle")]
+    pub unsafe fn from_raw_window_handle(
+        instance: Arc<Instance>,
+        handle: RawWindowHandle,
+        win: W,
+    ) -> Result<Arc<Surface<W>>, SurfaceCreationError> {
+        match handle {
+            RawWindowHandle::AndroidNdk(h) => Self::from_android(instance, h.a_native_window, win),
+            RawWindowHandle::AppKit(h) => {
+                let layer = metal_layer_of_view(h.ns_view);
+
+                if instance.enabled_extensions().ext_metal_surface && !layer.is_null() {
+                    Self::from_metal(instance, layer, win)
+                } else {
+                    Self::from_mac_os(instance, h.ns_view, win)
+                }
+            }
+            RawWindowHandle::UiKit(h) => {
+                let layer = metal_layer_of_view(h.ui_view);
+
+                if instance.enabled_extensions().ext_metal_surface && !layer.is_null() {
+                    Self::from_metal(instance, layer, win)
+                } else {
+                    Self::from_ios(instance, h.ui_view, win)
+                }
+            }
+            RawWindowHandle::Wayland(h) => Self::from_wayland(instance, h.display, h.surface, win),
+            RawWindowHandle::Win32(h) => Self::from_win32(instance, h.hinstance, h.hwnd, win),
+            RawWindowHandle::Xcb(h) => Self::from_xcb(instance, h.connection, h.window, win),
+            RawWindowHandle::Xlib(h) => Self::from_xlib(instance, h.display, h.window, win),
+            _ => Err(SurfaceCreationError::UnsupportedWindowHandle),
+        }
+    }
+
     /// Returns the instance this surface was created with.
     #[inline]
     pub fn instance(&self) -> &Arc<Instance> {
@@ -583,6 +637,57 @@ impl<W> Surface<W> {
     pub fn window(&self) -> &W {
         &self.window
     }
+
+    /// Returns the capabilities of the surface when used by `physical_device`.
+    ///
+    /// This is a shortcut for
+    /// [`PhysicalDevice::surface_capabilities`](crate::device::physical::PhysicalDevice::surface_capabilities)
+    /// with the default [`SurfaceInfo`].
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the physical device and the surface don't belong to the same instance.
+    #[inline]
+    pub fn capabilities(
+        &self,
+        physical_device: PhysicalDevice,
+    ) -> Result<SurfaceCapabilities, SurfacePropertiesError> {
+        physical_device.surface_capabilities(self, SurfaceInfo::default())
+    }
+
+    /// Returns the combinations of format and color space that are supported for the surface
+    /// by `physical_device`.
+    ///
+    /// This is a shortcut for
+    /// [`PhysicalDevice::surface_formats`](crate::device::physical::PhysicalDevice::surface_formats)
+    /// with the default [`SurfaceInfo`].
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the physical device and the surface don't belong to the same instance.
+    #[inline]
+    pub fn supported_formats(
+        &self,
+        physical_device: PhysicalDevice,
+    ) -> Result<Vec<(Format, ColorSpace)>, SurfacePropertiesError> {
+        physical_device.surface_formats(self, SurfaceInfo::default())
+    }
+
+    /// Returns the present modes that are supported for the surface by `physical_device`.
+    ///
+    /// This is a shortcut for
+    /// [`PhysicalDevice::surface_present_modes`](crate::device::physical::PhysicalDevice::surface_present_modes).
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the physical device and the surface don't belong to the same instance.
+    #[inline]
+    pub fn supported_present_modes(
+        &self,
+        physical_device: PhysicalDevice,
+    ) -> Result<Vec<PresentMode>, SurfacePropertiesError> {
+        Ok(physical_device.surface_present_modes(self)?.collect())
+    }
 }
 
 impl<W> Drop for Surface<W> {
@@ -593,12 +698,71 @@ impl<W> Drop for Surface<W> {
             (fns.khr_surface.destroy_surface_khr)(
                 self.instance.internal_object(),
                 self.handle,
-                ptr::null(),
+                self.instance.allocation_callbacks(),
             );
         }
     }
 }
 
+#[cfg(feature = "raw-window-handle")]
+impl<W> Surface<Arc<W>>
+where
+    W: HasRawWindowHandle,
+{
+    /// Creates a `Surface` from a window that implements `HasRawWindowHandle`.
+    ///
+    /// The window is kept alive by the surface, so it can't be destroyed while the surface still
+    /// uses it. See [`from_raw_window_handle`](Self::from_raw_window_handle) for how the surface
+    /// is created.
+    ///
+    /// The surface extension of the platform, for example `khr_win32_surface` or
+    /// `khr_wayland_surface`, must be enabled on the instance, otherwise
+    /// [`SurfaceCreationError::MissingExtension`] is returned.
+    #[inline]
+    pub fn from_window(
+        instance: Arc<Instance>,
+        window: Arc<W>,
+    ) -> Result<Arc<Self>, SurfaceCreationError> {
+        let handle = window.raw_window_handle();
+        unsafe { Self::from_raw_window_handle(instance, handle, window) }
+    }
+}
+
+// Returns the layer of `view` if it is a `CAMetalLayer`, or null otherwise.
+#[cfg(all(
+    feature = "raw-window-handle",
+    any(target_os = "macos", target_os = "ios")
+))]
+unsafe fn metal_layer_of_view(view: *mut c_void) -> *mut c_void {
+    use objc::{
+        class, msg_send,
+        runtime::{Object, BOOL, NO},
+        sel, sel_impl,
+    };
+
+    let layer: *mut Object = msg_send![view as *mut Object, layer];
+
+    if layer.is_null() {
+        return ptr::null_mut();
+    }
+
+    let is_metal_layer: BOOL = msg_send![layer, isKindOfClass: class!(CAMetalLayer)];
+
+    if is_metal_layer == NO {
+        ptr::null_mut()
+    } else {
+        layer as *mut c_void
+    }
+}
+
+#[cfg(all(
+    feature = "raw-window-handle",
+    not(any(target_os = "macos", target_os = "ios"))
+))]
+unsafe fn metal_layer_of_view(_view: *mut c_void) -> *mut c_void {
+    ptr::null_mut()
+}
+
 unsafe impl<W> VulkanObject for Surface<W> {
     type Object = ash::vk::SurfaceKHR;
 
@@ -665,6 +829,9 @@ pub enum SurfaceCreationError {
         /// Name of the missing extension.
         name: &'static str,
     },
+
+    /// The window handle is of a kind that vulkano can't create a surface from.
+    UnsupportedWindowHandle,
 }
 
 impl error::Error for SurfaceCreationError {
@@ -688,6 +855,9 @@ impl fmt::Display for SurfaceCreationError {
                 SurfaceCreationError::MissingExtension { .. } => {
                     "the extension required for this function was not enabled"
                 }
+                SurfaceCreationError::UnsupportedWindowHandle => {
+                    "the window handle is of a kind that a surface can't be created from"
+                }
             }
         )
     }
@@ -1204,6 +1374,52 @@ impl From<ash::vk::ColorSpaceKHR> for ColorSpace {
     }
 }
 
+/// Chooses the best combination of format and color space from a list of supported surface
+/// formats, as returned by
+/// [`PhysicalDevice::surface_formats`](crate::device::physical::PhysicalDevice::surface_formats).
+/// HDR combinations are preferred over sRGB ones.
+///
+/// The following combinations are tried in order:
+///
+/// 1. A 10-bit UNORM format with the [`Hdr10St2084`](ColorSpace::Hdr10St2084) color space.
+/// 2. A 10-bit UNORM format with the [`Bt2020Linear`](ColorSpace::Bt2020Linear) color space.
+/// 3. An 8-bit sRGB format with the [`SrgbNonLinear`](ColorSpace::SrgbNonLinear) color space.
+/// 4. Any format with the `SrgbNonLinear` color space.
+///
+/// Returns `None` if none of these are supported.
+///
+/// Note that color spaces other than `SrgbNonLinear` are only reported by the implementation if
+/// the [`ext_swapchain_colorspace`](crate::instance::InstanceExtensions::ext_swapchain_colorspace)
+/// extension is enabled on the instance.
+pub fn choose_hdr_or_srgb(
+    surface_formats: &[(Format, ColorSpace)],
+) -> Option<(Format, ColorSpace)> {
+    const HDR_FORMATS: [Format; 2] = [
+        Format::A2B10G10R10_UNORM_PACK32,
+        Format::A2R10G10B10_UNORM_PACK32,
+    ];
+    const SRGB_FORMATS: [Format; 2] = [Format::B8G8R8A8_SRGB, Format::R8G8B8A8_SRGB];
+
+    let find = |formats: &[Format], color_space: ColorSpace| {
+        formats.iter().find_map(|&format| {
+            surface_formats
+                .iter()
+                .copied()
+                .find(|&(f, c)| f == format && c == color_space)
+        })
+    };
+
+    find(&HDR_FORMATS, ColorSpace::Hdr10St2084)
+        .or_else(|| find(&HDR_FORMATS, ColorSpace::Bt2020Linear))
+        .or_else(|| find(&SRGB_FORMATS, ColorSpace::SrgbNonLinear))
+        .or_else(|| {
+            surface_formats
+                .iter()
+                .copied()
+                .find(|&(_, c)| c == ColorSpace::SrgbNonLinear)
+        })
+}
+
 /// Parameters for
 /// [`PhysicalDevice::surface_capabilities`](crate::device::physical::PhysicalDevice::surface_capabilities)
 /// and
@@ -1272,10 +1488,38 @@ pub struct SurfaceCapabilities {
 
 #[cfg(test)]
 mod tests {
+    use crate::format::Format;
+    use crate::swapchain::choose_hdr_or_srgb;
+    use crate::swapchain::ColorSpace;
     use crate::swapchain::Surface;
     use crate::swapchain::SurfaceCreationError;
     use std::ptr;
 
+    #[test]
+    fn choose_hdr_or_srgb_preference() {
+        let sdr = [
+            (Format::B8G8R8A8_UNORM, ColorSpace::SrgbNonLinear),
+            (Format::B8G8R8A8_SRGB, ColorSpace::SrgbNonLinear),
+        ];
+        assert_eq!(
+            choose_hdr_or_srgb(&sdr),
+            Some((Format::B8G8R8A8_SRGB, ColorSpace::SrgbNonLinear))
+        );
+
+        let hdr = [
+            (Format::B8G8R8A8_SRGB, ColorSpace::SrgbNonLinear),
+            (Format::A2B10G10R10_UNORM_PACK32, ColorSpace::SrgbNonLinear),
+            (Format::A2B10G10R10_UNORM_PACK32, ColorSpace::Hdr10St2084),
+        ];
+        assert_eq!(
+            choose_hdr_or_srgb(&hdr),
+            Some((Format::A2B10G10R10_UNORM_PACK32, ColorSpace::Hdr10St2084))
+        );
+
+        let other = [(Format::R16G16B16A16_SFLOAT, ColorSpace::ExtendedSrgbLinear)];
+        assert_eq!(choose_hdr_or_srgb(&other), None);
+    }
+
     #[test]
     fn khr_win32_surface_ext_missing() {
         let instance = instance!();
@@ -36,10 +36,11 @@ use std::{
     error, fmt,
     hash::{Hash, Hasher},
     mem::MaybeUninit,
+    num::NonZeroU64,
     ops::Range,
     ptr,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
     time::Duration,
@@ -69,6 +70,9 @@ pub struct Swapchain<W> {
     // Whether full-screen exclusive is currently held.
     full_screen_exclusive_held: AtomicBool,
 
+    // The highest present id that was used in a present operation on this swapchain.
+    prev_present_id: AtomicU64,
+
     // The images of this swapchain.
     images: Vec<ImageEntry>,
 
@@ -168,6 +172,7 @@ impl<W> Swapchain<W> {
             win32_monitor,
 
             full_screen_exclusive_held: AtomicBool::new(false),
+            prev_present_id: AtomicU64::new(0),
             images,
             retired: Mutex::new(false),
         });
@@ -262,6 +267,7 @@ impl<W> Swapchain<W> {
             win32_monitor,
 
             full_screen_exclusive_held: AtomicBool::new(full_screen_exclusive_held),
+            prev_present_id: AtomicU64::new(0),
             images,
             retired: Mutex::new(false),
         });
@@ -584,7 +590,7 @@ impl<W> Swapchain<W> {
             check_errors((fns.khr_swapchain.create_swapchain_khr)(
                 device.internal_object(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -828,6 +834,132 @@ impl<W> Swapchain<W> {
         }
     }
 
+    /// Waits for a previous present operation with the given `present_id` to be presented on the
+    /// surface.
+    ///
+    /// Present ids are provided when presenting with [`present_with_id`]. If a larger present id
+    /// has already been presented, this returns immediately.
+    ///
+    /// `timeout` is the maximum duration to wait, or `None` to wait indefinitely. Returns
+    /// `Ok(true)` if the present has happened, or `Ok(false)` if the timeout expired first.
+    ///
+    /// The [`present_wait`](crate::device::Features::present_wait) feature must be enabled on
+    /// the device.
+    pub fn wait_for_present(
+        &self,
+        present_id: NonZeroU64,
+        timeout: Option<Duration>,
+    ) -> Result<bool, PresentWaitError> {
+        // VUID-vkWaitForPresentKHR-presentWait-06234
+        if !self.device.enabled_features().present_wait {
+            return Err(PresentWaitError::FeatureNotEnabled {
+                feature: "present_wait",
+                reason: "waited for a present",
+            });
+        }
+
+        let timeout_ns = if let Some(timeout) = timeout {
+            timeout
+                .as_secs()
+                .saturating_mul(1_000_000_000)
+                .saturating_add(timeout.subsec_nanos() as u64)
+        } else {
+            u64::MAX
+        };
+
+        let result = unsafe {
+            let fns = self.device.fns();
+            check_errors((fns.khr_present_wait.wait_for_present_khr)(
+                self.device.internal_object(),
+                self.handle,
+                present_id.get(),
+                timeout_ns,
+            ))
+        };
+
+        match result {
+            Ok(Success::Success) | Ok(Success::Suboptimal) => Ok(true),
+            Ok(Success::Timeout) => Ok(false),
+            Ok(s) => panic!("unexpected success value: {:?}", s),
+            Err(Error::FullScreenExclusiveLost) => {
+                self.full_screen_exclusive_held
+                    .store(false, Ordering::SeqCst);
+                Err(PresentWaitError::FullScreenExclusiveLost)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Sets the HDR metadata of the swapchain, which describes the display that the content was
+    /// mastered on and the light levels of the content.
+    ///
+    /// The metadata applies to images that are presented after this call. It is only useful for
+    /// swapchains with an HDR color space, such as the one chosen by
+    /// [`choose_hdr_or_srgb`](crate::swapchain::choose_hdr_or_srgb).
+    ///
+    /// The [`ext_hdr_metadata`](crate::device::DeviceExtensions::ext_hdr_metadata) extension must
+    /// be enabled on the device.
+    pub fn set_hdr_metadata(&self, metadata: HdrMetadata) -> Result<(), HdrMetadataError> {
+        if !self.device.enabled_extensions().ext_hdr_metadata {
+            return Err(HdrMetadataError::ExtensionNotEnabled {
+                extension: "ext_hdr_metadata",
+                reason: "tried to set the HDR metadata of a swapchain",
+            });
+        }
+
+        let HdrMetadata {
+            display_primary_red,
+            display_primary_green,
+            display_primary_blue,
+            white_point,
+            max_luminance,
+            min_luminance,
+            max_content_light_level,
+            max_frame_average_light_level,
+            _ne: _,
+        } = metadata;
+
+        let xy_color = |[x, y]: [f32; 2]| ash::vk::XYColorEXT { x, y };
+        let metadata_vk = ash::vk::HdrMetadataEXT {
+            display_primary_red: xy_color(display_primary_red),
+            display_primary_green: xy_color(display_primary_green),
+            display_primary_blue: xy_color(display_primary_blue),
+            white_point: xy_color(white_point),
+            max_luminance,
+            min_luminance,
+            max_content_light_level,
+            max_frame_average_light_level,
+            ..Default::default()
+        };
+
+        unsafe {
+            let fns = self.device.fns();
+            (fns.ext_hdr_metadata.set_hdr_metadata_ext)(
+                self.device.internal_object(),
+                1,
+                &self.handle,
+                &metadata_vk,
+            );
+        }
+
+        Ok(())
+    }
+
+    // Records `present_id` as used for a present operation on this swapchain, returning false if
+    // it is not greater than all previously used present ids.
+    fn try_claim_present_id(&self, present_id: NonZeroU64) -> bool {
+        let present_id = present_id.get();
+        self.prev_present_id
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |prev| {
+                if present_id > prev {
+                    Some(present_id)
+                } else {
+                    None
+                }
+            })
+            .is_ok()
+    }
+
     // This method is necessary to allow `SwapchainImage`s to signal when they have been
     // transitioned out of their initial `undefined` image layout.
     //
@@ -857,7 +989,7 @@ impl<W> Drop for Swapchain<W> {
             (fns.khr_swapchain.destroy_swapchain_khr)(
                 self.device.internal_object(),
                 self.handle,
-                ptr::null(),
+                self.device.allocation_callbacks(),
             );
             self.surface.flag().store(false, Ordering::Release);
         }
@@ -1096,6 +1228,9 @@ pub enum SwapchainCreationError {
         supported: SupportedSurfaceTransforms,
     },
 
+    /// The surface is not supported by any queue family of the device's physical device.
+    SurfaceNotSupported,
+
     /// The swapchain has already been used to create a new one.
     SwapchainAlreadyRetired,
 
@@ -1181,6 +1316,10 @@ impl fmt::Display for SwapchainCreationError {
                 fmt,
                 "the provided `pre_transform` is not supported by the surface for this device",
             ),
+            Self::SurfaceNotSupported => write!(
+                fmt,
+                "the surface is not supported by any queue family of the physical device",
+            ),
             Self::SwapchainAlreadyRetired => write!(
                 fmt,
                 "the swapchain has already been used to create a new one",
@@ -1221,6 +1360,8 @@ impl From<SurfacePropertiesError> for SwapchainCreationError {
             SurfacePropertiesError::OomError(err) => Self::OomError(err),
             SurfacePropertiesError::SurfaceLost => Self::SurfaceLost,
             SurfacePropertiesError::NotSupported => unreachable!(),
+            // VUID-VkSwapchainCreateInfoKHR-surface-01270
+            SurfacePropertiesError::SurfaceNotSupported => Self::SurfaceNotSupported,
         }
     }
 }
@@ -1358,6 +1499,183 @@ impl From<OomError> for FullScreenExclusiveError {
     }
 }
 
+/// Error that can happen when calling `Swapchain::wait_for_present` or `present_with_id`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PresentWaitError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The connection to the device has been lost.
+    DeviceLost,
+
+    /// The surface is no longer accessible and must be recreated.
+    SurfaceLost,
+
+    /// The swapchain has lost or doesn't have full-screen exclusivity possibly for
+    /// implementation-specific reasons outside of the application’s control.
+    FullScreenExclusiveLost,
+
+    /// The surface has changed in a way that makes the swapchain unusable. You must query the
+    /// surface's new properties and recreate a new swapchain if you want to continue drawing.
+    OutOfDate,
+
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+
+    /// The provided present id is not greater than a present id that was previously used with
+    /// the swapchain.
+    PresentIdNotIncreasing { provided: u64, previous: u64 },
+}
+
+impl error::Error for PresentWaitError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PresentWaitError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::OomError(_) => write!(fmt, "not enough memory"),
+            Self::DeviceLost => write!(fmt, "the connection to the device has been lost"),
+            Self::SurfaceLost => write!(fmt, "the surface of this swapchain is no longer valid"),
+            Self::FullScreenExclusiveLost => {
+                write!(fmt, "the swapchain no longer has full-screen exclusivity")
+            }
+            Self::OutOfDate => write!(fmt, "the swapchain needs to be recreated"),
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
+            Self::PresentIdNotIncreasing { provided, previous } => write!(
+                fmt,
+                "the provided present id {} is not greater than the previously used present id {}",
+                provided, previous
+            ),
+        }
+    }
+}
+
+impl From<Error> for PresentWaitError {
+    #[inline]
+    fn from(err: Error) -> PresentWaitError {
+        match err {
+            err @ Error::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            Error::DeviceLost => Self::DeviceLost,
+            Error::SurfaceLost => Self::SurfaceLost,
+            Error::OutOfDate => Self::OutOfDate,
+            Error::FullScreenExclusiveLost => Self::FullScreenExclusiveLost,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<OomError> for PresentWaitError {
+    #[inline]
+    fn from(err: OomError) -> PresentWaitError {
+        Self::OomError(err)
+    }
+}
+
+/// HDR metadata of a swapchain, set with
+/// [`Swapchain::set_hdr_metadata`](crate::swapchain::Swapchain::set_hdr_metadata).
+///
+/// Chromaticity coordinates are given as `[x, y]` in the CIE 1931 color space, and luminance
+/// values are given in nits (candela per square meter).
+#[derive(Clone, Debug, PartialEq)]
+pub struct HdrMetadata {
+    /// The chromaticity of the red primary of the mastering display.
+    ///
+    /// The default value is `[0.708, 0.292]`, the red primary of BT.2020.
+    pub display_primary_red: [f32; 2],
+
+    /// The chromaticity of the green primary of the mastering display.
+    ///
+    /// The default value is `[0.170, 0.797]`, the green primary of BT.2020.
+    pub display_primary_green: [f32; 2],
+
+    /// The chromaticity of the blue primary of the mastering display.
+    ///
+    /// The default value is `[0.131, 0.046]`, the blue primary of BT.2020.
+    pub display_primary_blue: [f32; 2],
+
+    /// The chromaticity of the white point of the mastering display.
+    ///
+    /// The default value is `[0.3127, 0.3290]`, the D65 white point.
+    pub white_point: [f32; 2],
+
+    /// The maximum luminance of the mastering display.
+    ///
+    /// The default value is `1000.0`.
+    pub max_luminance: f32,
+
+    /// The minimum luminance of the mastering display.
+    ///
+    /// The default value is `0.0`.
+    pub min_luminance: f32,
+
+    /// The maximum luminance of any single pixel of the content (MaxCLL), or `0.0` if unknown.
+    ///
+    /// The default value is `0.0`.
+    pub max_content_light_level: f32,
+
+    /// The maximum average luminance of any single frame of the content (MaxFALL), or `0.0` if
+    /// unknown.
+    ///
+    /// The default value is `0.0`.
+    pub max_frame_average_light_level: f32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for HdrMetadata {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            display_primary_red: [0.708, 0.292],
+            display_primary_green: [0.170, 0.797],
+            display_primary_blue: [0.131, 0.046],
+            white_point: [0.3127, 0.3290],
+            max_luminance: 1000.0,
+            min_luminance: 0.0,
+            max_content_light_level: 0.0,
+            max_frame_average_light_level: 0.0,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Error that can happen when calling `Swapchain::set_hdr_metadata`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HdrMetadataError {
+    ExtensionNotEnabled {
+        extension: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl error::Error for HdrMetadataError {}
+
+impl fmt::Display for HdrMetadataError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::ExtensionNotEnabled { extension, reason } => write!(
+                fmt,
+                "the extension {} must be enabled: {}",
+                extension, reason
+            ),
+        }
+    }
+}
+
 /// Tries to take ownership of an image in order to draw on it.
 ///
 /// The function returns the index of the image in the array of images that was returned
@@ -1370,6 +1688,10 @@ impl From<OomError> for FullScreenExclusiveError {
 /// The second field in the tuple in the Ok result is a bool represent if the acquisition was
 /// suboptimal. In this case the acquired image is still usable, but the swapchain should be
 /// recreated as the Surface's properties no longer match the swapchain.
+///
+/// `timeout` is the maximum duration to wait for an image to become available, or `None` to wait
+/// indefinitely. If no image is available, `AcquireError::NotReady` is returned if the timeout
+/// is zero, and `AcquireError::Timeout` is returned if a non-zero timeout has elapsed.
 pub fn acquire_next_image<W>(
     swapchain: Arc<Swapchain<W>>,
     timeout: Option<Duration>,
@@ -1442,6 +1764,7 @@ where
         swapchain,
         image_id: index,
         present_region: None,
+        present_id: None,
         flushed: AtomicBool::new(false),
         finished: AtomicBool::new(false),
     }
@@ -1478,11 +1801,60 @@ where
         swapchain,
         image_id: index,
         present_region: Some(present_region),
+        present_id: None,
         flushed: AtomicBool::new(false),
         finished: AtomicBool::new(false),
     }
 }
 
+/// Same as `swapchain::present`, except it also associates a present id with the present
+/// operation.
+///
+/// The present id can then be passed to `Swapchain::wait_for_present` to wait until the image
+/// has actually been presented. It must be greater than any present id previously used with the
+/// same swapchain.
+///
+/// The [`present_id`](crate::device::Features::present_id) feature must be enabled on the device.
+pub fn present_with_id<F, W>(
+    swapchain: Arc<Swapchain<W>>,
+    before: F,
+    queue: Arc<Queue>,
+    index: usize,
+    present_id: NonZeroU64,
+) -> Result<PresentFuture<F, W>, PresentWaitError>
+where
+    F: GpuFuture,
+{
+    assert!(index < swapchain.images.len());
+
+    // VUID-VkPresentInfoKHR-pNext-06235
+    if !swapchain.device.enabled_features().present_id {
+        return Err(PresentWaitError::FeatureNotEnabled {
+            feature: "present_id",
+            reason: "presented with a present id",
+        });
+    }
+
+    // VUID-VkPresentIdKHR-presentIds-04999
+    if !swapchain.try_claim_present_id(present_id) {
+        return Err(PresentWaitError::PresentIdNotIncreasing {
+            provided: present_id.get(),
+            previous: swapchain.prev_present_id.load(Ordering::SeqCst),
+        });
+    }
+
+    Ok(PresentFuture {
+        previous: before,
+        queue,
+        swapchain,
+        image_id: index,
+        present_region: None,
+        present_id: Some(present_id),
+        flushed: AtomicBool::new(false),
+        finished: AtomicBool::new(false),
+    })
+}
+
 /// Represents the moment when the GPU will have access to a swapchain image.
 #[must_use]
 pub struct SwapchainAcquireFuture<W> {
@@ -1626,6 +1998,9 @@ pub enum AcquireError {
     /// The timeout of the function has been reached before an image was available.
     Timeout,
 
+    /// The timeout of the function was zero and no image was available.
+    NotReady,
+
     /// The surface is no longer accessible and must be recreated.
     SurfaceLost,
 
@@ -1661,6 +2036,9 @@ impl fmt::Display for AcquireError {
                 AcquireError::OomError(_) => "not enough memory",
                 AcquireError::DeviceLost => "the connection to the device has been lost",
                 AcquireError::Timeout => "no image is available for acquiring yet",
+                AcquireError::NotReady => {
+                    "no image is available for acquiring yet, and the timeout was zero"
+                }
                 AcquireError::SurfaceLost => "the surface of this swapchain is no longer valid",
                 AcquireError::OutOfDate => "the swapchain needs to be recreated",
                 AcquireError::FullScreenExclusiveLost => {
@@ -1711,6 +2089,7 @@ where
     swapchain: Arc<Swapchain<W>>,
     image_id: usize,
     present_region: Option<PresentRegion>,
+    present_id: Option<NonZeroU64>,
     // True if `flush()` has been called on the future, which means that the present command has
     // been submitted.
     flushed: AtomicBool,
@@ -1763,6 +2142,7 @@ where
                     &self.swapchain,
                     self.image_id as u32,
                     self.present_region.as_ref(),
+                    self.present_id,
                 );
                 SubmitAnyBuilder::QueuePresent(builder)
             }
@@ -1772,6 +2152,7 @@ where
                     &self.swapchain,
                     self.image_id as u32,
                     self.present_region.as_ref(),
+                    self.present_id,
                 );
                 SubmitAnyBuilder::QueuePresent(builder)
             }
@@ -1785,6 +2166,7 @@ where
                     &self.swapchain,
                     self.image_id as u32,
                     self.present_region.as_ref(),
+                    self.present_id,
                 );
                 SubmitAnyBuilder::QueuePresent(builder)
             }
@@ -1798,6 +2180,7 @@ where
                     &self.swapchain,
                     self.image_id as u32,
                     self.present_region.as_ref(),
+                    self.present_id,
                 );
                 SubmitAnyBuilder::QueuePresent(builder)
             }
@@ -1940,6 +2323,8 @@ pub struct AcquiredImage {
 
 /// Unsafe variant of `acquire_next_image`.
 ///
+/// `timeout` has the same meaning as in `acquire_next_image`.
+///
 /// # Safety
 ///
 /// - The semaphore and/or the fence must be kept alive until it is signaled.
@@ -1980,7 +2365,7 @@ pub unsafe fn acquire_next_image_raw<W>(
     let (id, suboptimal) = match r {
         Success::Success => (out as usize, false),
         Success::Suboptimal => (out as usize, true),
-        Success::NotReady => return Err(AcquireError::Timeout),
+        Success::NotReady => return Err(AcquireError::NotReady),
         Success::Timeout => return Err(AcquireError::Timeout),
         s => panic!("unexpected success value: {:?}", s),
     };
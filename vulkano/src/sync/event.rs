@@ -15,7 +15,6 @@ use crate::{
 use std::{
     hash::{Hash, Hasher},
     mem::MaybeUninit,
-    ptr,
     sync::Arc,
 };
 
@@ -46,7 +45,7 @@ impl Event {
             check_errors((fns.v1_0.create_event)(
                 device.internal_object(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -168,7 +167,11 @@ impl Drop for Event {
                 self.device.event_pool().lock().unwrap().push(raw_event);
             } else {
                 let fns = self.device.fns();
-                (fns.v1_0.destroy_event)(self.device.internal_object(), self.handle, ptr::null());
+                (fns.v1_0.destroy_event)(
+                    self.device.internal_object(),
+                    self.handle,
+                    self.device.allocation_callbacks(),
+                );
             }
         }
     }
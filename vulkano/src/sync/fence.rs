@@ -17,10 +17,10 @@ use std::{
     error, fmt,
     hash::{Hash, Hasher},
     mem::MaybeUninit,
-    ptr,
+    ops::Deref,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Mutex,
     },
     time::Duration,
 };
@@ -67,7 +67,7 @@ impl Fence {
             check_errors((fns.v1_0.create_fence)(
                 device.internal_object(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -316,7 +316,11 @@ impl Drop for Fence {
                 self.device.fence_pool().lock().unwrap().push(raw_fence);
             } else {
                 let fns = self.device.fns();
-                (fns.v1_0.destroy_fence)(self.device.internal_object(), self.handle, ptr::null());
+                (fns.v1_0.destroy_fence)(
+                    self.device.internal_object(),
+                    self.handle,
+                    self.device.allocation_callbacks(),
+                );
             }
         }
     }
@@ -355,6 +359,121 @@ impl Hash for Fence {
     }
 }
 
+/// A pool of fences that are recycled instead of being destroyed.
+///
+/// Unlike [`Fence::from_pool`], which shares a single pool for the whole device, a `FencePool`
+/// only takes back fences that are known to be signaled, so a recycled fence is never reset
+/// while a queue operation may still signal it.
+#[derive(Debug)]
+pub struct FencePool {
+    device: Arc<Device>,
+
+    // Every fence in here is known to be signaled.
+    fences: Mutex<Vec<Fence>>,
+}
+
+impl FencePool {
+    /// Creates a new empty `FencePool`.
+    #[inline]
+    pub fn new(device: Arc<Device>) -> Arc<FencePool> {
+        Arc::new(FencePool {
+            device,
+            fences: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Takes an unsignaled fence from the pool. If the pool is empty, a new fence is created.
+    ///
+    /// This is the same as [`get_unsignaled`](Self::get_unsignaled).
+    #[inline]
+    pub fn get(self: &Arc<Self>) -> Result<PooledFence, OomError> {
+        self.get_unsignaled()
+    }
+
+    /// Takes an unsignaled fence from the pool. If the pool is empty, a new fence is created.
+    pub fn get_unsignaled(self: &Arc<Self>) -> Result<PooledFence, OomError> {
+        let fence = self.fences.lock().unwrap().pop();
+        let fence = match fence {
+            Some(mut fence) => {
+                fence.reset()?;
+                fence
+            }
+            None => Fence::new(self.device.clone(), FenceCreateInfo::default())?,
+        };
+
+        Ok(PooledFence {
+            fence: Some(fence),
+            pool: self.clone(),
+        })
+    }
+
+    /// Takes a signaled fence from the pool. If the pool is empty, a new fence is created.
+    pub fn get_signaled(self: &Arc<Self>) -> Result<PooledFence, OomError> {
+        let fence = self.fences.lock().unwrap().pop();
+        let fence = match fence {
+            Some(fence) => fence,
+            None => Fence::new(
+                self.device.clone(),
+                FenceCreateInfo {
+                    signaled: true,
+                    ..Default::default()
+                },
+            )?,
+        };
+
+        Ok(PooledFence {
+            fence: Some(fence),
+            pool: self.clone(),
+        })
+    }
+}
+
+unsafe impl DeviceOwned for FencePool {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+/// A fence taken from a [`FencePool`].
+///
+/// When dropped, the fence is returned to the pool if it is known to be signaled, that is, if
+/// [`is_signaled`](Fence::is_signaled) returned `true` or [`wait`](Fence::wait) succeeded.
+/// Otherwise it is destroyed.
+#[derive(Debug)]
+pub struct PooledFence {
+    fence: Option<Fence>,
+    pool: Arc<FencePool>,
+}
+
+impl PooledFence {
+    /// Returns the pool that the fence was taken from.
+    #[inline]
+    pub fn pool(&self) -> &Arc<FencePool> {
+        &self.pool
+    }
+}
+
+impl Deref for PooledFence {
+    type Target = Fence;
+
+    #[inline]
+    fn deref(&self) -> &Fence {
+        self.fence.as_ref().unwrap()
+    }
+}
+
+impl Drop for PooledFence {
+    #[inline]
+    fn drop(&mut self) {
+        let fence = self.fence.take().unwrap();
+
+        if fence.is_signaled.load(Ordering::Relaxed) {
+            self.pool.fences.lock().unwrap().push(fence);
+        }
+    }
+}
+
 /// Parameters to create a new `Fence`.
 #[derive(Clone, Debug)]
 pub struct FenceCreateInfo {
@@ -429,6 +548,7 @@ impl From<Error> for FenceWaitError {
 #[cfg(test)]
 mod tests {
     use crate::sync::fence::FenceCreateInfo;
+    use crate::sync::fence::FencePool;
     use crate::sync::Fence;
     use crate::VulkanObject;
     use std::time::Duration;
@@ -552,6 +672,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fence_pool_recycle() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let pool = FencePool::new(device);
+
+        let handle = {
+            let fence = pool.get().unwrap();
+            assert!(!fence.is_signaled().unwrap());
+
+            unsafe {
+                queue.submit_batch(&[], Some(&*fence)).unwrap();
+            }
+            fence.wait(None).unwrap();
+            fence.internal_object()
+        };
+
+        let fence = pool.get().unwrap();
+        assert_eq!(fence.internal_object(), handle);
+        assert!(!fence.is_signaled().unwrap());
+    }
+
+    #[test]
+    fn fence_pool_unsignaled_not_recycled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = FencePool::new(device);
+
+        {
+            let _fence = pool.get_unsignaled().unwrap();
+        }
+        assert!(pool.fences.lock().unwrap().is_empty());
+
+        {
+            let fence = pool.get_signaled().unwrap();
+            assert!(fence.is_signaled().unwrap());
+        }
+        assert_eq!(pool.fences.lock().unwrap().len(), 1);
+    }
+
     #[test]
     fn fence_pool() {
         let (device, _) = gfx_dev_and_queue!();
@@ -148,6 +148,36 @@ pub unsafe trait GpuFuture: DeviceOwned {
         join::join(self, other)
     }
 
+    /// Joins this future with another one that is going to be used on a different queue,
+    /// possibly of a different queue family.
+    ///
+    /// A semaphore is signalled after this future, and any submission made after the returned
+    /// future waits on it. This is typically used to make a submission on a graphics queue wait
+    /// for an upload that was done on a transfer queue. The semaphore is taken from the device's
+    /// semaphore pool, and returned to it once the returned future is dropped.
+    ///
+    /// # Queue family ownership
+    ///
+    /// The semaphore only provides an execution and memory dependency between the two queues. If
+    /// the two queues belong to different queue families, then a buffer or image created with
+    /// [`Sharing::Exclusive`](crate::sync::Sharing::Exclusive) that is written by this future and
+    /// read after the returned future must also have its ownership transferred to the other
+    /// queue family. This requires a release barrier recorded on the source queue family before
+    /// the semaphore is signalled, and a matching acquire barrier recorded on the destination
+    /// queue family after it is waited on, both with the same
+    /// [`QueueFamilyTransfer`](crate::sync::QueueFamilyTransfer). Vulkano doesn't record these
+    /// barriers automatically, so the simplest option is to create such resources with
+    /// [`Sharing::Concurrent`](crate::sync::Sharing::Concurrent), listing both queue families.
+    /// In that case no ownership transfer is needed, and the semaphore alone is enough.
+    #[inline]
+    fn join_cross_queue<F>(self, other: F) -> JoinFuture<SemaphoreSignalFuture<Self>, F>
+    where
+        Self: Sized,
+        F: GpuFuture,
+    {
+        join::join(self.then_signal_semaphore(), other)
+    }
+
     /// Executes a command buffer after this future.
     ///
     /// > **Note**: This is just a shortcut function. The actual implementation is in the
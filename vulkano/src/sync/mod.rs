@@ -108,7 +108,7 @@ use std::sync::Arc;
 
 pub use self::{
     event::{Event, EventCreateInfo},
-    fence::{Fence, FenceCreateInfo, FenceWaitError},
+    fence::{Fence, FenceCreateInfo, FencePool, FenceWaitError, PooledFence},
     future::{
         now, AccessCheckError, AccessError, FenceSignalFuture, FlushError, GpuFuture, JoinFuture,
         NowFuture, SemaphoreSignalFuture,
@@ -121,6 +121,10 @@ pub use self::{
         ExternalSemaphoreHandleType, ExternalSemaphoreHandleTypes, ExternalSemaphoreInfo,
         ExternalSemaphoreProperties, Semaphore, SemaphoreCreateInfo, SemaphoreCreationError,
     },
+    timeline_semaphore::{
+        TimelineSemaphore, TimelineSemaphoreCreateInfo, TimelineSemaphoreCreationError,
+        TimelineSemaphoreError,
+    },
 };
 
 mod event;
@@ -128,6 +132,7 @@ mod fence;
 mod future;
 mod pipeline;
 mod semaphore;
+mod timeline_semaphore;
 
 /// Declares in which queue(s) a resource can be used.
 ///
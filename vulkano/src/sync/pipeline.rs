@@ -19,12 +19,18 @@ use std::{
 };
 
 macro_rules! pipeline_stages {
-    ($($elem:ident, $var:ident => $val:ident, $queue:expr;)+) => (
+    (
+        synchronization2: [$($elem2:ident => $val2:ident,)+]
+        $($elem:ident, $var:ident => $val:ident, $queue:expr;)+
+    ) => (
         #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
         pub struct PipelineStages {
             $(
                 pub $elem: bool,
             )+
+            $(
+                pub $elem2: bool,
+            )+
         }
 
         impl PipelineStages {
@@ -34,6 +40,9 @@ macro_rules! pipeline_stages {
                     $(
                         $elem: false,
                     )+
+                    $(
+                        $elem2: false,
+                    )+
                 }
             }
         }
@@ -41,6 +50,7 @@ macro_rules! pipeline_stages {
         impl From<PipelineStages> for ash::vk::PipelineStageFlags {
             #[inline]
             fn from(val: PipelineStages) -> Self {
+                let val = val.to_synchronization1();
                 let mut result = ash::vk::PipelineStageFlags::empty();
                 $(
                     if val.$elem { result |= ash::vk::PipelineStageFlags::$val }
@@ -56,6 +66,9 @@ macro_rules! pipeline_stages {
                 $(
                     if val.$elem { result |= ash::vk::PipelineStageFlags2::$val }
                 )+
+                $(
+                    if val.$elem2 { result |= ash::vk::PipelineStageFlags2::$val2 }
+                )+
                 result
             }
         }
@@ -69,6 +82,9 @@ macro_rules! pipeline_stages {
                     $(
                         $elem: self.$elem || rhs.$elem,
                     )+
+                    $(
+                        $elem2: self.$elem2 || rhs.$elem2,
+                    )+
                 }
             }
         }
@@ -79,6 +95,9 @@ macro_rules! pipeline_stages {
                 $(
                     self.$elem = self.$elem || rhs.$elem;
                 )+
+                $(
+                    self.$elem2 = self.$elem2 || rhs.$elem2;
+                )+
             }
         }
 
@@ -134,6 +153,14 @@ impl PipelineStages {
             all_graphics,
             all_commands,
             ray_tracing_shader,
+            acceleration_structure_build,
+            mut copy,
+            mut resolve,
+            mut blit,
+            mut clear,
+            mut index_input,
+            mut vertex_attribute_input,
+            mut pre_rasterization_shaders,
         } = *self;
 
         if all_graphics {
@@ -141,10 +168,7 @@ impl PipelineStages {
             //task_shader = true;
             //mesh_shader = true;
             vertex_input = true;
-            vertex_shader = true;
-            tessellation_control_shader = true;
-            tessellation_evaluation_shader = true;
-            geometry_shader = true;
+            pre_rasterization_shaders = true;
             fragment_shader = true;
             early_fragment_tests = true;
             late_fragment_tests = true;
@@ -155,10 +179,29 @@ impl PipelineStages {
             //fragment_density_process = true;
         }
 
+        if vertex_input {
+            index_input = true;
+            vertex_attribute_input = true;
+        }
+
+        if pre_rasterization_shaders {
+            vertex_shader = true;
+            tessellation_control_shader = true;
+            tessellation_evaluation_shader = true;
+            geometry_shader = true;
+        }
+
+        if transfer {
+            copy = true;
+            resolve = true;
+            blit = true;
+            clear = true;
+        }
+
         AccessFlags {
-            indirect_command_read: draw_indirect, /*|| acceleration_structure_build*/
-            index_read: vertex_input,
-            vertex_attribute_read: vertex_input,
+            indirect_command_read: draw_indirect || acceleration_structure_build,
+            index_read: index_input,
+            vertex_attribute_read: vertex_attribute_input,
             uniform_read:
                 // task_shader
                 // mesh_shader
@@ -170,10 +213,10 @@ impl PipelineStages {
                 || fragment_shader
                 || compute_shader,
             shader_read:
-                // acceleration_structure_build
                 // task_shader
                 // mesh_shader
-                ray_tracing_shader
+                acceleration_structure_build
+                || ray_tracing_shader
                 || vertex_shader
                 || tessellation_control_shader
                 || tessellation_evaluation_shader
@@ -197,14 +240,54 @@ impl PipelineStages {
             color_attachment_write: color_attachment_output,
             depth_stencil_attachment_read: early_fragment_tests || late_fragment_tests,
             depth_stencil_attachment_write: early_fragment_tests || late_fragment_tests,
-            transfer_read: transfer,
-                // acceleration_structure_build
-            transfer_write: transfer,
-                // acceleration_structure_build
+            transfer_read: copy || resolve || blit || acceleration_structure_build,
+            transfer_write: copy || resolve || blit || clear || acceleration_structure_build,
             host_read: host,
             host_write: host,
             memory_read: true,
             memory_write: true,
+            shader_sampled_read:
+                // task_shader
+                // mesh_shader
+                ray_tracing_shader
+                || vertex_shader
+                || tessellation_control_shader
+                || tessellation_evaluation_shader
+                || geometry_shader
+                || fragment_shader
+                || compute_shader,
+            shader_storage_read:
+                // task_shader
+                // mesh_shader
+                ray_tracing_shader
+                || vertex_shader
+                || tessellation_control_shader
+                || tessellation_evaluation_shader
+                || geometry_shader
+                || fragment_shader
+                || compute_shader,
+            shader_storage_write:
+                // task_shader
+                // mesh_shader
+                ray_tracing_shader
+                || vertex_shader
+                || tessellation_control_shader
+                || tessellation_evaluation_shader
+                || geometry_shader
+                || fragment_shader
+                || compute_shader,
+            acceleration_structure_read:
+                // task_shader
+                // mesh_shader
+                ray_tracing_shader
+                || vertex_shader
+                || tessellation_control_shader
+                || tessellation_evaluation_shader
+                || geometry_shader
+                || fragment_shader
+                || compute_shader
+                || acceleration_structure_build,
+            acceleration_structure_write: acceleration_structure_build,
 
             /*
             color_attachment_read_noncoherent: color_attachment_output,
@@ -216,12 +299,31 @@ impl PipelineStages {
             transform_feedback_write: transform_feedback,
             transform_feedback_counter_write: transform_feedback,
             transform_feedback_counter_read: transform_feedback || draw_indirect,
-            acceleration_structure_read: task_shader || mesh_shader || vertex_shader || tessellation_control_shader || tessellation_evaluation_shader || geometry_shader || fragment_shader || compute_shader || ray_tracing_shader || acceleration_structure_build,
-            acceleration_structure_write: acceleration_structure_build,
             fragment_density_map_read: fragment_density_process,
             */
         }
     }
+
+    // Replaces the stages that only exist with `synchronization2` with the stages that include
+    // them, so that the stages can be given to the original synchronization commands.
+    #[inline]
+    fn to_synchronization1(mut self) -> PipelineStages {
+        if self.copy || self.resolve || self.blit || self.clear {
+            self.transfer = true;
+        }
+
+        if self.index_input || self.vertex_attribute_input {
+            self.vertex_input = true;
+        }
+
+        // The tessellation and geometry shader stages can't be used if their features aren't
+        // enabled, so fall back to all graphics stages instead.
+        if self.pre_rasterization_shaders {
+            self.all_graphics = true;
+        }
+
+        self
+    }
 }
 
 impl From<PipelineStage> for ash::vk::PipelineStageFlags {
@@ -239,6 +341,17 @@ impl From<PipelineStage> for ash::vk::PipelineStageFlags2 {
 }
 
 pipeline_stages! {
+    // The stages that can only be used with the `synchronization2` feature. When given to a
+    // command that doesn't use it, they are replaced with the stages that include them.
+    synchronization2: [
+        copy => COPY,
+        resolve => RESOLVE,
+        blit => BLIT,
+        clear => CLEAR,
+        index_input => INDEX_INPUT,
+        vertex_attribute_input => VERTEX_ATTRIBUTE_INPUT,
+        pre_rasterization_shaders => PRE_RASTERIZATION_SHADERS,
+    ]
     top_of_pipe, TopOfPipe => TOP_OF_PIPE, ash::vk::QueueFlags::empty();
     draw_indirect, DrawIndirect => DRAW_INDIRECT, ash::vk::QueueFlags::GRAPHICS | ash::vk::QueueFlags::COMPUTE;
     vertex_input, VertexInput => VERTEX_INPUT, ash::vk::QueueFlags::GRAPHICS;
@@ -257,16 +370,23 @@ pipeline_stages! {
     all_graphics, AllGraphics => ALL_GRAPHICS, ash::vk::QueueFlags::GRAPHICS;
     all_commands, AllCommands => ALL_COMMANDS, ash::vk::QueueFlags::empty();
     ray_tracing_shader, RayTracingShader => RAY_TRACING_SHADER_KHR, ash::vk::QueueFlags::GRAPHICS | ash::vk::QueueFlags::COMPUTE | ash::vk::QueueFlags::TRANSFER;
+    acceleration_structure_build, AccelerationStructureBuild => ACCELERATION_STRUCTURE_BUILD_KHR, ash::vk::QueueFlags::COMPUTE;
 }
 
 macro_rules! access_flags {
-    ($($elem:ident => $val:ident,)+) => (
+    (
+        synchronization2: [$($elem2:ident => $val2:ident,)+]
+        $($elem:ident => $val:ident,)+
+    ) => (
         #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
         #[allow(missing_docs)]
         pub struct AccessFlags {
             $(
                 pub $elem: bool,
             )+
+            $(
+                pub $elem2: bool,
+            )+
         }
 
         impl AccessFlags {
@@ -276,6 +396,9 @@ macro_rules! access_flags {
                     $(
                         $elem: true,
                     )+
+                    $(
+                        $elem2: true,
+                    )+
                 }
             }
 
@@ -285,6 +408,9 @@ macro_rules! access_flags {
                     $(
                         $elem: false,
                     )+
+                    $(
+                        $elem2: false,
+                    )+
                 }
             }
 
@@ -293,12 +419,16 @@ macro_rules! access_flags {
                 $(
                     (self.$elem || !other.$elem)
                 )&&+
+                $(
+                    && (self.$elem2 || !other.$elem2)
+                )+
             }
         }
 
         impl From<AccessFlags> for ash::vk::AccessFlags {
             #[inline]
             fn from(val: AccessFlags) -> Self {
+                let val = val.to_synchronization1();
                 let mut result = ash::vk::AccessFlags::empty();
                 $(
                     if val.$elem { result |= ash::vk::AccessFlags::$val }
@@ -314,6 +444,9 @@ macro_rules! access_flags {
                 $(
                     if val.$elem { result |= ash::vk::AccessFlags2::$val }
                 )+
+                $(
+                    if val.$elem2 { result |= ash::vk::AccessFlags2::$val2 }
+                )+
                 result
             }
         }
@@ -327,6 +460,9 @@ macro_rules! access_flags {
                     $(
                         $elem: self.$elem || rhs.$elem,
                     )+
+                    $(
+                        $elem2: self.$elem2 || rhs.$elem2,
+                    )+
                 }
             }
         }
@@ -337,12 +473,22 @@ macro_rules! access_flags {
                 $(
                     self.$elem = self.$elem || rhs.$elem;
                 )+
+                $(
+                    self.$elem2 = self.$elem2 || rhs.$elem2;
+                )+
             }
         }
     );
 }
 
 access_flags! {
+    // The access types that can only be used with the `synchronization2` feature. When given to
+    // a command that doesn't use it, they are replaced with the access types that include them.
+    synchronization2: [
+        shader_sampled_read => SHADER_SAMPLED_READ,
+        shader_storage_read => SHADER_STORAGE_READ,
+        shader_storage_write => SHADER_STORAGE_WRITE,
+    ]
     indirect_command_read => INDIRECT_COMMAND_READ,
     index_read => INDEX_READ,
     vertex_attribute_read => VERTEX_ATTRIBUTE_READ,
@@ -360,6 +506,25 @@ access_flags! {
     host_write => HOST_WRITE,
     memory_read => MEMORY_READ,
     memory_write => MEMORY_WRITE,
+    acceleration_structure_read => ACCELERATION_STRUCTURE_READ_KHR,
+    acceleration_structure_write => ACCELERATION_STRUCTURE_WRITE_KHR,
+}
+
+impl AccessFlags {
+    // Replaces the access types that only exist with `synchronization2` with the access types
+    // that include them, so that they can be given to the original synchronization commands.
+    #[inline]
+    fn to_synchronization1(mut self) -> AccessFlags {
+        if self.shader_sampled_read || self.shader_storage_read {
+            self.shader_read = true;
+        }
+
+        if self.shader_storage_write {
+            self.shader_write = true;
+        }
+
+        self
+    }
 }
 
 /// The full specification of memory access by the pipeline for a particular resource.
@@ -18,7 +18,6 @@ use std::{
     hash::{Hash, Hasher},
     mem::MaybeUninit,
     ops::BitOr,
-    ptr,
     sync::Arc,
 };
 
@@ -98,7 +97,7 @@ impl Semaphore {
             check_errors((fns.v1_0.create_semaphore)(
                 device.internal_object(),
                 &create_info.build(),
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             ))?;
             output.assume_init()
@@ -199,7 +198,7 @@ impl Drop for Semaphore {
                 (fns.v1_0.destroy_semaphore)(
                     self.device.internal_object(),
                     self.handle,
-                    ptr::null(),
+                    self.device.allocation_callbacks(),
                 );
             }
         }
@@ -0,0 +1,437 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    check_errors,
+    device::{Device, DeviceOwned},
+    Error, OomError, Success, Version, VulkanObject,
+};
+use std::{
+    error, fmt,
+    hash::{Hash, Hasher},
+    mem::MaybeUninit,
+    sync::Arc,
+    time::Duration,
+};
+
+/// A semaphore whose state is a 64-bit counter that only ever increases.
+///
+/// Contrary to a binary `Semaphore`, a timeline semaphore can be waited upon and signaled from
+/// both the GPU and the CPU. Each wait operation waits until the counter has reached a given
+/// value, and each signal operation sets the counter to a new, higher value.
+///
+/// The [`timeline_semaphore`](crate::device::Features::timeline_semaphore) feature must be
+/// enabled on the device.
+#[derive(Debug)]
+pub struct TimelineSemaphore {
+    handle: ash::vk::Semaphore,
+    device: Arc<Device>,
+}
+
+impl TimelineSemaphore {
+    /// Creates a new `TimelineSemaphore`.
+    pub fn new(
+        device: Arc<Device>,
+        create_info: TimelineSemaphoreCreateInfo,
+    ) -> Result<TimelineSemaphore, TimelineSemaphoreCreationError> {
+        let TimelineSemaphoreCreateInfo {
+            initial_value,
+            _ne: _,
+        } = create_info;
+
+        // VUID-VkSemaphoreTypeCreateInfo-timelineSemaphore-03252
+        if !device.enabled_features().timeline_semaphore {
+            return Err(TimelineSemaphoreCreationError::FeatureNotEnabled {
+                feature: "timeline_semaphore",
+                reason: "tried to create a timeline semaphore",
+            });
+        }
+
+        let mut semaphore_type_create_info = ash::vk::SemaphoreTypeCreateInfo {
+            semaphore_type: ash::vk::SemaphoreType::TIMELINE,
+            initial_value,
+            ..Default::default()
+        };
+        let create_info = ash::vk::SemaphoreCreateInfo::builder()
+            .push_next(&mut semaphore_type_create_info)
+            .build();
+
+        let handle = unsafe {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            check_errors((fns.v1_0.create_semaphore)(
+                device.internal_object(),
+                &create_info,
+                device.allocation_callbacks(),
+                output.as_mut_ptr(),
+            ))?;
+            output.assume_init()
+        };
+
+        Ok(TimelineSemaphore { handle, device })
+    }
+
+    /// Returns the current value of the counter.
+    pub fn value(&self) -> Result<u64, TimelineSemaphoreError> {
+        unsafe {
+            let fns = self.device.fns();
+            let mut output = MaybeUninit::uninit();
+
+            if self.device.api_version() >= Version::V1_2 {
+                check_errors((fns.v1_2.get_semaphore_counter_value)(
+                    self.device.internal_object(),
+                    self.handle,
+                    output.as_mut_ptr(),
+                ))?;
+            } else {
+                check_errors(
+                    (fns.khr_timeline_semaphore.get_semaphore_counter_value_khr)(
+                        self.device.internal_object(),
+                        self.handle,
+                        output.as_mut_ptr(),
+                    ),
+                )?;
+            }
+
+            Ok(output.assume_init())
+        }
+    }
+
+    /// Signals the semaphore from the CPU, setting the counter to `value`.
+    ///
+    /// Returns an error if `value` is not greater than the current value of the counter.
+    ///
+    /// # Safety
+    ///
+    /// - `value` must be less than the value of any signal operation on this semaphore that is
+    ///   pending execution in a queue.
+    pub unsafe fn signal(&self, value: u64) -> Result<(), TimelineSemaphoreError> {
+        // VUID-VkSemaphoreSignalInfo-value-03258
+        let current_value = self.value()?;
+
+        if value <= current_value {
+            return Err(TimelineSemaphoreError::ValueNotGreater {
+                value,
+                current_value,
+            });
+        }
+
+        // VUID-VkSemaphoreSignalInfo-value-03259
+        // TODO: not checked, so unsafe for now
+
+        let signal_info = ash::vk::SemaphoreSignalInfo {
+            semaphore: self.handle,
+            value,
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+
+        if self.device.api_version() >= Version::V1_2 {
+            check_errors((fns.v1_2.signal_semaphore)(
+                self.device.internal_object(),
+                &signal_info,
+            ))?;
+        } else {
+            check_errors((fns.khr_timeline_semaphore.signal_semaphore_khr)(
+                self.device.internal_object(),
+                &signal_info,
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    /// Waits until the counter has reached at least `value`, or at least until the timeout
+    /// duration has elapsed.
+    ///
+    /// Returns `Ok` if the counter has reached `value`. Returns an error if the timeout was
+    /// reached instead.
+    ///
+    /// If you pass a duration of 0, then the function will return without blocking.
+    pub fn wait(
+        &self,
+        value: u64,
+        timeout: Option<Duration>,
+    ) -> Result<(), TimelineSemaphoreError> {
+        unsafe {
+            let timeout_ns = if let Some(timeout) = timeout {
+                timeout
+                    .as_secs()
+                    .saturating_mul(1_000_000_000)
+                    .saturating_add(timeout.subsec_nanos() as u64)
+            } else {
+                u64::MAX
+            };
+
+            let wait_info = ash::vk::SemaphoreWaitInfo {
+                semaphore_count: 1,
+                p_semaphores: &self.handle,
+                p_values: &value,
+                ..Default::default()
+            };
+
+            let fns = self.device.fns();
+            let r = if self.device.api_version() >= Version::V1_2 {
+                check_errors((fns.v1_2.wait_semaphores)(
+                    self.device.internal_object(),
+                    &wait_info,
+                    timeout_ns,
+                ))?
+            } else {
+                check_errors((fns.khr_timeline_semaphore.wait_semaphores_khr)(
+                    self.device.internal_object(),
+                    &wait_info,
+                    timeout_ns,
+                ))?
+            };
+
+            match r {
+                Success::Success => Ok(()),
+                Success::Timeout => Err(TimelineSemaphoreError::Timeout),
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
+impl Drop for TimelineSemaphore {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.v1_0.destroy_semaphore)(
+                self.device.internal_object(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
+        }
+    }
+}
+
+unsafe impl VulkanObject for TimelineSemaphore {
+    type Object = ash::vk::Semaphore;
+
+    #[inline]
+    fn internal_object(&self) -> ash::vk::Semaphore {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for TimelineSemaphore {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl PartialEq for TimelineSemaphore {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle && self.device() == other.device()
+    }
+}
+
+impl Eq for TimelineSemaphore {}
+
+impl Hash for TimelineSemaphore {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.handle.hash(state);
+        self.device().hash(state);
+    }
+}
+
+/// Parameters to create a new `TimelineSemaphore`.
+#[derive(Clone, Debug)]
+pub struct TimelineSemaphoreCreateInfo {
+    /// The initial value of the counter.
+    ///
+    /// The default value is `0`.
+    pub initial_value: u64,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for TimelineSemaphoreCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            initial_value: 0,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Error that can be returned when creating a `TimelineSemaphore`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimelineSemaphoreCreationError {
+    /// Not enough memory available.
+    OomError(OomError),
+
+    /// A feature that is required to create a timeline semaphore was not enabled.
+    FeatureNotEnabled {
+        feature: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl error::Error for TimelineSemaphoreCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TimelineSemaphoreCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::OomError(_) => write!(fmt, "not enough memory available"),
+            Self::FeatureNotEnabled { feature, reason } => {
+                write!(fmt, "the feature {} must be enabled: {}", feature, reason)
+            }
+        }
+    }
+}
+
+impl From<Error> for TimelineSemaphoreCreationError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            e @ Error::OutOfHostMemory | e @ Error::OutOfDeviceMemory => Self::OomError(e.into()),
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<OomError> for TimelineSemaphoreCreationError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+/// Error that can be returned when querying, signaling or waiting on a `TimelineSemaphore`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimelineSemaphoreError {
+    /// Not enough memory available.
+    OomError(OomError),
+
+    /// The device has been lost.
+    DeviceLost,
+
+    /// The specified timeout wasn't long enough.
+    Timeout,
+
+    /// The value to signal was not greater than the current value of the counter.
+    ValueNotGreater { value: u64, current_value: u64 },
+}
+
+impl error::Error for TimelineSemaphoreError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TimelineSemaphoreError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Self::OomError(_) => write!(fmt, "not enough memory available"),
+            Self::DeviceLost => write!(fmt, "the device was lost"),
+            Self::Timeout => write!(fmt, "the timeout has been reached"),
+            Self::ValueNotGreater {
+                value,
+                current_value,
+            } => write!(
+                fmt,
+                "the value to signal ({}) was not greater than the current value of the counter ({})",
+                value, current_value,
+            ),
+        }
+    }
+}
+
+impl From<Error> for TimelineSemaphoreError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        match err {
+            e @ Error::OutOfHostMemory | e @ Error::OutOfDeviceMemory => Self::OomError(e.into()),
+            Error::DeviceLost => Self::DeviceLost,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<OomError> for TimelineSemaphoreError {
+    #[inline]
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sync::{
+        TimelineSemaphore, TimelineSemaphoreCreateInfo, TimelineSemaphoreCreationError,
+        TimelineSemaphoreError,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert!(matches!(
+            TimelineSemaphore::new(device, Default::default()),
+            Err(TimelineSemaphoreCreationError::FeatureNotEnabled { .. })
+        ));
+    }
+
+    #[test]
+    fn signal_and_wait() {
+        let (device, _) = gfx_dev_and_queue!(timeline_semaphore);
+
+        let semaphore = TimelineSemaphore::new(
+            device,
+            TimelineSemaphoreCreateInfo {
+                initial_value: 2,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(semaphore.value().unwrap(), 2);
+        assert_eq!(
+            semaphore.wait(3, Some(Duration::from_secs(0))),
+            Err(TimelineSemaphoreError::Timeout)
+        );
+
+        unsafe {
+            assert_eq!(
+                semaphore.signal(2),
+                Err(TimelineSemaphoreError::ValueNotGreater {
+                    value: 2,
+                    current_value: 2,
+                })
+            );
+            semaphore.signal(5).unwrap();
+        }
+
+        assert_eq!(semaphore.value().unwrap(), 5);
+        semaphore.wait(3, Some(Duration::from_secs(5))).unwrap();
+    }
+}